@@ -0,0 +1,427 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Access control of the node APIs: API keys mapped to method allow-lists, and rate limits.
+//!
+//! Clients authenticate with an `Authorization: Bearer <key>` or an `X-Api-Key: <key>` header.
+//! Requests without credentials are anonymous: they can be refused, or accepted with their own
+//! allow-list and limits, applied to each client IP. Anonymous clients whose IP is unknown are refused
+//! when these limits are set, so that they can not consume the budget of each other.
+//! Rate limits are token buckets refilled every second, whose capacity is one second of budget.
+
+use displaydoc::Display;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::Instant;
+
+/// Above this number of tracked clients, the clients whose budgets are fully refilled are forgotten
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Rate limits of a client, 0 meaning unlimited
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct RateLimits {
+    /// maximum number of method calls per second (each call of a batch counts)
+    #[serde(default)]
+    pub requests_per_second: u64,
+    /// maximum gas per second of the read-only executions and calls
+    #[serde(default)]
+    pub read_only_gas_per_second: u64,
+}
+
+/// API key settings
+#[derive(Debug, Deserialize, Clone)]
+pub struct ApiKeyConfig {
+    /// name of the key owner, for the logs
+    pub name: String,
+    /// secret key, sent by the client as a bearer token or in the `X-Api-Key` header
+    pub key: String,
+    /// methods the key can call, empty to allow every method
+    #[serde(default)]
+    pub methods: Vec<String>,
+    /// rate limits of the key
+    #[serde(default)]
+    pub limits: RateLimits,
+}
+
+/// Access control settings of an API server
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct AccessConfig {
+    /// whether to check the credentials and the rate limits of the requests
+    #[serde(default)]
+    pub enabled: bool,
+    /// whether to accept requests without credentials
+    #[serde(default)]
+    pub allow_anonymous: bool,
+    /// methods callable without credentials, empty to allow every method
+    #[serde(default)]
+    pub anonymous_methods: Vec<String>,
+    /// rate limits of each anonymous client IP
+    #[serde(default)]
+    pub anonymous_limits: RateLimits,
+    /// accepted API keys
+    #[serde(default)]
+    pub keys: Vec<ApiKeyConfig>,
+    /// header carrying the IP of the client, when the node is behind a trusted load balancer
+    /// (e.g. `X-Real-IP`). Otherwise the IP of the connection is used when known.
+    #[serde(default)]
+    pub client_ip_header: Option<String>,
+}
+
+/// Access control errors
+#[derive(Display, thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum AccessError {
+    /// missing or unknown API key
+    Unauthorized,
+    /// method {0} is not allowed
+    Forbidden(String),
+    /// rate limit exceeded, retry later
+    RateLimited,
+    /// unknown client IP, required to rate-limit the anonymous clients
+    UnknownClientIp,
+}
+
+/// Client of an API, identified by its key or by its IP
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AccessClient {
+    /// client authenticated with the key at this index of the configuration
+    Key(usize),
+    /// anonymous client with a known IP
+    Ip(IpAddr),
+    /// anonymous client with an unknown IP, when the anonymous clients are not rate-limited
+    Anonymous,
+}
+
+/// Token bucket, allowing up to one second of budget at once
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64, now: Instant) -> Self {
+        TokenBucket {
+            tokens: rate as f64,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, rate: u64, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate as f64).min(rate as f64);
+        self.last_refill = now;
+    }
+
+    /// Whether `amount` can be consumed. An amount above the capacity is accepted when the bucket is full,
+    /// the bucket then goes into debt so that the average rate is still respected.
+    fn can_take(&self, rate: u64, amount: u64) -> bool {
+        rate == 0 || self.tokens >= amount.min(rate) as f64
+    }
+
+    fn take(&mut self, rate: u64, amount: u64) {
+        if rate > 0 {
+            self.tokens -= amount as f64;
+        }
+    }
+
+    fn is_full(&self, rate: u64) -> bool {
+        self.tokens >= rate as f64
+    }
+}
+
+/// Budgets of a client
+#[derive(Debug)]
+struct ClientBuckets {
+    requests: TokenBucket,
+    read_only_gas: TokenBucket,
+}
+
+/// Access control of an API server, shared by all its connections
+#[derive(Debug)]
+pub struct AccessControl {
+    config: AccessConfig,
+    key_indexes: HashMap<String, usize>,
+    buckets: Mutex<HashMap<AccessClient, ClientBuckets>>,
+}
+
+impl AccessControl {
+    /// Create the access control of a server. `connection_ips_known` tells whether the server gives the IP of
+    /// the connections: otherwise the anonymous clients can only be rate-limited with `client_ip_header`.
+    pub fn new(config: AccessConfig, connection_ips_known: bool) -> Result<Self, AccessError> {
+        if config.allow_anonymous
+            && !is_unlimited(&config.anonymous_limits)
+            && config.client_ip_header.is_none()
+            && !connection_ips_known
+        {
+            return Err(AccessError::UnknownClientIp);
+        }
+        let key_indexes = config
+            .keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| (key.key.clone(), index))
+            .collect();
+        Ok(AccessControl {
+            config,
+            key_indexes,
+            buckets: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Access control settings
+    pub fn config(&self) -> &AccessConfig {
+        &self.config
+    }
+
+    /// Extract the credential of a request from its `Authorization` and `X-Api-Key` headers
+    pub fn credential<'a>(
+        authorization: Option<&'a str>,
+        api_key: Option<&'a str>,
+    ) -> Option<&'a str> {
+        authorization
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .or(api_key)
+            .map(str::trim)
+    }
+
+    /// Identify a client and check that it can call the given methods, consuming one request per method.
+    /// If the methods are unknown (`None`, e.g. when opening a WebSocket connection),
+    /// the client must be allowed to call every method.
+    pub fn authorize(
+        &self,
+        credential: Option<&str>,
+        ip: Option<IpAddr>,
+        methods: Option<&[String]>,
+    ) -> Result<AccessClient, AccessError> {
+        let (client, allowed_methods) = match credential {
+            Some(credential) => {
+                let index = *self
+                    .key_indexes
+                    .get(credential)
+                    .ok_or(AccessError::Unauthorized)?;
+                (AccessClient::Key(index), &self.config.keys[index].methods)
+            }
+            None if self.config.allow_anonymous => {
+                let client = match ip {
+                    Some(ip) => AccessClient::Ip(ip),
+                    None if is_unlimited(&self.config.anonymous_limits) => AccessClient::Anonymous,
+                    None => return Err(AccessError::UnknownClientIp),
+                };
+                (client, &self.config.anonymous_methods)
+            }
+            None => return Err(AccessError::Unauthorized),
+        };
+
+        let request_count = match methods {
+            Some(methods) => {
+                if !allowed_methods.is_empty() {
+                    if let Some(method) = methods
+                        .iter()
+                        .find(|method| !allowed_methods.contains(method))
+                    {
+                        return Err(AccessError::Forbidden(method.clone()));
+                    }
+                }
+                methods.len().max(1) as u64
+            }
+            None if allowed_methods.is_empty() => 1,
+            None => return Err(AccessError::Forbidden("subscription".to_string())),
+        };
+
+        self.consume(&client, request_count, 0)?;
+        Ok(client)
+    }
+
+    /// Consume requests from the budget of an authorized client, e.g. for the messages of a stream
+    pub fn consume_requests(&self, client: &AccessClient, count: u64) -> Result<(), AccessError> {
+        self.consume(client, count, 0)
+    }
+
+    /// Consume read-only execution gas from the budget of a client
+    pub fn consume_read_only_gas(
+        &self,
+        client: &AccessClient,
+        gas: u64,
+    ) -> Result<(), AccessError> {
+        self.consume(client, 0, gas)
+    }
+
+    fn limits(&self, client: &AccessClient) -> &RateLimits {
+        match client {
+            AccessClient::Key(index) => &self.config.keys[*index].limits,
+            AccessClient::Ip(_) | AccessClient::Anonymous => &self.config.anonymous_limits,
+        }
+    }
+
+    fn consume(&self, client: &AccessClient, requests: u64, gas: u64) -> Result<(), AccessError> {
+        let limits = self.limits(client);
+        if is_unlimited(limits) {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock();
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(client) {
+            buckets.retain(|client, client_buckets| {
+                let limits = self.limits(client);
+                client_buckets
+                    .requests
+                    .refill(limits.requests_per_second, now);
+                client_buckets
+                    .read_only_gas
+                    .refill(limits.read_only_gas_per_second, now);
+                !(client_buckets.requests.is_full(limits.requests_per_second)
+                    && client_buckets
+                        .read_only_gas
+                        .is_full(limits.read_only_gas_per_second))
+            });
+        }
+        let client_buckets = buckets
+            .entry(client.clone())
+            .or_insert_with(|| ClientBuckets {
+                requests: TokenBucket::new(limits.requests_per_second, now),
+                read_only_gas: TokenBucket::new(limits.read_only_gas_per_second, now),
+            });
+
+        client_buckets
+            .requests
+            .refill(limits.requests_per_second, now);
+        client_buckets
+            .read_only_gas
+            .refill(limits.read_only_gas_per_second, now);
+        if !client_buckets
+            .requests
+            .can_take(limits.requests_per_second, requests)
+            || !client_buckets
+                .read_only_gas
+                .can_take(limits.read_only_gas_per_second, gas)
+        {
+            return Err(AccessError::RateLimited);
+        }
+        client_buckets
+            .requests
+            .take(limits.requests_per_second, requests);
+        client_buckets
+            .read_only_gas
+            .take(limits.read_only_gas_per_second, gas);
+        Ok(())
+    }
+}
+
+fn is_unlimited(limits: &RateLimits) -> bool {
+    limits.requests_per_second == 0 && limits.read_only_gas_per_second == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access_config() -> AccessConfig {
+        AccessConfig {
+            enabled: true,
+            allow_anonymous: true,
+            anonymous_methods: vec!["get_status".to_string()],
+            anonymous_limits: RateLimits {
+                requests_per_second: 2,
+                read_only_gas_per_second: 0,
+            },
+            keys: vec![ApiKeyConfig {
+                name: "partner".to_string(),
+                key: "secret".to_string(),
+                methods: vec![],
+                limits: RateLimits {
+                    requests_per_second: 0,
+                    read_only_gas_per_second: 1000,
+                },
+            }],
+            client_ip_header: None,
+        }
+    }
+
+    fn access_control() -> AccessControl {
+        AccessControl::new(access_config(), true).unwrap()
+    }
+
+    #[test]
+    fn test_access_credentials() {
+        let access = access_control();
+        let methods = ["get_addresses".to_string()];
+        assert_eq!(
+            access.authorize(Some("secret"), None, Some(&methods)),
+            Ok(AccessClient::Key(0))
+        );
+        assert_eq!(
+            access.authorize(Some("unknown"), None, Some(&methods)),
+            Err(AccessError::Unauthorized)
+        );
+        // anonymous clients can only call their allowed methods
+        let ip: IpAddr = "192.168.0.1".parse().unwrap();
+        assert_eq!(
+            access.authorize(None, Some(ip), Some(&methods)),
+            Err(AccessError::Forbidden("get_addresses".to_string()))
+        );
+        assert_eq!(
+            access.authorize(None, Some(ip), None),
+            Err(AccessError::Forbidden("subscription".to_string()))
+        );
+        assert_eq!(
+            AccessControl::credential(Some("Bearer secret"), None),
+            Some("secret")
+        );
+        assert_eq!(
+            AccessControl::credential(None, Some("secret")),
+            Some("secret")
+        );
+    }
+
+    #[test]
+    fn test_access_rate_limits() {
+        let access = access_control();
+        let methods = ["get_status".to_string()];
+        let ip_1: IpAddr = "192.168.0.1".parse().unwrap();
+        let ip_2: IpAddr = "192.168.0.2".parse().unwrap();
+        assert!(access.authorize(None, Some(ip_1), Some(&methods)).is_ok());
+        assert!(access.authorize(None, Some(ip_1), Some(&methods)).is_ok());
+        assert_eq!(
+            access.authorize(None, Some(ip_1), Some(&methods)),
+            Err(AccessError::RateLimited)
+        );
+        // each IP has its own budget
+        assert!(access.authorize(None, Some(ip_2), Some(&methods)).is_ok());
+
+        // a call above the budget is accepted once, then the budget has to be refilled
+        let client = AccessClient::Key(0);
+        assert!(access.consume_read_only_gas(&client, 1500).is_ok());
+        assert_eq!(
+            access.consume_read_only_gas(&client, 1),
+            Err(AccessError::RateLimited)
+        );
+    }
+
+    #[test]
+    fn test_access_unknown_client_ip() {
+        // rate-limited anonymous clients must be identified by their IP
+        assert_eq!(
+            AccessControl::new(access_config(), false).unwrap_err(),
+            AccessError::UnknownClientIp
+        );
+        let access = access_control();
+        let methods = ["get_status".to_string()];
+        assert_eq!(
+            access.authorize(None, None, Some(&methods)),
+            Err(AccessError::UnknownClientIp)
+        );
+
+        // without anonymous limits, they share a single client
+        let mut config = access_config();
+        config.anonymous_limits = RateLimits::default();
+        let access = AccessControl::new(config, false).unwrap();
+        assert_eq!(
+            access.authorize(None, None, Some(&methods)),
+            Ok(AccessClient::Anonymous)
+        );
+    }
+}
@@ -2,6 +2,7 @@
 
 use massa_models::address::ExecutionAddressCycleInfo;
 use massa_models::endorsement::EndorsementId;
+use massa_models::execution::{AddressRewardsEntry, AddressTransactionsEntry, RewardKind};
 use massa_models::operation::OperationId;
 use massa_models::slot::{IndexedSlot, Slot};
 use massa_models::{address::Address, amount::Amount, block_id::BlockId};
@@ -177,3 +178,164 @@ impl std::fmt::Display for AddressFilter {
         }
     }
 }
+
+/// Draws request parameters
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct AddressDrawsRequest {
+    /// addresses to get the draws of
+    pub addresses: Vec<Address>,
+    /// first cycle (inclusive), or None to start from the current cycle
+    pub start_cycle: Option<u64>,
+    /// last cycle (inclusive), or None to stop at the last drawn cycle
+    pub end_cycle: Option<u64>,
+    /// index in `addresses` of the first address of the page, 0 by default
+    pub offset: Option<usize>,
+    /// maximum number of addresses in the page, capped by the node's `max_arguments`
+    pub limit: Option<usize>,
+}
+
+/// Draws of an address in a cycle
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct CycleDraws {
+    /// cycle
+    pub cycle: u64,
+    /// upcoming block draws
+    pub next_block_draws: Vec<Slot>,
+    /// upcoming endorsement draws
+    pub next_endorsement_draws: Vec<IndexedSlot>,
+}
+
+/// Upcoming draws of an address
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct AddressDraws {
+    /// the address
+    pub address: Address,
+    /// draws of the cycles in which the address is selected, in increasing cycle order
+    pub cycles: Vec<CycleDraws>,
+}
+
+/// Page of address draws
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct AddressDrawsPage {
+    /// draws of the addresses of the page, in request order
+    pub draws: Vec<AddressDraws>,
+    /// offset of the next page, None if this is the last one
+    pub next_offset: Option<usize>,
+}
+
+impl std::fmt::Display for AddressDrawsPage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for address_draws in &self.draws {
+            writeln!(f, "Address: {}", address_draws.address)?;
+            for cycle_draws in &address_draws.cycles {
+                writeln!(f, "\tCycle {}:", cycle_draws.cycle)?;
+                writeln!(
+                    f,
+                    "\t\tBlock draws: {}",
+                    cycle_draws
+                        .next_block_draws
+                        .iter()
+                        .map(|slot| slot.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )?;
+                writeln!(
+                    f,
+                    "\t\tEndorsement draws: {}",
+                    cycle_draws
+                        .next_endorsement_draws
+                        .iter()
+                        .map(|indexed| format!("{} (index {})", indexed.slot, indexed.index))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )?;
+            }
+        }
+        if let Some(next_offset) = self.next_offset {
+            writeln!(f, "Next page offset: {}", next_offset)?;
+        }
+        Ok(())
+    }
+}
+
+/// Address rewards request parameters
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AddressRewardsRequest {
+    /// address to query
+    pub address: Address,
+    /// first cycle (inclusive), or None to start from the oldest record
+    pub start_cycle: Option<u64>,
+    /// last cycle (inclusive), or None to stop at the latest final slot
+    pub end_cycle: Option<u64>,
+    /// first slot to return (inclusive) within the cycle range, used to get the next pages
+    pub start: Option<Slot>,
+    /// maximum number of entries to return, capped by the node's `max_arguments`
+    pub limit: Option<usize>,
+}
+
+/// Page of the final rewards of an address
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AddressRewardsPage {
+    /// rewards by slot, in increasing slot order.
+    /// Slashing amounts are losses, every other amount is a credit.
+    pub entries: Vec<AddressRewardsEntry>,
+    /// start slot of the next page, None if this is the last one
+    pub next_start: Option<Slot>,
+}
+
+impl std::fmt::Display for AddressRewardsPage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "Slot {}:", entry.slot)?;
+            for reward in &entry.rewards {
+                match reward.kind {
+                    RewardKind::Slashing => writeln!(f, "\t{}: -{}", reward.kind, reward.amount)?,
+                    _ => writeln!(f, "\t{}: +{}", reward.kind, reward.amount)?,
+                }
+            }
+        }
+        if let Some(next_start) = self.next_start {
+            writeln!(f, "Next page start: {}", next_start)?;
+        }
+        Ok(())
+    }
+}
+
+/// Address transactions request parameters
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AddressTransactionsRequest {
+    /// address to query
+    pub address: Address,
+    /// first cycle (inclusive), or None to start from the oldest record
+    pub start_cycle: Option<u64>,
+    /// last cycle (inclusive), or None to stop at the latest final slot
+    pub end_cycle: Option<u64>,
+    /// first slot to return (inclusive) within the cycle range, used to get the next pages
+    pub start: Option<Slot>,
+    /// maximum number of entries to return, capped by the node's `max_arguments`
+    pub limit: Option<usize>,
+}
+
+/// Page of the final transactions sent or received by an address
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AddressTransactionsPage {
+    /// transactions by slot, in increasing slot order
+    pub entries: Vec<AddressTransactionsEntry>,
+    /// start slot of the next page, None if this is the last one
+    pub next_start: Option<Slot>,
+}
+
+impl std::fmt::Display for AddressTransactionsPage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "Slot {}:", entry.slot)?;
+            for transaction in &entry.transactions {
+                writeln!(f, "\t{}", transaction)?;
+            }
+        }
+        if let Some(next_start) = self.next_start {
+            writeln!(f, "Next page start: {}", next_start)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+use massa_async_pool::AsyncMessage;
+use massa_models::slot::Slot;
+use serde::{Deserialize, Serialize};
+
+/// Pending asynchronous message query output structure
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct AsyncMessageOutput {
+    /// slot at which the message was emitted
+    pub emission_slot: Slot,
+    /// index of the message within its emission slot
+    pub emission_index: u64,
+    /// final state of the message, None if it is not in the final pool
+    pub final_message: Option<AsyncMessage>,
+    /// candidate state of the message, None if it is not in the candidate pool
+    pub candidate_message: Option<AsyncMessage>,
+}
+
+impl std::fmt::Display for AsyncMessageOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Message emitted at slot {} (index {})",
+            self.emission_slot, self.emission_index
+        )?;
+        for (status, message) in [
+            ("final", &self.final_message),
+            ("candidate", &self.candidate_message),
+        ] {
+            match message {
+                Some(msg) => {
+                    writeln!(f, "\t{}:", status)?;
+                    writeln!(
+                        f,
+                        "\t\tsender: {}, destination: {}, function: {}",
+                        msg.sender, msg.destination, msg.function
+                    )?;
+                    writeln!(
+                        f,
+                        "\t\tvalidity: [{}, {}), max gas: {}, fee: {}, coins: {}",
+                        msg.validity_start, msg.validity_end, msg.max_gas, msg.fee, msg.coins
+                    )?;
+                    if let Some(trigger) = &msg.trigger {
+                        writeln!(
+                            f,
+                            "\t\ttrigger: address {}, datastore key {:?}",
+                            trigger.address, trigger.datastore_key
+                        )?;
+                    }
+                    writeln!(f, "\t\tcan be executed: {}", msg.can_be_executed)?;
+                }
+                None => writeln!(f, "\t{}: absent", status)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,10 +1,12 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
+use crate::access::AccessControl;
 use massa_models::amount::Amount;
 use massa_signature::KeyPair;
 use massa_time::MassaTime;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use serde::Deserialize;
 
@@ -84,4 +86,10 @@ pub struct APIConfig {
     pub deferred_credits_delta: MassaTime,
     /// minimal fees to include an operation in a block
     pub minimal_fees: Amount,
+    /// access control shared by the public and API v2 servers, None if disabled
+    #[serde(skip)]
+    pub access: Option<Arc<AccessControl>>,
+    /// access control of the private server, None if disabled
+    #[serde(skip)]
+    pub private_access: Option<Arc<AccessControl>>,
 }
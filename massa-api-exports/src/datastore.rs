@@ -1,30 +1,93 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
+use massa_ledger_exports::DatastoreDirection;
 use massa_models::address::Address;
 use serde::{Deserialize, Serialize};
 
-/// Datastore entry query input structure
+/// Datastore entry query input structure.
+/// Exactly one of `key` and `range` must be given.
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct DatastoreEntryInput {
     /// associated address of the entry
     pub address: Address,
     /// datastore key
-    pub key: Vec<u8>,
+    pub key: Option<Vec<u8>>,
+    /// page of entries to return, instead of a single key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<DatastoreRangeInput>,
 }
 
 /// Datastore entry query output structure
-#[derive(Debug, Deserialize, Clone, Serialize)]
+#[derive(Debug, Default, Deserialize, Clone, Serialize)]
 pub struct DatastoreEntryOutput {
     /// final datastore entry value
     pub final_value: Option<Vec<u8>>,
     /// candidate datastore entry value
     pub candidate_value: Option<Vec<u8>>,
+    /// page of entries, if a range was queried
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<DatastoreRangeOutput>,
 }
 
 impl std::fmt::Display for DatastoreEntryOutput {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(page) = &self.page {
+            return write!(f, "{}", page);
+        }
         writeln!(f, "final value: {:?}", self.final_value)?;
         writeln!(f, "candidate value: {:?}", self.candidate_value)?;
         Ok(())
     }
 }
+
+/// Datastore range query input structure
+#[derive(Debug, Default, Deserialize, Clone, Serialize)]
+pub struct DatastoreRangeInput {
+    /// only return keys starting with this prefix
+    #[serde(default)]
+    pub prefix: Vec<u8>,
+    /// first key of the range (included)
+    pub start_key: Option<Vec<u8>>,
+    /// end of the range (excluded)
+    pub end_key: Option<Vec<u8>>,
+    /// `next_cursor` returned by the previous page, if any
+    pub cursor: Option<Vec<u8>>,
+    /// order in which the entries are returned
+    #[serde(default)]
+    pub direction: DatastoreDirection,
+    /// maximum number of entries to return, capped by the node's `max_arguments`
+    pub limit: Option<usize>,
+    /// query the final datastore instead of the candidate one
+    #[serde(default)]
+    pub is_final: bool,
+}
+
+/// Datastore key/value pair
+#[derive(Debug, Deserialize, Clone, Serialize, PartialEq, Eq)]
+pub struct DatastoreKeyValue {
+    /// datastore key
+    pub key: Vec<u8>,
+    /// datastore value
+    pub value: Vec<u8>,
+}
+
+/// Datastore range query output structure
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct DatastoreRangeOutput {
+    /// entries of the page, in the requested order
+    pub entries: Vec<DatastoreKeyValue>,
+    /// cursor to pass to get the next page, None if this is the last one
+    pub next_cursor: Option<Vec<u8>>,
+}
+
+impl std::fmt::Display for DatastoreRangeOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "key: {:?}, value: {:?}", entry.key, entry.value)?;
+        }
+        if let Some(cursor) = &self.next_cursor {
+            writeln!(f, "next cursor: {:?}", cursor)?;
+        }
+        Ok(())
+    }
+}
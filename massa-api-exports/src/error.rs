@@ -3,6 +3,7 @@
 use displaydoc::Display;
 use jsonrpsee::types::{ErrorObject, ErrorObjectOwned};
 
+use crate::access::AccessError;
 use massa_hash::MassaHashError;
 use massa_models::error::ModelsError;
 use massa_time::TimeError;
@@ -47,6 +48,8 @@ pub enum ApiError {
     InternalServerError(String),
     /// Versioning Factory error: {0}
     FactoryError(#[from] FactoryError),
+    /// Access error: {0}
+    AccessError(#[from] AccessError),
 }
 
 impl From<ApiError> for ErrorObjectOwned {
@@ -70,6 +73,7 @@ impl From<ApiError> for ErrorObjectOwned {
             ApiError::MissingConfig(_) => -32018,
             ApiError::WrongAPI => -32019,
             ApiError::FactoryError(_) => -32020,
+            ApiError::AccessError(_) => -32021,
         };
 
         ErrorObject::owned(code, err.to_string(), None::<()>)
@@ -1,7 +1,12 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
+use massa_db_exports::StateProof;
+use massa_hash::Hash;
+use massa_ledger_exports::{AddressHistoryEntry, KeyType};
+use massa_models::address::Address;
 use massa_models::amount::Amount;
 use massa_models::ledger::LedgerData;
+use massa_models::slot::Slot;
 
 use serde::{Deserialize, Serialize};
 
@@ -28,3 +33,135 @@ impl std::fmt::Display for LedgerInfo {
         Ok(())
     }
 }
+
+/// Address history request parameters
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AddressHistoryRequest {
+    /// address to query
+    pub address: Address,
+    /// first slot to return (inclusive), or None to start from the oldest record
+    pub start: Option<Slot>,
+    /// maximum number of entries to return, capped by the node's `max_arguments`
+    pub limit: Option<usize>,
+}
+
+/// Page of the final ledger changes of an address
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AddressHistoryPage {
+    /// recorded changes, in increasing slot order
+    pub entries: Vec<AddressHistoryEntry>,
+    /// start slot of the next page, None if this is the last one
+    pub next_start: Option<Slot>,
+}
+
+impl std::fmt::Display for AddressHistoryPage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "Slot {}:", entry.slot)?;
+            if let Some(balance) = &entry.balance {
+                writeln!(
+                    f,
+                    "	balance: {} -> {}",
+                    display_option(&balance.before),
+                    display_option(&balance.after)
+                )?;
+            }
+            if let Some(bytecode) = &entry.bytecode {
+                writeln!(
+                    f,
+                    "	bytecode: {} -> {} bytes",
+                    bytecode.before.as_ref().map_or(0, |b| b.0.len()),
+                    bytecode.after.as_ref().map_or(0, |b| b.0.len())
+                )?;
+            }
+            for (key, change) in &entry.datastore {
+                writeln!(
+                    f,
+                    "	datastore key {:?}: {:?} -> {:?}",
+                    key, change.before, change.after
+                )?;
+            }
+        }
+        if let Some(next_start) = self.next_start {
+            writeln!(f, "Next page starts at slot {}", next_start)?;
+        }
+        Ok(())
+    }
+}
+
+/// Ledger sub-entry of an address
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerSubEntryRequest {
+    /// balance of the address
+    Balance,
+    /// bytecode of the address
+    Bytecode,
+    /// datastore entry of the address
+    Datastore {
+        /// datastore key
+        key: Vec<u8>,
+    },
+}
+
+impl From<LedgerSubEntryRequest> for KeyType {
+    fn from(value: LedgerSubEntryRequest) -> Self {
+        match value {
+            LedgerSubEntryRequest::Balance => KeyType::BALANCE,
+            LedgerSubEntryRequest::Bytecode => KeyType::BYTECODE,
+            LedgerSubEntryRequest::Datastore { key } => KeyType::DATASTORE(key),
+        }
+    }
+}
+
+/// Ledger entry proof request parameters
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LedgerEntryProofRequest {
+    /// address of the ledger entry
+    pub address: Address,
+    /// sub-entry to prove
+    pub sub_entry: LedgerSubEntryRequest,
+}
+
+/// Proof of the value (or absence) of a final ledger sub-entry, against the root of the node.
+/// The root is computed by the node and is not committed in blocks, so the proof does not
+/// remove the need to trust the node: check the root against the roots of other nodes at the
+/// same slot before relying on it.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LedgerEntryProof {
+    /// final slot at which the proof was built
+    pub slot: Slot,
+    /// root of the state proof tree computed by the node at that slot, not committed on chain
+    pub node_root: Hash,
+    /// proof of the serialized ledger key and value, checked with `StateProof::verify(&node_root)`
+    pub proof: StateProof,
+}
+
+impl std::fmt::Display for LedgerEntryProof {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Final slot: {}", self.slot)?;
+        writeln!(
+            f,
+            "State proof root of the node (not committed on chain): {}",
+            self.node_root
+        )?;
+        match &self.proof.value {
+            Some(value) => writeln!(f, "Value: {:?}", value)?,
+            None => writeln!(f, "Value: absent")?,
+        }
+        writeln!(f, "Proof depth: {}", self.proof.siblings.len())?;
+        writeln!(
+            f,
+            "Matches the root of the node: {}",
+            self.proof.verify(&self.node_root)
+        )?;
+        Ok(())
+    }
+}
+
+/// Display an optional value, or "none"
+fn display_option<T: std::fmt::Display>(value: &Option<T>) -> String {
+    value
+        .as_ref()
+        .map_or_else(|| "none".to_string(), |v| v.to_string())
+}
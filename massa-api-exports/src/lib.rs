@@ -9,8 +9,12 @@ use crate::page::PageRequest;
 use massa_time::MassaTime;
 use serde::{Deserialize, Serialize};
 
+/// access control
+pub mod access;
 /// address related structures
 pub mod address;
+/// asynchronous messages
+pub mod async_message;
 /// block-related structures
 pub mod block;
 /// node configuration
@@ -2,9 +2,12 @@
 
 use massa_models::amount::Amount;
 use massa_models::node::NodeId;
-use massa_models::stats::{ConsensusStats, ExecutionStats, NetworkStats};
+use massa_models::stats::{
+    ConsensusStats, ExecutionStats, MissedSlot, NetworkStats, StakerMissStats,
+};
 use massa_models::{config::CompactConfig, slot::Slot, version::Version};
 use massa_time::MassaTime;
+use massa_versioning::mip_votes::MipVote;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::net::IpAddr;
@@ -94,3 +97,77 @@ impl std::fmt::Display for NodeStatus {
         Ok(())
     }
 }
+
+/// blocks and endorsements missed by the staking addresses of the node
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MissedSlotsInfo {
+    /// latest missed slots, from oldest to latest
+    pub missed_slots: Vec<MissedSlot>,
+    /// statistics of the latest cycles
+    pub stats: Vec<StakerMissStats>,
+}
+
+impl std::fmt::Display for MissedSlotsInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Missed slots:")?;
+        for missed_slot in &self.missed_slots {
+            writeln!(f, "\t{}", missed_slot)?;
+        }
+        for stats in &self.stats {
+            write!(f, "{}", stats)?;
+        }
+        Ok(())
+    }
+}
+
+/// status of a MIP known by the node, with the vote of the node operator
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MipStatus {
+    /// MIP name
+    pub name: String,
+    /// network version
+    pub version: u32,
+    /// versions of the components concerned by the MIP, by component name
+    pub components: BTreeMap<String, u32>,
+    /// timestamp at which the MIP can start being announced
+    pub start: MassaTime,
+    /// timestamp at which the deployment is considered failed
+    pub timeout: MassaTime,
+    /// once locked in, delay before the MIP is considered active
+    pub activation_delay: MassaTime,
+    /// current state: defined, started, locked in, active, failed or error
+    pub state: String,
+    /// vote of the node operator, if any
+    pub vote: Option<MipVote>,
+}
+
+impl std::fmt::Display for MipStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "MIP {} (version {}):", self.name, self.version)?;
+        writeln!(f, "\tState: {}", self.state)?;
+        writeln!(
+            f,
+            "\tStart: {}, timeout: {}",
+            self.start.format_instant(),
+            self.timeout.format_instant()
+        )?;
+        for (component, version) in &self.components {
+            writeln!(f, "\tComponent {}: version {}", component, version)?;
+        }
+        match self.vote {
+            Some(MipVote::For) => writeln!(f, "\tVote: for")?,
+            Some(MipVote::Against) => writeln!(f, "\tVote: against")?,
+            None => writeln!(f, "\tVote: none")?,
+        }
+        Ok(())
+    }
+}
+
+/// vote of the node operator on a MIP
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MipVoteInput {
+    /// MIP name
+    pub name: String,
+    /// vote, none to remove the vote
+    pub vote: Option<MipVote>,
+}
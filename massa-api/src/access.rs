@@ -0,0 +1,303 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Front HTTP server checking the API keys, method allow-lists and rate limits of the JSON-RPC requests.
+//! See `massa_api_exports::access` for the access rules.
+//!
+//! jsonrpsee does not give the remote address of a connection to its middlewares. When the access control is
+//! enabled, the JSON-RPC server listens on a loopback address, behind this front server which knows the IP of
+//! each accepted connection. The forwarded requests carry a random bearer token checked by the JSON-RPC server,
+//! so that only the front server can call it.
+//!
+//! The body of the HTTP requests is parsed to get the called methods and the gas of the read-only executions.
+//! WebSocket connections are checked when they are opened, then relayed as such: as their messages are not
+//! inspected, only the clients allowed to call every method can open them, and the `requests_per_second` limit
+//! does not apply to their messages.
+
+use futures::future::{try_join, BoxFuture};
+use hyper::body::{Bytes, HttpBody};
+use hyper::client::HttpConnector;
+use hyper::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, UPGRADE};
+use hyper::server::conn::AddrStream;
+use hyper::service::make_service_fn;
+use hyper::{Body, Client, Method, Request, Response, Server, StatusCode};
+use jsonrpsee::types::ErrorObjectOwned;
+use massa_api_exports::access::{AccessControl, AccessError};
+use massa_api_exports::error::ApiError;
+use serde_json::Value;
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tower::Service;
+use tracing::{debug, warn};
+
+/// Methods running read-only executions, whose gas is counted in the rate limits
+const READ_ONLY_METHODS: [&str; 2] = ["execute_read_only_bytecode", "execute_read_only_call"];
+
+/// Generate the token authenticating the front server to the JSON-RPC server
+pub(crate) fn backend_token() -> String {
+    let bytes: [u8; 32] = rand::random();
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Start the front server of the JSON-RPC server listening on `backend`, the access control being possibly
+/// shared with other servers. It stops gracefully when `shutdown` is triggered.
+pub(crate) fn start_front_server(
+    url: &SocketAddr,
+    backend: SocketAddr,
+    token: &str,
+    control: Arc<AccessControl>,
+    max_request_body_size: u32,
+    shutdown: oneshot::Receiver<()>,
+) -> Result<JoinHandle<()>, hyper::Error> {
+    let client = Client::new();
+    let authorization = HeaderValue::from_str(&format!("Bearer {}", token))
+        .expect("critical: invalid API front server token");
+
+    // called for each accepted connection, whose remote address is known
+    let make_service = make_service_fn(move |connection: &AddrStream| {
+        let service = AccessService {
+            client: client.clone(),
+            backend,
+            authorization: authorization.clone(),
+            control: control.clone(),
+            max_request_body_size,
+            remote_ip: connection.remote_addr().ip(),
+        };
+        async move { Ok::<_, Infallible>(service) }
+    });
+    let server = Server::try_bind(url)?
+        .serve(make_service)
+        .with_graceful_shutdown(async {
+            let _ = shutdown.await;
+        });
+
+    Ok(tokio::spawn(async move {
+        if let Err(err) = server.await {
+            warn!("API front server error: {}", err);
+        }
+    }))
+}
+
+/// Service checking the access rules before forwarding the requests of a connection to the JSON-RPC server
+#[derive(Clone)]
+struct AccessService {
+    client: Client<HttpConnector>,
+    backend: SocketAddr,
+    authorization: HeaderValue,
+    control: Arc<AccessControl>,
+    max_request_body_size: u32,
+    remote_ip: IpAddr,
+}
+
+impl Service<Request<Body>> for AccessService {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move { Ok(service.handle(request).await) })
+    }
+}
+
+impl AccessService {
+    async fn handle(self, request: Request<Body>) -> Response<Body> {
+        // CORS preflight requests do not call any method
+        if request.method() == Method::OPTIONS {
+            return self.forward(request).await;
+        }
+
+        let headers = request.headers();
+        let credential = AccessControl::credential(
+            header_str(headers, AUTHORIZATION.as_str()),
+            header_str(headers, "x-api-key"),
+        )
+        .map(str::to_string);
+        let ip = match &self.control.config().client_ip_header {
+            Some(header) => client_ip(headers, header),
+            None => Some(self.remote_ip),
+        };
+
+        if is_websocket_upgrade(headers) {
+            return match self.control.authorize(credential.as_deref(), ip, None) {
+                Ok(_) => self.forward_upgrade(request).await,
+                Err(err) => error_response(err),
+            };
+        }
+
+        let (parts, body) = request.into_parts();
+        let body = match read_body(body, self.max_request_body_size as usize).await {
+            Some(body) => body,
+            None => return status_response(StatusCode::PAYLOAD_TOO_LARGE),
+        };
+        let (methods, read_only_gas) = parse_calls(&body);
+        let checked = self
+            .control
+            .authorize(credential.as_deref(), ip, Some(&methods))
+            .and_then(|client| {
+                if read_only_gas > 0 {
+                    self.control.consume_read_only_gas(&client, read_only_gas)
+                } else {
+                    Ok(())
+                }
+            });
+        match checked {
+            Ok(()) => {
+                self.forward(Request::from_parts(parts, Body::from(body)))
+                    .await
+            }
+            Err(err) => error_response(err),
+        }
+    }
+
+    /// Forward a request to the JSON-RPC server, replacing its credential by the token of the front server
+    async fn forward(&self, request: Request<Body>) -> Response<Body> {
+        let (mut parts, body) = request.into_parts();
+        let path = parts
+            .uri
+            .path_and_query()
+            .map_or("/", |path_and_query| path_and_query.as_str());
+        parts.uri = match format!("http://{}{}", self.backend, path).parse() {
+            Ok(uri) => uri,
+            Err(_) => return status_response(StatusCode::BAD_REQUEST),
+        };
+        parts.headers.remove("x-api-key");
+        parts
+            .headers
+            .insert(AUTHORIZATION, self.authorization.clone());
+
+        match self.client.request(Request::from_parts(parts, body)).await {
+            Ok(response) => response,
+            Err(err) => {
+                warn!(
+                    "API front server could not reach the JSON-RPC server: {}",
+                    err
+                );
+                status_response(StatusCode::BAD_GATEWAY)
+            }
+        }
+    }
+
+    /// Forward a WebSocket upgrade request, then relay the connection once upgraded
+    async fn forward_upgrade(&self, mut request: Request<Body>) -> Response<Body> {
+        let client_upgrade = hyper::upgrade::on(&mut request);
+        let mut response = self.forward(request).await;
+        if response.status() == StatusCode::SWITCHING_PROTOCOLS {
+            let server_upgrade = hyper::upgrade::on(&mut response);
+            tokio::spawn(async move {
+                match try_join(client_upgrade, server_upgrade).await {
+                    Ok((mut client, mut server)) => {
+                        if let Err(err) =
+                            tokio::io::copy_bidirectional(&mut client, &mut server).await
+                        {
+                            debug!("API WebSocket connection closed: {}", err);
+                        }
+                    }
+                    Err(err) => debug!("API WebSocket upgrade failed: {}", err),
+                }
+            });
+        }
+        response
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+/// Get the client IP from a header set by a trusted load balancer (the first one of a forwarding chain)
+fn client_ip(headers: &HeaderMap, header: &str) -> Option<IpAddr> {
+    header_str(headers, header)?
+        .split(',')
+        .next()
+        .and_then(|ip| ip.trim().parse().ok())
+}
+
+fn is_websocket_upgrade(headers: &HeaderMap) -> bool {
+    header_str(headers, UPGRADE.as_str())
+        .map_or(false, |upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+}
+
+/// Read a request body, or None if it exceeds the maximum size
+async fn read_body(mut body: Body, max_size: usize) -> Option<Bytes> {
+    let mut buffer = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.ok()?;
+        if buffer.len() + chunk.len() > max_size {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk);
+    }
+    Some(Bytes::from(buffer))
+}
+
+/// Get the methods called by a single or batch JSON-RPC request, and the total gas of its read-only executions.
+/// Invalid requests are forwarded as such, and rejected by the server.
+fn parse_calls(body: &[u8]) -> (Vec<String>, u64) {
+    let calls = match serde_json::from_slice::<Value>(body) {
+        Ok(Value::Array(calls)) => calls,
+        Ok(call) => vec![call],
+        Err(_) => Vec::new(),
+    };
+    let mut methods = Vec::with_capacity(calls.len());
+    let mut read_only_gas: u64 = 0;
+    for call in calls {
+        let method = call
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        if READ_ONLY_METHODS.contains(&method) {
+            // positional or named parameters, each execution having its `max_gas`
+            let executions = call
+                .get("params")
+                .and_then(|params| params.get(0).or_else(|| params.get("arg")))
+                .and_then(Value::as_array);
+            for execution in executions.into_iter().flatten() {
+                let max_gas = execution
+                    .get("max_gas")
+                    .and_then(Value::as_u64)
+                    .unwrap_or_default();
+                read_only_gas = read_only_gas.saturating_add(max_gas);
+            }
+        }
+        methods.push(method.to_string());
+    }
+    (methods, read_only_gas)
+}
+
+/// Response without body
+fn status_response(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .expect("critical: invalid response")
+}
+
+/// JSON-RPC error response of a refused request
+fn error_response(err: AccessError) -> Response<Body> {
+    debug!("refused API request: {}", err);
+    let status = match err {
+        AccessError::Unauthorized => StatusCode::UNAUTHORIZED,
+        AccessError::Forbidden(_) => StatusCode::FORBIDDEN,
+        AccessError::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+        AccessError::UnknownClientIp => StatusCode::UNAUTHORIZED,
+    };
+    let error: ErrorObjectOwned = ApiError::AccessError(err).into();
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "error": error,
+        "id": Value::Null,
+    });
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("critical: invalid response")
+}
@@ -53,7 +53,7 @@ impl ApiServer for API<ApiV2> {
         url: &SocketAddr,
         api_config: &APIConfig,
     ) -> Result<StopHandle, JsonRpseeError> {
-        crate::serve(self.into_rpc(), url, api_config).await
+        crate::serve(self.into_rpc(), url, api_config, api_config.access.clone()).await
     }
 }
 
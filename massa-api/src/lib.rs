@@ -13,20 +13,30 @@ use jsonrpsee::server::{BatchRequestConfig, ServerBuilder, ServerHandle};
 use jsonrpsee::RpcModule;
 use massa_api_exports::execution::Transfer;
 use massa_api_exports::{
-    address::{AddressFilter, AddressInfo},
+    access::AccessControl,
+    address::{
+        AddressDrawsPage, AddressDrawsRequest, AddressFilter, AddressInfo, AddressRewardsPage,
+        AddressRewardsRequest, AddressTransactionsPage, AddressTransactionsRequest,
+    },
+    async_message::AsyncMessageOutput,
     block::{BlockInfo, BlockSummary},
     config::APIConfig,
     datastore::{DatastoreEntryInput, DatastoreEntryOutput},
     endorsement::EndorsementInfo,
     error::ApiError::WrongAPI,
     execution::{ExecuteReadOnlyResponse, ReadOnlyBytecodeExecution, ReadOnlyCall},
-    node::NodeStatus,
+    ledger::{
+        AddressHistoryPage, AddressHistoryRequest, LedgerEntryProof, LedgerEntryProofRequest,
+    },
+    node::{MipStatus, MipVoteInput, MissedSlotsInfo, NodeStatus},
     operation::{OperationInfo, OperationInput},
     page::{PageRequest, PagedVec},
     TimeInterval,
 };
+use massa_async_pool::AsyncMessageFilter;
 use massa_consensus_exports::{ConsensusBroadcasts, ConsensusController};
 use massa_execution_exports::ExecutionController;
+use massa_factory_exports::MissedSlots;
 use massa_models::clique::Clique;
 use massa_models::composite::PubkeySig;
 use massa_models::node::NodeId;
@@ -41,13 +51,19 @@ use massa_pool_exports::{PoolBroadcasts, PoolController};
 use massa_pos_exports::SelectorController;
 use massa_protocol_exports::{ProtocolConfig, ProtocolController};
 use massa_storage::Storage;
-use massa_versioning::keypair_factory::KeyPairFactory;
+use massa_versioning::{
+    keypair_factory::KeyPairFactory, mip_votes::MipVotes, versioning::MipStore,
+};
 use massa_wallet::Wallet;
 use parking_lot::RwLock;
 use serde_json::Value;
 use std::net::{IpAddr, SocketAddr};
 use std::sync::{Arc, Condvar, Mutex};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tower_http::auth::require_authorization::Bearer;
 use tower_http::cors::{Any, CorsLayer};
+use tower_http::validate_request::ValidateRequestHeaderLayer;
 use tracing::{info, warn};
 
 #[cfg(feature = "test-exports")]
@@ -55,6 +71,7 @@ use massa_channel as _;
 #[cfg(feature = "test-exports")]
 use massa_grpc as _;
 
+mod access;
 mod api;
 mod api_trait;
 mod private;
@@ -102,6 +119,12 @@ pub struct Private {
     pub stop_cv: Arc<(Mutex<bool>, Condvar)>,
     /// User wallet
     pub node_wallet: Arc<RwLock<Wallet>>,
+    /// slots missed by the staking addresses
+    pub missed_slots: MissedSlots,
+    /// MIP store
+    pub mip_store: MipStore,
+    /// votes of the node operator on MIPs
+    pub mip_votes: MipVotes,
 }
 
 /// API v2 content
@@ -149,6 +172,7 @@ async fn serve<T>(
     api: RpcModule<T>,
     url: &SocketAddr,
     api_config: &APIConfig,
+    access: Option<Arc<AccessControl>>,
 ) -> Result<StopHandle, JsonRpseeError> {
     let mut server_builder = ServerBuilder::new()
         .max_request_body_size(api_config.max_request_body_size)
@@ -188,30 +212,76 @@ async fn serve<T>(
 
     let allowed_hosts = HostFilterLayer::new(hosts).expect("failed to build allowed hosts filter");
 
+    // with the access control, the server is only reachable through its front server
+    let token = access.as_ref().map(|_| access::backend_token());
+    let token_check: Option<ValidateRequestHeaderLayer<Bearer<hyper::Body>>> =
+        token.as_deref().map(ValidateRequestHeaderLayer::bearer);
+
     let middleware = tower::ServiceBuilder::new()
         .layer(cors)
-        .layer(allowed_hosts);
+        .layer(allowed_hosts)
+        .option_layer(token_check);
 
-    let server = server_builder
-        .set_middleware(middleware)
-        .build(url)
-        .await
-        .expect("failed to build server");
+    let server_builder = server_builder.set_middleware(middleware);
+    let server = match access {
+        Some(_) => server_builder.build(loopback_address(url)).await,
+        None => server_builder.build(url).await,
+    }
+    .expect("failed to build server");
+
+    let front_server = match (access, token) {
+        (Some(control), Some(token)) => {
+            let backend = server
+                .local_addr()
+                .expect("failed to get the API server address");
+            let (shutdown_tx, shutdown_rx) = oneshot::channel();
+            let handle = access::start_front_server(
+                url,
+                backend,
+                &token,
+                control,
+                api_config.max_request_body_size,
+                shutdown_rx,
+            )
+            .expect("failed to build the API front server");
+            Some((shutdown_tx, handle))
+        }
+        _ => None,
+    };
 
     let server_handler = server.start(api);
-    let stop_handler = StopHandle { server_handler };
+    let stop_handler = StopHandle {
+        server_handler,
+        front_server,
+    };
 
     Ok(stop_handler)
 }
 
+/// Loopback address of the same family as `url`, on a port chosen by the system
+fn loopback_address(url: &SocketAddr) -> SocketAddr {
+    match url {
+        SocketAddr::V4(_) => SocketAddr::new(IpAddr::V4(std::net::Ipv4Addr::LOCALHOST), 0),
+        SocketAddr::V6(_) => SocketAddr::new(IpAddr::V6(std::net::Ipv6Addr::LOCALHOST), 0),
+    }
+}
+
 /// Used to be able to stop the API
 pub struct StopHandle {
     server_handler: ServerHandle,
+    /// stop signal and task of the front server of the access control
+    front_server: Option<(oneshot::Sender<()>, JoinHandle<()>)>,
 }
 
 impl StopHandle {
     /// stop the API gracefully
     pub async fn stop(self) {
+        if let Some((shutdown_tx, handle)) = self.front_server {
+            let _ = shutdown_tx.send(());
+            if let Err(err) = handle.await {
+                warn!("API front server panicked: {:?}", err);
+            }
+        }
         match self.server_handler.stop() {
             Ok(_) => {
                 info!("API stop signal sent successfully");
@@ -262,6 +332,20 @@ pub trait MassaRpc {
     #[method(name = "get_staking_addresses")]
     async fn get_staking_addresses(&self) -> RpcResult<PreHashSet<Address>>;
 
+    /// Return the blocks and endorsements missed by the given staking addresses, all of them if empty,
+    /// with the reason of each miss.
+    #[method(name = "get_missed_slots")]
+    async fn get_missed_slots(&self, arg: Vec<Address>) -> RpcResult<MissedSlotsInfo>;
+
+    /// Return the status of the MIPs known by the node, with the votes of the node operator.
+    #[method(name = "get_mip_status")]
+    async fn get_mip_status(&self) -> RpcResult<Vec<MipStatus>>;
+
+    /// Vote for or against a MIP, or remove the vote.
+    /// Votes choose the network version announced in the blocks produced by the node.
+    #[method(name = "node_set_mip_vote")]
+    async fn node_set_mip_vote(&self, arg: MipVoteInput) -> RpcResult<()>;
+
     /// Bans given IP address(es).
     /// No confirmation to expect.
     #[method(name = "node_ban_by_ip")]
@@ -365,6 +449,8 @@ pub trait MassaRpc {
     async fn get_graph_interval(&self, arg: TimeInterval) -> RpcResult<Vec<BlockSummary>>;
 
     /// Get multiple datastore entries.
+    /// An input can also ask for a page of the entries of an address, optionally restricted to a key prefix and range:
+    /// pages are chained by passing the returned `next_cursor` to the next request.
     #[method(name = "get_datastore_entries")]
     async fn get_datastore_entries(
         &self,
@@ -397,6 +483,58 @@ pub trait MassaRpc {
     async fn get_filtered_sc_output_event(&self, arg: EventFilter)
         -> RpcResult<Vec<SCOutputEvent>>;
 
+    /// Get the pending asynchronous messages optionally filtered by:
+    /// * sender address
+    /// * destination address
+    /// * validity slot range
+    /// * trigger address
+    /// * whether they can be executed
+    #[method(name = "get_async_messages")]
+    async fn get_async_messages(
+        &self,
+        arg: AsyncMessageFilter,
+    ) -> RpcResult<Vec<AsyncMessageOutput>>;
+
+    /// Get a page of the final ledger changes (balance, bytecode, datastore) of an address,
+    /// with their before/after values. Requires the node to record the address history.
+    #[method(name = "get_address_ledger_history")]
+    async fn get_address_ledger_history(
+        &self,
+        arg: AddressHistoryRequest,
+    ) -> RpcResult<AddressHistoryPage>;
+
+    /// Get a proof of the value (or absence) of a final ledger sub-entry,
+    /// against the state proof tree root computed by the node, which is not committed on chain.
+    /// Requires the node to maintain state proofs.
+    #[method(name = "get_ledger_entry_proof")]
+    async fn get_ledger_entry_proof(
+        &self,
+        arg: LedgerEntryProofRequest,
+    ) -> RpcResult<LedgerEntryProof>;
+
+    /// Get a page of the upcoming block and endorsement draws of a list of addresses,
+    /// optionally restricted to a cycle range.
+    #[method(name = "get_addresses_draws")]
+    async fn get_addresses_draws(&self, arg: AddressDrawsRequest) -> RpcResult<AddressDrawsPage>;
+
+    /// Get a page of the final rewards (block creation, endorsements, deferred credits)
+    /// and slashing losses of an address, optionally restricted to a cycle range.
+    /// Requires the node to record the reward history.
+    #[method(name = "get_address_rewards")]
+    async fn get_address_rewards(
+        &self,
+        arg: AddressRewardsRequest,
+    ) -> RpcResult<AddressRewardsPage>;
+
+    /// Get a page of the final operations and coin transfers sent or received by an address,
+    /// optionally restricted to a cycle range.
+    /// Requires the node to record the transaction history.
+    #[method(name = "get_address_transactions")]
+    async fn get_address_transactions(
+        &self,
+        arg: AddressTransactionsRequest,
+    ) -> RpcResult<AddressTransactionsPage>;
+
     /// Get OpenRPC specification.
     #[method(name = "rpc.discover")]
     async fn get_openrpc_spec(&self) -> RpcResult<Value>;
@@ -5,20 +5,28 @@ use crate::{MassaRpcServer, Private, RpcServer, StopHandle, Value, API};
 use async_trait::async_trait;
 use jsonrpsee::core::{Error as JsonRpseeError, RpcResult};
 use massa_api_exports::{
-    address::{AddressFilter, AddressInfo},
+    address::{
+        AddressDrawsPage, AddressDrawsRequest, AddressFilter, AddressInfo, AddressRewardsPage,
+        AddressRewardsRequest, AddressTransactionsPage, AddressTransactionsRequest,
+    },
+    async_message::AsyncMessageOutput,
     block::{BlockInfo, BlockSummary},
     config::APIConfig,
     datastore::{DatastoreEntryInput, DatastoreEntryOutput},
     endorsement::EndorsementInfo,
     error::ApiError,
     execution::{ExecuteReadOnlyResponse, ReadOnlyBytecodeExecution, ReadOnlyCall, Transfer},
-    node::NodeStatus,
+    ledger::{
+        AddressHistoryPage, AddressHistoryRequest, LedgerEntryProof, LedgerEntryProofRequest,
+    },
+    node::{MipStatus, MipVoteInput, MissedSlotsInfo, NodeStatus},
     operation::{OperationInfo, OperationInput},
     page::{PageRequest, PagedVec},
     ListType, ScrudOperation, TimeInterval,
 };
+use massa_async_pool::AsyncMessageFilter;
 use massa_execution_exports::ExecutionController;
-use massa_hash::Hash;
+use massa_factory_exports::MissedSlots;
 use massa_models::{
     address::Address, block::Block, block_id::BlockId, clique::Clique, composite::PubkeySig,
     endorsement::EndorsementId, execution::EventFilter, node::NodeId, operation::OperationId,
@@ -26,7 +34,8 @@ use massa_models::{
 };
 use massa_protocol_exports::{PeerId, ProtocolController};
 use massa_signature::KeyPair;
-use massa_wallet::Wallet;
+use massa_versioning::{mip_votes::MipVotes, versioning::MipStore};
+use massa_wallet::{Signer, Wallet};
 use parking_lot::RwLock;
 use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
@@ -46,6 +55,9 @@ impl API<Private> {
         api_settings: APIConfig,
         stop_cv: Arc<(Mutex<bool>, Condvar)>,
         node_wallet: Arc<RwLock<Wallet>>,
+        missed_slots: MissedSlots,
+        mip_store: MipStore,
+        mip_votes: MipVotes,
     ) -> Self {
         API(Private {
             protocol_controller,
@@ -53,6 +65,9 @@ impl API<Private> {
             api_settings,
             stop_cv,
             node_wallet,
+            missed_slots,
+            mip_store,
+            mip_votes,
         })
     }
 }
@@ -64,7 +79,13 @@ impl RpcServer for API<Private> {
         url: &SocketAddr,
         settings: &APIConfig,
     ) -> Result<StopHandle, JsonRpseeError> {
-        crate::serve(self.into_rpc(), url, settings).await
+        crate::serve(
+            self.into_rpc(),
+            url,
+            settings,
+            settings.private_access.clone(),
+        )
+        .await
     }
 }
 
@@ -78,22 +99,10 @@ impl MassaRpcServer for API<Private> {
     }
 
     async fn node_sign_message(&self, message: Vec<u8>) -> RpcResult<PubkeySig> {
-        let signature = match self
-            .0
-            .api_settings
-            .keypair
-            .sign(&Hash::compute_from(&message))
-        {
-            Ok(signature) => signature,
-            Err(e) => {
-                return Err(
-                    ApiError::InconsistencyError(format!("error signing message: {}", e)).into(),
-                );
-            }
-        };
-        Ok(PubkeySig {
-            public_key: self.0.api_settings.keypair.get_public_key(),
-            signature,
+        let signer: &dyn Signer = &self.0.api_settings.keypair;
+        let address = Address::from_public_key(&self.0.api_settings.keypair.get_public_key());
+        signer.sign_message(&address, &message).map_err(|e| {
+            ApiError::InconsistencyError(format!("error signing message: {}", e)).into()
         })
     }
 
@@ -147,6 +156,56 @@ impl MassaRpcServer for API<Private> {
         Ok(w_wallet.get_wallet_address_list())
     }
 
+    async fn get_missed_slots(&self, addresses: Vec<Address>) -> RpcResult<MissedSlotsInfo> {
+        if addresses.len() as u64 > self.0.api_settings.max_arguments {
+            return Err(ApiError::BadRequest("too many arguments".into()).into());
+        }
+        Ok(MissedSlotsInfo {
+            missed_slots: self.0.missed_slots.get_missed_slots(&addresses),
+            stats: self.0.missed_slots.get_stats(&addresses),
+        })
+    }
+
+    async fn get_mip_status(&self) -> RpcResult<Vec<MipStatus>> {
+        let votes = self.0.mip_votes.get_votes();
+        Ok(self
+            .0
+            .mip_store
+            .get_mip_status()
+            .into_iter()
+            .map(|(mip_info, state_id)| MipStatus {
+                vote: votes.get(&mip_info.name).copied(),
+                name: mip_info.name,
+                version: mip_info.version,
+                components: mip_info
+                    .components
+                    .into_iter()
+                    .map(|(component, version)| (format!("{:?}", component), version))
+                    .collect(),
+                start: mip_info.start,
+                timeout: mip_info.timeout,
+                activation_delay: mip_info.activation_delay,
+                state: format!("{:?}", state_id),
+            })
+            .collect())
+    }
+
+    async fn node_set_mip_vote(&self, arg: MipVoteInput) -> RpcResult<()> {
+        if !self
+            .0
+            .mip_store
+            .get_mip_status()
+            .keys()
+            .any(|mip_info| mip_info.name == arg.name)
+        {
+            return Err(ApiError::BadRequest(format!("unknown MIP {}", arg.name)).into());
+        }
+        self.0
+            .mip_votes
+            .set_vote(arg.name, arg.vote)
+            .map_err(|e| ApiError::InternalServerError(e.to_string()).into())
+    }
+
     async fn node_ban_by_ip(&self, _ips: Vec<IpAddr>) -> RpcResult<()> {
         //TODO: Reinvoke
         // let network_command_sender = self.0.network_command_sender.clone();
@@ -254,6 +313,42 @@ impl MassaRpcServer for API<Private> {
         crate::wrong_api::<Vec<SCOutputEvent>>()
     }
 
+    async fn get_async_messages(
+        &self,
+        _: AsyncMessageFilter,
+    ) -> RpcResult<Vec<AsyncMessageOutput>> {
+        crate::wrong_api::<Vec<AsyncMessageOutput>>()
+    }
+
+    async fn get_address_ledger_history(
+        &self,
+        _: AddressHistoryRequest,
+    ) -> RpcResult<AddressHistoryPage> {
+        crate::wrong_api::<AddressHistoryPage>()
+    }
+
+    async fn get_ledger_entry_proof(
+        &self,
+        _: LedgerEntryProofRequest,
+    ) -> RpcResult<LedgerEntryProof> {
+        crate::wrong_api::<LedgerEntryProof>()
+    }
+
+    async fn get_addresses_draws(&self, _: AddressDrawsRequest) -> RpcResult<AddressDrawsPage> {
+        crate::wrong_api::<AddressDrawsPage>()
+    }
+
+    async fn get_address_rewards(&self, _: AddressRewardsRequest) -> RpcResult<AddressRewardsPage> {
+        crate::wrong_api::<AddressRewardsPage>()
+    }
+
+    async fn get_address_transactions(
+        &self,
+        _: AddressTransactionsRequest,
+    ) -> RpcResult<AddressTransactionsPage> {
+        crate::wrong_api::<AddressTransactionsPage>()
+    }
+
     async fn node_peers_whitelist(&self) -> RpcResult<Vec<IpAddr>> {
         //TODO: Reinvoke
         // let network_command_sender = self.0.network_command_sender.clone();
@@ -6,28 +6,40 @@ use async_trait::async_trait;
 use itertools::{izip, Itertools};
 use jsonrpsee::core::{Error as JsonRpseeError, RpcResult};
 use massa_api_exports::{
-    address::{AddressFilter, AddressInfo},
+    address::{
+        AddressDraws, AddressDrawsPage, AddressDrawsRequest, AddressFilter, AddressInfo,
+        AddressRewardsPage, AddressRewardsRequest, AddressTransactionsPage,
+        AddressTransactionsRequest, CycleDraws,
+    },
+    async_message::AsyncMessageOutput,
     block::{BlockInfo, BlockInfoContent, BlockSummary},
     config::APIConfig,
-    datastore::{DatastoreEntryInput, DatastoreEntryOutput},
+    datastore::{
+        DatastoreEntryInput, DatastoreEntryOutput, DatastoreKeyValue, DatastoreRangeOutput,
+    },
     endorsement::EndorsementInfo,
     error::ApiError,
     execution::{
         ExecuteReadOnlyResponse, ReadOnlyBytecodeExecution, ReadOnlyCall, ReadOnlyResult, Transfer,
     },
-    node::NodeStatus,
+    ledger::{
+        AddressHistoryPage, AddressHistoryRequest, LedgerEntryProof, LedgerEntryProofRequest,
+    },
+    node::{MipStatus, MipVoteInput, MissedSlotsInfo, NodeStatus},
     operation::{OperationInfo, OperationInput},
     page::{PageRequest, PagedVec},
     slot::SlotAmount,
     TimeInterval,
 };
+use massa_async_pool::{AsyncMessageFilter, AsyncMessageId};
 use massa_consensus_exports::block_status::DiscardReason;
 use massa_consensus_exports::ConsensusController;
 use massa_execution_exports::{
-    ExecutionController, ExecutionQueryRequest, ExecutionQueryRequestItem,
+    ExecutionController, ExecutionQueryError, ExecutionQueryRequest, ExecutionQueryRequestItem,
     ExecutionQueryResponseItem, ExecutionStackElement, ReadOnlyExecutionRequest,
     ReadOnlyExecutionTarget,
 };
+use massa_ledger_exports::DatastoreRange;
 use massa_models::{
     address::Address,
     amount::Amount,
@@ -48,7 +60,7 @@ use massa_models::{
     output_event::SCOutputEvent,
     prehash::{PreHashMap, PreHashSet},
     secure_share::SecureShareDeserializer,
-    slot::{IndexedSlot, Slot},
+    slot::Slot,
     timeslots,
     timeslots::{get_latest_block_slot_at_timestamp, time_range_to_slot_range},
     version::Version,
@@ -65,6 +77,7 @@ use massa_versioning::{
 };
 use std::collections::BTreeMap;
 use std::net::{IpAddr, SocketAddr};
+use std::ops::Bound;
 
 impl API<Public> {
     /// generate a new public API
@@ -104,7 +117,7 @@ impl RpcServer for API<Public> {
         url: &SocketAddr,
         api_config: &APIConfig,
     ) -> Result<StopHandle, JsonRpseeError> {
-        crate::serve(self.into_rpc(), url, api_config).await
+        crate::serve(self.into_rpc(), url, api_config, api_config.access.clone()).await
     }
 }
 
@@ -434,6 +447,18 @@ impl MassaRpcServer for API<Public> {
         crate::wrong_api::<PreHashSet<Address>>()
     }
 
+    async fn get_missed_slots(&self, _: Vec<Address>) -> RpcResult<MissedSlotsInfo> {
+        crate::wrong_api::<MissedSlotsInfo>()
+    }
+
+    async fn get_mip_status(&self) -> RpcResult<Vec<MipStatus>> {
+        crate::wrong_api::<Vec<MipStatus>>()
+    }
+
+    async fn node_set_mip_vote(&self, _: MipVoteInput) -> RpcResult<()> {
+        crate::wrong_api::<()>()
+    }
+
     async fn node_ban_by_ip(&self, _: Vec<IpAddr>) -> RpcResult<()> {
         crate::wrong_api::<()>()
     }
@@ -924,26 +949,129 @@ impl MassaRpcServer for API<Public> {
         Ok(res)
     }
 
-    /// get datastore entries
+    /// get datastore entries, or pages of entries
     async fn get_datastore_entries(
         &self,
         entries: Vec<DatastoreEntryInput>,
     ) -> RpcResult<Vec<DatastoreEntryOutput>> {
-        Ok(self
-            .0
-            .execution_controller
-            .get_final_and_active_data_entry(
-                entries
-                    .into_iter()
-                    .map(|input| (input.address, input.key))
-                    .collect::<Vec<_>>(),
-            )
-            .into_iter()
-            .map(|output| DatastoreEntryOutput {
-                final_value: output.0,
-                candidate_value: output.1,
-            })
-            .collect())
+        let max_arguments = self.0.api_settings.max_arguments as usize;
+
+        // split the single key lookups from the range queries, keeping their index in the input
+        let mut key_queries = Vec::new();
+        let mut range_queries = Vec::new();
+        let mut page_limits = Vec::new();
+        for (index, input) in entries.iter().enumerate() {
+            match (&input.key, &input.range) {
+                (Some(key), None) => key_queries.push((index, (input.address, key.clone()))),
+                (None, Some(range_input)) => {
+                    let limit = range_input.limit.unwrap_or(max_arguments);
+                    if limit > max_arguments {
+                        return Err(ApiError::BadRequest("too many arguments".into()).into());
+                    }
+                    let mut range = DatastoreRange {
+                        prefix: range_input.prefix.clone(),
+                        start_key: range_input
+                            .start_key
+                            .clone()
+                            .map_or(Bound::Unbounded, Bound::Included),
+                        end_key: range_input
+                            .end_key
+                            .clone()
+                            .map_or(Bound::Unbounded, Bound::Excluded),
+                        direction: range_input.direction,
+                    };
+                    if let Some(cursor) = &range_input.cursor {
+                        range = range.after(cursor.clone());
+                    }
+
+                    // fetch one extra entry to know whether there is a next page
+                    let addr = input.address;
+                    let query_limit = limit.saturating_add(1);
+                    range_queries.push(if range_input.is_final {
+                        ExecutionQueryRequestItem::AddressDatastoreEntriesFinal {
+                            addr,
+                            range,
+                            limit: query_limit,
+                        }
+                    } else {
+                        ExecutionQueryRequestItem::AddressDatastoreEntriesCandidate {
+                            addr,
+                            range,
+                            limit: query_limit,
+                        }
+                    });
+                    page_limits.push((index, limit));
+                }
+                _ => {
+                    return Err(ApiError::BadRequest(
+                        "each datastore entry input needs either a key or a range".into(),
+                    )
+                    .into())
+                }
+            }
+        }
+
+        let mut outputs = vec![DatastoreEntryOutput::default(); entries.len()];
+
+        let (key_indexes, key_queries): (Vec<usize>, Vec<(Address, Vec<u8>)>) =
+            key_queries.into_iter().unzip();
+        if !key_queries.is_empty() {
+            let values = self
+                .0
+                .execution_controller
+                .get_final_and_active_data_entry(key_queries);
+            for (index, (final_value, candidate_value)) in key_indexes.into_iter().zip(values) {
+                outputs[index].final_value = final_value;
+                outputs[index].candidate_value = candidate_value;
+            }
+        }
+
+        if !range_queries.is_empty() {
+            let query_response = self
+                .0
+                .execution_controller
+                .query_state(ExecutionQueryRequest {
+                    requests: range_queries,
+                });
+            if query_response.responses.len() != page_limits.len() {
+                return Err(ApiError::InternalServerError(
+                    "missing response to datastore entries query".to_string(),
+                )
+                .into());
+            }
+            for ((index, limit), response) in page_limits.into_iter().zip(query_response.responses)
+            {
+                let mut entries = match response {
+                    Ok(ExecutionQueryResponseItem::DatastoreEntries(entries)) => entries,
+                    Ok(_) => {
+                        return Err(ApiError::InternalServerError(
+                            "unexpected response type to datastore entries query".to_string(),
+                        )
+                        .into())
+                    }
+                    // the address has no ledger entry: its datastore is empty
+                    Err(ExecutionQueryError::NotFound(_)) => Vec::new(),
+                    Err(err) => return Err(ApiError::InternalServerError(err.to_string()).into()),
+                };
+
+                let next_cursor = if entries.len() > limit {
+                    entries.truncate(limit);
+                    entries.last().map(|(key, _value)| key.clone())
+                } else {
+                    None
+                };
+
+                outputs[index].page = Some(DatastoreRangeOutput {
+                    entries: entries
+                        .into_iter()
+                        .map(|(key, value)| DatastoreKeyValue { key, value })
+                        .collect(),
+                    next_cursor,
+                });
+            }
+        }
+
+        Ok(outputs)
     }
 
     /// get addresses
@@ -1018,32 +1146,33 @@ impl MassaRpcServer for API<Public> {
                     .saturating_add(self.0.api_settings.draw_lookahead_period_count),
                 cur_slot.thread,
             );
-            let selections = self
-                .0
-                .selector_controller
-                .get_available_selections_in_range(
-                    cur_slot..=slot_end,
-                    Some(&addresses.iter().copied().collect()),
-                )
-                .unwrap_or_default();
+            let cycle_range = cur_slot.get_cycle(self.0.api_settings.periods_per_cycle)
+                ..=slot_end.get_cycle(self.0.api_settings.periods_per_cycle);
+            let slot_range = cur_slot..=slot_end;
 
             addresses
                 .iter()
                 .map(|addr| {
                     let mut producer_slots = Vec::new();
                     let mut endorser_slots = Vec::new();
-                    for (selection_slot, selection) in &selections {
-                        if selection.producer == *addr {
-                            producer_slots.push(*selection_slot);
-                        }
-                        for (index, endorser) in selection.endorsements.iter().enumerate() {
-                            if endorser == addr {
-                                endorser_slots.push(IndexedSlot {
-                                    slot: *selection_slot,
-                                    index,
-                                });
-                            }
-                        }
+                    for cycle_draws in self
+                        .0
+                        .selector_controller
+                        .get_address_draws(addr, cycle_range.clone())
+                        .unwrap_or_default()
+                    {
+                        producer_slots.extend(
+                            cycle_draws
+                                .block_slots
+                                .into_iter()
+                                .filter(|slot| slot_range.contains(slot)),
+                        );
+                        endorser_slots.extend(
+                            cycle_draws
+                                .endorsement_slots
+                                .into_iter()
+                                .filter(|indexed| slot_range.contains(&indexed.slot)),
+                        );
                     }
                     (producer_slots, endorser_slots)
                 })
@@ -1236,6 +1365,362 @@ impl MassaRpcServer for API<Public> {
         Ok(events)
     }
 
+    /// Get the pending asynchronous messages optionally filtered by:
+    /// * sender address
+    /// * destination address
+    /// * validity slot range
+    /// * trigger address
+    /// * whether they can be executed
+    async fn get_async_messages(
+        &self,
+        filter: AsyncMessageFilter,
+    ) -> RpcResult<Vec<AsyncMessageOutput>> {
+        let query_response = self
+            .0
+            .execution_controller
+            .query_state(ExecutionQueryRequest {
+                requests: vec![
+                    ExecutionQueryRequestItem::AsyncMessagesFinal(filter.clone()),
+                    ExecutionQueryRequestItem::AsyncMessagesCandidate(filter),
+                ],
+            });
+
+        let mut responses = query_response.responses.into_iter().map(|resp| match resp {
+            Ok(ExecutionQueryResponseItem::AsyncMessages(messages)) => Ok(messages),
+            Ok(_) => Err(ApiError::InternalServerError(
+                "unexpected response type to asynchronous messages query".to_string(),
+            )),
+            Err(err) => Err(ApiError::InternalServerError(err.to_string())),
+        });
+        let (Some(final_messages), Some(candidate_messages)) = (responses.next(), responses.next())
+        else {
+            return Err(ApiError::InternalServerError(
+                "missing response to asynchronous messages query".to_string(),
+            )
+            .into());
+        };
+
+        // merge final and candidate views, keeping the pool priority order
+        let mut messages: BTreeMap<AsyncMessageId, AsyncMessageOutput> = BTreeMap::new();
+        for (id, msg) in final_messages? {
+            messages.insert(
+                id,
+                AsyncMessageOutput {
+                    emission_slot: msg.emission_slot,
+                    emission_index: msg.emission_index,
+                    final_message: Some(msg),
+                    candidate_message: None,
+                },
+            );
+        }
+        for (id, msg) in candidate_messages? {
+            messages
+                .entry(id)
+                .or_insert_with(|| AsyncMessageOutput {
+                    emission_slot: msg.emission_slot,
+                    emission_index: msg.emission_index,
+                    final_message: None,
+                    candidate_message: None,
+                })
+                .candidate_message = Some(msg);
+        }
+
+        Ok(messages.into_values().collect())
+    }
+
+    /// Get a page of the final ledger changes of an address, in increasing slot order
+    async fn get_address_ledger_history(
+        &self,
+        request: AddressHistoryRequest,
+    ) -> RpcResult<AddressHistoryPage> {
+        let max_arguments = self.0.api_settings.max_arguments as usize;
+        let limit = request.limit.unwrap_or(max_arguments);
+        if limit > max_arguments {
+            return Err(ApiError::BadRequest("too many arguments".into()).into());
+        }
+
+        // fetch one extra entry to know where the next page starts
+        let query_response = self
+            .0
+            .execution_controller
+            .query_state(ExecutionQueryRequest {
+                requests: vec![ExecutionQueryRequestItem::AddressHistoryFinal {
+                    addr: request.address,
+                    start: request.start,
+                    limit: limit.saturating_add(1),
+                }],
+            });
+
+        let mut entries = match query_response.responses.into_iter().next() {
+            Some(Ok(ExecutionQueryResponseItem::AddressHistory(entries))) => entries,
+            Some(Ok(_)) => {
+                return Err(ApiError::InternalServerError(
+                    "unexpected response type to address history query".to_string(),
+                )
+                .into())
+            }
+            Some(Err(err)) => return Err(ApiError::InternalServerError(err.to_string()).into()),
+            None => {
+                return Err(ApiError::InternalServerError(
+                    "missing response to address history query".to_string(),
+                )
+                .into())
+            }
+        };
+
+        let next_start = if entries.len() > limit {
+            entries.pop().map(|entry| entry.slot)
+        } else {
+            None
+        };
+
+        Ok(AddressHistoryPage {
+            entries,
+            next_start,
+        })
+    }
+
+    /// Get a proof of the value (or absence) of a final ledger sub-entry
+    async fn get_ledger_entry_proof(
+        &self,
+        request: LedgerEntryProofRequest,
+    ) -> RpcResult<LedgerEntryProof> {
+        let query_response = self
+            .0
+            .execution_controller
+            .query_state(ExecutionQueryRequest {
+                requests: vec![ExecutionQueryRequestItem::LedgerEntryProofFinal {
+                    addr: request.address,
+                    key_type: request.sub_entry.into(),
+                }],
+            });
+
+        match query_response.responses.into_iter().next() {
+            Some(Ok(ExecutionQueryResponseItem::StateProof(state_proof))) => Ok(LedgerEntryProof {
+                slot: state_proof.slot,
+                node_root: state_proof.root,
+                proof: state_proof.proof,
+            }),
+            Some(Ok(_)) => Err(ApiError::InternalServerError(
+                "unexpected response type to ledger entry proof query".to_string(),
+            )
+            .into()),
+            Some(Err(ExecutionQueryError::Unavailable(err))) => {
+                Err(ApiError::BadRequest(err).into())
+            }
+            Some(Err(err)) => Err(ApiError::InternalServerError(err.to_string()).into()),
+            None => Err(ApiError::InternalServerError(
+                "missing response to ledger entry proof query".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    /// Get a page of the upcoming draws of a list of addresses
+    async fn get_addresses_draws(
+        &self,
+        request: AddressDrawsRequest,
+    ) -> RpcResult<AddressDrawsPage> {
+        let max_arguments = self.0.api_settings.max_arguments as usize;
+        let limit = request.limit.unwrap_or(max_arguments);
+        if limit > max_arguments {
+            return Err(ApiError::BadRequest("too many arguments".into()).into());
+        }
+
+        // only upcoming draws are returned
+        let cur_slot = timeslots::get_current_latest_block_slot(
+            self.0.api_settings.thread_count,
+            self.0.api_settings.t0,
+            self.0.api_settings.genesis_timestamp,
+        )
+        .expect("could not get latest current slot")
+        .unwrap_or_else(|| Slot::new(0, 0));
+        let start_cycle = std::cmp::max(
+            request.start_cycle.unwrap_or(0),
+            cur_slot.get_cycle(self.0.api_settings.periods_per_cycle),
+        );
+        let end_cycle = request.end_cycle.unwrap_or(u64::MAX);
+
+        let offset = request.offset.unwrap_or(0);
+        let mut draws = Vec::new();
+        for address in request.addresses.iter().skip(offset).take(limit) {
+            let cycles = self
+                .0
+                .selector_controller
+                .get_address_draws(address, start_cycle..=end_cycle)
+                .map_err(|err| ApiError::InternalServerError(err.to_string()))?
+                .into_iter()
+                .map(|cycle_draws| CycleDraws {
+                    cycle: cycle_draws.cycle,
+                    next_block_draws: cycle_draws
+                        .block_slots
+                        .into_iter()
+                        .filter(|slot| *slot >= cur_slot)
+                        .collect(),
+                    next_endorsement_draws: cycle_draws
+                        .endorsement_slots
+                        .into_iter()
+                        .filter(|indexed| indexed.slot >= cur_slot)
+                        .collect(),
+                })
+                .filter(|cycle_draws| {
+                    !cycle_draws.next_block_draws.is_empty()
+                        || !cycle_draws.next_endorsement_draws.is_empty()
+                })
+                .collect();
+            draws.push(AddressDraws {
+                address: *address,
+                cycles,
+            });
+        }
+
+        let next_offset = offset.saturating_add(limit);
+        Ok(AddressDrawsPage {
+            draws,
+            next_offset: (next_offset < request.addresses.len()).then_some(next_offset),
+        })
+    }
+
+    /// Get a page of the final rewards of an address
+    async fn get_address_rewards(
+        &self,
+        request: AddressRewardsRequest,
+    ) -> RpcResult<AddressRewardsPage> {
+        let max_arguments = self.0.api_settings.max_arguments as usize;
+        let limit = request.limit.unwrap_or(max_arguments);
+        if limit > max_arguments {
+            return Err(ApiError::BadRequest("too many arguments".into()).into());
+        }
+
+        // convert the cycle range to a slot range
+        let periods_per_cycle = self.0.api_settings.periods_per_cycle;
+        let thread_count = self.0.api_settings.thread_count;
+        let mut start = match request.start_cycle {
+            Some(cycle) => Slot::new_first_of_cycle(cycle, periods_per_cycle)
+                .map_err(|err| ApiError::BadRequest(err.to_string()))?,
+            None => Slot::min(),
+        };
+        if let Some(page_start) = request.start {
+            start = std::cmp::max(start, page_start);
+        }
+        let end = match request.end_cycle {
+            Some(cycle) => Slot::new_last_of_cycle(cycle, periods_per_cycle, thread_count)
+                .map_err(|err| ApiError::BadRequest(err.to_string()))?,
+            None => Slot::max(thread_count),
+        };
+
+        // fetch one extra entry to know where the next page starts
+        let query_response = self
+            .0
+            .execution_controller
+            .query_state(ExecutionQueryRequest {
+                requests: vec![ExecutionQueryRequestItem::AddressRewardsFinal {
+                    addr: request.address,
+                    start,
+                    end,
+                    limit: limit.saturating_add(1),
+                }],
+            });
+
+        let mut entries = match query_response.responses.into_iter().next() {
+            Some(Ok(ExecutionQueryResponseItem::AddressRewards(entries))) => entries,
+            Some(Ok(_)) => {
+                return Err(ApiError::InternalServerError(
+                    "unexpected response type to address rewards query".to_string(),
+                )
+                .into())
+            }
+            Some(Err(err)) => return Err(ApiError::InternalServerError(err.to_string()).into()),
+            None => {
+                return Err(ApiError::InternalServerError(
+                    "missing response to address rewards query".to_string(),
+                )
+                .into())
+            }
+        };
+
+        let next_start = if entries.len() > limit {
+            entries.pop().map(|entry| entry.slot)
+        } else {
+            None
+        };
+
+        Ok(AddressRewardsPage {
+            entries,
+            next_start,
+        })
+    }
+
+    /// Get a page of the final transactions of an address
+    async fn get_address_transactions(
+        &self,
+        request: AddressTransactionsRequest,
+    ) -> RpcResult<AddressTransactionsPage> {
+        let max_arguments = self.0.api_settings.max_arguments as usize;
+        let limit = request.limit.unwrap_or(max_arguments);
+        if limit > max_arguments {
+            return Err(ApiError::BadRequest("too many arguments".into()).into());
+        }
+
+        // convert the cycle range to a slot range
+        let periods_per_cycle = self.0.api_settings.periods_per_cycle;
+        let thread_count = self.0.api_settings.thread_count;
+        let mut start = match request.start_cycle {
+            Some(cycle) => Slot::new_first_of_cycle(cycle, periods_per_cycle)
+                .map_err(|err| ApiError::BadRequest(err.to_string()))?,
+            None => Slot::min(),
+        };
+        if let Some(page_start) = request.start {
+            start = std::cmp::max(start, page_start);
+        }
+        let end = match request.end_cycle {
+            Some(cycle) => Slot::new_last_of_cycle(cycle, periods_per_cycle, thread_count)
+                .map_err(|err| ApiError::BadRequest(err.to_string()))?,
+            None => Slot::max(thread_count),
+        };
+
+        // fetch one extra entry to know where the next page starts
+        let query_response = self
+            .0
+            .execution_controller
+            .query_state(ExecutionQueryRequest {
+                requests: vec![ExecutionQueryRequestItem::AddressTransactionsFinal {
+                    addr: request.address,
+                    start,
+                    end,
+                    limit: limit.saturating_add(1),
+                }],
+            });
+
+        let mut entries = match query_response.responses.into_iter().next() {
+            Some(Ok(ExecutionQueryResponseItem::AddressTransactions(entries))) => entries,
+            Some(Ok(_)) => {
+                return Err(ApiError::InternalServerError(
+                    "unexpected response type to address transactions query".to_string(),
+                )
+                .into())
+            }
+            Some(Err(err)) => return Err(ApiError::InternalServerError(err.to_string()).into()),
+            None => {
+                return Err(ApiError::InternalServerError(
+                    "missing response to address transactions query".to_string(),
+                )
+                .into())
+            }
+        };
+
+        let next_start = if entries.len() > limit {
+            entries.pop().map(|entry| entry.slot)
+        } else {
+            None
+        };
+
+        Ok(AddressTransactionsPage {
+            entries,
+            next_start,
+        })
+    }
+
     async fn node_peers_whitelist(&self) -> RpcResult<Vec<IpAddr>> {
         crate::wrong_api::<Vec<IpAddr>>()
     }
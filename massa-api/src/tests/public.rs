@@ -4,34 +4,52 @@
 use std::{
     collections::{BTreeMap, HashMap},
     net::{IpAddr, SocketAddr},
+    ops::Bound,
     str::FromStr,
+    sync::Arc,
 };
 
+use hyper::header::{HeaderMap, HeaderValue, AUTHORIZATION};
 use jsonrpsee::{
     core::{client::ClientT, Error},
     http_client::HttpClientBuilder,
     rpc_params,
 };
 use massa_api_exports::{
-    address::{AddressFilter, AddressInfo},
+    access::{AccessConfig, AccessControl, ApiKeyConfig, RateLimits},
+    address::{
+        AddressDrawsPage, AddressDrawsRequest, AddressFilter, AddressInfo, AddressRewardsPage,
+        AddressRewardsRequest, AddressTransactionsPage, AddressTransactionsRequest,
+    },
+    async_message::AsyncMessageOutput,
     block::{BlockInfo, BlockSummary},
-    datastore::{DatastoreEntryInput, DatastoreEntryOutput},
+    datastore::{
+        DatastoreEntryInput, DatastoreEntryOutput, DatastoreKeyValue, DatastoreRangeInput,
+    },
     endorsement::EndorsementInfo,
     execution::{ExecuteReadOnlyResponse, ReadOnlyBytecodeExecution, ReadOnlyCall},
+    ledger::{
+        AddressHistoryPage, AddressHistoryRequest, LedgerEntryProof, LedgerEntryProofRequest,
+        LedgerSubEntryRequest,
+    },
     operation::{OperationInfo, OperationInput},
     TimeInterval,
 };
+use massa_async_pool::{AsyncMessage, AsyncMessageFilter};
 use massa_consensus_exports::{
     block_graph_export::BlockGraphExport, block_status::ExportCompiledBlock,
     MockConsensusController,
 };
+use massa_db_exports::StateProof;
+use massa_ledger_exports::{AddressHistoryEntry, DatastoreDirection, KeyType, ValueChange};
 use massa_pool_exports::MockPoolController;
-use massa_pos_exports::MockSelectorController;
+use massa_pos_exports::{AddressCycleDraws, MockSelectorController};
 
 use crate::{tests::mock::start_public_api, RpcServer};
 use massa_execution_exports::{
-    ExecutionAddressInfo, ExecutionQueryResponse, ExecutionQueryResponseItem,
-    MockExecutionController, ReadOnlyExecutionOutput,
+    ExecutionAddressInfo, ExecutionQueryError, ExecutionQueryRequestItem, ExecutionQueryResponse,
+    ExecutionQueryResponseItem, ExecutionQueryStateProof, MockExecutionController,
+    ReadOnlyExecutionOutput,
 };
 use massa_models::{
     address::Address,
@@ -39,13 +57,17 @@ use massa_models::{
     block::{Block, BlockGraphStatus},
     bytecode::Bytecode,
     clique::Clique,
+    config::{PERIODS_PER_CYCLE, THREAD_COUNT},
     endorsement::EndorsementId,
-    execution::EventFilter,
+    execution::{
+        AddressReward, AddressRewardsEntry, AddressTransaction, AddressTransactionsEntry,
+        EventFilter, RewardKind, TransactionKind,
+    },
     node::NodeId,
     operation::OperationId,
     output_event::SCOutputEvent,
     prehash::{CapacityAllocator, PreHashMap},
-    slot::Slot,
+    slot::{IndexedSlot, Slot},
     stats::{ConsensusStats, ExecutionStats, NetworkStats},
 };
 use massa_protocol_exports::{
@@ -166,6 +188,99 @@ async fn get_cliques() {
     api_public_handle.stop().await;
 }
 
+#[tokio::test]
+async fn access_control() {
+    let addr: SocketAddr = "[::]:5057".parse().unwrap();
+    let (mut api_public, mut config) = start_public_api(addr);
+    config.access = Some(Arc::new(
+        AccessControl::new(
+            AccessConfig {
+                enabled: true,
+                allow_anonymous: true,
+                anonymous_methods: vec!["get_cliques".to_string()],
+                anonymous_limits: RateLimits {
+                    requests_per_second: 1,
+                    read_only_gas_per_second: 0,
+                },
+                keys: vec![ApiKeyConfig {
+                    name: "partner".to_string(),
+                    key: "secret".to_string(),
+                    methods: vec!["get_cliques".to_string()],
+                    limits: RateLimits {
+                        requests_per_second: 2,
+                        read_only_gas_per_second: 0,
+                    },
+                }],
+                ..Default::default()
+            },
+            true,
+        )
+        .unwrap(),
+    ));
+
+    let mut consensus_ctrl = MockConsensusController::new();
+    consensus_ctrl
+        .expect_get_cliques()
+        .returning(|| vec![Clique::default()]);
+
+    api_public.0.consensus_controller = Box::new(consensus_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let url = format!(
+        "http://localhost:{}",
+        addr.to_string().split(':').last().unwrap()
+    );
+    let anonymous_client = HttpClientBuilder::default().build(&url).unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer unknown"));
+    let unknown_client = HttpClientBuilder::default()
+        .set_headers(headers)
+        .build(&url)
+        .unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer secret"));
+    let client = HttpClientBuilder::default()
+        .set_headers(headers)
+        .build(&url)
+        .unwrap();
+
+    // requests with an unknown API key are refused
+    let response: Result<Vec<Clique>, Error> =
+        unknown_client.request("get_cliques", rpc_params![]).await;
+    assert!(response.is_err());
+
+    // anonymous requests are limited by the IP of the connection
+    let response: Result<Value, Error> =
+        anonymous_client.request("get_stakers", rpc_params![]).await;
+    assert!(response.is_err());
+    let response: Vec<Clique> = anonymous_client
+        .request("get_cliques", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(response.len(), 1);
+    let response: Result<Vec<Clique>, Error> =
+        anonymous_client.request("get_cliques", rpc_params![]).await;
+    assert!(response.is_err());
+
+    // methods outside of the allow-list of the key are refused
+    let response: Result<Value, Error> = client.request("get_stakers", rpc_params![]).await;
+    assert!(response.is_err());
+
+    // the key can call its methods until its rate limit is reached
+    let response: Vec<Clique> = client.request("get_cliques", rpc_params![]).await.unwrap();
+    assert_eq!(response.len(), 1);
+    let response: Vec<Clique> = client.request("get_cliques", rpc_params![]).await.unwrap();
+    assert_eq!(response.len(), 1);
+    let response: Result<Vec<Clique>, Error> = client.request("get_cliques", rpc_params![]).await;
+    assert!(response.is_err());
+
+    api_public_handle.stop().await;
+}
+
 #[tokio::test]
 async fn get_operations() {
     let addr: SocketAddr = "[::]:5003".parse().unwrap();
@@ -701,6 +816,8 @@ async fn execute_read_only_bytecode() {
                     deferred_credits_execution: vec![],
                     cancel_async_message_execution: vec![],
                     auto_sell_execution: vec![],
+                    reward_credits: vec![],
+                    transactions: vec![],
                 },
                 gas_cost: 100,
                 call_result: "toto".as_bytes().to_vec(),
@@ -788,6 +905,8 @@ async fn execute_read_only_call() {
                     deferred_credits_execution: vec![],
                     cancel_async_message_execution: vec![],
                     auto_sell_execution: vec![],
+                    reward_credits: vec![],
+                    transactions: vec![],
                 },
                 gas_cost: 100,
                 call_result: "toto".as_bytes().to_vec(),
@@ -856,8 +975,8 @@ async fn get_addresses() {
 
     let mut selector_ctrl = MockSelectorController::new();
     selector_ctrl
-        .expect_get_available_selections_in_range()
-        .returning(|_range, _addrs| Ok(BTreeMap::new()));
+        .expect_get_address_draws()
+        .returning(|_addr, _cycles| Ok(Vec::new()));
 
     api_public.0.execution_controller = Box::new(exec_ctrl);
     api_public.0.selector_controller = Box::new(selector_ctrl);
@@ -893,6 +1012,82 @@ async fn get_addresses() {
     api_public_handle.stop().await;
 }
 
+#[tokio::test]
+async fn get_addresses_draws() {
+    let addr: SocketAddr = "[::]:5054".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let future_slot = Slot::new(1_000_000_000, 3);
+    let mut selector_ctrl = MockSelectorController::new();
+    selector_ctrl
+        .expect_get_address_draws()
+        .returning(move |_addr, _cycles| {
+            Ok(vec![AddressCycleDraws {
+                cycle: 10,
+                block_slots: vec![future_slot],
+                endorsement_slots: vec![IndexedSlot {
+                    slot: future_slot,
+                    index: 4,
+                }],
+            }])
+        });
+    api_public.0.selector_controller = Box::new(selector_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    let address =
+        Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap();
+    let response: AddressDrawsPage = client
+        .request(
+            "get_addresses_draws",
+            rpc_params![AddressDrawsRequest {
+                addresses: vec![address, address, address],
+                start_cycle: None,
+                end_cycle: None,
+                offset: None,
+                limit: Some(2),
+            }],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.draws.len(), 2);
+    assert_eq!(response.next_offset, Some(2));
+    assert_eq!(
+        response.draws[0].cycles[0].next_block_draws,
+        vec![future_slot]
+    );
+    assert_eq!(response.draws[0].cycles[0].next_endorsement_draws.len(), 1);
+
+    let response: AddressDrawsPage = client
+        .request(
+            "get_addresses_draws",
+            rpc_params![AddressDrawsRequest {
+                addresses: vec![address, address, address],
+                start_cycle: None,
+                end_cycle: None,
+                offset: Some(2),
+                limit: Some(2),
+            }],
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.draws.len(), 1);
+    assert_eq!(response.next_offset, None);
+
+    api_public_handle.stop().await;
+}
+
 #[tokio::test]
 async fn get_addresses_bytecode() {
     let addr: SocketAddr = "[::]:5019".parse().unwrap();
@@ -977,7 +1172,8 @@ async fn get_datastore_entries() {
     let params = rpc_params![vec![DatastoreEntryInput {
         address: Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x")
             .unwrap(),
-        key: "massa".as_bytes().to_vec()
+        key: Some("massa".as_bytes().to_vec()),
+        range: None,
     }]];
     let response: Vec<DatastoreEntryOutput> = client
         .request("get_datastore_entries", params.clone())
@@ -997,6 +1193,549 @@ async fn get_datastore_entries() {
     api_public_handle.stop().await;
 }
 
+#[tokio::test]
+async fn get_async_messages() {
+    let addr: SocketAddr = "[::]:5050".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let sender =
+        Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap();
+    let final_message = AsyncMessage::new(
+        Slot::new(1, 0),
+        0,
+        sender,
+        sender,
+        String::from("final"),
+        100_000,
+        Amount::from_str("1").unwrap(),
+        Amount::zero(),
+        Slot::new(2, 0),
+        Slot::new(10, 0),
+        vec![],
+        None,
+        None,
+    );
+    let candidate_message = AsyncMessage::new(
+        Slot::new(3, 0),
+        0,
+        sender,
+        sender,
+        String::from("candidate"),
+        100_000,
+        Amount::from_str("1").unwrap(),
+        Amount::zero(),
+        Slot::new(4, 0),
+        Slot::new(10, 0),
+        vec![],
+        None,
+        None,
+    );
+
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl.expect_query_state().returning(move |_| {
+        let final_messages = BTreeMap::from([(final_message.compute_id(), final_message.clone())]);
+        let mut candidate_messages = final_messages.clone();
+        candidate_messages.insert(candidate_message.compute_id(), candidate_message.clone());
+        ExecutionQueryResponse {
+            responses: vec![
+                Ok(ExecutionQueryResponseItem::AsyncMessages(final_messages)),
+                Ok(ExecutionQueryResponseItem::AsyncMessages(
+                    candidate_messages,
+                )),
+            ],
+            candidate_cursor: Slot::new(3, 0),
+            final_cursor: Slot::new(1, 0),
+            final_state_fingerprint: massa_hash::Hash::compute_from(&Vec::new()),
+        }
+    });
+
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    let params = rpc_params![AsyncMessageFilter {
+        sender: Some(sender),
+        ..Default::default()
+    }];
+    let response: Vec<AsyncMessageOutput> = client
+        .request("get_async_messages", params.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(response.len(), 2);
+    let final_entry = response
+        .iter()
+        .find(|entry| entry.emission_slot == Slot::new(1, 0))
+        .unwrap();
+    assert_eq!(
+        final_entry.final_message.as_ref().unwrap().function,
+        "final"
+    );
+    assert_eq!(
+        final_entry.candidate_message.as_ref().unwrap().function,
+        "final"
+    );
+    let candidate_entry = response
+        .iter()
+        .find(|entry| entry.emission_slot == Slot::new(3, 0))
+        .unwrap();
+    assert!(candidate_entry.final_message.is_none());
+    assert_eq!(
+        candidate_entry.candidate_message.as_ref().unwrap().function,
+        "candidate"
+    );
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_datastore_entries_range() {
+    let addr: SocketAddr = "[::]:5052".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let address =
+        Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap();
+
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl.expect_query_state().returning(|request| {
+        let response = match &request.requests[0] {
+            ExecutionQueryRequestItem::AddressDatastoreEntriesCandidate {
+                range, limit, ..
+            } => {
+                assert_eq!(range.start_key, Bound::Excluded(b"a".to_vec()));
+                assert_eq!(*limit, 3);
+                Ok(ExecutionQueryResponseItem::DatastoreEntries(vec![
+                    (b"b".to_vec(), b"2".to_vec()),
+                    (b"c".to_vec(), b"3".to_vec()),
+                    (b"d".to_vec(), b"4".to_vec()),
+                ]))
+            }
+            _ => Err(ExecutionQueryError::NotFound("final".to_string())),
+        };
+        ExecutionQueryResponse {
+            responses: vec![response],
+            candidate_cursor: Slot::new(1, 0),
+            final_cursor: Slot::new(1, 0),
+            final_state_fingerprint: massa_hash::Hash::compute_from(&Vec::new()),
+        }
+    });
+
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    let range = DatastoreRangeInput {
+        prefix: Vec::new(),
+        start_key: None,
+        end_key: None,
+        cursor: Some(b"a".to_vec()),
+        direction: DatastoreDirection::Forward,
+        limit: Some(2),
+        is_final: false,
+    };
+    let response: Vec<DatastoreEntryOutput> = client
+        .request(
+            "get_datastore_entries",
+            rpc_params![vec![DatastoreEntryInput {
+                address,
+                key: None,
+                range: Some(range.clone()),
+            }]],
+        )
+        .await
+        .unwrap();
+    let response = response[0].page.clone().unwrap();
+    assert_eq!(
+        response.entries,
+        vec![
+            DatastoreKeyValue {
+                key: b"b".to_vec(),
+                value: b"2".to_vec()
+            },
+            DatastoreKeyValue {
+                key: b"c".to_vec(),
+                value: b"3".to_vec()
+            },
+        ]
+    );
+    assert_eq!(response.next_cursor, Some(b"c".to_vec()));
+
+    // address not found in the final datastore: empty page
+    let response: Vec<DatastoreEntryOutput> = client
+        .request(
+            "get_datastore_entries",
+            rpc_params![vec![DatastoreEntryInput {
+                address,
+                key: None,
+                range: Some(DatastoreRangeInput {
+                    is_final: true,
+                    ..range
+                }),
+            }]],
+        )
+        .await
+        .unwrap();
+    let page = response[0].page.clone().unwrap();
+    assert!(page.entries.is_empty());
+    assert_eq!(page.next_cursor, None);
+
+    // an input needs either a key or a range
+    let response: Result<Vec<DatastoreEntryOutput>, Error> = client
+        .request(
+            "get_datastore_entries",
+            rpc_params![vec![DatastoreEntryInput {
+                address,
+                key: None,
+                range: None,
+            }]],
+        )
+        .await;
+    assert!(response.is_err());
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_address_ledger_history() {
+    let addr: SocketAddr = "[::]:5051".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let address =
+        Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap();
+
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl.expect_query_state().returning(|_| {
+        let entries = (1..=3u64)
+            .map(|period| AddressHistoryEntry {
+                slot: Slot::new(period, 0),
+                balance: ValueChange::new(
+                    Some(Amount::from_raw(period - 1)),
+                    Some(Amount::from_raw(period)),
+                ),
+                bytecode: None,
+                datastore: BTreeMap::new(),
+                datastore_truncated: false,
+            })
+            .collect();
+        ExecutionQueryResponse {
+            responses: vec![Ok(ExecutionQueryResponseItem::AddressHistory(entries))],
+            candidate_cursor: Slot::new(3, 0),
+            final_cursor: Slot::new(3, 0),
+            final_state_fingerprint: massa_hash::Hash::compute_from(&Vec::new()),
+        }
+    });
+
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    // the extra entry is only used to compute the next page start
+    let params = rpc_params![AddressHistoryRequest {
+        address,
+        start: None,
+        limit: Some(2),
+    }];
+    let response: AddressHistoryPage = client
+        .request("get_address_ledger_history", params)
+        .await
+        .unwrap();
+    assert_eq!(response.entries.len(), 2);
+    assert_eq!(response.entries[0].slot, Slot::new(1, 0));
+    assert_eq!(response.next_start, Some(Slot::new(3, 0)));
+
+    // too many arguments
+    let params = rpc_params![AddressHistoryRequest {
+        address,
+        start: None,
+        limit: Some(config.max_arguments as usize + 1),
+    }];
+    let response: Result<AddressHistoryPage, Error> =
+        client.request("get_address_ledger_history", params).await;
+    assert!(response.is_err());
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_address_rewards() {
+    let addr: SocketAddr = "[::]:5055".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let address =
+        Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap();
+
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl.expect_query_state().returning(|req| {
+        let (start, end, limit) = match req.requests.first() {
+            Some(ExecutionQueryRequestItem::AddressRewardsFinal {
+                start, end, limit, ..
+            }) => (*start, *end, *limit),
+            _ => panic!("unexpected query"),
+        };
+        // cycle 1 of the request
+        assert_eq!(start, Slot::new(PERIODS_PER_CYCLE, 0));
+        assert_eq!(end, Slot::new(2 * PERIODS_PER_CYCLE - 1, THREAD_COUNT - 1));
+        let entries = (0..limit as u64)
+            .map(|index| AddressRewardsEntry {
+                slot: Slot::new(start.period + index, 0),
+                rewards: vec![
+                    AddressReward {
+                        kind: RewardKind::BlockCreation,
+                        amount: Amount::from_raw(100),
+                    },
+                    AddressReward {
+                        kind: RewardKind::Endorsement,
+                        amount: Amount::from_raw(10),
+                    },
+                ],
+            })
+            .collect();
+        ExecutionQueryResponse {
+            responses: vec![Ok(ExecutionQueryResponseItem::AddressRewards(entries))],
+            candidate_cursor: Slot::new(3, 0),
+            final_cursor: Slot::new(3, 0),
+            final_state_fingerprint: massa_hash::Hash::compute_from(&Vec::new()),
+        }
+    });
+
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    // the extra entry is only used to compute the next page start
+    let params = rpc_params![AddressRewardsRequest {
+        address,
+        start_cycle: Some(1),
+        end_cycle: Some(1),
+        start: None,
+        limit: Some(2),
+    }];
+    let response: AddressRewardsPage = client.request("get_address_rewards", params).await.unwrap();
+    assert_eq!(response.entries.len(), 2);
+    assert_eq!(response.entries[0].rewards.len(), 2);
+    assert_eq!(
+        response.next_start,
+        Some(Slot::new(response.entries[0].slot.period + 2, 0))
+    );
+
+    // too many arguments
+    let params = rpc_params![AddressRewardsRequest {
+        address,
+        start_cycle: None,
+        end_cycle: None,
+        start: None,
+        limit: Some(config.max_arguments as usize + 1),
+    }];
+    let response: Result<AddressRewardsPage, Error> =
+        client.request("get_address_rewards", params).await;
+    assert!(response.is_err());
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_address_transactions() {
+    let addr: SocketAddr = "[::]:5056".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let address =
+        Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap();
+
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl.expect_query_state().returning(move |req| {
+        let (start, end, limit) = match req.requests.first() {
+            Some(ExecutionQueryRequestItem::AddressTransactionsFinal {
+                start, end, limit, ..
+            }) => (*start, *end, *limit),
+            _ => panic!("unexpected query"),
+        };
+        // no cycle range: the whole history, from the requested page start
+        assert_eq!(start, Slot::new(5, 0));
+        assert_eq!(end, Slot::max(THREAD_COUNT));
+        let entries = (0..limit as u64)
+            .map(|index| AddressTransactionsEntry {
+                slot: Slot::new(start.period + index, 0),
+                transactions: vec![AddressTransaction {
+                    kind: TransactionKind::Transaction,
+                    operation_id: None,
+                    from: address,
+                    to: Some(address),
+                    amount: Amount::from_raw(100),
+                    fee: Amount::from_raw(1),
+                    success: true,
+                }],
+            })
+            .collect();
+        ExecutionQueryResponse {
+            responses: vec![Ok(ExecutionQueryResponseItem::AddressTransactions(entries))],
+            candidate_cursor: Slot::new(3, 0),
+            final_cursor: Slot::new(3, 0),
+            final_state_fingerprint: massa_hash::Hash::compute_from(&Vec::new()),
+        }
+    });
+
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    let params = rpc_params![AddressTransactionsRequest {
+        address,
+        start_cycle: None,
+        end_cycle: None,
+        start: Some(Slot::new(5, 0)),
+        limit: Some(3),
+    }];
+    let response: AddressTransactionsPage = client
+        .request("get_address_transactions", params)
+        .await
+        .unwrap();
+    assert_eq!(response.entries.len(), 3);
+    assert_eq!(response.entries[0].transactions.len(), 1);
+    assert_eq!(response.next_start, Some(Slot::new(8, 0)));
+
+    // too many arguments
+    let params = rpc_params![AddressTransactionsRequest {
+        address,
+        start_cycle: None,
+        end_cycle: None,
+        start: None,
+        limit: Some(config.max_arguments as usize + 1),
+    }];
+    let response: Result<AddressTransactionsPage, Error> =
+        client.request("get_address_transactions", params).await;
+    assert!(response.is_err());
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_ledger_entry_proof() {
+    let addr: SocketAddr = "[::]:5053".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let address =
+        Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap();
+
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl.expect_query_state().returning(|req| {
+        let response = match req.requests.first() {
+            Some(ExecutionQueryRequestItem::LedgerEntryProofFinal {
+                key_type: KeyType::BALANCE,
+                ..
+            }) => {
+                let proof = StateProof {
+                    key: vec![1, 2, 3],
+                    value: Some(vec![4, 5, 6]),
+                    other_leaf: None,
+                    siblings: vec![],
+                };
+                Ok(ExecutionQueryResponseItem::StateProof(
+                    ExecutionQueryStateProof {
+                        slot: Slot::new(3, 0),
+                        root: proof.compute_root().unwrap(),
+                        proof,
+                    },
+                ))
+            }
+            _ => Err(ExecutionQueryError::Unavailable(
+                "state proofs are disabled on this node".to_string(),
+            )),
+        };
+        ExecutionQueryResponse {
+            responses: vec![response],
+            candidate_cursor: Slot::new(3, 0),
+            final_cursor: Slot::new(3, 0),
+            final_state_fingerprint: massa_hash::Hash::compute_from(&Vec::new()),
+        }
+    });
+
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    let params = rpc_params![LedgerEntryProofRequest {
+        address,
+        sub_entry: LedgerSubEntryRequest::Balance,
+    }];
+    let response: LedgerEntryProof = client
+        .request("get_ledger_entry_proof", params)
+        .await
+        .unwrap();
+    assert_eq!(response.slot, Slot::new(3, 0));
+    assert!(response.proof.verify(&response.node_root));
+
+    // unavailable
+    let params = rpc_params![LedgerEntryProofRequest {
+        address,
+        sub_entry: LedgerSubEntryRequest::Datastore { key: vec![1] },
+    }];
+    let response: Result<LedgerEntryProof, Error> =
+        client.request("get_ledger_entry_proof", params).await;
+    assert!(response.is_err());
+
+    api_public_handle.stop().await;
+}
+
 #[tokio::test]
 async fn wrong_api() {
     let addr: SocketAddr = "[::]:5004".parse().unwrap();
@@ -1048,6 +1787,20 @@ async fn wrong_api() {
         .to_string()
         .contains("The wrong API (either Public or Private) was called"));
 
+    let response: Result<(), Error> = client
+        .request("get_missed_slots", rpc_params![Vec::<Address>::new()])
+        .await;
+    assert!(response
+        .unwrap_err()
+        .to_string()
+        .contains("The wrong API (either Public or Private) was called"));
+
+    let response: Result<(), Error> = client.request("get_mip_status", params.clone()).await;
+    assert!(response
+        .unwrap_err()
+        .to_string()
+        .contains("The wrong API (either Public or Private) was called"));
+
     let response: Result<(), Error> = client
         .request("node_ban_by_ip", rpc_params![Vec::<IpAddr>::new()])
         .await;
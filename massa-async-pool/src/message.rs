@@ -680,6 +680,88 @@ impl From<AsyncMessage> for AsyncMessageInfo {
     }
 }
 
+/// Filter used to select asynchronous messages from the pool.
+/// Every criterion that is set must match for a message to be selected.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AsyncMessageFilter {
+    /// Only messages sent by this address
+    pub sender: Option<Address>,
+    /// Only messages targeting this address
+    pub destination: Option<Address>,
+    /// Only messages whose validity range ends after this slot (included)
+    pub validity_start: Option<Slot>,
+    /// Only messages whose validity range starts before this slot (excluded)
+    pub validity_end: Option<Slot>,
+    /// Only messages whose trigger watches this address
+    pub trigger_address: Option<Address>,
+    /// Only messages whose `can_be_executed` flag has this value
+    pub can_be_executed: Option<bool>,
+}
+
+impl AsyncMessageFilter {
+    /// Checks the criteria that can be evaluated from the lightweight `AsyncMessageInfo`.
+    /// A message that does not match here can be skipped without being fetched from the database.
+    pub fn matches_info(&self, info: &AsyncMessageInfo) -> bool {
+        self.matches_common(
+            info.validity_start,
+            info.validity_end,
+            info.trigger.as_ref(),
+            info.can_be_executed,
+        )
+    }
+
+    /// Checks all the criteria of the filter against a full message
+    pub fn matches(&self, message: &AsyncMessage) -> bool {
+        if let Some(sender) = self.sender {
+            if message.sender != sender {
+                return false;
+            }
+        }
+        if let Some(destination) = self.destination {
+            if message.destination != destination {
+                return false;
+            }
+        }
+        self.matches_common(
+            message.validity_start,
+            message.validity_end,
+            message.trigger.as_ref(),
+            message.can_be_executed,
+        )
+    }
+
+    fn matches_common(
+        &self,
+        validity_start: Slot,
+        validity_end: Slot,
+        trigger: Option<&AsyncMessageTrigger>,
+        can_be_executed: bool,
+    ) -> bool {
+        if let Some(start) = self.validity_start {
+            if validity_end <= start {
+                return false;
+            }
+        }
+        if let Some(end) = self.validity_end {
+            if validity_start >= end {
+                return false;
+            }
+        }
+        if let Some(trigger_address) = self.trigger_address {
+            match trigger {
+                Some(trigger) if trigger.address == trigger_address => {}
+                _ => return false,
+            }
+        }
+        if let Some(expected) = self.can_be_executed {
+            if can_be_executed != expected {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 /// represents an update to one or more fields of a `AsyncMessage`
 #[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct AsyncMessageUpdate {
@@ -1012,8 +1094,9 @@ mod tests {
 
     use crate::{
         message::{AsyncMessageUpdateDeserializer, AsyncMessageUpdateSerializer},
-        AsyncMessage, AsyncMessageDeserializer, AsyncMessageId, AsyncMessageIdDeserializer,
-        AsyncMessageIdSerializer, AsyncMessageSerializer, AsyncMessageTrigger, AsyncMessageUpdate,
+        AsyncMessage, AsyncMessageDeserializer, AsyncMessageFilter, AsyncMessageId,
+        AsyncMessageIdDeserializer, AsyncMessageIdSerializer, AsyncMessageInfo,
+        AsyncMessageSerializer, AsyncMessageTrigger, AsyncMessageUpdate,
     };
     use massa_models::{
         address::Address,
@@ -1509,4 +1592,83 @@ mod tests {
             .deserialize::<DeserializeError>(&serialized)
             .unwrap_err();
     }
+
+    #[test]
+    fn filter_message() {
+        let sender =
+            Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap();
+        let destination =
+            Address::from_str("AS12htxRWiEm8jDJpJptr6cwEhWNcCSFWstN1MLSa96DDkVM9Y42G").unwrap();
+        let message = AsyncMessage::new(
+            Slot::new(1, 0),
+            0,
+            sender,
+            destination,
+            String::from("test"),
+            10000000,
+            Amount::from_str("1").unwrap(),
+            Amount::from_str("1").unwrap(),
+            Slot::new(2, 0),
+            Slot::new(5, 0),
+            vec![1, 2, 3, 4],
+            Some(AsyncMessageTrigger {
+                address: destination,
+                datastore_key: None,
+            }),
+            None,
+        );
+
+        // an empty filter matches everything
+        assert!(AsyncMessageFilter::default().matches(&message));
+
+        // address criteria
+        let filter = AsyncMessageFilter {
+            sender: Some(sender),
+            destination: Some(destination),
+            trigger_address: Some(destination),
+            ..Default::default()
+        };
+        assert!(filter.matches(&message));
+        let filter = AsyncMessageFilter {
+            sender: Some(destination),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&message));
+        let filter = AsyncMessageFilter {
+            trigger_address: Some(sender),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&message));
+
+        // the validity range [2, 5) overlaps [4, 10) but not [5, 10) nor [0, 2)
+        let filter = AsyncMessageFilter {
+            validity_start: Some(Slot::new(4, 0)),
+            validity_end: Some(Slot::new(10, 0)),
+            ..Default::default()
+        };
+        assert!(filter.matches(&message));
+        let filter = AsyncMessageFilter {
+            validity_start: Some(Slot::new(5, 0)),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&message));
+        let filter = AsyncMessageFilter {
+            validity_end: Some(Slot::new(2, 0)),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&message));
+
+        // a triggered message cannot be executed until its trigger is matched
+        let filter = AsyncMessageFilter {
+            can_be_executed: Some(false),
+            ..Default::default()
+        };
+        assert!(filter.matches(&message));
+        assert!(filter.matches_info(&AsyncMessageInfo::from(message.clone())));
+        let filter = AsyncMessageFilter {
+            can_be_executed: Some(true),
+            ..Default::default()
+        };
+        assert!(!filter.matches_info(&AsyncMessageInfo::from(message)));
+    }
 }
@@ -5,7 +5,9 @@
 use crate::{
     changes::AsyncPoolChanges,
     config::AsyncPoolConfig,
-    message::{AsyncMessage, AsyncMessageId, AsyncMessageInfo, AsyncMessageUpdate},
+    message::{
+        AsyncMessage, AsyncMessageFilter, AsyncMessageId, AsyncMessageInfo, AsyncMessageUpdate,
+    },
     AsyncMessageDeserializer, AsyncMessageIdDeserializer, AsyncMessageIdSerializer,
     AsyncMessageSerializer,
 };
@@ -357,6 +359,25 @@ impl AsyncPool {
         fetched_messages
     }
 
+    /// Query all the messages of the pool that match a filter.
+    ///
+    /// The `message_info_cache` is used to discard messages early,
+    /// so that only the messages that may match are fetched from the database.
+    pub fn fetch_filtered_messages(
+        &self,
+        filter: &AsyncMessageFilter,
+    ) -> BTreeMap<AsyncMessageId, AsyncMessage> {
+        self.message_info_cache
+            .iter()
+            .filter(|(_id, info)| filter.matches_info(info))
+            .filter_map(|(id, _info)| {
+                self.fetch_message(id)
+                    .filter(|message| filter.matches(message))
+                    .map(|message| (*id, message))
+            })
+            .collect()
+    }
+
     /// Deserializes the key and value, useful after bootstrap
     pub fn is_key_value_valid(&self, serialized_key: &[u8], serialized_value: &[u8]) -> bool {
         if !serialized_key.starts_with(ASYNC_POOL_PREFIX.as_bytes()) {
@@ -1100,6 +1121,8 @@ mod tests {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 100,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let db: ShareableMassaDBController = Arc::new(RwLock::new(
             Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>,
@@ -1142,6 +1165,8 @@ mod tests {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 100,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let db: ShareableMassaDBController = Arc::new(RwLock::new(
             Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>,
@@ -1204,6 +1229,8 @@ mod tests {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 100,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let db: ShareableMassaDBController = Arc::new(RwLock::new(
             Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>,
@@ -1261,6 +1288,8 @@ mod tests {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 100,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let db: ShareableMassaDBController = Arc::new(RwLock::new(
             Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>,
@@ -1318,6 +1347,8 @@ mod tests {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 100,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let db: ShareableMassaDBController = Arc::new(RwLock::new(
             Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>,
@@ -1368,6 +1399,8 @@ mod tests {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 100,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let db: ShareableMassaDBController = Arc::new(RwLock::new(Box::new(MassaDB::new(
             db_config.clone(),
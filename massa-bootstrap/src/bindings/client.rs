@@ -8,6 +8,7 @@ use crate::messages::{
 };
 use crate::settings::BootstrapClientConfig;
 use massa_hash::Hash;
+use massa_models::block_header::BlockHeaderVersionAt;
 use massa_models::config::{
     MAX_BOOTSTRAP_MESSAGE_SIZE, MAX_BOOTSTRAP_MESSAGE_SIZE_BYTES, SIGNATURE_DESER_SIZE,
 };
@@ -16,6 +17,7 @@ use massa_models::version::{Version, VersionSerializer};
 use massa_serialization::{DeserializeError, Deserializer, Serializer};
 use massa_signature::{PublicKey, Signature};
 use rand::{rngs::StdRng, RngCore, SeedableRng};
+use std::sync::Arc;
 use std::time::Instant;
 use std::{net::TcpStream, time::Duration};
 use stream_limiter::{Limiter, LimiterOptions};
@@ -27,6 +29,7 @@ pub struct BootstrapClientBinder {
     prev_message: Option<Hash>,
     version_serializer: VersionSerializer,
     cfg: BootstrapClientConfig,
+    block_header_version_at: Option<Arc<dyn BlockHeaderVersionAt>>,
 }
 
 const KNOWN_PREFIX_LEN: usize = SIGNATURE_DESER_SIZE + MAX_BOOTSTRAP_MESSAGE_SIZE_BYTES;
@@ -58,9 +61,19 @@ impl BootstrapClientBinder {
             prev_message: None,
             version_serializer: VersionSerializer::new(),
             cfg,
+            block_header_version_at: None,
         }
     }
 
+    /// Select the layout of the block headers of the received graph from the block header version active at their slot
+    pub fn with_block_header_version_at(
+        mut self,
+        block_header_version_at: Arc<dyn BlockHeaderVersionAt>,
+    ) -> Self {
+        self.block_header_version_at = Some(block_header_version_at);
+        self
+    }
+
     /// Performs a handshake. Should be called after connection
     /// NOT cancel-safe
     pub fn handshake(&mut self, version: Version) -> Result<(), BootstrapError> {
@@ -99,7 +112,11 @@ impl BootstrapClientBinder {
         let ServerMessageLeader { sig, msg_len } = self.decode_msg_leader(&known_len_buff)?;
 
         // Update this bindings "most recently received" message hash, retaining the replaced value
-        let message_deserializer = BootstrapServerMessageDeserializer::new((&self.cfg).into());
+        let message_deserializer =
+            BootstrapServerMessageDeserializer::new_with_block_header_version_at(
+                (&self.cfg).into(),
+                self.block_header_version_at.clone(),
+            );
         let prev_msg = self
             .prev_message
             .replace(Hash::compute_from(&sig.to_bytes()));
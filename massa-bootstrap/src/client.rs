@@ -3,10 +3,16 @@ use massa_db_exports::DBBatch;
 use massa_final_state::{FinalStateController, FinalStateError};
 use massa_logging::massa_trace;
 use massa_metrics::MassaMetrics;
-use massa_models::{node::NodeId, slot::Slot, streaming_step::StreamingStep, version::Version};
+use massa_models::{
+    block_header::BlockHeaderVersionAt, node::NodeId, slot::Slot, streaming_step::StreamingStep,
+    version::Version,
+};
 use massa_signature::PublicKey;
 use massa_time::MassaTime;
-use massa_versioning::versioning::{ComponentStateTypeId, MipInfo, MipState, StateAtError};
+use massa_versioning::{
+    block_header_factory::BlockHeaderFactory,
+    versioning::{ComponentStateTypeId, MipInfo, MipState, StateAtError},
+};
 use parking_lot::RwLock;
 use rand::{
     prelude::{SliceRandom, StdRng},
@@ -353,15 +359,14 @@ pub(crate) fn connect_to_server(
     addr: &SocketAddr,
     pub_key: &PublicKey,
     rw_limit: Option<u64>,
+    block_header_version_at: Arc<dyn BlockHeaderVersionAt>,
 ) -> Result<BootstrapClientBinder, BootstrapError> {
     let socket = connector.connect_timeout(*addr, Some(bootstrap_config.connect_timeout))?;
     socket.set_nonblocking(false)?;
-    Ok(BootstrapClientBinder::new(
-        socket,
-        *pub_key,
-        bootstrap_config.into(),
-        rw_limit,
-    ))
+    Ok(
+        BootstrapClientBinder::new(socket, *pub_key, bootstrap_config.into(), rw_limit)
+            .with_block_header_version_at(block_header_version_at),
+    )
 }
 
 fn filter_bootstrap_list(
@@ -399,6 +404,7 @@ pub fn get_state(
     final_state: Arc<RwLock<dyn FinalStateController>>,
     mut connector: impl BSConnector,
     version: Version,
+    t0: MassaTime,
     genesis_timestamp: MassaTime,
     end_timestamp: Option<MassaTime>,
     restart_from_snapshot_at_period: Option<u64>,
@@ -486,6 +492,13 @@ pub fn get_state(
             last_consensus_step: StreamingStep::Started,
             send_last_start_period: true,
         };
+    // the block headers of the received graph use the layout of the block version active at their slot
+    let block_header_version_at: Arc<dyn BlockHeaderVersionAt> = Arc::new(BlockHeaderFactory {
+        mip_store: final_state.read().get_mip_store().clone(),
+        thread_count: bootstrap_config.thread_count,
+        t0,
+        genesis_timestamp,
+    });
     let mut global_bootstrap_state = GlobalBootstrapState::new(final_state);
 
     let limit = bootstrap_config.rate_limit;
@@ -509,6 +522,7 @@ pub fn get_state(
                 addr,
                 &node_id.get_public_key(),
                 Some(limit),
+                block_header_version_at.clone(),
             );
             match conn {
                 Ok(mut client) => {
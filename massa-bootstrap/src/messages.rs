@@ -7,6 +7,8 @@ use massa_consensus_exports::bootstrapable_graph::{
 
 use massa_db_exports::StreamBatch;
 
+use massa_models::block::BlockDeserializerArgs;
+use massa_models::block_header::BlockHeaderVersionAt;
 use massa_models::block_id::{BlockId, BlockIdDeserializer, BlockIdSerializer};
 
 use massa_models::prehash::PreHashSet;
@@ -39,6 +41,7 @@ use nom::{
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use std::convert::TryInto;
 use std::ops::Bound::{Excluded, Included};
+use std::sync::Arc;
 
 /// Messages used during bootstrap by server
 #[derive(Debug, Clone)]
@@ -333,8 +336,18 @@ pub struct BootstrapServerMessageDeserializer {
 }
 
 impl BootstrapServerMessageDeserializer {
-    /// Creates a new `BootstrapServerMessageDeserializer`
+    /// Creates a new `BootstrapServerMessageDeserializer`, using the block header layout of version 0 at every slot
     pub fn new(args: BootstrapServerMessageDeserializerArgs) -> Self {
+        Self::new_with_block_header_version_at(args, None)
+    }
+
+    /// Creates a new `BootstrapServerMessageDeserializer`, selecting the layout of the block headers of the graph
+    pub fn new_with_block_header_version_at(
+        args: BootstrapServerMessageDeserializerArgs,
+        block_header_version_at: Option<Arc<dyn BlockHeaderVersionAt>>,
+    ) -> Self {
+        let mut block_der_args = BlockDeserializerArgs::from(&args);
+        block_der_args.block_header_version_at = block_header_version_at;
         Self {
             message_id_deserializer: U32VarIntDeserializer::new(Included(0), Included(u32::MAX)),
             time_deserializer: MassaTimeDeserializer::new((
@@ -359,7 +372,7 @@ impl BootstrapServerMessageDeserializer {
                 Included(args.max_datastore_value_length),
             )),
             bootstrapable_graph_deserializer: BootstrapableGraphDeserializer::new(
-                (&args).into(),
+                block_der_args,
                 args.max_bootstrap_blocks_length,
             ),
             block_id_set_deserializer: PreHashSetDeserializer::new(
@@ -215,6 +215,7 @@ impl From<&BootstrapServerMessageDeserializerArgs> for BlockDeserializerArgs {
             max_denunciations_per_block_header: value.max_denunciations_per_block_header,
             last_start_period: None,
             chain_id: value.chain_id,
+            block_header_version_at: None,
         }
     }
 }
@@ -1,15 +1,25 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::settings::BootstrapClientConfig;
 use crate::tests::tools::{
-    parametric_test, BootstrapClientMessageFaultyPart, BootstrapServerMessageFaultyPart,
+    gen_export_active_blocks, get_bootstrap_config, parametric_test,
+    BootstrapClientMessageFaultyPart, BootstrapServerMessageFaultyPart,
 };
 use crate::{
     BootstrapClientMessage, BootstrapClientMessageDeserializer, BootstrapClientMessageSerializer,
     BootstrapServerMessage, BootstrapServerMessageDeserializer, BootstrapServerMessageSerializer,
 };
+use massa_consensus_exports::bootstrapable_graph::BootstrapableGraph;
+use massa_db_exports::StreamBatch;
+use massa_models::block_header::BlockHeaderVersionAt;
 use massa_models::config::*;
+use massa_models::node::NodeId;
+use massa_models::prehash::PreHashSet;
+use massa_models::slot::Slot;
 use massa_serialization::{DeserializeError, Deserializer, Serializer};
+use massa_signature::KeyPair;
 
 #[test]
 fn test_serialize_bootstrap_server_message() {
@@ -200,3 +210,55 @@ fn test_serialize_error_cases_servermsg() {
         println!("===========");
     }
 }
+
+/// Block header version used at every slot
+struct FixedBlockHeaderVersion(u32);
+
+impl BlockHeaderVersionAt for FixedBlockHeaderVersion {
+    fn block_header_version_at(&self, _slot: Slot) -> u32 {
+        self.0
+    }
+}
+
+#[test]
+fn test_bootstrap_graph_block_header_version() {
+    let config: BootstrapClientConfig =
+        (&get_bootstrap_config(NodeId::new(KeyPair::generate(0).unwrap().get_public_key()))).into();
+    let mut rng = rand::thread_rng();
+    let empty_batch = StreamBatch {
+        new_elements: BTreeMap::new(),
+        updates_on_previous_elements: BTreeMap::new(),
+        change_id: Slot::new(1, 0),
+    };
+    let msg = BootstrapServerMessage::BootstrapPart {
+        slot: Slot::new(1, 0),
+        state_part: empty_batch.clone(),
+        versioning_part: empty_batch,
+        consensus_part: BootstrapableGraph {
+            final_blocks: (0..5).map(|_| gen_export_active_blocks(&mut rng)).collect(),
+        },
+        consensus_outdated_ids: PreHashSet::default(),
+        last_start_period: None,
+        last_slot_before_downtime: None,
+    };
+    let mut bytes = Vec::new();
+    BootstrapServerMessageSerializer::new()
+        .serialize(&msg, &mut bytes)
+        .unwrap();
+
+    // the headers of the graph use the layout of version 0
+    let deser = BootstrapServerMessageDeserializer::new_with_block_header_version_at(
+        (&config).into(),
+        Some(Arc::new(FixedBlockHeaderVersion(0))),
+    );
+    let (rest, deserialized) = deser.deserialize::<DeserializeError>(&bytes).unwrap();
+    assert!(rest.is_empty());
+    assert!(msg.equals(&deserialized));
+
+    // the graph deserializer follows the version of the slots: no layout is implemented for version 1 yet
+    let deser = BootstrapServerMessageDeserializer::new_with_block_header_version_at(
+        (&config).into(),
+        Some(Arc::new(FixedBlockHeaderVersion(1))),
+    );
+    assert!(deser.deserialize::<DeserializeError>(&bytes).is_err());
+}
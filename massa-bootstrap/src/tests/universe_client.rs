@@ -8,13 +8,17 @@ use massa_db_worker::MassaDB;
 use massa_final_state::MockFinalStateController;
 use massa_models::{
     config::{
-        MAX_BOOTSTRAP_FINAL_STATE_PARTS_SIZE, MAX_BOOTSTRAP_VERSIONING_ELEMENTS_SIZE, THREAD_COUNT,
+        GENESIS_TIMESTAMP, MAX_BOOTSTRAP_FINAL_STATE_PARTS_SIZE,
+        MAX_BOOTSTRAP_VERSIONING_ELEMENTS_SIZE, T0, THREAD_COUNT,
     },
     node::NodeId,
     streaming_step::StreamingStep,
 };
 use massa_test_framework::TestUniverse;
-use massa_versioning::versioning::{MipStatsConfig, MipStore};
+use massa_versioning::{
+    block_header_factory::BlockHeaderFactory,
+    versioning::{MipStatsConfig, MipStore},
+};
 use num::rational::Ratio;
 use parking_lot::RwLock;
 use tempfile::TempDir;
@@ -42,6 +46,7 @@ pub struct BootstrapClientTestUniverse {
     controllers: BootstrapClientForeignControllers,
     database: ShareableMassaDBController,
     config: BootstrapConfig,
+    block_header_factory: BlockHeaderFactory,
     pub(crate) global_bootstrap_state: GlobalBootstrapState,
 }
 
@@ -60,6 +65,8 @@ impl TestUniverse for BootstrapClientTestUniverse {
             max_final_state_elements_size: MAX_BOOTSTRAP_FINAL_STATE_PARTS_SIZE as usize,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         }))
             as Box<(dyn MassaDBController + 'static)>));
         controllers
@@ -85,6 +92,12 @@ impl TestUniverse for BootstrapClientTestUniverse {
             },
         )
         .unwrap();
+        let block_header_factory = BlockHeaderFactory {
+            mip_store: client_mip_store.clone(),
+            thread_count: THREAD_COUNT,
+            t0: T0,
+            genesis_timestamp: *GENESIS_TIMESTAMP,
+        };
         controllers
             .final_state_controller
             .write()
@@ -93,6 +106,7 @@ impl TestUniverse for BootstrapClientTestUniverse {
         let universe = Self {
             controllers,
             config,
+            block_header_factory,
             global_bootstrap_state,
             database,
         };
@@ -130,6 +144,7 @@ impl BootstrapClientTestUniverse {
             &remote_addr,
             &remote_node_id.get_public_key(),
             Some(self.config.rate_limit),
+            Arc::new(self.block_header_factory.clone()),
         )
         .unwrap();
         bootstrap_from_server(
@@ -20,3 +20,15 @@ pub const HASH_PARAMS: Params = Params {
     rounds: 600_000,
     output_length: 32,
 };
+
+/// `Argon2id` memory cost, in KiB.
+pub const ARGON2_MEMORY_COST: u32 = 64 * 1024;
+
+/// `Argon2id` number of passes.
+pub const ARGON2_TIME_COST: u32 = 3;
+
+/// `Argon2id` degree of parallelism.
+pub const ARGON2_PARALLELISM: u32 = 4;
+
+/// Size of the AES-GCM key derived from the password.
+pub const KEY_SIZE: usize = 32;
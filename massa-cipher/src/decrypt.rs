@@ -6,35 +6,26 @@
 
 use aes_gcm::aead::Aead;
 use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
-use pbkdf2::{
-    password_hash::{PasswordHasher, SaltString},
-    Pbkdf2,
-};
 
-use crate::constants::HASH_PARAMS;
 use crate::encrypt::CipherData;
 use crate::error::CipherError;
+use crate::kdf::derive_key;
 
 /// Decryption function using AES-GCM cipher.
 ///
+/// The cipher key is derived from the password with the key derivation function of the data version.
+///
 /// Read `lib.rs` module documentation for more information.
 pub fn decrypt(password: &str, data: CipherData) -> Result<Vec<u8>, CipherError> {
-    // get PBKDF2 salt
-    let salt = SaltString::encode_b64(&data.salt)
-        .map_err(|e| CipherError::DecryptionError(e.to_string()))?;
-
-    // compute PBKDF2 password hash
-    let password_hash = Pbkdf2
-        .hash_password_customized(password.as_bytes(), None, None, HASH_PARAMS, &salt)
-        .map_err(|e| CipherError::DecryptionError(e.to_string()))?
-        .hash
-        .expect("content is missing after a successful hash");
+    // compute the password hash
+    let password_hash =
+        derive_key(data.version, password, &data.salt).map_err(CipherError::DecryptionError)?;
 
     // parse AES-GCM nonce
     let nonce = Nonce::from_slice(&data.nonce);
 
     // decrypt the data
-    let cipher = Aes256Gcm::new_from_slice(password_hash.as_bytes()).expect("invalid size key");
+    let cipher = Aes256Gcm::new_from_slice(&password_hash).expect("invalid size key");
     let decrypted_bytes = cipher
         .decrypt(nonce, data.encrypted_bytes.as_ref())
         .map_err(|_| {
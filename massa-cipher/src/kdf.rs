@@ -0,0 +1,93 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! massa-cipher key derivation module.
+//!
+//! Read `lib.rs` module documentation for more information.
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use pbkdf2::{
+    password_hash::{PasswordHasher, SaltString},
+    Pbkdf2,
+};
+
+use crate::constants::{
+    ARGON2_MEMORY_COST, ARGON2_PARALLELISM, ARGON2_TIME_COST, HASH_PARAMS, KEY_SIZE, SALT_SIZE,
+};
+
+/// Version of the encryption scheme, identifying the key derivation function of the cipher key.
+///
+/// The version numbers are the ones of the wallet file format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CipherVersion {
+    /// Key derived with `PBKDF2` (version 1)
+    #[default]
+    Pbkdf2,
+    /// Key derived with the memory-hard `Argon2id` (version 2)
+    Argon2id,
+}
+
+impl From<CipherVersion> for u64 {
+    fn from(version: CipherVersion) -> Self {
+        match version {
+            CipherVersion::Pbkdf2 => 1,
+            CipherVersion::Argon2id => 2,
+        }
+    }
+}
+
+impl TryFrom<u64> for CipherVersion {
+    type Error = String;
+
+    fn try_from(version: u64) -> Result<Self, Self::Error> {
+        match version {
+            1 => Ok(CipherVersion::Pbkdf2),
+            2 => Ok(CipherVersion::Argon2id),
+            v => Err(format!("unknown cipher version {}", v)),
+        }
+    }
+}
+
+impl std::str::FromStr for CipherVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pbkdf2" => Ok(CipherVersion::Pbkdf2),
+            "argon2id" => Ok(CipherVersion::Argon2id),
+            _ => Err(format!("unknown key derivation function {}", s)),
+        }
+    }
+}
+
+/// Derives the AES-GCM key from the password with the key derivation function of the version
+pub(crate) fn derive_key(
+    version: CipherVersion,
+    password: &str,
+    salt: &[u8; SALT_SIZE],
+) -> Result<Vec<u8>, String> {
+    match version {
+        CipherVersion::Pbkdf2 => {
+            let salt = SaltString::encode_b64(salt).map_err(|e| e.to_string())?;
+            let password_hash = Pbkdf2
+                .hash_password_customized(password.as_bytes(), None, None, HASH_PARAMS, &salt)
+                .map_err(|e| e.to_string())?
+                .hash
+                .expect("content is missing after a successful hash");
+            Ok(password_hash.as_bytes().to_vec())
+        }
+        CipherVersion::Argon2id => {
+            let params = Params::new(
+                ARGON2_MEMORY_COST,
+                ARGON2_TIME_COST,
+                ARGON2_PARALLELISM,
+                Some(KEY_SIZE),
+            )
+            .map_err(|e| e.to_string())?;
+            let mut key = vec![0u8; KEY_SIZE];
+            Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                .hash_password_into(password.as_bytes(), salt, &mut key)
+                .map_err(|e| e.to_string())?;
+            Ok(key)
+        }
+    }
+}
@@ -8,7 +8,9 @@
 //! that provides confidentiality and authenticity.
 //!
 //! To hash the password before using it as a cipher key, we use the `PBKDF2` key derivation function
-//! as specified in [RFC 2898](https://datatracker.ietf.org/doc/html/rfc2898).
+//! as specified in [RFC 2898](https://datatracker.ietf.org/doc/html/rfc2898),
+//! or the memory-hard `Argon2id` key derivation function as specified in [RFC 9106](https://datatracker.ietf.org/doc/html/rfc9106).
+//! The `CipherVersion` of the encrypted data identifies the key derivation function used.
 //!
 //! The AES-GCM crate we use has received one security audit by NCC Group, with no significant findings.
 
@@ -16,11 +18,13 @@ mod constants;
 mod decrypt;
 mod encrypt;
 mod error;
+mod kdf;
 mod tests;
 
 pub use decrypt::decrypt;
-pub use encrypt::encrypt;
 pub use encrypt::CipherData;
+pub use encrypt::{encrypt, encrypt_with_version};
 pub use error::CipherError;
+pub use kdf::CipherVersion;
 
 pub type Salt = [u8; constants::SALT_SIZE];
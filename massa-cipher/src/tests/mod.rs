@@ -3,7 +3,9 @@ use crate::constants::{HASH_PARAMS, NONCE_SIZE, SALT_SIZE};
 #[cfg(test)]
 use crate::decrypt::decrypt;
 #[cfg(test)]
-use crate::encrypt::encrypt;
+use crate::encrypt::{encrypt, encrypt_with_version};
+#[cfg(test)]
+use crate::kdf::CipherVersion;
 
 #[test]
 fn test_encrypt() {
@@ -42,3 +44,25 @@ fn test_encrypt_decrypt_bad_password() {
     let cipher_data = encrypt("password", data.as_bytes()).unwrap();
     decrypt("wrong", cipher_data).expect_err("Wrong password should failed");
 }
+
+#[test]
+fn test_encrypt_decrypt_argon2id() {
+    let data = "data";
+
+    let mut cipher_data =
+        encrypt_with_version(CipherVersion::Argon2id, "password", data.as_bytes()).unwrap();
+    assert_eq!(cipher_data.version, CipherVersion::Argon2id);
+    decrypt(
+        "wrong",
+        encrypt_with_version(CipherVersion::Argon2id, "password", data.as_bytes()).unwrap(),
+    )
+    .expect_err("Wrong password should failed");
+
+    // the key derivation function is the one of the version
+    cipher_data.version = CipherVersion::Pbkdf2;
+    decrypt("password", cipher_data).expect_err("Wrong version should failed");
+
+    let cipher_data =
+        encrypt_with_version(CipherVersion::Argon2id, "password", data.as_bytes()).unwrap();
+    assert_eq!(decrypt("password", cipher_data).unwrap(), data.as_bytes());
+}
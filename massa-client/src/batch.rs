@@ -0,0 +1,360 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Batch mode: runs a script of commands, printing their JSON output, and stops at the first failure.
+//!
+//! Each line of a script is one of:
+//! * `COMMAND PARAMETERS...`: runs a client command
+//! * `let NAME = COMMAND PARAMETERS...`: runs a client command and captures its JSON output in `NAME`
+//! * `wait_final OperationId [TimeoutSeconds]`: waits for an operation to be final (60 seconds by default)
+//! * `sleep Milliseconds`: waits for some time
+//! * `assert LEFT == RIGHT` or `assert LEFT != RIGHT`: compares two values
+//!
+//! Empty lines and lines starting with `#` are ignored.
+//! `${NAME}` is replaced by a captured output, and `${NAME.field.0}` by a field or an item of it:
+//! a JSON string is replaced by its content, any other JSON value by its JSON text.
+
+use crate::cmds::Command;
+use crate::repl::group_parameters;
+use crate::watch::{wait_operation_final, DEFAULT_WAIT_FINAL_TIMEOUT};
+use crate::{ask_password, JsonError};
+use anyhow::{anyhow, bail, Context, Result};
+use massa_models::operation::OperationId;
+use massa_sdk::Client;
+use massa_wallet::Wallet;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Runs a script of commands, returns an error at the first failing step
+pub(crate) async fn run(
+    client: &mut Client,
+    wallet_path: &Path,
+    password: Option<String>,
+    script_path: &Path,
+) -> Result<()> {
+    let script = std::fs::read_to_string(script_path)
+        .with_context(|| format!("could not read {}", script_path.display()))?;
+    let mut variables: HashMap<String, Value> = HashMap::new();
+    let mut wallet_opt = None;
+
+    for (index, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Err(e) = run_line(
+            client,
+            &mut wallet_opt,
+            wallet_path,
+            &password,
+            &mut variables,
+            line,
+        )
+        .await
+        {
+            let error = format!("line {}: {}", index + 1, e);
+            println!(
+                "{}",
+                serde_json::to_string(&JsonError {
+                    error: error.clone()
+                })?
+            );
+            bail!(error);
+        }
+    }
+    Ok(())
+}
+
+/// Step of a script
+#[derive(Debug, PartialEq)]
+enum Step {
+    /// runs a client command, capturing its JSON output in the variable if any
+    Command {
+        variable: Option<String>,
+        tokens: Vec<String>,
+    },
+    /// waits for an operation to be final
+    WaitFinal {
+        operation_id: OperationId,
+        timeout: Duration,
+    },
+    /// waits for some time
+    Sleep(Duration),
+    /// compares two values, expecting them to be equal or different
+    Assert {
+        left: String,
+        right: String,
+        equal: bool,
+    },
+}
+
+async fn run_line(
+    client: &mut Client,
+    wallet_opt: &mut Option<Wallet>,
+    wallet_path: &Path,
+    password: &Option<String>,
+    variables: &mut HashMap<String, Value>,
+    line: &str,
+) -> Result<()> {
+    match parse_line(line, variables)? {
+        None => {}
+        Some(Step::Command { variable, tokens }) => {
+            let output = run_command(client, wallet_opt, wallet_path, password, &tokens).await?;
+            if let Some(variable) = variable {
+                variables.insert(variable, output);
+            }
+        }
+        Some(Step::WaitFinal {
+            operation_id,
+            timeout,
+        }) => wait_operation_final(client, operation_id, timeout).await?,
+        Some(Step::Sleep(duration)) => tokio::time::sleep(duration).await,
+        Some(Step::Assert { left, right, equal }) => check_assertion(&left, &right, equal)?,
+    }
+    Ok(())
+}
+
+/// Parses a line of a script, after replacing its variable references
+fn parse_line(line: &str, variables: &HashMap<String, Value>) -> Result<Option<Step>> {
+    let tokens = group_parameters(line.split_whitespace().map(|x| x.to_string()).collect())
+        .iter()
+        .map(|token| substitute(token, variables))
+        .collect::<Result<Vec<String>>>()?;
+    if tokens.is_empty() {
+        return Ok(None);
+    }
+
+    let step = match tokens[0].as_str() {
+        "let" => {
+            if tokens.len() < 4 || tokens[2] != "=" {
+                bail!("expected `let NAME = COMMAND PARAMETERS...`");
+            }
+            Step::Command {
+                variable: Some(tokens[1].clone()),
+                tokens: tokens[3..].to_vec(),
+            }
+        }
+        "wait_final" => {
+            let (operation_id, timeout) = match &tokens[1..] {
+                [operation_id] => (operation_id, DEFAULT_WAIT_FINAL_TIMEOUT),
+                [operation_id, timeout] => (operation_id, Duration::from_secs(timeout.parse()?)),
+                _ => bail!("expected `wait_final OperationId [TimeoutSeconds]`"),
+            };
+            Step::WaitFinal {
+                operation_id: OperationId::from_str(operation_id)?,
+                timeout,
+            }
+        }
+        "sleep" => {
+            if tokens.len() != 2 {
+                bail!("expected `sleep Milliseconds`");
+            }
+            Step::Sleep(Duration::from_millis(tokens[1].parse()?))
+        }
+        "assert" => match &tokens[1..] {
+            [left, operator, right] if operator == "==" || operator == "!=" => Step::Assert {
+                left: left.clone(),
+                right: right.clone(),
+                equal: operator == "==",
+            },
+            _ => bail!("expected `assert LEFT == RIGHT` or `assert LEFT != RIGHT`"),
+        },
+        _ => Step::Command {
+            variable: None,
+            tokens,
+        },
+    };
+    Ok(Some(step))
+}
+
+/// Checks that two values are equal, or different if `equal` is false
+fn check_assertion(left: &str, right: &str, equal: bool) -> Result<()> {
+    match (left == right, equal) {
+        (false, true) => bail!("assertion failed: {} == {}", left, right),
+        (true, false) => bail!("assertion failed: {} != {}", left, right),
+        _ => Ok(()),
+    }
+}
+
+/// Runs a client command, prints and returns its JSON output
+async fn run_command(
+    client: &mut Client,
+    wallet_opt: &mut Option<Wallet>,
+    wallet_path: &Path,
+    password: &Option<String>,
+    tokens: &[String],
+) -> Result<Value> {
+    let command =
+        Command::from_str(&tokens[0]).map_err(|_| anyhow!("unknown command {}", tokens[0]))?;
+    if command.is_pwd_needed() && wallet_opt.is_none() {
+        let password = match (password, std::env::var("MASSA_CLIENT_PASSWORD")) {
+            (Some(pwd), _) => pwd.clone(),
+            (_, Ok(pwd)) => pwd,
+            _ => ask_password(wallet_path),
+        };
+        *wallet_opt = Some(Wallet::new(
+            wallet_path.to_path_buf(),
+            password,
+            client.chain_id,
+        )?);
+    }
+    let output = command
+        .run(client, wallet_opt, &tokens[1..], true)
+        .await?
+        .to_json_value()?;
+    println!("{}", output);
+    Ok(output)
+}
+
+/// Replaces the `${NAME.path}` references to captured outputs in a token
+fn substitute(token: &str, variables: &HashMap<String, Value>) -> Result<String> {
+    let mut result = String::new();
+    let mut rest = token;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| anyhow!("unclosed variable reference in {}", token))?
+            + start;
+        let mut path = rest[start + 2..end].split('.');
+        let name = path.next().unwrap_or_default();
+        let mut value = variables
+            .get(name)
+            .ok_or_else(|| anyhow!("unknown variable {}", name))?;
+        for field in path {
+            value = match value {
+                Value::Array(items) => field.parse::<usize>().ok().and_then(|i| items.get(i)),
+                Value::Object(fields) => fields.get(field),
+                _ => None,
+            }
+            .ok_or_else(|| anyhow!("{} has no field {}", &rest[start + 2..end], field))?;
+        }
+        match value {
+            Value::String(string) => result.push_str(string),
+            other => result.push_str(&other.to_string()),
+        }
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn variables() -> HashMap<String, Value> {
+        let mut variables = HashMap::new();
+        variables.insert(
+            "op".to_string(),
+            json!({"id": "O1abc", "slots": [{"period": 3}], "fee": 0.01}),
+        );
+        variables.insert("name".to_string(), json!("alice"));
+        variables
+    }
+
+    #[test]
+    fn test_substitute_paths() {
+        let variables = variables();
+        assert_eq!(substitute("${name}", &variables).unwrap(), "alice");
+        assert_eq!(substitute("${op.id}", &variables).unwrap(), "O1abc");
+        assert_eq!(substitute("${op.slots.0.period}", &variables).unwrap(), "3");
+        assert_eq!(substitute("${op.fee}", &variables).unwrap(), "0.01");
+        assert_eq!(
+            substitute("${op.slots}", &variables).unwrap(),
+            r#"[{"period":3}]"#
+        );
+        assert_eq!(
+            substitute("from_${name}_at_${op.slots.0.period}", &variables).unwrap(),
+            "from_alice_at_3"
+        );
+        assert_eq!(
+            substitute("no_reference", &variables).unwrap(),
+            "no_reference"
+        );
+    }
+
+    #[test]
+    fn test_substitute_errors() {
+        let variables = variables();
+        let error = substitute("${unknown}", &variables).unwrap_err();
+        assert_eq!(error.to_string(), "unknown variable unknown");
+        let error = substitute("${op.missing}", &variables).unwrap_err();
+        assert_eq!(error.to_string(), "op.missing has no field missing");
+        assert!(substitute("${op.slots.1}", &variables).is_err());
+        assert!(substitute("${name.0}", &variables).is_err());
+        let error = substitute("${op.id", &variables).unwrap_err();
+        assert_eq!(error.to_string(), "unclosed variable reference in ${op.id");
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let variables = variables();
+        assert_eq!(parse_line("", &variables).unwrap(), None);
+        assert_eq!(
+            parse_line("let balance = wallet_info ${name}", &variables).unwrap(),
+            Some(Step::Command {
+                variable: Some("balance".to_string()),
+                tokens: vec!["wallet_info".to_string(), "alice".to_string()],
+            })
+        );
+        assert_eq!(
+            parse_line("get_status", &variables).unwrap(),
+            Some(Step::Command {
+                variable: None,
+                tokens: vec!["get_status".to_string()],
+            })
+        );
+        assert_eq!(
+            parse_line("sleep 500", &variables).unwrap(),
+            Some(Step::Sleep(Duration::from_millis(500)))
+        );
+        assert!(parse_line("let balance wallet_info", &variables).is_err());
+        assert!(parse_line("let balance =", &variables).is_err());
+        assert!(parse_line("sleep", &variables).is_err());
+        assert!(parse_line("sleep soon", &variables).is_err());
+    }
+
+    #[test]
+    fn test_parse_wait_final() {
+        let variables = variables();
+        assert!(parse_line("wait_final", &variables).is_err());
+        assert!(parse_line("wait_final ${op.id}", &variables).is_err());
+        let operation_id = "O1xcVGtyWAyrehW1NDpnZ1wE5K95n8qVJCV9dEJSp1ypU8eJsQU";
+        assert_eq!(
+            parse_line(&format!("wait_final {} 10", operation_id), &variables).unwrap(),
+            Some(Step::WaitFinal {
+                operation_id: OperationId::from_str(operation_id).unwrap(),
+                timeout: Duration::from_secs(10),
+            })
+        );
+        assert_eq!(
+            parse_line(&format!("wait_final {}", operation_id), &variables).unwrap(),
+            Some(Step::WaitFinal {
+                operation_id: OperationId::from_str(operation_id).unwrap(),
+                timeout: DEFAULT_WAIT_FINAL_TIMEOUT,
+            })
+        );
+        assert!(parse_line(&format!("wait_final {} soon", operation_id), &variables).is_err());
+    }
+
+    #[test]
+    fn test_assertions() {
+        let variables = variables();
+        let run = |line: &str| match parse_line(line, &variables)? {
+            Some(Step::Assert { left, right, equal }) => check_assertion(&left, &right, equal),
+            step => bail!("unexpected step {:?}", step),
+        };
+        assert!(run("assert ${name} == alice").is_ok());
+        assert!(run("assert ${op.slots.0.period} != 4").is_ok());
+        let error = run("assert ${name} == bob").unwrap_err();
+        assert_eq!(error.to_string(), "assertion failed: alice == bob");
+        let error = run("assert ${op.id} != O1abc").unwrap_err();
+        assert_eq!(error.to_string(), "assertion failed: O1abc != O1abc");
+        assert!(run("assert ${name} < bob").is_err());
+        assert!(run("assert ${name}").is_err());
+    }
+}
@@ -1,13 +1,23 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
-use crate::display::Output;
-use crate::{client_warning, rpc_error};
+use crate::datastore_file::{read_datastore_file, serialize_datastore, split_datastore_parameter};
+use crate::display::{DecodedReadOnlyResponse, Output};
+use crate::history_file::{split_export_parameter, write_history_file};
+use crate::offline::{write_json_file, SignedOperationFile, UnsignedOperationFile};
+use crate::watch::{
+    wait_operation_final, watch, watch_events, Watched, DEFAULT_WAIT_FINAL_TIMEOUT,
+};
+use crate::{ask_mnemonic, ask_new_password};
+use crate::{client_warning, grpc_error, rpc_error};
 use anyhow::{anyhow, bail, Result};
 use console::style;
 use massa_api_exports::{
-    address::{AddressInfo, CompactAddressInfo},
+    address::{
+        AddressInfo, AddressTransactionsPage, AddressTransactionsRequest, CompactAddressInfo,
+    },
     datastore::DatastoreEntryInput,
-    execution::{ReadOnlyBytecodeExecution, ReadOnlyCall},
+    execution::{ReadOnlyBytecodeExecution, ReadOnlyCall, ReadOnlyResult},
+    node::MipVoteInput,
     operation::OperationInput,
 };
 use massa_models::node::NodeId;
@@ -22,20 +32,32 @@ use massa_models::{
     operation::{Operation, OperationId, OperationType},
     slot::Slot,
 };
+use massa_proto_rs::massa::api::v1 as grpc_api;
+use massa_proto_rs::massa::api::v1::execution_query_request_item::RequestItem;
+use massa_proto_rs::massa::api::v1::{search_blocks_filter, selector_draws_filter};
+use massa_proto_rs::massa::model::v1 as grpc_model;
+use massa_sdk::args::{decode_hex, deserialize_args, serialize_args, ArgType, ArgValue, ArgsError};
+use massa_sdk::grpc::{GrpcPrivateClient, GrpcPublicClient};
+use massa_sdk::grpc_ext;
 use massa_sdk::Client;
-use massa_signature::KeyPair;
-use massa_wallet::Wallet;
+use massa_signature::{KeyPair, PublicKey};
+use massa_versioning::mip_votes::MipVote;
+use massa_wallet::{generate_mnemonic, CipherVersion, Wallet};
 
 use serde::Serialize;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Write as _;
 use std::fmt::{Debug, Display};
 use std::net::IpAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 use strum::{EnumMessage, EnumProperty, IntoEnumIterator};
 use strum_macros::{Display, EnumIter, EnumString};
 
+/// Number of successive unused addresses after which `wallet_restore_mnemonic` stops scanning derived addresses
+const HD_RESTORE_GAP_LIMIT: u32 = 20;
+
 /// All the client commands
 /// the order they are defined is the order they are displayed in so be careful
 /// Maybe it would be worth renaming some of them for consistency
@@ -100,6 +122,27 @@ pub enum Command {
     )]
     node_get_staking_addresses,
 
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "[Address1 Address2 ...]", pwd_not_needed = "true"),
+        message = "show the blocks and endorsements missed by the staking addresses, with the reason of each miss. No args shows all staking addresses"
+    )]
+    node_get_missed_slots,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(pwd_not_needed = "true"),
+        message = "show the status of the MIPs known by the node, with your votes"
+    )]
+    node_get_mip_status,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "MipName (for, against or none)", pwd_not_needed = "true"),
+        message = "vote for or against a MIP: the vote chooses the network version announced in the blocks you produce"
+    )]
+    node_set_mip_vote,
+
     #[strum(
         ascii_case_insensitive,
         props(args = "Address1 Address2 ..."),
@@ -156,6 +199,16 @@ pub enum Command {
     )]
     get_addresses,
 
+    #[strum(
+        ascii_case_insensitive,
+        props(
+            args = "Address [StartCycle EndCycle] [export=PathToFile]",
+            pwd_not_needed = "true"
+        ),
+        message = "get the final operations and coin transfers sent or received by an address (the node must record the transaction history), optionally exported to a CSV (.csv extension) or JSON file"
+    )]
+    get_address_history,
+
     #[strum(
         ascii_case_insensitive,
         props(args = "Address Key", pwd_not_needed = "true"),
@@ -194,6 +247,130 @@ pub enum Command {
     )]
     get_filtered_sc_output_event,
 
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "OperationId [TimeoutSeconds]", pwd_not_needed = "true"),
+        message = "wait until an operation is final, for 60 seconds by default"
+    )]
+    wait_operation_final,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "[Address]", pwd_not_needed = "true"),
+        message = "stream the new blocks, optionally only those created by an address, until Ctrl-C"
+    )]
+    watch_blocks,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "[Address]", pwd_not_needed = "true"),
+        message = "stream the operations of the new blocks, optionally only those involving an address, until Ctrl-C"
+    )]
+    watch_operations,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "[Address]", pwd_not_needed = "true"),
+        message = "stream the endorsements of the new blocks, optionally only those created by an address, until Ctrl-C"
+    )]
+    watch_endorsements,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "[Address]", pwd_not_needed = "true"),
+        message = "stream the new final smart contract events, optionally only those with an address in their call stack, until Ctrl-C"
+    )]
+    watch_events,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(pwd_not_needed = "true"),
+        message = "show the status of the node, using the public gRPC API"
+    )]
+    grpc_get_status,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "Address1 Address2 ...", pwd_not_needed = "true"),
+        message = "query the final and candidate balances and rolls of addresses, using the public gRPC API"
+    )]
+    grpc_query_state,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(
+            args = "BlockId1|Address1 BlockId2|Address2 ...",
+            pwd_not_needed = "true"
+        ),
+        message = "search blocks by ids or by creator addresses, using the public gRPC API"
+    )]
+    grpc_search_blocks,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "[Address1 Address2 ...]", pwd_not_needed = "true"),
+        message = "show the block and endorsement draws of the selector, optionally only those of some addresses, using the public gRPC API"
+    )]
+    grpc_get_selector_draws,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(pwd_not_needed = "true"),
+        message = "show the status of the node, including its private information, using the private gRPC API"
+    )]
+    grpc_get_node_status,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(pwd_not_needed = "true"),
+        message = "show the MIP store status of the node, using the private gRPC API"
+    )]
+    grpc_get_mip_status,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(
+            args = "Address prefix=Prefix cursor=HexCursor limit=Number reverse=bool is_final=bool",
+            pwd_not_needed = "true"
+        ),
+        message = "show a page of the datastore entries of an address, optionally only the keys starting with a prefix (must be UTF-8), using the public gRPC API"
+    )]
+    grpc_get_datastore_entries_page,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(
+            args = "Address1 Address2 ... start_cycle=Cycle end_cycle=Cycle offset=Number limit=Number",
+            pwd_not_needed = "true"
+        ),
+        message = "show a page of the upcoming block and endorsement draws of addresses, using the public gRPC API"
+    )]
+    grpc_get_addresses_draws,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(
+            args = "sender=Address destination=Address validity_start=slot_period,slot_thread validity_end=slot_period,slot_thread trigger_address=Address can_be_executed=bool",
+            pwd_not_needed = "true"
+        ),
+        message = "show the pending asynchronous messages of the final and candidate pools with various filters, using the public gRPC API"
+    )]
+    grpc_get_async_messages,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(pwd_not_needed = "true"),
+        message = "show the status of the MIPs known by the node, with your votes, using the private gRPC API"
+    )]
+    grpc_get_mip_status_with_votes,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "MipName (for, against or none)", pwd_not_needed = "true"),
+        message = "vote for or against a MIP, using the private gRPC API"
+    )]
+    grpc_set_mip_vote,
+
     #[strum(
         ascii_case_insensitive,
         props(args = "show-all-keys"),
@@ -217,10 +394,29 @@ pub enum Command {
 
     #[strum(
         ascii_case_insensitive,
-        message = "generate a secret key and add it into the wallet"
+        message = "generate a secret key and add it into the wallet (derived from the wallet mnemonic, if any)"
     )]
     wallet_generate_secret_key,
 
+    #[strum(
+        ascii_case_insensitive,
+        message = "generate a mnemonic from which the secret keys of the wallet will be derived, to back up the wallet once"
+    )]
+    wallet_generate_mnemonic,
+
+    #[strum(
+        ascii_case_insensitive,
+        message = "restore the secret keys derived from a mnemonic (asked in a hidden prompt) that hold coins or rolls"
+    )]
+    wallet_restore_mnemonic,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "[argon2id|pbkdf2]"),
+        message = "change the wallet password, re-encrypting the wallet with a key derived by argon2id (default) or pbkdf2"
+    )]
+    wallet_change_password,
+
     #[strum(
         ascii_case_insensitive,
         props(args = "SecretKey1 SecretKey2 ..."),
@@ -228,10 +424,24 @@ pub enum Command {
     )]
     wallet_add_secret_keys,
 
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "Address|PublicKey [Label]"),
+        message = "add a watch-only address to the wallet, from the address or its public key, to follow its balance without its secret key"
+    )]
+    wallet_add_watch_only,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "Address [Label]"),
+        message = "set the label of an address of the wallet, or remove it if no label is given"
+    )]
+    wallet_set_label,
+
     #[strum(
         ascii_case_insensitive,
         props(args = "Address1 Address2 ..."),
-        message = "remove a list of addresses from the wallet"
+        message = "remove a list of addresses from the wallet, watch-only or not"
     )]
     wallet_remove_addresses,
 
@@ -242,6 +452,27 @@ pub enum Command {
     )]
     wallet_sign,
 
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "UnsignedFile Command Parameter1 Parameter2 ..."),
+        message = "build the operation of a command (send_transaction, buy_rolls, sell_rolls, execute_smart_contract or call_smart_contract) into an unsigned operation file, to sign it offline with sign_operation"
+    )]
+    build_operation,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "UnsignedFile SignedFile"),
+        message = "sign an unsigned operation file with the wallet, without network access, into a signed operation file"
+    )]
+    sign_operation,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "SignedFile1 SignedFile2 ...", pwd_not_needed = "true"),
+        message = "send signed operation files to the node"
+    )]
+    send_signed_operations,
+
     #[strum(
         ascii_case_insensitive,
         props(args = "Address RollCount Fee"),
@@ -265,32 +496,35 @@ pub enum Command {
 
     #[strum(
         ascii_case_insensitive,
-        props(args = "SenderAddress PathToBytecode MaxGas MaxCoins Fee"),
-        message = "create and send an operation containing byte code"
+        props(args = "SenderAddress PathToBytecode MaxGas MaxCoins Fee datastore=PathToDatastore"),
+        message = "create and send an operation containing byte code, with the operation datastore entries of a JSON file (optional)"
     )]
     execute_smart_contract,
 
     #[strum(
         ascii_case_insensitive,
         props(args = "SenderAddress TargetAddress FunctionName Parameter MaxGas Coins Fee"),
-        message = "create and send an operation to call a function of a smart contract"
+        message = "create and send an operation to call a function of a smart contract, Parameter is either a raw string or typed arguments (e.g. u64:100 'string:foo bar' address:AU...) encoded in the smart contract Args format"
     )]
     call_smart_contract,
 
     #[strum(
         ascii_case_insensitive,
-        props(args = "PathToBytecode MaxGas Address Fee", pwd_not_needed = "true"),
-        message = "execute byte code, address is optional, fee is optional. Nothing is really executed on chain"
+        props(
+            args = "PathToBytecode MaxGas Address Fee datastore=PathToDatastore",
+            pwd_not_needed = "true"
+        ),
+        message = "execute byte code, address, fee and operation datastore file are optional. Nothing is really executed on chain"
     )]
     read_only_execute_smart_contract,
 
     #[strum(
         ascii_case_insensitive,
         props(
-            args = "TargetAddress TargetFunction Parameter MaxGas SenderAddress Coins Fee",
+            args = "TargetAddress TargetFunction Parameter MaxGas SenderAddress Coins Fee result=Type1,Type2",
             pwd_not_needed = "true"
         ),
-        message = "call a smart contract function, sender address, coins and fee are optional. Nothing is really executed on chain"
+        message = "call a smart contract function, sender address, coins, fee and result types are optional. Parameter is either a raw string or typed arguments (e.g. u64:100 'string:foo bar' address:AU...), and the result is decoded with the result types, both in the smart contract Args format. Nothing is really executed on chain"
     )]
     read_only_call,
 
@@ -330,8 +564,12 @@ pub enum ListOperation {
 /// TODO re-factor me
 #[derive(Debug, Serialize)]
 pub(crate) struct ExtendedWalletEntry {
-    /// the keypair
-    pub keypair: KeyPair,
+    /// the keypair, none for a watch-only address
+    pub keypair: Option<KeyPair>,
+    /// the public key of a watch-only address, if known
+    pub public_key: Option<PublicKey>,
+    /// user label of the address
+    pub label: Option<String>,
     /// address and balance information
     pub address_info: CompactAddressInfo,
     /// whether to display the public/secret keys or just the address info
@@ -340,9 +578,21 @@ pub(crate) struct ExtendedWalletEntry {
 
 impl Display for ExtendedWalletEntry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.show_keys {
-            writeln!(f, "Secret key: {}", self.keypair)?;
-            writeln!(f, "Public key: {}", self.keypair.get_public_key())?;
+        if let Some(label) = &self.label {
+            writeln!(f, "Label: {}", label)?;
+        }
+        match &self.keypair {
+            Some(keypair) if self.show_keys => {
+                writeln!(f, "Secret key: {}", keypair)?;
+                writeln!(f, "Public key: {}", keypair.get_public_key())?;
+            }
+            Some(_) => {}
+            None => {
+                writeln!(f, "Watch-only")?;
+                if let Some(public_key) = &self.public_key {
+                    writeln!(f, "Public key: {}", public_key)?;
+                }
+            }
         }
         writeln!(f, "{}", self.address_info)?;
         writeln!(f, "\n=====\n")?;
@@ -362,14 +612,20 @@ impl ExtendedWallet {
             addresses_info
                 .iter()
                 .map(|x| {
-                    let keypair = wallet
-                        .keys
-                        .get(&x.address)
-                        .ok_or_else(|| anyhow!("missing key"))?;
+                    let keypair = wallet.keys.get(&x.address).cloned();
+                    let public_key = match &keypair {
+                        Some(_) => None,
+                        None => *wallet
+                            .get_watch_only_addresses()
+                            .get(&x.address)
+                            .ok_or_else(|| anyhow!("missing key"))?,
+                    };
                     Ok((
                         x.address,
                         ExtendedWalletEntry {
-                            keypair: keypair.clone(),
+                            keypair,
+                            public_key,
+                            label: wallet.get_label(&x.address).cloned(),
                             address_info: x.compact(),
                             show_keys,
                         },
@@ -441,6 +697,45 @@ impl Command {
             }
         }
 
+        if let Command::build_operation = self {
+            if parameters.len() < 2 {
+                bail!("wrong number of parameters");
+            }
+            let command = parameters[1].parse::<Command>()?;
+            if !matches!(
+                command,
+                Command::send_transaction
+                    | Command::buy_rolls
+                    | Command::sell_rolls
+                    | Command::execute_smart_contract
+                    | Command::call_smart_contract
+            ) {
+                bail!("{} does not send an operation", command);
+            }
+            let unsigned_output = PathBuf::from(&parameters[0]);
+            return command
+                .execute(
+                    client,
+                    wallet_opt,
+                    &parameters[2..],
+                    json,
+                    Some(&unsigned_output),
+                )
+                .await;
+        }
+        self.execute(client, wallet_opt, parameters, json, None)
+            .await
+    }
+
+    /// Run the command, building the operations into the `unsigned_output` file instead of sending them, if any
+    async fn execute(
+        &self,
+        client: &mut Client,
+        wallet_opt: &mut Option<Wallet>,
+        parameters: &[String],
+        json: bool,
+        unsigned_output: Option<&Path>,
+    ) -> Result<Box<dyn Output>> {
         match self {
             Command::help => {
                 if !json {
@@ -531,6 +826,44 @@ impl Command {
                 }
             }
 
+            Command::node_get_missed_slots => {
+                let addresses = parse_vec::<Address>(parameters)?;
+                match client.private.get_missed_slots(addresses).await {
+                    Ok(missed_slots) => Ok(Box::new(missed_slots)),
+                    Err(e) => rpc_error!(e),
+                }
+            }
+
+            Command::node_get_mip_status => match client.private.get_mip_status().await {
+                Ok(mip_status) => Ok(Box::new(mip_status)),
+                Err(e) => rpc_error!(e),
+            },
+
+            Command::node_set_mip_vote => {
+                if parameters.len() != 2 {
+                    bail!("wrong number of parameters");
+                }
+                let vote = match parameters[1].to_lowercase().as_str() {
+                    "for" => Some(MipVote::For),
+                    "against" => Some(MipVote::Against),
+                    "none" => None,
+                    _ => bail!("the vote must be for, against or none"),
+                };
+                let mip_vote = MipVoteInput {
+                    name: parameters[0].clone(),
+                    vote,
+                };
+                match client.private.node_set_mip_vote(mip_vote).await {
+                    Ok(()) => {
+                        if !json {
+                            println!("Vote successfully set");
+                        }
+                    }
+                    Err(e) => rpc_error!(e),
+                };
+                Ok(Box::new(()))
+            }
+
             Command::node_testnet_rewards_program_ownership_proof => {
                 let wallet = wallet_opt.as_mut().unwrap();
 
@@ -577,6 +910,61 @@ impl Command {
                 }
             }
 
+            Command::get_address_history => {
+                let (parameters, export_path) = split_export_parameter(parameters);
+                if parameters.len() != 1 && parameters.len() != 3 {
+                    bail!("wrong number of parameters");
+                }
+                let address = parameters[0].parse::<Address>()?;
+                let (start_cycle, end_cycle) = match parameters.get(1..3) {
+                    Some([start_cycle, end_cycle]) => (
+                        Some(start_cycle.parse::<u64>()?),
+                        Some(end_cycle.parse::<u64>()?),
+                    ),
+                    _ => (None, None),
+                };
+
+                // fetch every page of the history
+                let mut entries = Vec::new();
+                let mut start = None;
+                loop {
+                    let page = match client
+                        .public
+                        .get_address_transactions(AddressTransactionsRequest {
+                            address,
+                            start_cycle,
+                            end_cycle,
+                            start,
+                            limit: None,
+                        })
+                        .await
+                    {
+                        Ok(page) => page,
+                        Err(e) => rpc_error!(e),
+                    };
+                    entries.extend(page.entries);
+                    match page.next_start {
+                        Some(next_start) => start = Some(next_start),
+                        None => break,
+                    }
+                }
+
+                match export_path {
+                    Some(path) => {
+                        write_history_file(&path, &entries)?;
+                        Ok(Box::new(format!(
+                            "{} slots of transactions exported to {}",
+                            entries.len(),
+                            path.display()
+                        )))
+                    }
+                    None => Ok(Box::new(AddressTransactionsPage {
+                        entries,
+                        next_start: None,
+                    })),
+                }
+            }
+
             Command::get_datastore_entry => {
                 if parameters.len() != 2 {
                     bail!("invalid number of parameters");
@@ -585,7 +973,11 @@ impl Command {
                 let key = parameters[1].as_bytes().to_vec();
                 match client
                     .public
-                    .get_datastore_entries(vec![DatastoreEntryInput { address, key }])
+                    .get_datastore_entries(vec![DatastoreEntryInput {
+                        address,
+                        key: Some(key),
+                        range: None,
+                    }])
                     .await
                 {
                     Ok(result) => Ok(Box::new(result)),
@@ -620,6 +1012,309 @@ impl Command {
                 }
             }
 
+            Command::wait_operation_final => {
+                let (operation_id, timeout) = match parameters {
+                    [operation_id] => (operation_id, DEFAULT_WAIT_FINAL_TIMEOUT),
+                    [operation_id, timeout] => {
+                        (operation_id, Duration::from_secs(timeout.parse::<u64>()?))
+                    }
+                    _ => bail!("wrong number of parameters"),
+                };
+                let operation_id = operation_id.parse::<OperationId>()?;
+                wait_operation_final(client, operation_id, timeout).await?;
+                if !json {
+                    println!("Operation {} is final", operation_id);
+                }
+                Ok(Box::new(()))
+            }
+
+            Command::watch_blocks
+            | Command::watch_operations
+            | Command::watch_endorsements
+            | Command::watch_events => {
+                let address = match parameters {
+                    [] => None,
+                    [address] => Some(address.parse::<Address>()?),
+                    _ => bail!("wrong number of parameters"),
+                };
+                match self {
+                    Command::watch_blocks => watch(client, Watched::Blocks, address, json).await?,
+                    Command::watch_operations => {
+                        watch(client, Watched::Operations, address, json).await?
+                    }
+                    Command::watch_endorsements => {
+                        watch(client, Watched::Endorsements, address, json).await?
+                    }
+                    _ => watch_events(client, address, json).await?,
+                }
+                Ok(Box::new(()))
+            }
+
+            Command::grpc_get_status => {
+                match grpc_public(client)?
+                    .get_status(grpc_api::GetStatusRequest {})
+                    .await
+                {
+                    Ok(response) => Ok(Box::new(format!("{:#?}", response))),
+                    Err(e) => grpc_error!(e),
+                }
+            }
+
+            Command::grpc_query_state => {
+                if parameters.is_empty() {
+                    bail!("wrong number of parameters");
+                }
+                let addresses = parse_vec::<Address>(parameters)?;
+                let queries = addresses
+                    .into_iter()
+                    .flat_map(|address| {
+                        let address = address.to_string();
+                        [
+                            RequestItem::AddressBalanceFinal(grpc_api::AddressBalanceFinal {
+                                address: address.clone(),
+                            }),
+                            RequestItem::AddressBalanceCandidate(
+                                grpc_api::AddressBalanceCandidate {
+                                    address: address.clone(),
+                                },
+                            ),
+                            RequestItem::AddressRollsFinal(grpc_api::AddressRollsFinal {
+                                address: address.clone(),
+                            }),
+                            RequestItem::AddressRollsCandidate(grpc_api::AddressRollsCandidate {
+                                address,
+                            }),
+                        ]
+                    })
+                    .map(|item| grpc_api::ExecutionQueryRequestItem {
+                        request_item: Some(item),
+                    })
+                    .collect();
+                match grpc_public(client)?
+                    .query_state(grpc_api::QueryStateRequest { queries })
+                    .await
+                {
+                    Ok(response) => Ok(Box::new(format!("{:#?}", response))),
+                    Err(e) => grpc_error!(e),
+                }
+            }
+
+            Command::grpc_search_blocks => {
+                let mut block_ids = Vec::new();
+                let mut addresses = Vec::new();
+                for parameter in parameters {
+                    if let Ok(block_id) = BlockId::from_str(parameter) {
+                        block_ids.push(block_id.to_string());
+                    } else if let Ok(address) = Address::from_str(parameter) {
+                        addresses.push(address.to_string());
+                    } else {
+                        bail!("\"{}\" is neither a block id nor an address", parameter);
+                    }
+                }
+                let mut filters = Vec::new();
+                if !block_ids.is_empty() {
+                    filters.push(grpc_api::SearchBlocksFilter {
+                        filter: Some(search_blocks_filter::Filter::BlockIds(
+                            grpc_model::BlockIds { block_ids },
+                        )),
+                    });
+                }
+                if !addresses.is_empty() {
+                    filters.push(grpc_api::SearchBlocksFilter {
+                        filter: Some(search_blocks_filter::Filter::Addresses(
+                            grpc_model::Addresses { addresses },
+                        )),
+                    });
+                }
+                if filters.is_empty() {
+                    bail!("wrong number of parameters");
+                }
+                match grpc_public(client)?
+                    .search_blocks(grpc_api::SearchBlocksRequest { filters })
+                    .await
+                {
+                    Ok(response) => Ok(Box::new(format!("{:#?}", response))),
+                    Err(e) => grpc_error!(e),
+                }
+            }
+
+            Command::grpc_get_selector_draws => {
+                let addresses: Vec<String> = parse_vec::<Address>(parameters)?
+                    .iter()
+                    .map(|address| address.to_string())
+                    .collect();
+                let mut filters = Vec::new();
+                if !addresses.is_empty() {
+                    filters.push(grpc_api::SelectorDrawsFilter {
+                        filter: Some(selector_draws_filter::Filter::Addresses(
+                            grpc_model::Addresses { addresses },
+                        )),
+                    });
+                }
+                match grpc_public(client)?
+                    .get_selector_draws(grpc_api::GetSelectorDrawsRequest { filters })
+                    .await
+                {
+                    Ok(response) => Ok(Box::new(format!("{:#?}", response))),
+                    Err(e) => grpc_error!(e),
+                }
+            }
+
+            Command::grpc_get_node_status => {
+                match grpc_private(client)?
+                    .get_node_status(grpc_api::GetNodeStatusRequest {})
+                    .await
+                {
+                    Ok(response) => Ok(Box::new(format!("{:#?}", response))),
+                    Err(e) => grpc_error!(e),
+                }
+            }
+
+            Command::grpc_get_mip_status => {
+                match grpc_private(client)?
+                    .get_mip_status(grpc_api::GetMipStatusRequest {})
+                    .await
+                {
+                    Ok(response) => Ok(Box::new(format!("{:#?}", response))),
+                    Err(e) => grpc_error!(e),
+                }
+            }
+
+            Command::grpc_get_datastore_entries_page => {
+                let Some((address, parameters)) = parameters.split_first() else {
+                    bail!("wrong number of parameters");
+                };
+                let address = address.parse::<Address>()?;
+                let p_list: [&str; 5] = ["prefix", "cursor", "limit", "reverse", "is_final"];
+                let p = parse_key_value_parameters(
+                    parameters,
+                    &p_list,
+                    "grpc_get_datastore_entries_page",
+                )?;
+                let cursor = match p.get(p_list[1]) {
+                    Some(cursor) => Some(
+                        decode_hex(cursor)
+                            .ok_or_else(|| anyhow!("the cursor must be hexadecimal"))?,
+                    ),
+                    None => None,
+                };
+                let request = grpc_ext::GetDatastoreEntriesPageRequest {
+                    address: address.to_string(),
+                    prefix: p
+                        .get(p_list[0])
+                        .map(|prefix| prefix.as_bytes().to_vec())
+                        .unwrap_or_default(),
+                    start_key: None,
+                    end_key: None,
+                    cursor,
+                    limit: parse_key_value(&p, p_list[2])?,
+                    reverse: parse_key_value(&p, p_list[3])?.unwrap_or_default(),
+                    is_final: parse_key_value(&p, p_list[4])?.unwrap_or_default(),
+                };
+                match grpc_public(client)?
+                    .get_datastore_entries_page(request)
+                    .await
+                {
+                    Ok(response) => {
+                        let mut output = format!("{:#?}", response);
+                        if let Some(next_cursor) = &response.next_cursor {
+                            let next_cursor: String =
+                                next_cursor.iter().map(|b| format!("{:02x}", b)).collect();
+                            output.push_str(&format!("\nnext page: cursor={}", next_cursor));
+                        }
+                        Ok(Box::new(output))
+                    }
+                    Err(e) => grpc_error!(e),
+                }
+            }
+
+            Command::grpc_get_addresses_draws => {
+                let p_list: [&str; 4] = ["start_cycle", "end_cycle", "offset", "limit"];
+                let (parameters, addresses): (Vec<&String>, Vec<&String>) =
+                    parameters.iter().partition(|v| v.contains('='));
+                if addresses.is_empty() {
+                    bail!("wrong number of parameters");
+                }
+                let parameters: Vec<String> = parameters.into_iter().cloned().collect();
+                let p =
+                    parse_key_value_parameters(&parameters, &p_list, "grpc_get_addresses_draws")?;
+                let addresses = addresses
+                    .into_iter()
+                    .map(|address| Ok(address.parse::<Address>()?.to_string()))
+                    .collect::<Result<Vec<_>>>()?;
+                let request = grpc_ext::GetAddressesDrawsRequest {
+                    addresses,
+                    start_cycle: parse_key_value(&p, p_list[0])?,
+                    end_cycle: parse_key_value(&p, p_list[1])?,
+                    offset: parse_key_value(&p, p_list[2])?,
+                    limit: parse_key_value(&p, p_list[3])?,
+                };
+                match grpc_public(client)?.get_addresses_draws(request).await {
+                    Ok(response) => Ok(Box::new(format!("{:#?}", response))),
+                    Err(e) => grpc_error!(e),
+                }
+            }
+
+            Command::grpc_get_async_messages => {
+                let p_list: [&str; 6] = [
+                    "sender",
+                    "destination",
+                    "validity_start",
+                    "validity_end",
+                    "trigger_address",
+                    "can_be_executed",
+                ];
+                let p = parse_key_value_parameters(parameters, &p_list, "grpc_get_async_messages")?;
+                let request = grpc_ext::GetAsyncMessagesRequest {
+                    sender: parse_key_value::<Address>(&p, p_list[0])?.map(|a| a.to_string()),
+                    destination: parse_key_value::<Address>(&p, p_list[1])?.map(|a| a.to_string()),
+                    validity_start: parse_key_value::<Slot>(&p, p_list[2])?.map(Into::into),
+                    validity_end: parse_key_value::<Slot>(&p, p_list[3])?.map(Into::into),
+                    trigger_address: parse_key_value::<Address>(&p, p_list[4])?
+                        .map(|a| a.to_string()),
+                    can_be_executed: parse_key_value(&p, p_list[5])?,
+                };
+                match grpc_public(client)?.get_async_messages(request).await {
+                    Ok(response) => Ok(Box::new(format!("{:#?}", response))),
+                    Err(e) => grpc_error!(e),
+                }
+            }
+
+            Command::grpc_get_mip_status_with_votes => {
+                match grpc_private(client)?
+                    .get_mip_status_with_votes(grpc_ext::GetMipStatusWithVotesRequest {})
+                    .await
+                {
+                    Ok(response) => Ok(Box::new(format!("{:#?}", response))),
+                    Err(e) => grpc_error!(e),
+                }
+            }
+
+            Command::grpc_set_mip_vote => {
+                if parameters.len() != 2 {
+                    bail!("wrong number of parameters");
+                }
+                let vote = match parameters[1].to_lowercase().as_str() {
+                    "for" => grpc_ext::MipVoteKind::For,
+                    "against" => grpc_ext::MipVoteKind::Against,
+                    "none" => grpc_ext::MipVoteKind::Unspecified,
+                    _ => bail!("the vote must be for, against or none"),
+                };
+                let request = grpc_ext::SetMipVoteRequest {
+                    name: parameters[0].clone(),
+                    vote: vote as i32,
+                };
+                match grpc_private(client)?.set_mip_vote(request).await {
+                    Ok(_) => {
+                        if !json {
+                            println!("Vote successfully set");
+                        }
+                    }
+                    Err(e) => grpc_error!(e),
+                };
+                Ok(Box::new(()))
+            }
+
             Command::get_filtered_sc_output_event => {
                 let p_list: [&str; 7] = [
                     "start",
@@ -630,15 +1325,11 @@ impl Command {
                     "is_final",
                     "is_error",
                 ];
-                let mut p: HashMap<&str, &str> = HashMap::new();
-                for v in parameters {
-                    let s: Vec<&str> = v.split('=').collect();
-                    if s.len() == 2 && p_list.contains(&s[0]) {
-                        p.insert(s[0], s[1]);
-                    } else {
-                        bail!("invalid parameter: {}, type \"help get_filtered_sc_output_event\" to get the list of valid parameters", v);
-                    }
-                }
+                let p = parse_key_value_parameters(
+                    parameters,
+                    &p_list,
+                    "get_filtered_sc_output_event",
+                )?;
                 let filter = EventFilter {
                     start: parse_key_value(&p, p_list[0])?,
                     end: parse_key_value(&p, p_list[1])?,
@@ -662,11 +1353,13 @@ impl Command {
                 if !json && show_keys {
                     client_warning!("do not share your secret key");
                 }
-                match client
-                    .public
-                    .get_addresses(wallet.get_full_wallet().keys().copied().collect())
-                    .await
-                {
+                let addresses = wallet
+                    .get_full_wallet()
+                    .keys()
+                    .chain(wallet.get_watch_only_addresses().keys())
+                    .copied()
+                    .collect();
+                match client.public.get_addresses(addresses).await {
                     Ok(addresses_info) => Ok(Box::new(ExtendedWallet::new(
                         wallet,
                         &addresses_info,
@@ -784,10 +1477,14 @@ impl Command {
                 //       manually.
                 //       This will allow to generate keypair on hardware ledger / computer without internet...
 
-                let keypair_version: u64 = 0;
-                let key = KeyPair::generate(keypair_version).expect("Unable to generate key pair");
-
-                let ad = wallet.add_keypairs(vec![key])?[0];
+                let ad = if wallet.has_mnemonic() {
+                    wallet.generate_hd_keypair()?
+                } else {
+                    let keypair_version: u64 = 0;
+                    let key =
+                        KeyPair::generate(keypair_version).expect("Unable to generate key pair");
+                    wallet.add_keypairs(vec![key])?[0]
+                };
                 if json {
                     Ok(Box::new(ad.to_string()))
                 } else {
@@ -799,6 +1496,96 @@ impl Command {
                 }
             }
 
+            Command::wallet_generate_mnemonic => {
+                let wallet = wallet_opt.as_mut().unwrap();
+
+                let mnemonic = generate_mnemonic();
+                wallet.set_mnemonic(&mnemonic, "")?;
+                if json {
+                    Ok(Box::new(mnemonic))
+                } else {
+                    client_warning!("write down these words and keep them secret: anyone knowing them can spend the coins of the keys generated from now on");
+                    println!("{}\n", mnemonic);
+                    println!(
+                        "Type `wallet_generate_secret_key` to generate keys from this mnemonic.\n"
+                    );
+                    Ok(Box::new(()))
+                }
+            }
+
+            Command::wallet_restore_mnemonic => {
+                if !parameters.is_empty() {
+                    bail!("wrong number of parameters: the mnemonic is prompted so that it is not saved in the command history");
+                }
+                let mnemonic = ask_mnemonic()?;
+                let wallet = wallet_opt.as_mut().unwrap();
+
+                wallet.set_mnemonic(mnemonic.trim(), "")?;
+
+                // scan the derived addresses until HD_RESTORE_GAP_LIMIT successive ones are unused
+                let mut restored_keys = Vec::new();
+                let mut next_index = 0;
+                let mut index: u32 = 0;
+                loop {
+                    let keypairs = (index..index + HD_RESTORE_GAP_LIMIT)
+                        .map(|i| wallet.derive_hd_keypair(i))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let addresses = keypairs
+                        .iter()
+                        .map(|keypair| Address::from_public_key(&keypair.get_public_key()))
+                        .collect();
+                    let addresses_info = match client.public.get_addresses(addresses).await {
+                        Ok(addresses_info) => addresses_info,
+                        Err(e) => rpc_error!(e),
+                    };
+                    let mut found = false;
+                    for ((i, keypair), info) in (index..).zip(keypairs).zip(addresses_info) {
+                        if !info.final_balance.is_zero()
+                            || !info.candidate_balance.is_zero()
+                            || info.final_roll_count > 0
+                            || info.candidate_roll_count > 0
+                        {
+                            restored_keys.push(keypair);
+                            next_index = i + 1;
+                            found = true;
+                        }
+                    }
+                    if !found {
+                        break;
+                    }
+                    index += HD_RESTORE_GAP_LIMIT;
+                }
+                let addresses = wallet.add_keypairs(restored_keys)?;
+                wallet.set_hd_next_index(next_index)?;
+                if json {
+                    return Ok(Box::new(addresses));
+                } else {
+                    for address in addresses {
+                        println!("Restored address {} in the wallet.", address);
+                    }
+                    println!(
+                        "Type `wallet_info` to show wallet info (keys, addresses, balances ...).\n"
+                    );
+                }
+                Ok(Box::new(()))
+            }
+
+            Command::wallet_change_password => {
+                let cipher_version = match parameters.len() {
+                    0 => CipherVersion::Argon2id,
+                    1 => CipherVersion::from_str(&parameters[0]).map_err(|e| anyhow!(e))?,
+                    _ => bail!("wrong number of parameters"),
+                };
+                let wallet = wallet_opt.as_mut().unwrap();
+
+                let new_password = ask_new_password()?;
+                wallet.change_password(new_password, cipher_version)?;
+                if !json {
+                    println!("Wallet password changed.");
+                }
+                Ok(Box::new(()))
+            }
+
             Command::wallet_add_secret_keys => {
                 if parameters.is_empty() {
                     bail!("wrong number of parameters");
@@ -818,6 +1605,111 @@ impl Command {
                 Ok(Box::new(()))
             }
 
+            Command::build_operation => bail!("build_operation cannot build another command"),
+
+            Command::sign_operation => {
+                if parameters.len() != 2 {
+                    bail!("wrong number of parameters");
+                }
+                let wallet = wallet_opt.as_mut().unwrap();
+
+                let unsigned = UnsignedOperationFile::read(Path::new(&parameters[0]))?;
+                // the wallet signs for the chain id of the client
+                if unsigned.chain_id != client.chain_id {
+                    bail!(
+                        "the operation was built for chain id {} but the client uses chain id {}",
+                        unsigned.chain_id,
+                        client.chain_id
+                    );
+                }
+                if !json {
+                    println!("Signing operation of {}:", unsigned.creator_address);
+                    println!("{}", unsigned.operation);
+                }
+                let op = wallet.create_operation(unsigned.operation, unsigned.creator_address)?;
+                let signed = SignedOperationFile::new(
+                    unsigned.chain_id,
+                    op.id,
+                    OperationInput {
+                        creator_public_key: op.content_creator_pub_key,
+                        serialized_content: op.serialized_data,
+                        signature: op.signature,
+                    },
+                );
+                write_json_file(Path::new(&parameters[1]), &signed)?;
+                if !json {
+                    println!(
+                        "Signed operation {} written to {}, send it with `send_signed_operations`.",
+                        op.id, parameters[1]
+                    );
+                }
+                Ok(Box::new(vec![op.id]))
+            }
+
+            Command::send_signed_operations => {
+                if parameters.is_empty() {
+                    bail!("wrong number of parameters");
+                }
+
+                let mut operations = Vec::with_capacity(parameters.len());
+                for path in parameters {
+                    let signed = SignedOperationFile::read(Path::new(path))?;
+                    if signed.chain_id != client.chain_id {
+                        bail!(
+                            "{} was signed for chain id {} but the client uses chain id {}",
+                            path,
+                            signed.chain_id,
+                            client.chain_id
+                        );
+                    }
+                    operations.push(signed.operation);
+                }
+                match client.public.send_operations(operations).await {
+                    Ok(operation_ids) => {
+                        if !json {
+                            println!("Sent operation IDs:");
+                        }
+                        Ok(Box::new(operation_ids))
+                    }
+                    Err(e) => rpc_error!(e),
+                }
+            }
+
+            Command::wallet_add_watch_only => {
+                if parameters.is_empty() {
+                    bail!("wrong number of parameters");
+                }
+                let wallet = wallet_opt.as_mut().unwrap();
+
+                let (address, public_key) = match PublicKey::from_str(&parameters[0]) {
+                    Ok(public_key) => (Address::from_public_key(&public_key), Some(public_key)),
+                    Err(_) => (Address::from_str(&parameters[0])?, None),
+                };
+                let label = (parameters.len() > 1).then(|| parameters[1..].join(" "));
+                wallet.add_watch_only(address, public_key, label)?;
+                if json {
+                    Ok(Box::new(address.to_string()))
+                } else {
+                    println!("Added watch-only address {} to the wallet", address);
+                    Ok(Box::new(()))
+                }
+            }
+
+            Command::wallet_set_label => {
+                if parameters.is_empty() {
+                    bail!("wrong number of parameters");
+                }
+                let wallet = wallet_opt.as_mut().unwrap();
+
+                let address = Address::from_str(&parameters[0])?;
+                let label = (parameters.len() > 1).then(|| parameters[1..].join(" "));
+                wallet.set_label(address, label)?;
+                if !json {
+                    println!("Label of {} updated", address);
+                }
+                Ok(Box::new(()))
+            }
+
             Command::wallet_remove_addresses => {
                 if parameters.is_empty() {
                     bail!("wrong number of parameters");
@@ -895,6 +1787,7 @@ impl Command {
                     fee,
                     addr,
                     json,
+                    unsigned_output,
                 )
                 .await
             }
@@ -931,6 +1824,7 @@ impl Command {
                     fee,
                     addr,
                     json,
+                    unsigned_output,
                 )
                 .await
             }
@@ -971,6 +1865,7 @@ impl Command {
                     fee,
                     addr,
                     json,
+                    unsigned_output,
                 )
                 .await
             }
@@ -984,6 +1879,7 @@ impl Command {
             Command::execute_smart_contract => {
                 let wallet = wallet_opt.as_mut().unwrap();
 
+                let (parameters, datastore_path) = split_datastore_parameter(parameters);
                 if parameters.len() != 5 {
                     bail!("wrong number of parameters");
                 }
@@ -1007,16 +1903,23 @@ impl Command {
                     }
                 };
                 let data = get_file_as_byte_vec(&path).await?;
+                let datastore = match datastore_path {
+                    Some(datastore_path) => read_datastore_file(&datastore_path)?,
+                    None => BTreeMap::new(),
+                };
                 if !json {
                     let max_block_size = match client.public.get_status().await {
                         Ok(node_status) => node_status.config.max_block_size,
                         Err(e) => bail!("RpcError: {}", e),
                     };
-                    if data.len() > max_block_size as usize {
-                        client_warning!("bytecode size exceeded the maximum size of a block, operation will be rejected");
+                    let datastore_size: usize = datastore
+                        .iter()
+                        .map(|(key, value)| key.len() + value.len())
+                        .sum();
+                    if data.len() + datastore_size > max_block_size as usize {
+                        client_warning!("bytecode and datastore size exceeded the maximum size of a block, operation will be rejected");
                     }
                 }
-                let datastore = BTreeMap::new();
 
                 send_operation(
                     client,
@@ -1030,22 +1933,26 @@ impl Command {
                     fee,
                     addr,
                     json,
+                    unsigned_output,
                 )
                 .await
             }
             Command::call_smart_contract => {
                 let wallet = wallet_opt.as_mut().unwrap();
 
-                if parameters.len() != 7 {
+                if parameters.len() < 7 {
                     bail!("wrong number of parameters");
                 }
                 let addr = parameters[0].parse::<Address>()?;
                 let target_addr = parameters[1].parse::<Address>()?;
                 let target_func = parameters[2].clone();
-                let param = parameters[3].clone().into_bytes();
-                let max_gas = parameters[4].parse::<u64>()?;
-                let coins = parameters[5].parse::<Amount>()?;
-                let fee = parameters[6].parse::<Amount>()?;
+                let (param, parameters) = split_call_parameter(&parameters[3..])?;
+                if parameters.len() != 3 {
+                    bail!("wrong number of parameters");
+                }
+                let max_gas = parameters[0].parse::<u64>()?;
+                let coins = parameters[1].parse::<Amount>()?;
+                let fee = parameters[2].parse::<Amount>()?;
                 if !json {
                     match coins.checked_add(fee) {
                         Some(total) => {
@@ -1085,6 +1992,7 @@ impl Command {
                     fee,
                     addr,
                     json,
+                    unsigned_output,
                 )
                 .await
             }
@@ -1103,6 +2011,7 @@ impl Command {
                 }
             }
             Command::read_only_execute_smart_contract => {
+                let (parameters, datastore_path) = split_datastore_parameter(parameters);
                 if parameters.len() < 2 || parameters.len() > 4 {
                     bail!("wrong number of parameters");
                 }
@@ -1118,13 +2027,18 @@ impl Command {
                     .map(|fee| Amount::from_str(fee))
                     .transpose()?;
                 let bytecode = get_file_as_byte_vec(&path).await?;
+                let operation_datastore = datastore_path
+                    .map(|datastore_path| {
+                        serialize_datastore(&read_datastore_file(&datastore_path)?)
+                    })
+                    .transpose()?;
                 match client
                     .public
                     .execute_read_only_bytecode(ReadOnlyBytecodeExecution {
                         max_gas,
                         bytecode,
                         address,
-                        operation_datastore: None, // TODO - #3072
+                        operation_datastore,
                         fee,
                     })
                     .await
@@ -1134,22 +2048,26 @@ impl Command {
                 }
             }
             Command::read_only_call => {
-                if parameters.len() < 4 || parameters.len() > 7 {
+                let (parameters, result_types) = split_result_types_parameter(parameters)?;
+                if parameters.len() < 4 {
                     bail!("wrong number of parameters");
                 }
 
                 let target_address = parameters[0].parse::<Address>()?;
                 let target_function = parameters[1].parse::<String>()?;
-                let parameter = parameters[2].parse::<String>()?.into_bytes();
-                let max_gas = parameters[3].parse::<u64>()?;
-                let caller_address = if let Some(addr) = parameters.get(4) {
+                let (parameter, parameters) = split_call_parameter(&parameters[2..])?;
+                if parameters.is_empty() || parameters.len() > 4 {
+                    bail!("wrong number of parameters");
+                }
+                let max_gas = parameters[0].parse::<u64>()?;
+                let caller_address = if let Some(addr) = parameters.get(1) {
                     Some(addr.parse::<Address>()?)
                 } else {
                     None
                 };
-                let coins = parameters.get(5).map(|c| Amount::from_str(c)).transpose()?;
+                let coins = parameters.get(2).map(|c| Amount::from_str(c)).transpose()?;
                 let fee = parameters
-                    .get(6)
+                    .get(3)
                     .map(|fee| Amount::from_str(fee))
                     .transpose()?;
                 match client
@@ -1165,7 +2083,20 @@ impl Command {
                     })
                     .await
                 {
-                    Ok(res) => Ok(Box::new(res)),
+                    Ok(res) => match (result_types, &res.result) {
+                        (Some(types), ReadOnlyResult::Ok(result)) => {
+                            let decoded_result = deserialize_args(result, &types)
+                                .map_err(|e| anyhow!("could not decode the call result: {}", e))?
+                                .iter()
+                                .map(ToString::to_string)
+                                .collect();
+                            Ok(Box::new(DecodedReadOnlyResponse {
+                                response: res,
+                                decoded_result,
+                            }))
+                        }
+                        _ => Ok(Box::new(res)),
+                    },
                     Err(e) => rpc_error!(e),
                 }
             }
@@ -1359,6 +2290,7 @@ async fn send_operation(
     fee: Amount,
     addr: Address,
     json: bool,
+    unsigned_output: Option<&Path>,
 ) -> Result<Box<dyn Output>> {
     let status = match client.public.get_status().await {
         Ok(node_status) => node_status,
@@ -1384,14 +2316,29 @@ async fn send_operation(
         expire_period += 1;
     };
 
-    let op = wallet.create_operation(
-        Operation {
-            fee,
-            expire_period,
-            op,
-        },
-        addr,
-    )?;
+    let operation = Operation {
+        fee,
+        expire_period,
+        op,
+    };
+
+    // build the operation to sign it offline
+    if let Some(path) = unsigned_output {
+        write_json_file(
+            path,
+            &UnsignedOperationFile::new(client.chain_id, addr, operation),
+        )?;
+        if json {
+            return Ok(Box::new(path.display().to_string()));
+        }
+        println!(
+            "Unsigned operation written to {}, sign it with `sign_operation`.",
+            path.display()
+        );
+        return Ok(Box::new(()));
+    }
+
+    let op = wallet.create_operation(operation, addr)?;
 
     match client
         .public
@@ -1412,6 +2359,63 @@ async fn send_operation(
     }
 }
 
+/// Splits the parameter of a smart contract function call from the following parameters, and encodes it.
+/// The parameter is either a raw string, or the typed arguments (`type:value`) at the beginning of the
+/// parameters, serialized in the smart contract `Args` format.
+fn split_call_parameter(parameters: &[String]) -> Result<(Vec<u8>, &[String])> {
+    let args_count = parameters
+        .iter()
+        .take_while(|parameter| {
+            !matches!(
+                parameter.parse::<ArgValue>(),
+                Err(ArgsError::UnknownType(_))
+            )
+        })
+        .count();
+    if args_count == 0 {
+        match parameters.split_first() {
+            Some((raw, others)) => Ok((raw.clone().into_bytes(), others)),
+            None => bail!("wrong number of parameters"),
+        }
+    } else {
+        let (args, others) = parameters.split_at(args_count);
+        Ok((serialize_args(&parse_vec::<ArgValue>(args)?), others))
+    }
+}
+
+/// Splits the optional trailing `result=Type1,Type2` parameter, giving the types of the `Args` encoded result
+/// of a read-only call, from the others
+fn split_result_types_parameter(
+    parameters: &[String],
+) -> Result<(&[String], Option<Vec<ArgType>>)> {
+    match parameters.split_last() {
+        Some((last, others)) if last.starts_with("result=") => {
+            let types = last
+                .trim_start_matches("result=")
+                .split(',')
+                .filter(|arg_type| !arg_type.is_empty())
+                .map(ArgType::from_str)
+                .collect::<Result<_, _>>()?;
+            Ok((others, Some(types)))
+        }
+        _ => Ok((parameters, None)),
+    }
+}
+
+/// get the public gRPC client, if the client could connect to the node public gRPC API
+fn grpc_public(client: &Client) -> Result<&GrpcPublicClient> {
+    client.grpc_public.as_ref().ok_or_else(|| {
+        anyhow!("not connected to the public gRPC API, check the node grpc settings and the [client.grpc_public] settings")
+    })
+}
+
+/// get the private gRPC client, if the client could connect to the node private gRPC API
+fn grpc_private(client: &Client) -> Result<&GrpcPrivateClient> {
+    client.grpc_private.as_ref().ok_or_else(|| {
+        anyhow!("not connected to the private gRPC API, check the node grpc settings and the [client.grpc_private] settings")
+    })
+}
+
 /// TODO: ugly utilities functions
 /// takes a slice of string and makes it into a `Vec<T>`
 pub fn parse_vec<T: std::str::FromStr>(args: &[String]) -> anyhow::Result<Vec<T>, anyhow::Error>
@@ -1431,6 +2435,28 @@ async fn get_file_as_byte_vec(filename: &std::path::Path) -> Result<Vec<u8>> {
     Ok(tokio::fs::read(filename).await?)
 }
 
+/// parses `key=value` parameters, whose keys must be in `p_list`
+fn parse_key_value_parameters<'a>(
+    parameters: &'a [String],
+    p_list: &[&str],
+    command: &str,
+) -> Result<HashMap<&'a str, &'a str>> {
+    let mut p: HashMap<&str, &str> = HashMap::new();
+    for v in parameters {
+        let s: Vec<&str> = v.split('=').collect();
+        if s.len() == 2 && p_list.contains(&s[0]) {
+            p.insert(s[0], s[1]);
+        } else {
+            bail!(
+                "invalid parameter: {}, type \"help {}\" to get the list of valid parameters",
+                v,
+                command
+            );
+        }
+    }
+    Ok(p)
+}
+
 // chains get_key_value with its parsing
 pub fn parse_key_value<T: std::str::FromStr>(
     p: &HashMap<&str, &str>,
@@ -0,0 +1,95 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Operation datastore files, attached to `ExecuteSC` operations, e.g. to deploy several
+//! contracts whose bytecode is read by the executed bytecode through `get_op_keys`/`get_op_data`.
+//!
+//! A datastore file is a JSON list of entries, each key and value being given as UTF-8 text,
+//! hexadecimal, a byte list, or the content of a file (relative to the datastore file directory):
+//! ```json
+//! [
+//!     {"key": {"text": "contract_0"}, "value": {"file": "build/main.wasm"}},
+//!     {"key": {"hex": "00ff"}, "value": {"bytes": [1, 2, 3]}}
+//! ]
+//! ```
+
+use anyhow::{anyhow, bail, Result};
+use massa_models::datastore::{Datastore, DatastoreSerializer};
+use massa_sdk::args::decode_hex;
+use massa_serialization::Serializer;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Bytes of a datastore key or value
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum DatastoreBytes {
+    /// UTF-8 text
+    Text(String),
+    /// hexadecimal string
+    Hex(String),
+    /// raw bytes
+    Bytes(Vec<u8>),
+    /// content of a file, relative to the datastore file directory
+    File(PathBuf),
+}
+
+#[derive(Debug, Deserialize)]
+struct DatastoreFileEntry {
+    key: DatastoreBytes,
+    value: DatastoreBytes,
+}
+
+impl DatastoreBytes {
+    fn into_bytes(self, base_dir: &Path) -> Result<Vec<u8>> {
+        match self {
+            DatastoreBytes::Text(text) => Ok(text.into_bytes()),
+            DatastoreBytes::Hex(hex) => {
+                decode_hex(&hex).ok_or_else(|| anyhow!("invalid hexadecimal string {}", hex))
+            }
+            DatastoreBytes::Bytes(bytes) => Ok(bytes),
+            DatastoreBytes::File(path) => {
+                let path = base_dir.join(path);
+                std::fs::read(&path)
+                    .map_err(|e| anyhow!("could not read {}: {}", path.display(), e))
+            }
+        }
+    }
+}
+
+/// Reads an operation datastore file
+pub(crate) fn read_datastore_file(path: &Path) -> Result<Datastore> {
+    let content =
+        std::fs::read(path).map_err(|e| anyhow!("could not read {}: {}", path.display(), e))?;
+    let entries: Vec<DatastoreFileEntry> = serde_json::from_slice(&content)
+        .map_err(|e| anyhow!("could not parse {}: {}", path.display(), e))?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut datastore = Datastore::new();
+    for entry in entries {
+        let key = entry.key.into_bytes(base_dir)?;
+        let value = entry.value.into_bytes(base_dir)?;
+        if datastore.insert(key.clone(), value).is_some() {
+            bail!("duplicate datastore key {:?} in {}", key, path.display());
+        }
+    }
+    Ok(datastore)
+}
+
+/// Serializes an operation datastore, as expected by the read-only executions
+pub(crate) fn serialize_datastore(datastore: &Datastore) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    DatastoreSerializer::new()
+        .serialize(datastore, &mut buffer)
+        .map_err(|e| anyhow!("could not serialize the datastore: {}", e))?;
+    Ok(buffer)
+}
+
+/// Splits the optional trailing `datastore=PathToDatastore` parameter from the others
+pub(crate) fn split_datastore_parameter(parameters: &[String]) -> (&[String], Option<PathBuf>) {
+    match parameters.split_last() {
+        Some((last, others)) if last.starts_with("datastore=") => (
+            others,
+            Some(PathBuf::from(last.trim_start_matches("datastore="))),
+        ),
+        _ => (parameters, None),
+    }
+}
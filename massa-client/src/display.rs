@@ -4,8 +4,12 @@ use crate::cmds::ExtendedWallet;
 use console::style;
 use erased_serde::{Serialize, Serializer};
 use massa_api_exports::{
-    address::AddressInfo, block::BlockInfo, datastore::DatastoreEntryOutput,
-    endorsement::EndorsementInfo, execution::ExecuteReadOnlyResponse, node::NodeStatus,
+    address::{AddressInfo, AddressTransactionsPage},
+    block::BlockInfo,
+    datastore::DatastoreEntryOutput,
+    endorsement::EndorsementInfo,
+    execution::ExecuteReadOnlyResponse,
+    node::{MipStatus, MissedSlotsInfo, NodeStatus},
     operation::OperationInfo,
 };
 use massa_models::composite::PubkeySig;
@@ -120,6 +124,15 @@ impl dyn Output {
         self.erased_serialize(&mut format)?;
         Ok(())
     }
+
+    pub(crate) fn to_json_value(&self) -> anyhow::Result<serde_json::Value> {
+        let mut buffer = Vec::new();
+        let json = &mut serde_json::Serializer::new(&mut buffer);
+        let mut format: Box<dyn Serializer> = Box::new(<dyn Serializer>::erase(json));
+        self.erased_serialize(&mut format)?;
+        drop(format);
+        Ok(serde_json::from_slice(&buffer)?)
+    }
 }
 
 impl Output for Wallet {
@@ -135,17 +148,33 @@ impl Output for ExtendedWallet {
         }
         println!("{}", Style::Separator.style("====="));
         for entry in self.0.values() {
-            if entry.show_keys {
-                println!("Secret key: {}", Style::Secret.style(&entry.keypair));
-                println!(
-                    "Public key: {}",
-                    Style::Wallet.style(entry.keypair.get_public_key())
-                );
+            if let Some(label) = &entry.label {
+                println!("Label: {}", Style::Wallet.style(label));
+            }
+            match &entry.keypair {
+                Some(keypair) if entry.show_keys => {
+                    println!("Secret key: {}", Style::Secret.style(keypair));
+                    println!(
+                        "Public key: {}",
+                        Style::Wallet.style(keypair.get_public_key())
+                    );
+                }
+                Some(_) => {}
+                None => {
+                    if let Some(public_key) = &entry.public_key {
+                        println!("Public key: {}", Style::Wallet.style(public_key));
+                    }
+                }
             }
             println!(
-                "Address: {} (thread {}):",
+                "Address: {} (thread {}){}:",
                 Style::Wallet.style(entry.address_info.address),
                 Style::Protocol.style(entry.address_info.thread),
+                if entry.keypair.is_none() {
+                    " (watch-only)"
+                } else {
+                    ""
+                },
             );
             println!(
                 "\tBalance: {}={}, {}={}",
@@ -399,6 +428,20 @@ impl Output for BlockInfo {
     }
 }
 
+impl Output for Vec<MipStatus> {
+    fn pretty_print(&self) {
+        for mip_status in self {
+            println!("{}", mip_status);
+        }
+    }
+}
+
+impl Output for MissedSlotsInfo {
+    fn pretty_print(&self) {
+        println!("{}", self);
+    }
+}
+
 impl Output for PreHashSet<Address> {
     fn pretty_print(&self) {
         println!(
@@ -587,6 +630,12 @@ impl Output for Vec<SCOutputEvent> {
     }
 }
 
+impl Output for AddressTransactionsPage {
+    fn pretty_print(&self) {
+        print!("{}", self);
+    }
+}
+
 impl Output for PubkeySig {
     fn pretty_print(&self) {
         println!("{}", self);
@@ -598,3 +647,19 @@ impl Output for ExecuteReadOnlyResponse {
         println!("{}", self);
     }
 }
+
+/// Read-only call response, with its result decoded from the smart contract `Args` format
+#[derive(serde::Serialize)]
+pub(crate) struct DecodedReadOnlyResponse {
+    #[serde(flatten)]
+    pub response: ExecuteReadOnlyResponse,
+    /// decoded result values, in their `type:value` text form
+    pub decoded_result: Vec<String>,
+}
+
+impl Output for DecodedReadOnlyResponse {
+    fn pretty_print(&self) {
+        println!("{}", self.response);
+        println!("Decoded result: {}", self.decoded_result.join(" "));
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Transaction history exports, written by `get_address_history` to reconcile a wallet.
+//!
+//! The format depends on the file extension: `.csv` files have one line per transaction,
+//! with the columns listed in [`CSV_HEADER`], any other file is written as a JSON list of
+//! entries, as returned by the node.
+
+use anyhow::{anyhow, Result};
+use massa_models::execution::AddressTransactionsEntry;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Columns of the CSV exports
+const CSV_HEADER: &str = "period,thread,kind,operation_id,from,to,amount,fee,success";
+
+/// Writes the transaction history of an address to a CSV or JSON file
+pub(crate) fn write_history_file(path: &Path, entries: &[AddressTransactionsEntry]) -> Result<()> {
+    let content = match path.extension() {
+        Some(extension) if extension.eq_ignore_ascii_case("csv") => to_csv(entries),
+        _ => serde_json::to_string_pretty(entries)?,
+    };
+    std::fs::write(path, content).map_err(|e| anyhow!("could not write {}: {}", path.display(), e))
+}
+
+/// Splits the optional trailing `export=PathToFile` parameter from the others
+pub(crate) fn split_export_parameter(parameters: &[String]) -> (&[String], Option<PathBuf>) {
+    match parameters.split_last() {
+        Some((last, others)) if last.starts_with("export=") => (
+            others,
+            Some(PathBuf::from(last.trim_start_matches("export="))),
+        ),
+        _ => (parameters, None),
+    }
+}
+
+fn to_csv(entries: &[AddressTransactionsEntry]) -> String {
+    let mut csv = String::new();
+    let _ = writeln!(csv, "{}", CSV_HEADER);
+    for entry in entries {
+        for transaction in &entry.transactions {
+            // none of the fields can contain a comma or a quote, no escaping is needed
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{}",
+                entry.slot.period,
+                entry.slot.thread,
+                transaction.kind,
+                transaction
+                    .operation_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+                transaction.from,
+                transaction.to.map(|to| to.to_string()).unwrap_or_default(),
+                transaction.amount,
+                transaction.fee,
+                transaction.success,
+            );
+        }
+    }
+    csv
+}
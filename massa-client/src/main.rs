@@ -9,7 +9,7 @@ use cmds::Command;
 use console::style;
 use dialoguer::Password;
 use is_terminal::IsTerminal;
-use massa_sdk::{Client, ClientConfig, HttpConfig};
+use massa_sdk::{Client, ClientConfig, GrpcConfig, HttpConfig, WsConfig};
 use massa_wallet::Wallet;
 use serde::Serialize;
 use std::env;
@@ -17,10 +17,15 @@ use std::net::IpAddr;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+mod batch;
 mod cmds;
+mod datastore_file;
 mod display;
+mod history_file;
+mod offline;
 mod repl;
 mod settings;
+mod watch;
 
 #[cfg(test)]
 pub mod tests;
@@ -40,6 +45,9 @@ struct Args {
     /// Port to listen on (Massa GRPC Private API).
     #[arg(long)]
     grpc_private_port: Option<u16>,
+    /// Port to listen on (Massa API V2, used by the WebSocket subscriptions).
+    #[arg(long)]
+    api_port: Option<u16>,
     /// Chain id
     #[arg(long)]
     chain_id: Option<u64>,
@@ -61,10 +69,14 @@ struct Args {
     #[arg(short = 'p', long = "pwd")]
     /// Wallet password
     password: Option<String>,
+    /// Run the commands of a script file (batch mode), see the `batch` module documentation for its syntax.
+    /// Outputs are printed as JSON and the client exits with an error at the first failure.
+    #[arg(long = "script")]
+    script: Option<PathBuf>,
 }
 
 #[derive(Serialize)]
-struct JsonError {
+pub(crate) struct JsonError {
     error: String,
 }
 
@@ -77,14 +89,23 @@ pub(crate) fn ask_password(wallet_path: &Path) -> String {
             .interact()
             .expect("IO error: Password reading failed, walled couldn't be unlocked")
     } else {
-        Password::new()
-            .with_prompt("Enter new password for wallet")
-            .with_confirmation("Confirm password", "Passwords mismatching")
-            .interact()
-            .expect("IO error: Password reading failed, wallet couldn't be created")
+        ask_new_password().expect("IO error: Password reading failed, wallet couldn't be created")
     }
 }
 
+/// Ask for a new wallet password, with confirmation
+pub(crate) fn ask_new_password() -> dialoguer::Result<String> {
+    Password::new()
+        .with_prompt("Enter new password for wallet")
+        .with_confirmation("Confirm password", "Passwords mismatching")
+        .interact()
+}
+
+/// Ask for a mnemonic without echoing it, so that it does not end up in the command history
+pub(crate) fn ask_mnemonic() -> dialoguer::Result<String> {
+    Password::new().with_prompt("Enter mnemonic").interact()
+}
+
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
     let tokio_rt = tokio::runtime::Builder::new_multi_thread()
@@ -112,9 +133,19 @@ async fn run(args: Args) -> Result<()> {
     };
 
     let http_config = HttpConfig {
-        client_config,
+        client_config: client_config.clone(),
         enabled: SETTINGS.client.http.enabled,
     };
+    let ws_config = WsConfig {
+        client_config,
+        enabled: SETTINGS.client.ws.enabled,
+        max_notifs_per_subscription: SETTINGS.client.ws.max_notifs_per_subscription,
+        max_redirections: SETTINGS.client.ws.max_redirections,
+    };
+    let grpc_config = GrpcConfig {
+        public: (&SETTINGS.client.grpc_public).into(),
+        private: (&SETTINGS.client.grpc_private).into(),
+    };
 
     // TODO: move settings loading in another crate ... see #1277
     let settings = SETTINGS.clone();
@@ -139,6 +170,10 @@ async fn run(args: Args) -> Result<()> {
         Some(grpc_port) => grpc_port,
         None => settings.default_node.grpc_private_port,
     };
+    let api_port = match args.api_port {
+        Some(api_port) => api_port,
+        None => settings.default_node.api_port,
+    };
     let chain_id = match args.chain_id {
         Some(chain_id) => chain_id,
         None => match settings.default_node.chain_id {
@@ -164,11 +199,17 @@ async fn run(args: Args) -> Result<()> {
         private_port,
         grpc_port,
         grpc_priv_port,
+        api_port,
         chain_id,
         &http_config,
+        &ws_config,
+        &grpc_config,
     )
     .await?;
-    if std::io::stdout().is_terminal() && args.command == Command::help && !args.json {
+    if let Some(script) = &args.script {
+        // Batch mode
+        batch::run(&mut client, &args.wallet, args.password, script).await?;
+    } else if std::io::stdout().is_terminal() && args.command == Command::help && !args.json {
         // Interactive mode
         repl::run(&mut client, &args.wallet, args.password).await?;
     } else {
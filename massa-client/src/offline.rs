@@ -0,0 +1,106 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! File formats of the offline signing workflow: an operation is built online into an unsigned
+//! operation file, signed on an air-gapped machine into a signed operation file,
+//! then broadcast online with `send_signed_operations`.
+
+use anyhow::{anyhow, bail, Result};
+use massa_api_exports::operation::OperationInput;
+use massa_models::{
+    address::Address,
+    operation::{Operation, OperationId},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::Path;
+
+/// Version of the unsigned and signed operation file formats
+const OFFLINE_OPERATION_FILE_VERSION: u64 = 1;
+
+/// Operation to sign, with everything fetched from the node to build it
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct UnsignedOperationFile {
+    /// file format version
+    pub version: u64,
+    /// chain id the operation is to be signed for
+    pub chain_id: u64,
+    /// address whose secret key signs the operation
+    pub creator_address: Address,
+    /// operation content: fee, expire period and operation type
+    pub operation: Operation,
+}
+
+/// Signed operation, ready to be sent to a node
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SignedOperationFile {
+    /// file format version
+    pub version: u64,
+    /// chain id the operation was signed for
+    pub chain_id: u64,
+    /// id of the signed operation
+    pub operation_id: OperationId,
+    /// signed operation, as sent through `send_operations`
+    pub operation: OperationInput,
+}
+
+impl UnsignedOperationFile {
+    /// Unsigned operation file of the current format version
+    pub(crate) fn new(chain_id: u64, creator_address: Address, operation: Operation) -> Self {
+        UnsignedOperationFile {
+            version: OFFLINE_OPERATION_FILE_VERSION,
+            chain_id,
+            creator_address,
+            operation,
+        }
+    }
+
+    /// Reads an unsigned operation file, checking its version
+    pub(crate) fn read(path: &Path) -> Result<Self> {
+        let file: Self = read_json_file(path)?;
+        check_version(path, file.version)?;
+        Ok(file)
+    }
+}
+
+impl SignedOperationFile {
+    /// Signed operation file of the current format version
+    pub(crate) fn new(chain_id: u64, operation_id: OperationId, operation: OperationInput) -> Self {
+        SignedOperationFile {
+            version: OFFLINE_OPERATION_FILE_VERSION,
+            chain_id,
+            operation_id,
+            operation,
+        }
+    }
+
+    /// Reads a signed operation file, checking its version
+    pub(crate) fn read(path: &Path) -> Result<Self> {
+        let file: Self = read_json_file(path)?;
+        check_version(path, file.version)?;
+        Ok(file)
+    }
+}
+
+/// Writes an offline signing file, as pretty JSON to be reviewed before signing or sending
+pub(crate) fn write_json_file<T: Serialize>(path: &Path, content: &T) -> Result<()> {
+    std::fs::write(path, serde_json::to_string_pretty(content)?)
+        .map_err(|e| anyhow!("could not write {}: {}", path.display(), e))
+}
+
+fn read_json_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let content =
+        std::fs::read(path).map_err(|e| anyhow!("could not read {}: {}", path.display(), e))?;
+    serde_json::from_slice(&content)
+        .map_err(|e| anyhow!("could not parse {}: {}", path.display(), e))
+}
+
+fn check_version(path: &Path, version: u64) -> Result<()> {
+    if version != OFFLINE_OPERATION_FILE_VERSION {
+        bail!(
+            "unsupported version {} of {}, expected {}",
+            version,
+            path.display(),
+            OFFLINE_OPERATION_FILE_VERSION
+        );
+    }
+    Ok(())
+}
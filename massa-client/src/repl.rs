@@ -18,7 +18,7 @@ use std::path::Path;
 use strum::IntoEnumIterator;
 use strum::ParseError;
 
-fn group_parameters(parameters: Vec<String>) -> Vec<String> {
+pub(crate) fn group_parameters(parameters: Vec<String>) -> Vec<String> {
     let mut new_parameters = Vec::new();
     let mut has_opening_simple_quote = false;
     let mut temp_simple_quote = String::new();
@@ -2,6 +2,7 @@
 
 //! Build here the default client settings from the configuration file toml
 use massa_models::config::build_massa_settings;
+use massa_sdk::GrpcTlsConfig;
 use massa_time::MassaTime;
 use serde::Deserialize;
 use std::{net::IpAddr, path::PathBuf};
@@ -26,6 +27,7 @@ pub struct DefaultNode {
     pub public_port: u16,
     pub grpc_public_port: u16,
     pub grpc_private_port: u16,
+    pub api_port: u16,
     pub chain_id: Option<u64>,
 }
 
@@ -41,9 +43,11 @@ pub struct ClientSettings {
     pub max_log_length: u32,
     pub headers: Vec<(String, String)>,
     pub http: HttpSettings,
+    pub ws: WsSettings,
+    pub grpc_public: GrpcTlsSettings,
+    pub grpc_private: GrpcTlsSettings,
 }
 
-///TODO add WebSocket to CLI
 /// Http client settings.
 /// the Http client settings
 #[derive(Debug, Deserialize, Clone)]
@@ -51,6 +55,40 @@ pub struct HttpSettings {
     pub enabled: bool,
 }
 
+/// WebSocket client settings, used by the subscription commands.
+/// the WebSocket client settings
+#[derive(Debug, Deserialize, Clone)]
+pub struct WsSettings {
+    pub enabled: bool,
+    pub max_notifs_per_subscription: usize,
+    pub max_redirections: usize,
+}
+
+/// gRPC client TLS settings, of the public or the private service.
+/// the gRPC client TLS settings
+#[derive(Debug, Deserialize, Clone)]
+pub struct GrpcTlsSettings {
+    pub enable_tls: bool,
+    pub enable_mtls: bool,
+    pub domain_name: String,
+    pub certificate_authority_root_path: PathBuf,
+    pub client_certificate_path: PathBuf,
+    pub client_private_key_path: PathBuf,
+}
+
+impl From<&GrpcTlsSettings> for GrpcTlsConfig {
+    fn from(settings: &GrpcTlsSettings) -> Self {
+        GrpcTlsConfig {
+            enable_tls: settings.enable_tls,
+            enable_mtls: settings.enable_mtls,
+            domain_name: settings.domain_name.clone(),
+            certificate_authority_root_path: settings.certificate_authority_root_path.clone(),
+            client_certificate_path: settings.client_certificate_path.clone(),
+            client_private_key_path: settings.client_private_key_path.clone(),
+        }
+    }
+}
+
 #[cfg(test)]
 #[test]
 fn test_load_client_config() {
@@ -0,0 +1,273 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Live streams of the node activity, through the WebSocket subscriptions of the API V2,
+//! printed to the terminal or as JSON lines until interrupted with Ctrl-C.
+
+use crate::client_warning;
+use anyhow::{anyhow, bail, Result};
+use console::style;
+use futures::{Stream, StreamExt};
+use massa_api_exports::{block::BlockInfo, node::NodeStatus};
+use massa_models::{
+    address::Address, block::FilledBlock, block_header::SecuredHeader, execution::EventFilter,
+    operation::OperationId, slot::Slot,
+};
+use massa_sdk::Client;
+use serde::Serialize;
+use std::fmt::Display;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Default timeout of `wait_operation_final`
+pub(crate) const DEFAULT_WAIT_FINAL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Interval between two checks of the finality of an operation
+const WAIT_FINAL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Kind of activity to stream
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Watched {
+    /// new blocks, optionally created by an address
+    Blocks,
+    /// operations of the new blocks, optionally involving an address
+    Operations,
+    /// endorsements of the new blocks, optionally created by an address
+    Endorsements,
+}
+
+/// Streams a kind of activity until interrupted
+pub(crate) async fn watch(
+    client: &Client,
+    watched: Watched,
+    address: Option<Address>,
+    json: bool,
+) -> Result<()> {
+    let api = client.api.as_ref().ok_or_else(|| {
+        anyhow!("not connected to the WebSocket API of the node, check the api port and the client.ws settings")
+    })?;
+    match watched {
+        Watched::Blocks => {
+            stream(
+                subscribe(api.subscribe_new_blocks()).await?,
+                |block: BlockInfo| {
+                    let content = block.content.as_ref()?;
+                    let creator = content.block.header.content_creator_address;
+                    address
+                        .map_or(true, |address| address == creator)
+                        .then(|| vec![block])
+                },
+                json,
+            )
+            .await
+        }
+        Watched::Operations => {
+            stream(
+                subscribe(api.subscribe_new_filled_blocks()).await?,
+                |filled_block: FilledBlock| {
+                    Some(
+                        filled_block
+                            .operations
+                            .into_iter()
+                            .filter_map(|(_, operation)| operation)
+                            .filter(|operation| {
+                                address.map_or(true, |address| {
+                                    operation.get_ledger_involved_addresses().contains(&address)
+                                })
+                            })
+                            .collect(),
+                    )
+                },
+                json,
+            )
+            .await
+        }
+        Watched::Endorsements => {
+            stream(
+                subscribe(api.subscribe_new_blocks_headers()).await?,
+                |header: SecuredHeader| {
+                    Some(
+                        header
+                            .content
+                            .endorsements
+                            .into_iter()
+                            .filter(|endorsement| {
+                                address.map_or(true, |address| {
+                                    endorsement.content_creator_address == address
+                                })
+                            })
+                            .collect(),
+                    )
+                },
+                json,
+            )
+            .await
+        }
+    }
+}
+
+/// Streams the final smart contract events, optionally those with an address in their call stack,
+/// until interrupted. There is no subscription to the events: they are polled at each slot.
+pub(crate) async fn watch_events(
+    client: &Client,
+    address: Option<Address>,
+    json: bool,
+) -> Result<()> {
+    let status = get_status(client).await?;
+    let thread_count = status.config.thread_count;
+    let poll_interval = status.config.t0.to_duration() / thread_count as u32;
+    // next period to print in each thread
+    let mut next_periods = next_periods_after(status.execution_stats.final_cursor, thread_count);
+    loop {
+        // only read the slots up to the final cursor, whose events are all stored
+        let final_cursor = get_status(client).await?.execution_stats.final_cursor;
+        let start = (0..thread_count)
+            .map(|thread| Slot::new(next_periods[thread as usize], thread))
+            .min()
+            .unwrap_or_else(|| Slot::new(0, 0));
+        if start <= final_cursor {
+            let events = client
+                .public
+                .get_filtered_sc_output_event(EventFilter {
+                    start: Some(start),
+                    end: Some(final_cursor.get_next_slot(thread_count)?),
+                    is_final: Some(true),
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| anyhow!("RpcError: {}", e))?;
+            let mut current_slot = None;
+            for event in events {
+                let slot = event.context.slot;
+                if slot.period < next_periods[slot.thread as usize] {
+                    continue;
+                }
+                if current_slot != Some(slot) {
+                    current_slot = Some(slot);
+                    // the node only keeps its latest final events, the first ones of a slot can be pruned
+                    if event.context.index_in_slot > 0 {
+                        print_warning(
+                            format!(
+                                "the first {} events of slot {} were pruned by the node before being read",
+                                event.context.index_in_slot, slot
+                            ),
+                            json,
+                        );
+                    }
+                }
+                if address.map_or(true, |address| event.context.call_stack.contains(&address)) {
+                    print_item(&event, json)?;
+                }
+            }
+            next_periods = next_periods_after(final_cursor, thread_count);
+        }
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+            _ = tokio::time::sleep(poll_interval) => {}
+        }
+    }
+}
+
+/// Waits for an operation to be final, fails if its execution failed or if it is not final before the timeout
+pub(crate) async fn wait_operation_final(
+    client: &Client,
+    operation_id: OperationId,
+    timeout: Duration,
+) -> Result<()> {
+    let start = Instant::now();
+    loop {
+        let operations = client
+            .public
+            .get_operations(vec![operation_id])
+            .await
+            .map_err(|e| anyhow!("RpcError: {}", e))?;
+        if let Some(operation) = operations.first() {
+            if operation.is_operation_final == Some(true) {
+                if operation.op_exec_status == Some(false) {
+                    bail!(
+                        "operation {} is final but its execution failed",
+                        operation_id
+                    );
+                }
+                return Ok(());
+            }
+        }
+        if start.elapsed() >= timeout {
+            bail!(
+                "operation {} is not final after {} seconds",
+                operation_id,
+                timeout.as_secs()
+            );
+        }
+        tokio::time::sleep(WAIT_FINAL_POLL_INTERVAL).await;
+    }
+}
+
+async fn get_status(client: &Client) -> Result<NodeStatus> {
+    client
+        .public
+        .get_status()
+        .await
+        .map_err(|e| anyhow!("RpcError: {}", e))
+}
+
+/// First period of each thread after a slot
+fn next_periods_after(slot: Slot, thread_count: u8) -> Vec<u64> {
+    (0..thread_count)
+        .map(|thread| {
+            if thread > slot.thread {
+                slot.period
+            } else {
+                slot.period + 1
+            }
+        })
+        .collect()
+}
+
+async fn subscribe<S, E: Display>(subscription: impl Future<Output = Result<S, E>>) -> Result<S> {
+    subscription
+        .await
+        .map_err(|e| anyhow!("subscription failed: {}", e))
+}
+
+/// Prints the items selected from each notification, until interrupted or until the subscription ends
+async fn stream<S, T, E, U, F>(mut subscription: S, mut select: F, json: bool) -> Result<()>
+where
+    S: Stream<Item = Result<T, E>> + Unpin,
+    E: Display,
+    U: Serialize + Display,
+    F: FnMut(T) -> Option<Vec<U>>,
+{
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+            notification = subscription.next() => match notification {
+                Some(Ok(notification)) => {
+                    for item in select(notification).unwrap_or_default() {
+                        print_item(&item, json)?;
+                    }
+                }
+                Some(Err(e)) => bail!("subscription error: {}", e),
+                None => bail!("subscription closed by the node"),
+            },
+        }
+    }
+}
+
+/// Prints a warning, to the standard error in JSON mode to keep the output parsable
+fn print_warning(message: String, json: bool) {
+    if json {
+        eprintln!("WARNING: {}", message);
+    } else {
+        client_warning!(message);
+    }
+}
+
+/// Prints an item, as a JSON line in JSON mode
+fn print_item<T: Serialize + Display>(item: &T, json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string(item)?);
+    } else {
+        println!("{}", item);
+    }
+    Ok(())
+}
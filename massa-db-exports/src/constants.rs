@@ -2,12 +2,18 @@
 pub const METADATA_CF: &str = "metadata";
 pub const STATE_CF: &str = "state";
 pub const VERSIONING_CF: &str = "versioning";
+pub const INDEX_CF: &str = "index";
+pub const STATE_PROOF_CF: &str = "state_proof";
 
 // Hash
 pub const STATE_HASH_BYTES_LEN: usize = 512;
 pub const STATE_HASH_KEY: &[u8; 1] = b"h";
 pub const STATE_HASH_INITIAL_BYTES: &[u8; STATE_HASH_BYTES_LEN] = &[0; STATE_HASH_BYTES_LEN];
 
+// State proofs
+pub const STATE_PROOF_HASH_KEY: &[u8; 1] = b"p";
+pub const STATE_PROOF_DISABLED_ERROR: &str = "state proofs are disabled on this node";
+
 // Change_id
 pub const CHANGE_ID_KEY: &[u8; 1] = b"c";
 pub const CHANGE_ID_DESER_ERROR: &str = "critical: change_id deserialization failed";
@@ -29,6 +35,10 @@ pub const LEDGER_PREFIX: &str = "ledger/";
 pub const MIP_STORE_PREFIX: &str = "versioning/";
 pub const MIP_STORE_STATS_PREFIX: &str = "versioning_stats/";
 pub const EXECUTION_TRAIL_HASH_PREFIX: &str = "execution_trail_hash/";
+pub const ADDRESS_HISTORY_PREFIX: &str = "address_history/";
+pub const REWARD_HISTORY_PREFIX: &str = "reward_history/";
+pub const TRANSACTION_HISTORY_PREFIX: &str = "transaction_history/";
+pub const INDEX_JOURNAL_PREFIX: &str = "index_journal/";
 
 // Async Pool
 pub const MESSAGE_DESER_ERROR: &str = "critical: message deserialization failed";
@@ -56,3 +66,21 @@ pub const EXECUTED_DENUNCIATIONS_INDEX_SER_ERROR: &str =
 pub const KEY_DESER_ERROR: &str = "critical: key deserialization failed";
 pub const KEY_SER_ERROR: &str = "critical: key serialization failed";
 pub const KEY_LEN_SER_ERROR: &str = "critical: key length serialization failed";
+
+// Index journal
+pub const INDEX_JOURNAL_SER_ERROR: &str = "critical: index journal serialization failed";
+pub const INDEX_JOURNAL_DESER_ERROR: &str = "critical: index journal deserialization failed";
+
+// Address history
+pub const ADDRESS_HISTORY_SER_ERROR: &str = "critical: address history serialization failed";
+pub const ADDRESS_HISTORY_DESER_ERROR: &str = "critical: address history deserialization failed";
+
+// Reward history
+pub const REWARD_HISTORY_SER_ERROR: &str = "critical: reward history serialization failed";
+pub const REWARD_HISTORY_DESER_ERROR: &str = "critical: reward history deserialization failed";
+
+// Transaction history
+pub const TRANSACTION_HISTORY_SER_ERROR: &str =
+    "critical: transaction history serialization failed";
+pub const TRANSACTION_HISTORY_DESER_ERROR: &str =
+    "critical: transaction history deserialization failed";
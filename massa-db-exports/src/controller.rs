@@ -1,5 +1,5 @@
-use crate::{DBBatch, Key, MassaDBError, StreamBatch, Value};
-use massa_hash::{HashXof, HASH_XOF_SIZE_BYTES};
+use crate::{DBBatch, Key, MassaDBError, StateProof, StreamBatch, Value};
+use massa_hash::{Hash, HashXof, HASH_XOF_SIZE_BYTES};
 use massa_models::{error::ModelsError, slot::Slot, streaming_step::StreamingStep};
 use parking_lot::RwLock;
 use std::path::PathBuf;
@@ -25,6 +25,16 @@ pub trait MassaDBController: Send + Sync + Debug {
     /// Writes the batch to the DB
     fn write_batch(&mut self, batch: DBBatch, versioning_batch: DBBatch, change_id: Option<Slot>);
 
+    /// Writes the batch to the DB, along with the node-local index entries of the slot `change_id`.
+    /// Index entries are written to INDEX_CF in the same atomic write as the state changes.
+    fn write_batch_with_index(
+        &mut self,
+        batch: DBBatch,
+        versioning_batch: DBBatch,
+        index_batch: DBBatch,
+        change_id: Slot,
+    );
+
     /// Utility function to put / update a key & value in the batch
     fn put_or_update_entry_value(&self, batch: &mut DBBatch, key: Vec<u8>, value: &[u8]);
 
@@ -60,6 +70,14 @@ pub trait MassaDBController: Send + Sync + Debug {
     /// Get the current extended state hash of the database
     fn get_xof_db_hash(&self) -> HashXof<HASH_XOF_SIZE_BYTES>;
 
+    /// Get the root of the state proof tree.
+    /// Fails if state proofs are disabled.
+    fn get_state_proof_root(&self) -> Result<Hash, MassaDBError>;
+
+    /// Get a proof of the value (or absence) of a STATE_CF key against the state proof tree root.
+    /// Fails if state proofs are disabled.
+    fn get_state_proof(&self, key: &[u8]) -> Result<StateProof, MassaDBError>;
+
     /// Flushes the underlying db.
     fn flush(&self) -> Result<(), MassaDBError>;
 
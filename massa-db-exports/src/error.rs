@@ -21,4 +21,6 @@ pub enum MassaDBError {
     HashError(String),
     /// serialization error: {0}
     SerializeError(String),
+    /// state proof error: {0}
+    StateProofError(String),
 }
@@ -15,4 +15,8 @@ pub struct MassaDBConfig {
     pub thread_count: u8,
     /// Maximum number of ledger backups to keep
     pub max_ledger_backups: u64,
+    /// Whether to maintain the sparse Merkle tree used to build state proofs
+    pub state_proofs_enabled: bool,
+    /// Number of periods during which the entries of the node-local indexes (INDEX_CF) are kept
+    pub index_history_periods: u64,
 }
@@ -0,0 +1,116 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Sparse Merkle tree proofs over STATE_CF.
+//!
+//! Every STATE_CF key is a leaf of a binary tree of depth at most `STATE_PROOF_TREE_DEPTH`,
+//! on the path given by the hash of the key.
+//! A subtree holding a single leaf is replaced by that leaf, so that leaves sit about log2(key count)
+//! levels below the root, and empty subtrees hash to `EMPTY_NODE_HASH`.
+//!
+//! The root is computed by each node from its own final state: it is not committed in blocks.
+//! A proof only shows that a value is consistent with a root, which the verifier has to get
+//! from a source it trusts, for instance by comparing the roots of several independent nodes at the same slot.
+//! Verifying state without trusting any node would need the root in the block headers, checked by
+//! consensus: that is a new block version to deploy through a MIP, and every node would have to
+//! maintain the tree, which is optional here (`state_proofs_enabled`).
+
+use massa_hash::{Hash, HASH_SIZE_BYTES};
+use serde::{Deserialize, Serialize};
+
+/// Maximum depth of the state proof tree (number of bits of a path)
+pub const STATE_PROOF_TREE_DEPTH: usize = HASH_SIZE_BYTES * 8;
+
+/// Hash of an empty subtree
+pub const EMPTY_NODE_HASH: [u8; HASH_SIZE_BYTES] = [0; HASH_SIZE_BYTES];
+
+/// Path of a STATE_CF key in the state proof tree
+pub fn state_proof_path(key: &[u8]) -> [u8; HASH_SIZE_BYTES] {
+    Hash::compute_from(key).into_bytes()
+}
+
+/// Returns the bit of `path` at `depth`: false to go left, true to go right
+pub fn state_proof_path_bit(path: &[u8; HASH_SIZE_BYTES], depth: usize) -> bool {
+    (path[depth / 8] >> (7 - depth % 8)) & 1 == 1
+}
+
+/// Hash of the leaf of a key, given the hash of its value
+pub fn state_proof_leaf_hash(key: &[u8], value_hash: &Hash) -> [u8; HASH_SIZE_BYTES] {
+    Hash::compute_from_tuple(&[b"leaf", key, value_hash.to_bytes()]).into_bytes()
+}
+
+/// Hash of an inner node given its children
+pub fn state_proof_node_hash(
+    left: &[u8; HASH_SIZE_BYTES],
+    right: &[u8; HASH_SIZE_BYTES],
+) -> [u8; HASH_SIZE_BYTES] {
+    if left == &EMPTY_NODE_HASH && right == &EMPTY_NODE_HASH {
+        return EMPTY_NODE_HASH;
+    }
+    Hash::compute_from_tuple(&[b"node", left, right]).into_bytes()
+}
+
+/// Leaf of the state proof tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateProofLeaf {
+    /// serialized STATE_CF key
+    pub key: Vec<u8>,
+    /// hash of the serialized value
+    pub value_hash: Hash,
+}
+
+/// Proof that a STATE_CF key has a given value (inclusion), or no value (non-inclusion),
+/// in the state whose tree root is known by the verifier.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateProof {
+    /// serialized STATE_CF key
+    pub key: Vec<u8>,
+    /// serialized value, None if the key is absent
+    pub value: Option<Vec<u8>>,
+    /// leaf of another key found at the end of the path of an absent key, None if the path ends on an empty subtree
+    pub other_leaf: Option<StateProofLeaf>,
+    /// sibling hashes along the path of the key, from the root children down to the leaf,
+    /// None for empty subtrees
+    pub siblings: Vec<Option<Hash>>,
+}
+
+impl StateProof {
+    /// Computes the tree root implied by the proof, or None if the proof is malformed
+    pub fn compute_root(&self) -> Option<Hash> {
+        if self.siblings.len() > STATE_PROOF_TREE_DEPTH {
+            return None;
+        }
+        let path = state_proof_path(&self.key);
+        let mut hash = match (&self.value, &self.other_leaf) {
+            (Some(value), None) => state_proof_leaf_hash(&self.key, &Hash::compute_from(value)),
+            (None, None) => EMPTY_NODE_HASH,
+            (None, Some(leaf)) => {
+                // the other leaf has to be on the path of the key
+                let leaf_path = state_proof_path(&leaf.key);
+                if leaf.key == self.key
+                    || (0..self.siblings.len()).any(|depth| {
+                        state_proof_path_bit(&leaf_path, depth)
+                            != state_proof_path_bit(&path, depth)
+                    })
+                {
+                    return None;
+                }
+                state_proof_leaf_hash(&leaf.key, &leaf.value_hash)
+            }
+            (Some(_), Some(_)) => return None,
+        };
+        for (depth, sibling) in self.siblings.iter().enumerate().rev() {
+            let sibling = sibling.map_or(EMPTY_NODE_HASH, |s| s.into_bytes());
+            hash = if state_proof_path_bit(&path, depth) {
+                state_proof_node_hash(&sibling, &hash)
+            } else {
+                state_proof_node_hash(&hash, &sibling)
+            };
+        }
+        Some(Hash::from_bytes(&hash))
+    }
+
+    /// Checks the proof against a trusted tree root
+    pub fn verify(&self, root: &Hash) -> bool {
+        self.compute_root().as_ref() == Some(root)
+    }
+}
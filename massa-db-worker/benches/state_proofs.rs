@@ -0,0 +1,103 @@
+#[cfg(feature = "benchmarking")]
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+#[cfg(feature = "benchmarking")]
+fn criterion_benchmark(c: &mut Criterion) {
+    use massa_db_exports::{DBBatch, MassaDBConfig, MassaDBController};
+    use massa_db_worker::MassaDB;
+    use massa_models::config::THREAD_COUNT;
+    use std::path::Path;
+
+    /// Number of state entries of the benchmarked databases
+    const STATE_ENTRY_COUNT: u32 = 100_000;
+    /// Number of state entries changed by each benchmarked write
+    const CHANGED_ENTRY_COUNT: u32 = 1_000;
+
+    fn db_config(path: &Path, state_proofs_enabled: bool) -> MassaDBConfig {
+        MassaDBConfig {
+            path: path.to_path_buf(),
+            max_history_length: 100,
+            max_final_state_elements_size: 100,
+            max_versioning_elements_size: 100,
+            thread_count: THREAD_COUNT,
+            max_ledger_backups: 10,
+            state_proofs_enabled,
+            index_history_periods: 1000,
+        }
+    }
+
+    fn entries(range: std::ops::Range<u32>, value_seed: u32) -> DBBatch {
+        range
+            .map(|i| {
+                (
+                    i.to_be_bytes().to_vec(),
+                    Some([i.to_be_bytes(), value_seed.to_be_bytes()].concat()),
+                )
+            })
+            .collect()
+    }
+
+    let mut group = c.benchmark_group("state proofs");
+    group.sample_size(10);
+
+    // Write of a batch of changes to a database with and without the state proof tree
+    for state_proofs_enabled in [false, true] {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut db = MassaDB::try_new(db_config(temp_dir.path(), state_proofs_enabled)).unwrap();
+        db.write_batch(entries(0..STATE_ENTRY_COUNT, 0), DBBatch::new(), None);
+        let mut value_seed = 0;
+        group.bench_function(
+            format!(
+                "write {} changes, state proofs enabled: {}",
+                CHANGED_ENTRY_COUNT, state_proofs_enabled
+            ),
+            |b| {
+                b.iter(|| {
+                    value_seed += 1;
+                    let first = (value_seed * CHANGED_ENTRY_COUNT) % STATE_ENTRY_COUNT;
+                    db.write_batch(
+                        entries(first..first + CHANGED_ENTRY_COUNT, value_seed),
+                        DBBatch::new(),
+                        None,
+                    );
+                })
+            },
+        );
+    }
+
+    // Rebuild of the state proof tree when opening a database whose tree is out of sync
+    let temp_dir = tempfile::tempdir().unwrap();
+    MassaDB::try_new(db_config(temp_dir.path(), false))
+        .unwrap()
+        .write_batch(entries(0..STATE_ENTRY_COUNT, 0), DBBatch::new(), None);
+    let mut value_seed = 0;
+    group.bench_function(
+        format!("rebuild the tree of {} entries", STATE_ENTRY_COUNT),
+        |b| {
+            b.iter_batched(
+                || {
+                    // change the state without maintaining the tree
+                    value_seed += 1;
+                    MassaDB::try_new(db_config(temp_dir.path(), false))
+                        .unwrap()
+                        .write_batch(entries(0..1, value_seed), DBBatch::new(), None);
+                },
+                |_| drop(MassaDB::try_new(db_config(temp_dir.path(), true)).unwrap()),
+                BatchSize::PerIteration,
+            )
+        },
+    );
+
+    group.finish();
+}
+
+#[cfg(feature = "benchmarking")]
+criterion_group!(benches, criterion_benchmark);
+
+#[cfg(feature = "benchmarking")]
+criterion_main!(benches);
+
+#[cfg(not(feature = "benchmarking"))]
+fn main() {
+    println!("Please use the `--features benchmarking` flag to run this benchmark.");
+}
@@ -36,12 +36,39 @@
 //! * if we want to delete item b: 1000 ^ 1011 == 0011 (== item a)
 //!
 //! Note that this does not provides "Proof of present" nor "Proof of Absence"
-//! (operations avail with Merkle trees)
+//! (see the state proofs section below)
 //!
 //! For more details here: https://github.com/massalabs/massa/discussions/3852#discussioncomment-6188158
 //!
 //! This hash is often referred as 'final state hash'.
 //!
+//! # State proofs
+//!
+//! When `state_proofs_enabled` is set, a sparse Merkle tree over the 'state' column is maintained
+//! in the 'state_proof' column, in the same rocksdb write as the state itself.
+//! Each key is a leaf on the path given by the hash of the key, so the tree can prove that
+//! a key has a given value or that it is absent (see `StateProof` in massa-db-exports).
+//! A subtree holding a single leaf is stored as that leaf, so a write reads and writes
+//! about log2(key count) nodes per changed key.
+//!
+//! The root is not part of the final state hash and is not committed in blocks:
+//! a proof is only as trustworthy as the source of the root it is checked against.
+//!
+//! The metadata column records the final state hash the tree was last updated with:
+//! if it does not match at startup (e.g. proofs were just enabled), the tree is rebuilt from the state,
+//! which reads the whole 'state' column (see the `state_proofs` bench).
+//!
+//! # Indexes
+//!
+//! The 'index' column holds optional indexes built by this node from the slots it finalizes
+//! (ledger history, rewards and transactions of each address).
+//! They are not part of the final state: they are not hashed, not streamed to bootstrapping nodes,
+//! and they are cleared when the database is reset for a bootstrap, so they only cover the slots finalized locally.
+//!
+//! Index entries are written in the same rocksdb write as the state changes of their slot.
+//! The keys written at each slot are recorded in a journal entry of the same column,
+//! so that the entries older than `index_history_periods` periods are pruned.
+//!
 //! # Caches
 //!
 //! A cache of db changes is kept in memory allowing to easily stream it
@@ -61,5 +88,6 @@
 //!    we can send the updates
 
 mod massa_db;
+mod state_proof_tree;
 
 pub use crate::massa_db::*;
@@ -1,12 +1,16 @@
+use crate::state_proof_tree::{StateProofChange, StateProofTree};
 use massa_db_exports::{
     DBBatch, Key, MassaDBConfig, MassaDBController, MassaDBError, MassaDirection,
-    MassaIteratorMode, StreamBatch, Value, CF_ERROR, CHANGE_ID_DESER_ERROR, CHANGE_ID_KEY,
-    CHANGE_ID_SER_ERROR, CRUD_ERROR, METADATA_CF, OPEN_ERROR, STATE_CF, STATE_HASH_ERROR,
-    STATE_HASH_INITIAL_BYTES, STATE_HASH_KEY, VERSIONING_CF,
+    MassaIteratorMode, StateProof, StreamBatch, Value, CF_ERROR, CHANGE_ID_DESER_ERROR,
+    CHANGE_ID_KEY, CHANGE_ID_SER_ERROR, CRUD_ERROR, INDEX_CF, INDEX_JOURNAL_DESER_ERROR,
+    INDEX_JOURNAL_PREFIX, INDEX_JOURNAL_SER_ERROR, METADATA_CF, OPEN_ERROR, STATE_CF,
+    STATE_HASH_ERROR, STATE_HASH_INITIAL_BYTES, STATE_HASH_KEY, STATE_PROOF_CF,
+    STATE_PROOF_DISABLED_ERROR, STATE_PROOF_HASH_KEY, VERSIONING_CF,
 };
-use massa_hash::{HashXof, HASH_XOF_SIZE_BYTES};
+use massa_hash::{Hash, HashXof, HASH_XOF_SIZE_BYTES};
 use massa_models::{
     error::ModelsError,
+    serialization::{VecDeserializer, VecSerializer, VecU8Deserializer, VecU8Serializer},
     slot::{Slot, SlotDeserializer, SlotSerializer},
     streaming_step::StreamingStep,
 };
@@ -24,6 +28,9 @@ use std::{
     sync::Arc,
 };
 
+/// Number of STATE_CF entries inserted per write when rebuilding the state proof tree
+const STATE_PROOF_REBUILD_CHUNK_SIZE: usize = 10_000;
+
 /// Wrapped RocksDB database
 ///
 /// In our instance, we use Slot as the ChangeID
@@ -33,6 +40,7 @@ pub type MassaDB = RawMassaDB<Slot, SlotSerializer, SlotDeserializer>;
 ///
 /// The added features are:
 /// - Hash tracking with Xor
+/// - Optional sparse Merkle tree over the state, to build state proofs
 /// - Streaming the database while it is being actively updated
 #[derive()]
 pub struct RawMassaDB<
@@ -342,10 +350,12 @@ where
     /// - Bootstrap clients, to write on disk a new received Stream (reset_history: true)
     /// - Normal operations, to write changes associated to a given change_id (reset_history: false)
     ///
+    /// `index_changes` are written to INDEX_CF: they are not hashed nor kept in the change history.
     pub fn write_changes(
         &mut self,
         changes: BTreeMap<Key, Option<Value>>,
         versioning_changes: BTreeMap<Key, Option<Value>>,
+        index_changes: BTreeMap<Key, Option<Value>>,
         change_id: Option<ChangeID>,
         reset_history: bool,
     ) -> Result<(), MassaDBError> {
@@ -360,11 +370,24 @@ where
         let handle_state = self.db.cf_handle(STATE_CF).expect(CF_ERROR);
         let handle_metadata = self.db.cf_handle(METADATA_CF).expect(CF_ERROR);
         let handle_versioning = self.db.cf_handle(VERSIONING_CF).expect(CF_ERROR);
+        let handle_index = self.db.cf_handle(INDEX_CF).expect(CF_ERROR);
 
         let mut current_xor_hash = self.get_xof_db_hash();
 
         *self.current_batch.lock() = WriteBatch::default();
 
+        // Nodes of the state proof tree that are modified by the changes
+        let mut state_proof_node_changes = DBBatch::new();
+        if self.config.state_proofs_enabled {
+            self.state_proof_tree().apply(
+                changes
+                    .iter()
+                    .map(|(key, value)| StateProofChange::new(key, value.as_deref()))
+                    .collect(),
+                &mut state_proof_node_changes,
+            )?;
+        }
+
         for (key, value) in changes.iter() {
             if let Some(value) = value {
                 self.current_batch.lock().put_cf(handle_state, key, value);
@@ -401,6 +424,14 @@ where
             }
         }
 
+        for (key, value) in index_changes.iter() {
+            if let Some(value) = value {
+                self.current_batch.lock().put_cf(handle_index, key, value);
+            } else {
+                self.current_batch.lock().delete_cf(handle_index, key);
+            }
+        }
+
         if let Some(change_id) = change_id {
             self.set_change_id_to_batch(change_id);
         }
@@ -410,6 +441,23 @@ where
             .lock()
             .put_cf(handle_metadata, STATE_HASH_KEY, current_xor_hash.0);
 
+        // Update the state proof tree, and record the state hash it is in sync with
+        if self.config.state_proofs_enabled {
+            self.write_state_proof_nodes_to_batch(
+                state_proof_node_changes,
+                &mut self.current_batch.lock(),
+            );
+            self.current_batch.lock().put_cf(
+                handle_metadata,
+                STATE_PROOF_HASH_KEY,
+                current_xor_hash.0,
+            );
+        } else {
+            self.current_batch
+                .lock()
+                .delete_cf(handle_metadata, STATE_PROOF_HASH_KEY);
+        }
+
         {
             let mut current_batch_guard = self.current_batch.lock();
             let batch = WriteBatch::from_data(current_batch_guard.data());
@@ -548,6 +596,7 @@ where
         self.write_changes(
             changes,
             versioning_changes,
+            BTreeMap::new(),
             Some(stream_changes.change_id),
             true,
         )?;
@@ -571,13 +620,118 @@ where
             .as_deref()
             .map(|state_hash_bytes| HashXof(state_hash_bytes.try_into().expect(STATE_HASH_ERROR)))
     }
+
+    /// The state proof tree, reading its nodes from STATE_PROOF_CF
+    fn state_proof_tree(
+        &self,
+    ) -> StateProofTree<impl Fn(&[u8]) -> Result<Option<Vec<u8>>, MassaDBError> + '_> {
+        let handle = self.db.cf_handle(STATE_PROOF_CF).expect(CF_ERROR);
+        StateProofTree::new(move |node_key: &[u8]| {
+            self.db
+                .get_cf(handle, node_key)
+                .map_err(|e| MassaDBError::RocksDBError(format!("{:?}", e)))
+        })
+    }
+
+    /// Add the changes of the state proof tree nodes to `batch`, empty nodes are deleted
+    fn write_state_proof_nodes_to_batch(&self, node_changes: DBBatch, batch: &mut WriteBatch) {
+        let handle = self.db.cf_handle(STATE_PROOF_CF).expect(CF_ERROR);
+        for (node_key, node) in node_changes {
+            match node {
+                Some(node) => batch.put_cf(handle, node_key, node),
+                None => batch.delete_cf(handle, node_key),
+            }
+        }
+    }
+
+    /// Rebuild the state proof tree from STATE_CF if it is not in sync with the current state hash.
+    /// This happens when state proofs are enabled on an existing database.
+    fn sync_state_proof_tree(&self) -> Result<(), MassaDBError> {
+        let handle_state = self.db.cf_handle(STATE_CF).expect(CF_ERROR);
+        let handle_metadata = self.db.cf_handle(METADATA_CF).expect(CF_ERROR);
+        let handle_proof = self.db.cf_handle(STATE_PROOF_CF).expect(CF_ERROR);
+
+        let current_xor_hash = self.get_xof_db_hash();
+        let synced_hash = self
+            .db
+            .get_cf(handle_metadata, STATE_PROOF_HASH_KEY)
+            .map_err(|e| MassaDBError::RocksDBError(format!("{:?}", e)))?;
+        if synced_hash.as_deref() == Some(current_xor_hash.0.as_slice()) {
+            return Ok(());
+        }
+
+        let write = |batch: WriteBatch| {
+            self.db.write(batch).map_err(|e| {
+                MassaDBError::RocksDBError(format!("Can't write batch to disk: {}", e))
+            })
+        };
+
+        // Remove the outdated tree
+        let mut batch = WriteBatch::default();
+        batch.delete_cf(handle_metadata, STATE_PROOF_HASH_KEY);
+        batch.delete_range_cf(handle_proof, [u8::MIN].as_slice(), [u8::MAX].as_slice());
+        write(batch)?;
+
+        // Insert every state entry, one chunk at a time
+        let tree = self.state_proof_tree();
+        let write_chunk = |chunk: Vec<StateProofChange>, mut batch: WriteBatch| {
+            let mut node_changes = DBBatch::new();
+            tree.apply(chunk, &mut node_changes)?;
+            self.write_state_proof_nodes_to_batch(node_changes, &mut batch);
+            write(batch)
+        };
+        let mut chunk = Vec::with_capacity(STATE_PROOF_REBUILD_CHUNK_SIZE);
+        for entry in self.db.iterator_cf(handle_state, IteratorMode::Start) {
+            let (key, value) = entry.map_err(|e| MassaDBError::RocksDBError(format!("{:?}", e)))?;
+            chunk.push(StateProofChange::new(&key, Some(&value)));
+            if chunk.len() >= STATE_PROOF_REBUILD_CHUNK_SIZE {
+                write_chunk(std::mem::take(&mut chunk), WriteBatch::default())?;
+            }
+        }
+
+        let mut batch = WriteBatch::default();
+        batch.put_cf(handle_metadata, STATE_PROOF_HASH_KEY, current_xor_hash.0);
+        write_chunk(chunk, batch)
+    }
+
+    /// Get the root of the state proof tree
+    pub fn get_state_proof_root(&self) -> Result<Hash, MassaDBError> {
+        if !self.config.state_proofs_enabled {
+            return Err(MassaDBError::StateProofError(
+                STATE_PROOF_DISABLED_ERROR.to_string(),
+            ));
+        }
+        self.state_proof_tree().root()
+    }
+
+    /// Get a proof of the value (or absence) of a STATE_CF key
+    pub fn get_state_proof(&self, key: &[u8]) -> Result<StateProof, MassaDBError> {
+        if !self.config.state_proofs_enabled {
+            return Err(MassaDBError::StateProofError(
+                STATE_PROOF_DISABLED_ERROR.to_string(),
+            ));
+        }
+        let handle_state = self.db.cf_handle(STATE_CF).expect(CF_ERROR);
+        let value = self
+            .db
+            .get_cf(handle_state, key)
+            .map_err(|e| MassaDBError::RocksDBError(format!("{:?}", e)))?;
+
+        self.state_proof_tree().prove(key, value)
+    }
 }
 
 impl RawMassaDB<Slot, SlotSerializer, SlotDeserializer> {
     /// Returns a new `MassaDB` instance
     pub fn new(config: MassaDBConfig) -> Self {
+        Self::try_new(config).expect(OPEN_ERROR)
+    }
+
+    /// Returns a new `MassaDB` instance, or an error if the database can't be opened
+    /// or if its state proof tree can't be rebuilt
+    pub fn try_new(config: MassaDBConfig) -> Result<Self, MassaDBError> {
         let db_opts = Self::default_db_opts();
-        Self::new_with_options(config, db_opts).expect(OPEN_ERROR)
+        Self::new_with_options(config, db_opts)
     }
 
     pub fn default_db_opts() -> Options {
@@ -589,7 +743,7 @@ impl RawMassaDB<Slot, SlotSerializer, SlotDeserializer> {
     }
 
     /// Returns a new `MassaDB` instance given a config and RocksDB options
-    fn new_with_options(config: MassaDBConfig, db_opts: Options) -> Result<Self, rocksdb::Error> {
+    fn new_with_options(config: MassaDBConfig, db_opts: Options) -> Result<Self, MassaDBError> {
         let db = DB::open_cf_descriptors(
             &db_opts,
             &config.path,
@@ -597,38 +751,122 @@ impl RawMassaDB<Slot, SlotSerializer, SlotDeserializer> {
                 ColumnFamilyDescriptor::new(STATE_CF, Options::default()),
                 ColumnFamilyDescriptor::new(METADATA_CF, Options::default()),
                 ColumnFamilyDescriptor::new(VERSIONING_CF, Options::default()),
+                ColumnFamilyDescriptor::new(INDEX_CF, Options::default()),
+                ColumnFamilyDescriptor::new(STATE_PROOF_CF, Options::default()),
             ],
-        )?;
+        )
+        .map_err(|e| MassaDBError::RocksDBError(format!("Can't open the database: {}", e)))?;
+
+        let massa_db = Self::from_db(db, config);
+
+        if massa_db.get_change_id().is_err() {
+            massa_db.set_initial_change_id(Slot {
+                period: 0,
+                thread: 0,
+            });
+        }
 
-        let db = Arc::new(db);
-        let current_batch = Arc::new(Mutex::new(WriteBatch::default()));
+        if massa_db.config.state_proofs_enabled {
+            massa_db.sync_state_proof_tree()?;
+        }
+
+        Ok(massa_db)
+    }
 
+    /// Opens an existing `MassaDB` in read-only mode, e.g. to read the state of a stopped node.
+    /// Fails if there is no database at the configured path. Writes to the returned instance fail.
+    pub fn open_read_only(config: MassaDBConfig) -> Result<Self, MassaDBError> {
+        let mut db_opts = Self::default_db_opts();
+        db_opts.create_if_missing(false);
+        db_opts.create_missing_column_families(false);
+
+        // databases created by older versions may lack some column families
+        let cf_names = DB::list_cf(&db_opts, &config.path)
+            .map_err(|e| MassaDBError::RocksDBError(format!("Can't open the database: {}", e)))?;
+        let db = DB::open_cf_descriptors_read_only(
+            &db_opts,
+            &config.path,
+            cf_names
+                .into_iter()
+                .map(|name| ColumnFamilyDescriptor::new(name, Options::default())),
+            false,
+        )
+        .map_err(|e| MassaDBError::RocksDBError(format!("Can't open the database: {}", e)))?;
+
+        Ok(Self::from_db(db, config))
+    }
+
+    /// Wraps an opened RocksDB database
+    fn from_db(db: DB, config: MassaDBConfig) -> Self {
         let change_id_deserializer = SlotDeserializer::new(
             (Included(u64::MIN), Included(u64::MAX)),
             (Included(0), Excluded(config.thread_count)),
         );
 
-        let massa_db = Self {
-            db,
+        Self {
+            db: Arc::new(db),
             config,
             change_history: BTreeMap::new(),
             change_history_versioning: BTreeMap::new(),
             change_id_serializer: SlotSerializer::new(),
             change_id_deserializer,
-            current_batch,
+            current_batch: Arc::new(Mutex::new(WriteBatch::default())),
+        }
+    }
+
+    /// Adds to `index_batch` the deletion of the index entries that are older than
+    /// `index_history_periods` periods at `slot`, along with their journal entries.
+    fn prune_index_to_batch(&self, slot: &Slot, index_batch: &mut DBBatch) {
+        let Some(first_kept_period) = slot.period.checked_sub(self.config.index_history_periods)
+        else {
+            return;
         };
+        let handle = self.db.cf_handle(INDEX_CF).expect(CF_ERROR);
+        let journal_deserializer = VecDeserializer::new(
+            VecU8Deserializer::new(Included(u64::MIN), Included(u64::MAX)),
+            Included(u64::MIN),
+            Included(u64::MAX),
+        );
 
-        if massa_db.get_change_id().is_err() {
-            massa_db.set_initial_change_id(Slot {
-                period: 0,
-                thread: 0,
-            });
+        let end_key = index_journal_key(&Slot::new(first_kept_period, 0));
+        for (journal_key, journal) in self
+            .db
+            .iterator_cf(
+                handle,
+                IteratorMode::From(INDEX_JOURNAL_PREFIX.as_bytes(), Direction::Forward),
+            )
+            .flatten()
+            .take_while(|(key, _)| key.as_ref() < end_key.as_slice())
+        {
+            let (_, keys) = journal_deserializer
+                .deserialize::<DeserializeError>(&journal)
+                .expect(INDEX_JOURNAL_DESER_ERROR);
+            for key in keys {
+                index_batch.insert(key, None);
+            }
+            index_batch.insert(journal_key.to_vec(), None);
         }
+    }
 
-        Ok(massa_db)
+    /// Deletes every entry of INDEX_CF
+    fn clear_index(&self) {
+        let handle = self.db.cf_handle(INDEX_CF).expect(CF_ERROR);
+        let mut batch = WriteBatch::default();
+        for (key, _) in self.db.iterator_cf(handle, IteratorMode::Start).flatten() {
+            batch.delete_cf(handle, key);
+        }
+        self.db.write(batch).expect(CRUD_ERROR);
     }
 }
 
+/// Builds the INDEX_CF key of the journal of the index keys written at a given slot.
+/// Slots are serialized in a sortable way, so that iterating over the journal yields increasing slots.
+fn index_journal_key(slot: &Slot) -> Key {
+    let mut key = INDEX_JOURNAL_PREFIX.as_bytes().to_vec();
+    key.extend(slot.to_bytes_key());
+    key
+}
+
 impl MassaDBController for RawMassaDB<Slot, SlotSerializer, SlotDeserializer> {
     /// Creates a new hard copy of the DB, for the given slot
     fn backup_db(&self, slot: Slot) -> PathBuf {
@@ -679,10 +917,42 @@ impl MassaDBController for RawMassaDB<Slot, SlotSerializer, SlotDeserializer> {
 
     /// Writes the batch to the DB
     fn write_batch(&mut self, batch: DBBatch, versioning_batch: DBBatch, change_id: Option<Slot>) {
-        self.write_changes(batch, versioning_batch, change_id, false)
+        self.write_changes(batch, versioning_batch, DBBatch::new(), change_id, false)
             .expect(CRUD_ERROR);
     }
 
+    /// Writes the batch to the DB, along with the node-local index entries of the slot `change_id`
+    fn write_batch_with_index(
+        &mut self,
+        batch: DBBatch,
+        versioning_batch: DBBatch,
+        index_batch: DBBatch,
+        change_id: Slot,
+    ) {
+        let mut index_changes = DBBatch::new();
+        self.prune_index_to_batch(&change_id, &mut index_changes);
+
+        // record the keys written at this slot, so that they can be pruned later on
+        if !index_batch.is_empty() {
+            let keys: Vec<Key> = index_batch.keys().cloned().collect();
+            let mut journal = Vec::new();
+            VecSerializer::new(VecU8Serializer::new())
+                .serialize(&keys, &mut journal)
+                .expect(INDEX_JOURNAL_SER_ERROR);
+            index_changes.insert(index_journal_key(&change_id), Some(journal));
+        }
+        index_changes.extend(index_batch);
+
+        self.write_changes(
+            batch,
+            versioning_batch,
+            index_changes,
+            Some(change_id),
+            false,
+        )
+        .expect(CRUD_ERROR);
+    }
+
     /// Utility function to put / update a key & value in the batch
     fn put_or_update_entry_value(&self, batch: &mut DBBatch, key: Vec<u8>, value: &[u8]) {
         batch.insert(key, Some(value.to_vec()));
@@ -722,6 +992,8 @@ impl MassaDBController for RawMassaDB<Slot, SlotSerializer, SlotDeserializer> {
     fn reset(&mut self, slot: Slot) {
         self.set_initial_change_id(slot);
         self.change_history.clear();
+        // the node-local indexes only cover the slots finalized by this node
+        self.clear_index();
     }
 
     fn get_cf(&self, handle_cf: &str, key: Key) -> Result<Option<Value>, MassaDBError> {
@@ -805,6 +1077,16 @@ impl MassaDBController for RawMassaDB<Slot, SlotSerializer, SlotDeserializer> {
         self.set_initial_change_id(change_id)
     }
 
+    /// Get the root of the state proof tree
+    fn get_state_proof_root(&self) -> Result<Hash, MassaDBError> {
+        self.get_state_proof_root()
+    }
+
+    /// Get a proof of the value (or absence) of a STATE_CF key against the state proof tree root
+    fn get_state_proof(&self, key: &[u8]) -> Result<StateProof, MassaDBError> {
+        self.get_state_proof(key)
+    }
+
     /// Flushes the underlying db.
     fn flush(&self) -> Result<(), MassaDBError> {
         self.db
@@ -880,6 +1162,7 @@ mod test {
     use parking_lot::RwLock;
     use tempfile::tempdir;
 
+    use massa_db_exports::EMPTY_NODE_HASH;
     use massa_hash::Hash;
     use massa_models::config::THREAD_COUNT;
     use massa_models::streaming_step::StreamingStep;
@@ -925,6 +1208,8 @@ mod test {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let mut db_opts = MassaDB::default_db_opts();
         // Additional checks (only for testing)
@@ -935,7 +1220,47 @@ mod test {
         // Check not allowed to init a second instance
         let db2 = MassaDB::new_with_options(db_config, db_opts.clone());
         assert!(db2.is_err());
-        assert!(db2.err().unwrap().into_string().contains("IO error"));
+        assert!(db2.err().unwrap().to_string().contains("IO error"));
+    }
+
+    #[test]
+    fn test_open_read_only() {
+        // 1- Check a missing db is not created
+        // 2- Write some data, then read it back from a read-only instance
+
+        let temp_dir_db = tempdir().expect("Unable to create a temp folder");
+        let db_config = MassaDBConfig {
+            path: temp_dir_db.path().join("ledger"),
+            max_history_length: 100,
+            max_final_state_elements_size: 100,
+            max_versioning_elements_size: 100,
+            thread_count: THREAD_COUNT,
+            max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
+        };
+
+        assert!(MassaDB::open_read_only(db_config.clone()).is_err());
+        assert!(!db_config.path.exists());
+
+        let mut db = MassaDB::try_new(db_config.clone()).unwrap();
+        let slot = Slot::new(3, 1);
+        db.write_batch(
+            DBBatch::from([(vec![1, 2, 3], Some(vec![4, 5, 6]))]),
+            DBBatch::new(),
+            Some(slot),
+        );
+        let hash = db.get_xof_db_hash();
+        drop(db);
+
+        let db = MassaDB::open_read_only(db_config).unwrap();
+        assert_eq!(db.get_change_id().unwrap(), slot);
+        assert_eq!(db.get_xof_db_hash(), hash);
+        let handle = db.db.cf_handle(STATE_CF).expect(CF_ERROR);
+        assert_eq!(
+            db.db.get_cf(handle, [1, 2, 3]).unwrap(),
+            Some(vec![4, 5, 6])
+        );
     }
 
     #[test]
@@ -955,6 +1280,8 @@ mod test {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let mut db_opts = MassaDB::default_db_opts();
         // Additional checks (only for testing)
@@ -1039,6 +1366,8 @@ mod test {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let mut db_opts = MassaDB::default_db_opts();
         // Additional checks (only for testing)
@@ -1124,6 +1453,8 @@ mod test {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let mut db_opts = MassaDB::default_db_opts();
         // Additional checks (only for testing)
@@ -1173,6 +1504,8 @@ mod test {
                 max_versioning_elements_size: 100,
                 thread_count: THREAD_COUNT,
                 max_ledger_backups: 10,
+                state_proofs_enabled: false,
+                index_history_periods: 1000,
             };
             let mut db_backup_1_opts = MassaDB::default_db_opts();
             db_backup_1_opts.create_if_missing(false);
@@ -1197,6 +1530,8 @@ mod test {
                 max_versioning_elements_size: 100,
                 thread_count: THREAD_COUNT,
                 max_ledger_backups: 10,
+                state_proofs_enabled: false,
+                index_history_periods: 1000,
             };
             let mut db_backup_2_opts = MassaDB::default_db_opts();
             db_backup_2_opts.create_if_missing(false);
@@ -1230,6 +1565,8 @@ mod test {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let mut db_opts = MassaDB::default_db_opts();
         // Additional checks (only for testing)
@@ -1277,6 +1614,8 @@ mod test {
                 max_versioning_elements_size: 100,
                 thread_count: THREAD_COUNT,
                 max_ledger_backups: 10,
+                state_proofs_enabled: false,
+                index_history_periods: 1000,
             };
             // let db_backup_2_opts = MassaDB::default_db_opts();
 
@@ -1326,6 +1665,8 @@ mod test {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let mut db_opts = MassaDB::default_db_opts();
         // Additional checks (only for testing)
@@ -1419,6 +1760,8 @@ mod test {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let mut db_opts = MassaDB::default_db_opts();
         // Additional checks (only for testing)
@@ -1507,6 +1850,8 @@ mod test {
             max_versioning_elements_size: 10,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let mut db_opts = MassaDB::default_db_opts();
         // Additional checks (only for testing)
@@ -1592,6 +1937,8 @@ mod test {
             max_versioning_elements_size: 20,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let mut db_opts = MassaDB::default_db_opts();
         // Additional checks (only for testing)
@@ -1704,6 +2051,8 @@ mod test {
             max_versioning_elements_size: 20,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
 
         let slot_1 = Slot::new(1, 0);
@@ -1810,4 +2159,211 @@ mod test {
         assert!(stream_batch_.is_err());
         assert!(stream_batch_.unwrap_err().to_string().contains("all our changes are strictly after last_change_id, we can't be sure we did not miss any"));
     }
+
+    #[test]
+    fn test_state_proofs() {
+        // 1- Init a db with state proofs enabled
+        // 2- Add / remove data and check inclusion and non-inclusion proofs
+        // 3- Reopen the db after writes with state proofs disabled, check the tree is rebuilt
+
+        let temp_dir_db = tempdir().expect("Unable to create a temp folder");
+        let db_config = MassaDBConfig {
+            path: temp_dir_db.path().to_path_buf(),
+            max_history_length: 100,
+            max_final_state_elements_size: 100,
+            max_versioning_elements_size: 100,
+            thread_count: THREAD_COUNT,
+            max_ledger_backups: 10,
+            state_proofs_enabled: true,
+            index_history_periods: 1000,
+        };
+        let mut db_opts = MassaDB::default_db_opts();
+        // Additional checks (only for testing)
+        db_opts.set_paranoid_checks(true);
+
+        let mut db = MassaDB::new_with_options(db_config.clone(), db_opts.clone()).unwrap();
+        let empty_root = db.get_state_proof_root().unwrap();
+        assert_eq!(empty_root, Hash::from_bytes(&EMPTY_NODE_HASH));
+
+        let batch = DBBatch::from([
+            (vec![1, 2, 3], Some(vec![4, 5, 6])),
+            (vec![7, 8, 9], Some(vec![10, 11, 12])),
+            (vec![13], Some(vec![14])),
+        ]);
+        db.write_batch(batch, DBBatch::new(), None);
+        let root = db.get_state_proof_root().unwrap();
+        assert_ne!(root, empty_root);
+
+        // inclusion proof
+        let proof = db.get_state_proof(&[7, 8, 9]).unwrap();
+        assert_eq!(proof.value, Some(vec![10, 11, 12]));
+        assert!(proof.verify(&root));
+        let mut forged_proof = proof.clone();
+        forged_proof.value = Some(vec![0]);
+        assert!(!forged_proof.verify(&root));
+
+        // non-inclusion proof
+        let proof = db.get_state_proof(&[42]).unwrap();
+        assert_eq!(proof.value, None);
+        assert!(proof.verify(&root));
+
+        // deletion
+        db.write_batch(DBBatch::from([(vec![7, 8, 9], None)]), DBBatch::new(), None);
+        let root_after_delete = db.get_state_proof_root().unwrap();
+        assert_ne!(root_after_delete, root);
+        let proof = db.get_state_proof(&[7, 8, 9]).unwrap();
+        assert_eq!(proof.value, None);
+        assert!(proof.verify(&root_after_delete));
+        assert!(!proof.verify(&root));
+        drop(db);
+
+        // Write with state proofs disabled: the tree is no longer maintained
+        let mut db = MassaDB::new_with_options(
+            MassaDBConfig {
+                state_proofs_enabled: false,
+                ..db_config.clone()
+            },
+            db_opts.clone(),
+        )
+        .unwrap();
+        assert!(db.get_state_proof_root().is_err());
+        db.write_batch(
+            DBBatch::from([(vec![7, 8, 9], Some(vec![10, 11, 12]))]),
+            DBBatch::new(),
+            None,
+        );
+        drop(db);
+
+        // Enabling state proofs again rebuilds the tree
+        let db = MassaDB::new_with_options(db_config, db_opts).unwrap();
+        assert_eq!(db.get_state_proof_root().unwrap(), root);
+        let proof = db.get_state_proof(&[1, 2, 3]).unwrap();
+        assert!(proof.verify(&root));
+    }
+
+    #[test]
+    fn test_state_proof_tree_shape() {
+        // 1- Insert the same keys in one write in a db, and one write per key in another one
+        // 2- Check the roots match, and that proofs are short and valid
+        // 3- Delete the keys one by one, and check the tree collapses back to an empty one
+
+        let keys: Vec<Vec<u8>> = (0..300u32).map(|i| i.to_be_bytes().to_vec()).collect();
+        let db_config = |path: &std::path::Path| MassaDBConfig {
+            path: path.to_path_buf(),
+            max_history_length: 100,
+            max_final_state_elements_size: 100,
+            max_versioning_elements_size: 100,
+            thread_count: THREAD_COUNT,
+            max_ledger_backups: 10,
+            state_proofs_enabled: true,
+            index_history_periods: 1000,
+        };
+
+        let temp_dir_a = tempdir().expect("Unable to create a temp folder");
+        let mut db_a = MassaDB::try_new(db_config(temp_dir_a.path())).unwrap();
+        db_a.write_batch(
+            keys.iter()
+                .map(|key| (key.clone(), Some(key.repeat(2))))
+                .collect(),
+            DBBatch::new(),
+            None,
+        );
+
+        let temp_dir_b = tempdir().expect("Unable to create a temp folder");
+        let mut db_b = MassaDB::try_new(db_config(temp_dir_b.path())).unwrap();
+        for key in keys.iter().rev() {
+            db_b.write_batch(
+                DBBatch::from([(key.clone(), Some(key.repeat(2)))]),
+                DBBatch::new(),
+                None,
+            );
+        }
+
+        let root = db_a.get_state_proof_root().unwrap();
+        assert_eq!(db_b.get_state_proof_root().unwrap(), root);
+        for key in keys.iter() {
+            let proof = db_b.get_state_proof(key).unwrap();
+            assert_eq!(proof.value, Some(key.repeat(2)));
+            // leaves sit about log2(300) levels below the root
+            assert!(proof.siblings.len() < 32);
+            assert!(proof.verify(&root));
+        }
+        let proof = db_b.get_state_proof(b"absent").unwrap();
+        assert_eq!(proof.value, None);
+        assert!(proof.verify(&root));
+
+        // a leaf of another key can't prove the absence of a present key
+        if let Some(mut forged_proof) = db_b
+            .get_state_proof(b"absent")
+            .ok()
+            .filter(|proof| proof.other_leaf.is_some())
+        {
+            forged_proof.key = forged_proof.other_leaf.as_ref().unwrap().key.clone();
+            assert!(!forged_proof.verify(&root));
+        }
+
+        for (index, key) in keys.iter().enumerate() {
+            db_a.write_batch(DBBatch::from([(key.clone(), None)]), DBBatch::new(), None);
+            let root = db_a.get_state_proof_root().unwrap();
+            if index + 2 == keys.len() {
+                // a single leaf left: it is the root
+                let last = keys.last().unwrap();
+                let proof = db_a.get_state_proof(last).unwrap();
+                assert!(proof.siblings.is_empty());
+                assert!(proof.verify(&root));
+            }
+        }
+        assert_eq!(
+            db_a.get_state_proof_root().unwrap(),
+            Hash::from_bytes(&EMPTY_NODE_HASH)
+        );
+    }
+
+    #[test]
+    fn test_index_pruning() {
+        // Index entries are pruned after index_history_periods periods, and cleared on reset
+
+        let temp_dir_db = tempdir().expect("Unable to create a temp folder");
+        let db_config = MassaDBConfig {
+            path: temp_dir_db.path().to_path_buf(),
+            max_history_length: 100,
+            max_final_state_elements_size: 100,
+            max_versioning_elements_size: 100,
+            thread_count: THREAD_COUNT,
+            max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 2,
+        };
+        let mut db_opts = MassaDB::default_db_opts();
+        db_opts.set_paranoid_checks(true);
+
+        let db: Arc<RwLock<Box<dyn MassaDBController>>> = Arc::new(RwLock::new(Box::new(
+            MassaDB::new_with_options(db_config, db_opts).unwrap(),
+        )));
+        let state_hash = db.read().get_xof_db_hash();
+
+        for period in 1..=4u64 {
+            db.write().write_batch_with_index(
+                DBBatch::new(),
+                DBBatch::new(),
+                DBBatch::from([(vec![period as u8], Some(vec![1]))]),
+                Slot::new(period, 0),
+            );
+        }
+
+        // the index is not part of the state hash
+        assert_eq!(db.read().get_xof_db_hash(), state_hash);
+        // entries of the periods before 4 - 2 are pruned, with their journal entries
+        let index = dump_column(db.clone(), INDEX_CF);
+        let keys: Vec<Vec<u8>> = index
+            .keys()
+            .filter(|key| !key.starts_with(INDEX_JOURNAL_PREFIX.as_bytes()))
+            .cloned()
+            .collect();
+        assert_eq!(keys, vec![vec![2], vec![3], vec![4]]);
+        assert_eq!(index.len(), 6);
+
+        db.write().reset(Slot::new(0, 0));
+        assert!(dump_column(db, INDEX_CF).is_empty());
+    }
 }
@@ -0,0 +1,345 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Storage of the state proof tree in STATE_PROOF_CF (see `StateProof` in massa-db-exports).
+//!
+//! Only non-empty nodes are stored, under the key given by `state_proof_node_key`:
+//! an inner node stores its hash, and a leaf stores its key and the hash of its value,
+//! so that it can be moved down when another key reaches its subtree.
+//! Changes are applied top-down, in a single pass per write, so that each node along the paths
+//! of the changed keys is read and written at most once.
+
+use massa_db_exports::{
+    state_proof_leaf_hash, state_proof_node_hash, state_proof_path, state_proof_path_bit, DBBatch,
+    Key, MassaDBError, StateProof, StateProofLeaf, EMPTY_NODE_HASH, STATE_PROOF_TREE_DEPTH,
+};
+use massa_hash::{Hash, HASH_SIZE_BYTES};
+
+const INNER_NODE_TAG: u8 = 0;
+const LEAF_NODE_TAG: u8 = 1;
+const TREE_OUT_OF_SYNC_ERROR: &str = "the state proof tree is out of sync with the state";
+
+/// Node of the state proof tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum StateProofNode {
+    /// Empty subtree, not stored
+    Empty,
+    /// Subtree holding a single leaf
+    Leaf(StateProofLeaf),
+    /// Subtree holding several leaves, with its hash
+    Inner([u8; HASH_SIZE_BYTES]),
+}
+
+impl StateProofNode {
+    /// Hash of the subtree
+    pub(crate) fn hash(&self) -> [u8; HASH_SIZE_BYTES] {
+        match self {
+            StateProofNode::Empty => EMPTY_NODE_HASH,
+            StateProofNode::Leaf(leaf) => state_proof_leaf_hash(&leaf.key, &leaf.value_hash),
+            StateProofNode::Inner(hash) => *hash,
+        }
+    }
+
+    /// Stored representation of the node, None for an empty subtree
+    fn to_bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            StateProofNode::Empty => None,
+            StateProofNode::Leaf(leaf) => {
+                let mut bytes = Vec::with_capacity(1 + HASH_SIZE_BYTES + leaf.key.len());
+                bytes.push(LEAF_NODE_TAG);
+                bytes.extend_from_slice(leaf.value_hash.to_bytes());
+                bytes.extend_from_slice(&leaf.key);
+                Some(bytes)
+            }
+            StateProofNode::Inner(hash) => {
+                let mut bytes = Vec::with_capacity(1 + HASH_SIZE_BYTES);
+                bytes.push(INNER_NODE_TAG);
+                bytes.extend_from_slice(hash);
+                Some(bytes)
+            }
+        }
+    }
+
+    /// Parses a stored node, None being an empty subtree
+    fn from_bytes(bytes: Option<&[u8]>) -> Result<Self, MassaDBError> {
+        let invalid = || MassaDBError::StateProofError("invalid state proof tree node".to_string());
+        match bytes {
+            None => Ok(StateProofNode::Empty),
+            Some([INNER_NODE_TAG, hash @ ..]) => Ok(StateProofNode::Inner(
+                hash.try_into().map_err(|_| invalid())?,
+            )),
+            Some([LEAF_NODE_TAG, rest @ ..]) if rest.len() >= HASH_SIZE_BYTES => {
+                let (value_hash, key) = rest.split_at(HASH_SIZE_BYTES);
+                Ok(StateProofNode::Leaf(StateProofLeaf {
+                    key: key.to_vec(),
+                    value_hash: Hash::from_bytes(value_hash.try_into().map_err(|_| invalid())?),
+                }))
+            }
+            Some(_) => Err(invalid()),
+        }
+    }
+}
+
+/// Change of a STATE_CF key to apply to the state proof tree
+pub(crate) struct StateProofChange {
+    path: [u8; HASH_SIZE_BYTES],
+    key: Key,
+    /// hash of the new value, None if the key is deleted
+    value_hash: Option<Hash>,
+}
+
+impl StateProofChange {
+    /// Change of `key` to `value` (None for a deletion)
+    pub(crate) fn new(key: &[u8], value: Option<&[u8]>) -> Self {
+        StateProofChange {
+            path: state_proof_path(key),
+            key: key.to_vec(),
+            value_hash: value.map(Hash::compute_from),
+        }
+    }
+}
+
+/// State proof tree, reading its stored nodes with `read`
+pub(crate) struct StateProofTree<F>
+where
+    F: Fn(&[u8]) -> Result<Option<Vec<u8>>, MassaDBError>,
+{
+    read: F,
+}
+
+impl<F> StateProofTree<F>
+where
+    F: Fn(&[u8]) -> Result<Option<Vec<u8>>, MassaDBError>,
+{
+    pub(crate) fn new(read: F) -> Self {
+        StateProofTree { read }
+    }
+
+    /// Reads the node at `depth` on `path`
+    fn get_node(
+        &self,
+        depth: usize,
+        path: &[u8; HASH_SIZE_BYTES],
+    ) -> Result<StateProofNode, MassaDBError> {
+        let bytes = (self.read)(&state_proof_node_key(depth, path))?;
+        StateProofNode::from_bytes(bytes.as_deref())
+    }
+
+    /// Root of the tree
+    pub(crate) fn root(&self) -> Result<Hash, MassaDBError> {
+        Ok(Hash::from_bytes(
+            &self.get_node(0, &[0; HASH_SIZE_BYTES])?.hash(),
+        ))
+    }
+
+    /// Builds the proof of `key`, whose value in STATE_CF is `value`
+    pub(crate) fn prove(
+        &self,
+        key: &[u8],
+        value: Option<Vec<u8>>,
+    ) -> Result<StateProof, MassaDBError> {
+        let out_of_sync = || MassaDBError::StateProofError(TREE_OUT_OF_SYNC_ERROR.to_string());
+        let path = state_proof_path(key);
+        let mut siblings = Vec::new();
+        let mut depth = 0;
+        let (value_hash, other_leaf) = loop {
+            match self.get_node(depth, &path)? {
+                StateProofNode::Inner(_) if depth < STATE_PROOF_TREE_DEPTH => {
+                    let sibling = self
+                        .get_node(depth + 1, &flip_path_bit(&path, depth))?
+                        .hash();
+                    siblings.push((sibling != EMPTY_NODE_HASH).then(|| Hash::from_bytes(&sibling)));
+                    depth += 1;
+                }
+                StateProofNode::Inner(_) => return Err(out_of_sync()),
+                StateProofNode::Empty => break (None, None),
+                StateProofNode::Leaf(leaf) if leaf.key == key => {
+                    break (Some(leaf.value_hash), None)
+                }
+                StateProofNode::Leaf(leaf) => break (None, Some(leaf)),
+            }
+        };
+        if value.as_deref().map(Hash::compute_from) != value_hash {
+            return Err(out_of_sync());
+        }
+        Ok(StateProof {
+            key: key.to_vec(),
+            value,
+            other_leaf,
+            siblings,
+        })
+    }
+
+    /// Adds to `node_changes` the node changes applying `changes` to the tree
+    pub(crate) fn apply(
+        &self,
+        changes: Vec<StateProofChange>,
+        node_changes: &mut DBBatch,
+    ) -> Result<(), MassaDBError> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let root_path = [0; HASH_SIZE_BYTES];
+        let root = self.update(0, &root_path, changes, node_changes)?;
+        set_node(node_changes, 0, &root_path, &root);
+        Ok(())
+    }
+
+    /// Applies `changes` to the subtree at `depth` on `path`, and returns its new top node.
+    /// The nodes below the top one are updated in `node_changes`, while the top node is left to the caller,
+    /// because it moves up when it is the only leaf left under its parent.
+    fn update(
+        &self,
+        depth: usize,
+        path: &[u8; HASH_SIZE_BYTES],
+        changes: Vec<StateProofChange>,
+        node_changes: &mut DBBatch,
+    ) -> Result<StateProofNode, MassaDBError> {
+        let leaf = match self.get_node(depth, path)? {
+            StateProofNode::Inner(_) if depth < STATE_PROOF_TREE_DEPTH => {
+                let (right_changes, left_changes): (Vec<_>, Vec<_>) = changes
+                    .into_iter()
+                    .partition(|change| state_proof_path_bit(&change.path, depth));
+                let left_path = child_path(path, depth, false);
+                let right_path = child_path(path, depth, true);
+                let left_changed = !left_changes.is_empty();
+                let right_changed = !right_changes.is_empty();
+                let left = self.update_child(depth + 1, &left_path, left_changes, node_changes)?;
+                let right =
+                    self.update_child(depth + 1, &right_path, right_changes, node_changes)?;
+
+                return Ok(match (left, right) {
+                    // at most one leaf is left: it moves up
+                    (StateProofNode::Empty, node @ StateProofNode::Empty)
+                    | (StateProofNode::Empty, node @ StateProofNode::Leaf(_))
+                    | (node @ StateProofNode::Leaf(_), StateProofNode::Empty) => {
+                        set_node(node_changes, depth + 1, &left_path, &StateProofNode::Empty);
+                        set_node(node_changes, depth + 1, &right_path, &StateProofNode::Empty);
+                        node
+                    }
+                    (left, right) => {
+                        if left_changed {
+                            set_node(node_changes, depth + 1, &left_path, &left);
+                        }
+                        if right_changed {
+                            set_node(node_changes, depth + 1, &right_path, &right);
+                        }
+                        StateProofNode::Inner(state_proof_node_hash(&left.hash(), &right.hash()))
+                    }
+                });
+            }
+            StateProofNode::Inner(_) => {
+                return Err(MassaDBError::StateProofError(
+                    TREE_OUT_OF_SYNC_ERROR.to_string(),
+                ))
+            }
+            StateProofNode::Empty => None,
+            StateProofNode::Leaf(leaf) => Some(leaf),
+        };
+
+        // nothing is stored below an empty subtree or a leaf: build the subtree from its leaves
+        let mut leaves: Vec<([u8; HASH_SIZE_BYTES], StateProofLeaf)> = Vec::new();
+        if let Some(leaf) = leaf {
+            if !changes.iter().any(|change| change.key == leaf.key) {
+                leaves.push((state_proof_path(&leaf.key), leaf));
+            }
+        }
+        leaves.extend(changes.into_iter().filter_map(|change| {
+            change.value_hash.map(|value_hash| {
+                (
+                    change.path,
+                    StateProofLeaf {
+                        key: change.key,
+                        value_hash,
+                    },
+                )
+            })
+        }));
+        build(depth, path, leaves, node_changes)
+    }
+
+    /// Applies `changes` to a child subtree, or reads its top node if there is no change
+    fn update_child(
+        &self,
+        depth: usize,
+        path: &[u8; HASH_SIZE_BYTES],
+        changes: Vec<StateProofChange>,
+        node_changes: &mut DBBatch,
+    ) -> Result<StateProofNode, MassaDBError> {
+        if changes.is_empty() {
+            self.get_node(depth, path)
+        } else {
+            self.update(depth, path, changes, node_changes)
+        }
+    }
+}
+
+/// Builds the subtree at `depth` on `path` holding `leaves`, and returns its top node.
+/// The nodes below the top one are added to `node_changes`.
+fn build(
+    depth: usize,
+    path: &[u8; HASH_SIZE_BYTES],
+    mut leaves: Vec<([u8; HASH_SIZE_BYTES], StateProofLeaf)>,
+    node_changes: &mut DBBatch,
+) -> Result<StateProofNode, MassaDBError> {
+    if leaves.len() <= 1 {
+        return Ok(leaves.pop().map_or(StateProofNode::Empty, |(_path, leaf)| {
+            StateProofNode::Leaf(leaf)
+        }));
+    }
+    if depth == STATE_PROOF_TREE_DEPTH {
+        return Err(MassaDBError::StateProofError(
+            "two state keys have the same state proof path".to_string(),
+        ));
+    }
+    let (right_leaves, left_leaves): (Vec<_>, Vec<_>) = leaves
+        .into_iter()
+        .partition(|(leaf_path, _leaf)| state_proof_path_bit(leaf_path, depth));
+    let left_path = child_path(path, depth, false);
+    let right_path = child_path(path, depth, true);
+    let left = build(depth + 1, &left_path, left_leaves, node_changes)?;
+    let right = build(depth + 1, &right_path, right_leaves, node_changes)?;
+    set_node(node_changes, depth + 1, &left_path, &left);
+    set_node(node_changes, depth + 1, &right_path, &right);
+    Ok(StateProofNode::Inner(state_proof_node_hash(
+        &left.hash(),
+        &right.hash(),
+    )))
+}
+
+/// Adds to `node_changes` the write of `node` at `depth` on `path`
+fn set_node(
+    node_changes: &mut DBBatch,
+    depth: usize,
+    path: &[u8; HASH_SIZE_BYTES],
+    node: &StateProofNode,
+) {
+    node_changes.insert(state_proof_node_key(depth, path), node.to_bytes());
+}
+
+/// Key of a state proof tree node: the depth (big endian), followed by the first `depth` bits of the path
+fn state_proof_node_key(depth: usize, path: &[u8; HASH_SIZE_BYTES]) -> Key {
+    let mut node_key = Vec::with_capacity(2 + HASH_SIZE_BYTES);
+    node_key.extend_from_slice(&(depth as u16).to_be_bytes());
+    for (index, byte) in path.iter().enumerate() {
+        let kept_bits = depth.saturating_sub(index * 8).min(8);
+        let mask = (0xff_u16 << (8 - kept_bits)) as u8;
+        node_key.push(byte & mask);
+    }
+    node_key
+}
+
+/// Returns `path` with the bit at `depth` inverted, i.e. the path of the sibling at depth `depth + 1`
+fn flip_path_bit(path: &[u8; HASH_SIZE_BYTES], depth: usize) -> [u8; HASH_SIZE_BYTES] {
+    let mut flipped = *path;
+    flipped[depth / 8] ^= 1 << (7 - depth % 8);
+    flipped
+}
+
+/// Returns `path` with the bit at `depth` set to `bit`, i.e. the path of a child of the node at `depth`
+fn child_path(path: &[u8; HASH_SIZE_BYTES], depth: usize, bit: bool) -> [u8; HASH_SIZE_BYTES] {
+    if state_proof_path_bit(path, depth) == bit {
+        *path
+    } else {
+        flip_path_bit(path, depth)
+    }
+}
@@ -249,6 +249,8 @@ mod test {
             max_final_state_elements_size: 100,
             max_versioning_elements_size: 100,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
             thread_count: THREAD_COUNT,
         };
         let db = Arc::new(RwLock::new(
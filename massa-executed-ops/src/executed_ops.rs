@@ -302,6 +302,8 @@ mod test {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let db = Arc::new(RwLock::new(
             Box::new(MassaDB::new(db_config.clone())) as Box<(dyn MassaDBController + 'static)>
@@ -362,6 +364,8 @@ mod test {
             max_versioning_elements_size: 100,
             thread_count,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let db_c_config = MassaDBConfig {
             path: tempdir_c.path().to_path_buf(),
@@ -370,6 +374,8 @@ mod test {
             max_versioning_elements_size: 100,
             thread_count,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
 
         let db_a = Arc::new(RwLock::new(
@@ -186,9 +186,10 @@ pub fn to_execution_query_response(
 ) -> grpc_api::ExecutionQueryResponse {
     match value {
         Ok(item) => grpc_api::ExecutionQueryResponse {
-            response: Some(grpc_api::execution_query_response::Response::Result(
-                to_execution_query_result(item),
-            )),
+            response: Some(match to_execution_query_result(item) {
+                Ok(result) => grpc_api::execution_query_response::Response::Result(result),
+                Err(err) => grpc_api::execution_query_response::Response::Error(err),
+            }),
         },
         Err(err) => grpc_api::ExecutionQueryResponse {
             response: Some(grpc_api::execution_query_response::Response::Error(
@@ -199,9 +200,10 @@ pub fn to_execution_query_response(
 }
 
 // Convertss a `ExecutionQueryResponseItem` to a `grpc_api::ExecutionQueryResponseItem`
+// Fails for the items that have no gRPC representation yet
 fn to_execution_query_result(
     value: ExecutionQueryResponseItem,
-) -> grpc_api::ExecutionQueryResponseItem {
+) -> Result<grpc_api::ExecutionQueryResponseItem, grpc_model::Error> {
     let response_item = match value {
         ExecutionQueryResponseItem::Boolean(result) => {
             grpc_api::execution_query_response_item::ResponseItem::Boolean(result)
@@ -265,10 +267,48 @@ fn to_execution_query_result(
                 },
             )
         }
+        ExecutionQueryResponseItem::AsyncMessages(_) => {
+            return Err(not_available_error(
+                "asynchronous messages are served by the GetAsyncMessages gRPC method",
+            ))
+        }
+        ExecutionQueryResponseItem::DatastoreEntries(_) => {
+            return Err(not_available_error(
+                "datastore entry pages are served by the GetDatastoreEntriesPage gRPC method",
+            ))
+        }
+        ExecutionQueryResponseItem::AddressHistory(_) => {
+            return Err(not_available_error(
+                "address history is not available through gRPC",
+            ))
+        }
+        ExecutionQueryResponseItem::StateProof(_) => {
+            return Err(not_available_error(
+                "state proofs are not available through gRPC",
+            ))
+        }
+        ExecutionQueryResponseItem::AddressRewards(_) => {
+            return Err(not_available_error(
+                "reward history is not available through gRPC",
+            ))
+        }
+        ExecutionQueryResponseItem::AddressTransactions(_) => {
+            return Err(not_available_error(
+                "transaction history is not available through gRPC",
+            ))
+        }
     };
 
-    grpc_api::ExecutionQueryResponseItem {
+    Ok(grpc_api::ExecutionQueryResponseItem {
         response_item: Some(response_item),
+    })
+}
+
+// Error returned for the query results that have no gRPC representation
+fn not_available_error(message: &str) -> grpc_model::Error {
+    grpc_model::Error {
+        code: 501,
+        message: message.to_string(),
     }
 }
 
@@ -348,6 +388,10 @@ impl From<ExecutionQueryError> for grpc_model::Error {
                 code: 404,
                 message: error,
             },
+            ExecutionQueryError::Unavailable(error) => grpc_model::Error {
+                code: 503,
+                message: error,
+            },
         }
     }
 }
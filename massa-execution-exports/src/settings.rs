@@ -102,4 +102,8 @@ pub struct ExecutionConfig {
     pub max_execution_traces_slot_limit: usize,
     /// Where to dump blocks
     pub block_dump_folder_path: PathBuf,
+    /// record the rewards and slashing losses of every address at each final slot
+    pub reward_history_enabled: bool,
+    /// record the operations and coin transfers sent or received by every address at each final slot
+    pub transaction_history_enabled: bool,
 }
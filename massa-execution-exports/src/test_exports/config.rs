@@ -81,6 +81,8 @@ impl Default for ExecutionConfig {
             broadcast_slot_execution_traces_channel_capacity: 5000,
             max_execution_traces_slot_limit: 320,
             block_dump_folder_path,
+            reward_history_enabled: false,
+            transaction_history_enabled: false,
         }
     }
 }
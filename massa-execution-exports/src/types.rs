@@ -4,13 +4,18 @@
 
 use crate::error::ExecutionQueryError;
 use crate::event_store::EventStore;
+use massa_async_pool::{AsyncMessage, AsyncMessageFilter, AsyncMessageId};
+use massa_db_exports::StateProof;
 use massa_final_state::StateChanges;
 use massa_hash::Hash;
+use massa_ledger_exports::{AddressHistoryEntry, DatastoreRange, KeyType};
 use massa_models::block_id::BlockId;
 use massa_models::bytecode::Bytecode;
 use massa_models::datastore::Datastore;
 use massa_models::denunciation::DenunciationIndex;
-use massa_models::execution::EventFilter;
+use massa_models::execution::{
+    AddressRewardsEntry, AddressTransaction, AddressTransactionsEntry, EventFilter, RewardKind,
+};
 use massa_models::operation::OperationId;
 use massa_models::output_event::SCOutputEvent;
 use massa_models::prehash::PreHashSet;
@@ -19,7 +24,7 @@ use massa_models::{
 };
 use massa_pos_exports::ProductionStats;
 use massa_storage::Storage;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 
 #[cfg(feature = "execution-trace")]
@@ -80,6 +85,24 @@ pub enum ExecutionQueryRequestItem {
         /// Filter only entries whose key starts with a prefix
         prefix: Vec<u8>,
     },
+    /// gets a page of datastore entries (candidate) of an address, returns ExecutionQueryResponseItem::DatastoreEntries(entries) or an error if the address is not found
+    AddressDatastoreEntriesCandidate {
+        /// Address for which to query the datastore
+        addr: Address,
+        /// Range of keys to query, and iteration direction
+        range: DatastoreRange,
+        /// Maximum number of entries to return
+        limit: usize,
+    },
+    /// gets a page of datastore entries (final) of an address, returns ExecutionQueryResponseItem::DatastoreEntries(entries) or an error if the address is not found
+    AddressDatastoreEntriesFinal {
+        /// Address for which to query the datastore
+        addr: Address,
+        /// Range of keys to query, and iteration direction
+        range: DatastoreRange,
+        /// Maximum number of entries to return
+        limit: usize,
+    },
     /// gets a datastore value (candidate) for an address, returns ExecutionQueryResponseItem::DatastoreValue(keys) or an error if the address or key is not found
     AddressDatastoreValueCandidate {
         /// Address for which to query the datastore
@@ -123,6 +146,58 @@ pub enum ExecutionQueryRequestItem {
 
     /// get filtered events. Returns ExecutionQueryResponseItem::Events
     Events(EventFilter),
+
+    /// gets the pending asynchronous messages (candidate) matching a filter, returns ExecutionQueryResponseItem::AsyncMessages(messages)
+    AsyncMessagesCandidate(AsyncMessageFilter),
+    /// gets the pending asynchronous messages (final) matching a filter, returns ExecutionQueryResponseItem::AsyncMessages(messages)
+    AsyncMessagesFinal(AsyncMessageFilter),
+
+    /// gets the recorded final ledger changes of an address, in increasing slot order, returns ExecutionQueryResponseItem::AddressHistory(entries).
+    /// The list is empty if the address history index is disabled.
+    AddressHistoryFinal {
+        /// Address to query
+        addr: Address,
+        /// First slot to return (inclusive), or None to start from the oldest record
+        start: Option<Slot>,
+        /// Maximum number of entries to return
+        limit: usize,
+    },
+
+    /// gets the recorded final rewards of an address, in increasing slot order, returns ExecutionQueryResponseItem::AddressRewards(entries).
+    /// The list is empty if the reward history index is disabled.
+    AddressRewardsFinal {
+        /// Address to query
+        addr: Address,
+        /// First slot to return (inclusive)
+        start: Slot,
+        /// Last slot to return (inclusive)
+        end: Slot,
+        /// Maximum number of entries to return
+        limit: usize,
+    },
+
+    /// gets the recorded final operations and coin transfers sent or received by an address, in increasing slot order,
+    /// returns ExecutionQueryResponseItem::AddressTransactions(entries).
+    /// The list is empty if the transaction history index is disabled.
+    AddressTransactionsFinal {
+        /// Address to query
+        addr: Address,
+        /// First slot to return (inclusive)
+        start: Slot,
+        /// Last slot to return (inclusive)
+        end: Slot,
+        /// Maximum number of entries to return
+        limit: usize,
+    },
+
+    /// gets a proof of the value (or absence) of a final ledger sub-entry, returns ExecutionQueryResponseItem::StateProof(proof)
+    /// or an Unavailable error if state proofs are disabled on the node
+    LedgerEntryProofFinal {
+        /// Address of the ledger entry
+        addr: Address,
+        /// Sub-entry to prove
+        key_type: KeyType,
+    },
 }
 
 /// Execution state query response item
@@ -139,6 +214,8 @@ pub enum ExecutionQueryResponseItem {
     DatastoreValue(Vec<u8>),
     /// list of keys
     KeyList(BTreeSet<Vec<u8>>),
+    /// datastore key/value pairs, in the requested order
+    DatastoreEntries(Vec<(Vec<u8>, Vec<u8>)>),
     /// deferred credits value
     DeferredCredits(BTreeMap<Slot, Amount>),
     /// execution status value
@@ -147,6 +224,26 @@ pub enum ExecutionQueryResponseItem {
     CycleInfos(ExecutionQueryCycleInfos),
     /// Events
     Events(Vec<SCOutputEvent>),
+    /// asynchronous messages, sorted by priority
+    AsyncMessages(BTreeMap<AsyncMessageId, AsyncMessage>),
+    /// address history entries, sorted by slot
+    AddressHistory(Vec<AddressHistoryEntry>),
+    /// proof of a final state entry
+    StateProof(ExecutionQueryStateProof),
+    /// address reward entries, sorted by slot
+    AddressRewards(Vec<AddressRewardsEntry>),
+    /// address transaction entries, sorted by slot
+    AddressTransactions(Vec<AddressTransactionsEntry>),
+}
+
+/// Proof of a final state entry, against the state proof tree root at a given final slot
+pub struct ExecutionQueryStateProof {
+    /// final slot at which the proof was built
+    pub slot: Slot,
+    /// root of the state proof tree at that slot
+    pub root: Hash,
+    /// proof of the entry value (or absence)
+    pub proof: StateProof,
 }
 
 /// Execution status of an operation or denunciation
@@ -252,6 +349,22 @@ pub struct ExecutionOutput {
     pub cancel_async_message_execution: Vec<(Address, Result<Amount, String>)>,
     /// Auto sell roll execution (empty if execution-info feature is NOT enabled)
     pub auto_sell_execution: Vec<(Address, Amount)>,
+    /// rewards credited (and slashing losses) during the execution step
+    pub reward_credits: Vec<RewardCredit>,
+    /// operations and coin transfers executed during the execution step
+    pub transactions: Vec<AddressTransaction>,
+}
+
+/// Reward credited to an address during the execution of a slot.
+/// For `RewardKind::Slashing`, `amount` is the amount lost by the address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardCredit {
+    /// credited address
+    pub address: Address,
+    /// kind of reward
+    pub kind: RewardKind,
+    /// credited (or lost) amount
+    pub amount: Amount,
 }
 
 /// structure describing the output of a read only execution
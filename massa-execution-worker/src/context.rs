@@ -18,7 +18,7 @@ use massa_async_pool::{AsyncMessageId, AsyncMessageInfo};
 use massa_executed_ops::{ExecutedDenunciationsChanges, ExecutedOpsChanges};
 use massa_execution_exports::{
     EventStore, ExecutedBlockInfo, ExecutionConfig, ExecutionError, ExecutionOutput,
-    ExecutionStackElement,
+    ExecutionStackElement, RewardCredit,
 };
 use massa_final_state::{FinalStateController, StateChanges};
 use massa_hash::Hash;
@@ -27,6 +27,7 @@ use massa_models::address::ExecutionAddressCycleInfo;
 use massa_models::block_id::BlockIdSerializer;
 use massa_models::bytecode::Bytecode;
 use massa_models::denunciation::DenunciationIndex;
+use massa_models::execution::{AddressTransaction, RewardKind};
 use massa_models::timeslots::get_block_slot_timestamp;
 use massa_models::{
     address::Address,
@@ -158,6 +159,12 @@ pub struct ExecutionContext {
     /// generated events during this execution, with multiple indexes
     pub events: EventStore,
 
+    /// rewards credited (and slashing losses) during this execution
+    pub reward_credits: Vec<RewardCredit>,
+
+    /// operations and coin transfers executed during this execution
+    pub transactions: Vec<AddressTransaction>,
+
     /// Unsafe random state (can be predicted and manipulated)
     pub unsafe_rng: Xoshiro256PlusPlus,
 
@@ -235,6 +242,8 @@ impl ExecutionContext {
             stack: Default::default(),
             read_only: Default::default(),
             events: Default::default(),
+            reward_credits: Default::default(),
+            transactions: Default::default(),
             unsafe_rng: init_prng(&execution_trail_hash),
             creator_address: Default::default(),
             origin_operation_id: Default::default(),
@@ -842,6 +851,24 @@ impl ExecutionContext {
             .update_production_stats(creator, slot, block_id);
     }
 
+    /// Records a reward credited to (or, for slashing, taken from) an address during this execution.
+    /// Zero amounts are not recorded.
+    pub fn record_reward(&mut self, address: Address, kind: RewardKind, amount: Amount) {
+        if amount.is_zero() {
+            return;
+        }
+        self.reward_credits.push(RewardCredit {
+            address,
+            kind,
+            amount,
+        });
+    }
+
+    /// Records an operation or a coin transfer executed during this execution, for the transaction history index
+    pub fn record_transaction(&mut self, transaction: AddressTransaction) {
+        self.transactions.push(transaction);
+    }
+
     /// Execute the deferred credits of `slot`.
     ///
     /// # Arguments
@@ -866,6 +893,8 @@ impl ExecutionContext {
                         "could not credit {} deferred coins to {} at slot {}: {}",
                         amount, address, slot, e
                     );
+                } else {
+                    self.record_reward(address, RewardKind::DeferredCredits, amount);
                 }
 
                 #[cfg(feature = "execution-info")]
@@ -949,6 +978,8 @@ impl ExecutionContext {
             block_info,
             state_changes,
             events: std::mem::take(&mut self.events),
+            reward_credits: std::mem::take(&mut self.reward_credits),
+            transactions: std::mem::take(&mut self.transactions),
             #[cfg(feature = "execution-trace")]
             slot_trace: None,
             #[cfg(feature = "dump-block")]
@@ -211,6 +211,26 @@ impl ExecutionController for ExecutionControllerImpl {
                         None => Err(ExecutionQueryError::NotFound(format!("Account {}", addr))),
                     }
                 }
+                ExecutionQueryRequestItem::AddressDatastoreEntriesCandidate {
+                    addr,
+                    range,
+                    limit,
+                } => {
+                    let (_final_v, speculative_v) = execution_lock
+                        .get_final_and_candidate_datastore_entries(&addr, &range, limit);
+                    match speculative_v {
+                        Some(entries) => Ok(ExecutionQueryResponseItem::DatastoreEntries(entries)),
+                        None => Err(ExecutionQueryError::NotFound(format!("Account {}", addr))),
+                    }
+                }
+                ExecutionQueryRequestItem::AddressDatastoreEntriesFinal { addr, range, limit } => {
+                    let (final_v, _speculative_v) = execution_lock
+                        .get_final_and_candidate_datastore_entries(&addr, &range, limit);
+                    match final_v {
+                        Some(entries) => Ok(ExecutionQueryResponseItem::DatastoreEntries(entries)),
+                        None => Err(ExecutionQueryError::NotFound(format!("Account {}", addr))),
+                    }
+                }
                 ExecutionQueryRequestItem::AddressDatastoreValueCandidate { addr, key } => {
                     let (_final_v, speculative_v) =
                         execution_lock.get_final_and_active_data_entry(&addr, &key);
@@ -331,6 +351,42 @@ impl ExecutionController for ExecutionControllerImpl {
                         execution_lock.get_filtered_sc_output_event(filter),
                     ))
                 }
+                ExecutionQueryRequestItem::AsyncMessagesCandidate(filter) => {
+                    Ok(ExecutionQueryResponseItem::AsyncMessages(
+                        execution_lock.get_candidate_async_messages(&filter),
+                    ))
+                }
+                ExecutionQueryRequestItem::AsyncMessagesFinal(filter) => {
+                    Ok(ExecutionQueryResponseItem::AsyncMessages(
+                        execution_lock.get_final_async_messages(&filter),
+                    ))
+                }
+                ExecutionQueryRequestItem::AddressRewardsFinal {
+                    addr,
+                    start,
+                    end,
+                    limit,
+                } => Ok(ExecutionQueryResponseItem::AddressRewards(
+                    execution_lock.get_final_address_rewards(&addr, start, end, limit),
+                )),
+                ExecutionQueryRequestItem::AddressTransactionsFinal {
+                    addr,
+                    start,
+                    end,
+                    limit,
+                } => Ok(ExecutionQueryResponseItem::AddressTransactions(
+                    execution_lock.get_final_address_transactions(&addr, start, end, limit),
+                )),
+                ExecutionQueryRequestItem::AddressHistoryFinal { addr, start, limit } => {
+                    Ok(ExecutionQueryResponseItem::AddressHistory(
+                        execution_lock.get_final_address_history(&addr, start, limit),
+                    ))
+                }
+                ExecutionQueryRequestItem::LedgerEntryProofFinal { addr, key_type } => {
+                    execution_lock
+                        .get_final_ledger_entry_proof(&addr, key_type)
+                        .map(ExecutionQueryResponseItem::StateProof)
+                }
             };
             resp.responses.push(resp_item);
         }
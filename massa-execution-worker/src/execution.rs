@@ -11,25 +11,38 @@
 use crate::active_history::{ActiveHistory, HistorySearchResult};
 use crate::context::{ExecutionContext, ExecutionContextSnapshot};
 use crate::interface_impl::InterfaceImpl;
+use crate::reward_history::{apply_rewards_to_history_batch, get_reward_history};
 use crate::stats::ExecutionStatsCounter;
 #[cfg(feature = "dump-block")]
 use crate::storage_backend::StorageBackend;
-use massa_async_pool::AsyncMessage;
+#[cfg(feature = "execution-trace")]
+use crate::transaction_history::record_internal_transfers;
+use crate::transaction_history::{
+    apply_transactions_to_history_batch, get_transaction_history, operation_transaction,
+};
+use massa_async_pool::{AsyncMessage, AsyncMessageFilter, AsyncMessageId, AsyncPoolChanges};
+use massa_db_exports::{DBBatch, KEY_SER_ERROR};
 use massa_execution_exports::{
     EventStore, ExecutedBlockInfo, ExecutionBlockMetadata, ExecutionChannels, ExecutionConfig,
-    ExecutionError, ExecutionOutput, ExecutionQueryCycleInfos, ExecutionQueryStakerInfo,
-    ExecutionStackElement, ReadOnlyExecutionOutput, ReadOnlyExecutionRequest,
-    ReadOnlyExecutionTarget, SlotExecutionOutput,
+    ExecutionError, ExecutionOutput, ExecutionQueryCycleInfos, ExecutionQueryError,
+    ExecutionQueryStakerInfo, ExecutionQueryStateProof, ExecutionStackElement,
+    ReadOnlyExecutionOutput, ReadOnlyExecutionRequest, ReadOnlyExecutionTarget,
+    SlotExecutionOutput,
 };
 use massa_final_state::FinalStateController;
-use massa_ledger_exports::{SetOrDelete, SetUpdateOrDelete};
+use massa_ledger_exports::{
+    AddressHistoryEntry, Applicable, DatastoreDirection, DatastoreRange, Key, KeySerializer,
+    KeyType, SetOrDelete, SetUpdateOrDelete,
+};
 use massa_metrics::MassaMetrics;
 use massa_models::address::ExecutionAddressCycleInfo;
 use massa_models::bytecode::Bytecode;
 
 use massa_models::datastore::get_prefix_bounds;
 use massa_models::denunciation::{Denunciation, DenunciationIndex};
-use massa_models::execution::EventFilter;
+use massa_models::execution::{
+    AddressRewardsEntry, AddressTransactionsEntry, EventFilter, RewardKind,
+};
 use massa_models::output_event::SCOutputEvent;
 use massa_models::prehash::PreHashSet;
 use massa_models::stats::ExecutionStats;
@@ -44,6 +57,7 @@ use massa_module_cache::config::ModuleCacheConfig;
 use massa_module_cache::controller::ModuleCache;
 use massa_pos_exports::SelectorController;
 use massa_sc_runtime::{Interface, Response, VMError};
+use massa_serialization::Serializer;
 use massa_versioning::versioning::MipStore;
 use massa_wallet::Wallet;
 use parking_lot::{Mutex, RwLock};
@@ -278,10 +292,27 @@ impl ExecutionState {
             println!("{:#?}", serde_json::to_string_pretty(&exec_out));
             println!("<<<");
         }
+        // node-local index entries of the slot, written along with the state changes
+        let mut index_batch = DBBatch::new();
+        if self.config.reward_history_enabled {
+            apply_rewards_to_history_batch(
+                exec_out.slot,
+                &exec_out.reward_credits,
+                &mut index_batch,
+            );
+        }
+        if self.config.transaction_history_enabled {
+            apply_transactions_to_history_batch(
+                exec_out.slot,
+                &exec_out.transactions,
+                &mut index_batch,
+            );
+        }
+
         // apply state changes to the final ledger
         self.final_state
             .write()
-            .finalize(exec_out.slot, exec_out.state_changes);
+            .finalize(exec_out.slot, exec_out.state_changes, index_batch);
 
         // update the final ledger's slot
         self.final_cursor = exec_out.slot;
@@ -587,6 +618,15 @@ impl ExecutionState {
                 }
             }
 
+            // record the operation in the transaction history of its sender and recipient
+            if self.config.transaction_history_enabled {
+                context.record_transaction(operation_transaction(
+                    operation,
+                    self.config.roll_price,
+                    execution_result.is_ok(),
+                ));
+            }
+
             // check execution results
             match execution_result {
                 Ok(_value) => {
@@ -748,6 +788,7 @@ impl ExecutionState {
 
         match slashed.as_ref() {
             Ok(slashed_amount) => {
+                context.record_reward(addr_denounced, RewardKind::Slashing, *slashed_amount);
                 // Add slashed amount / 2 to block reward
                 let amount = slashed_amount.checked_div_u64(2).ok_or_else(|| {
                     ExecutionError::RuntimeError(format!(
@@ -1277,7 +1318,11 @@ impl ExecutionState {
                     cfg_if::cfg_if! {
                         if #[cfg(feature = "execution-trace")] {
                             // Safe to unwrap
-                            slot_trace.asc_call_stacks.push(_message_return.traces.unwrap().0);
+                            let traces = _message_return.traces.unwrap().0;
+                            if self.config.transaction_history_enabled {
+                                record_internal_transfers(&mut context_guard!(self), None, &traces);
+                            }
+                            slot_trace.asc_call_stacks.push(traces);
                         } else if #[cfg(feature = "execution-info")] {
                             slot_trace.asc_call_stacks.push(_message_return.traces.clone().unwrap().0);
                             exec_info.async_messages.push(Ok(_message_return));
@@ -1363,6 +1408,13 @@ impl ExecutionState {
                     Ok(_op_return) => {
                         #[cfg(feature = "execution-trace")]
                         {
+                            if self.config.transaction_history_enabled {
+                                record_internal_transfers(
+                                    &mut context_guard!(self),
+                                    Some(operation.id),
+                                    &_op_return.0,
+                                );
+                            }
                             slot_trace
                                 .operation_call_stacks
                                 .insert(operation.id, _op_return.0);
@@ -1489,6 +1541,11 @@ impl ExecutionState {
                 ) {
                     Ok(_) => {
                         remaining_credit = remaining_credit.saturating_sub(block_credit_part);
+                        context.record_reward(
+                            endorsement_creator,
+                            RewardKind::Endorsement,
+                            block_credit_part,
+                        );
 
                         #[cfg(feature = "execution-info")]
                         exec_info
@@ -1512,6 +1569,11 @@ impl ExecutionState {
                 ) {
                     Ok(_) => {
                         remaining_credit = remaining_credit.saturating_sub(block_credit_part);
+                        context.record_reward(
+                            endorsement_target_creator,
+                            RewardKind::EndorsedBlock,
+                            block_credit_part,
+                        );
                         #[cfg(feature = "execution-info")]
                         {
                             exec_info.endorsement_target_reward =
@@ -1536,6 +1598,11 @@ impl ExecutionState {
                     remaining_credit, block_creator_addr, err
                 )
             } else {
+                context.record_reward(
+                    block_creator_addr,
+                    RewardKind::BlockCreation,
+                    remaining_credit,
+                );
                 #[cfg(feature = "execution-info")]
                 {
                     exec_info.block_producer_reward = Some((block_creator_addr, remaining_credit));
@@ -2032,6 +2099,250 @@ impl ExecutionState {
         (final_keys, candidate_keys)
     }
 
+    /// Get a page of the final and candidate datastore entries of the given address
+    #[allow(clippy::type_complexity)]
+    pub fn get_final_and_candidate_datastore_entries(
+        &self,
+        addr: &Address,
+        range: &DatastoreRange,
+        limit: usize,
+    ) -> (
+        Option<Vec<(Vec<u8>, Vec<u8>)>>,
+        Option<Vec<(Vec<u8>, Vec<u8>)>>,
+    ) {
+        let final_state = self.final_state.read();
+        let final_entries = final_state
+            .get_ledger()
+            .get_datastore_entries(addr, range, limit);
+
+        // traverse the history from oldest to newest, to know whether the candidate datastore is
+        // fully defined by the history (`base`), or is the final one modified by `overlay`
+        let mut base: Option<BTreeMap<Vec<u8>, Vec<u8>>> = None;
+        let mut overlay: BTreeMap<Vec<u8>, Option<Vec<u8>>> = BTreeMap::new();
+        let mut exists: Option<bool> = None;
+        for output in &self.active_history.read().0 {
+            match output.state_changes.ledger_changes.get(addr) {
+                // address absent from the changes
+                None => (),
+
+                // address ledger entry being reset to an absolute new datastore
+                Some(SetUpdateOrDelete::Set(new_ledger_entry)) => {
+                    base = Some(
+                        new_ledger_entry
+                            .datastore
+                            .iter()
+                            .filter(|(k, _v)| range.contains(k))
+                            .map(|(k, v)| (k.clone(), v.clone()))
+                            .collect(),
+                    );
+                    overlay.clear();
+                    exists = Some(true);
+                }
+
+                // address ledger entry being updated
+                Some(SetUpdateOrDelete::Update(entry_updates)) => {
+                    for (ds_key, ds_update) in entry_updates.datastore.iter() {
+                        if !range.contains(ds_key) {
+                            continue;
+                        }
+                        let value = match ds_update {
+                            SetOrDelete::Set(value) => Some(value.clone()),
+                            SetOrDelete::Delete => None,
+                        };
+                        match (&mut base, value) {
+                            (Some(base), Some(value)) => {
+                                base.insert(ds_key.clone(), value);
+                            }
+                            (Some(base), None) => {
+                                base.remove(ds_key);
+                            }
+                            (None, value) => {
+                                overlay.insert(ds_key.clone(), value);
+                            }
+                        }
+                    }
+                    exists = Some(true);
+                }
+
+                // address ledger entry being deleted
+                Some(SetUpdateOrDelete::Delete) => {
+                    base = Some(BTreeMap::new());
+                    overlay.clear();
+                    exists = Some(false);
+                }
+            }
+        }
+
+        let candidate = match (exists, base) {
+            (Some(false), _) => None,
+            (_, Some(base)) => Some(base),
+            (_, None) => {
+                // at most `overlay.len()` final entries can be hidden by the history,
+                // so fetching that many more final entries is enough to fill the page
+                let final_window = final_state.get_ledger().get_datastore_entries(
+                    addr,
+                    range,
+                    limit.saturating_add(overlay.len()),
+                );
+                if final_window.is_none() && exists.is_none() {
+                    None
+                } else {
+                    let mut entries: BTreeMap<Vec<u8>, Vec<u8>> =
+                        final_window.unwrap_or_default().into_iter().collect();
+                    for (key, value) in overlay {
+                        match value {
+                            Some(value) => entries.insert(key, value),
+                            None => entries.remove(&key),
+                        };
+                    }
+                    Some(entries)
+                }
+            }
+        };
+        let candidate_entries = candidate.map(|entries| match range.direction {
+            DatastoreDirection::Forward => entries.into_iter().take(limit).collect(),
+            DatastoreDirection::Reverse => entries.into_iter().rev().take(limit).collect(),
+        });
+
+        (final_entries, candidate_entries)
+    }
+
+    /// Get the pending asynchronous messages matching a filter at the latest final executed slot
+    pub fn get_final_async_messages(
+        &self,
+        filter: &AsyncMessageFilter,
+    ) -> BTreeMap<AsyncMessageId, AsyncMessage> {
+        self.final_state
+            .read()
+            .get_async_pool()
+            .fetch_filtered_messages(filter)
+    }
+
+    /// Get the pending asynchronous messages matching a filter at the latest candidate executed slot
+    pub fn get_candidate_async_messages(
+        &self,
+        filter: &AsyncMessageFilter,
+    ) -> BTreeMap<AsyncMessageId, AsyncMessage> {
+        let final_state = self.final_state.read();
+        let final_pool = final_state.get_async_pool();
+        let final_messages = final_pool.fetch_filtered_messages(filter);
+
+        // consolidate the changes of the active history, oldest first
+        let mut history_changes = AsyncPoolChanges::default();
+        for output in &self.active_history.read().0 {
+            history_changes.apply(output.state_changes.async_pool_changes.clone());
+        }
+
+        // messages untouched by the active history are the same as the final ones
+        let mut candidate_messages: BTreeMap<AsyncMessageId, AsyncMessage> = final_messages
+            .into_iter()
+            .filter(|(id, _msg)| !history_changes.0.contains_key(id))
+            .collect();
+
+        // the other ones need to be rebuilt from the final state and the active history
+        for (id, change) in history_changes.0 {
+            let candidate_message = match change {
+                SetUpdateOrDelete::Set(msg) => Some(msg),
+                SetUpdateOrDelete::Update(update) => {
+                    final_pool.fetch_message(&id).map(|mut msg| {
+                        msg.apply(update);
+                        msg
+                    })
+                }
+                SetUpdateOrDelete::Delete => None,
+            };
+            if let Some(msg) = candidate_message.filter(|msg| filter.matches(msg)) {
+                candidate_messages.insert(id, msg);
+            }
+        }
+
+        candidate_messages
+    }
+
+    /// Get the recorded final ledger changes of an address, in increasing slot order.
+    /// Returns an empty list if the address history index is disabled.
+    pub fn get_final_address_history(
+        &self,
+        address: &Address,
+        start: Option<Slot>,
+        limit: usize,
+    ) -> Vec<AddressHistoryEntry> {
+        self.final_state
+            .read()
+            .get_ledger()
+            .get_address_history(address, start, limit)
+    }
+
+    /// Get a proof of the value (or absence) of a final ledger sub-entry.
+    /// The proof, the tree root and the final slot are read under the same database lock so that they are consistent.
+    pub fn get_final_ledger_entry_proof(
+        &self,
+        address: &Address,
+        key_type: KeyType,
+    ) -> Result<ExecutionQueryStateProof, ExecutionQueryError> {
+        let mut serialized_key = Vec::new();
+        KeySerializer::new(false)
+            .serialize(&Key::new(address, key_type), &mut serialized_key)
+            .expect(KEY_SER_ERROR);
+
+        let final_state = self.final_state.read();
+        let db = final_state.get_database().read();
+        let slot = db
+            .get_change_id()
+            .map_err(|err| ExecutionQueryError::Unavailable(err.to_string()))?;
+        let root = db
+            .get_state_proof_root()
+            .map_err(|err| ExecutionQueryError::Unavailable(err.to_string()))?;
+        let proof = db
+            .get_state_proof(&serialized_key)
+            .map_err(|err| ExecutionQueryError::Unavailable(err.to_string()))?;
+        Ok(ExecutionQueryStateProof { slot, root, proof })
+    }
+
+    /// Get the recorded final rewards of an address between two slots (inclusive), in increasing slot order.
+    /// Returns an empty list if the reward history index is disabled.
+    pub fn get_final_address_rewards(
+        &self,
+        address: &Address,
+        start: Slot,
+        end: Slot,
+        limit: usize,
+    ) -> Vec<AddressRewardsEntry> {
+        if !self.config.reward_history_enabled {
+            return Vec::new();
+        }
+        get_reward_history(
+            self.final_state.read().get_database(),
+            address,
+            start,
+            end,
+            limit,
+            self.config.thread_count,
+        )
+    }
+
+    /// Get the recorded final transactions of an address between two slots (inclusive), in increasing slot order.
+    /// Returns an empty list if the transaction history index is disabled.
+    pub fn get_final_address_transactions(
+        &self,
+        address: &Address,
+        start: Slot,
+        end: Slot,
+        limit: usize,
+    ) -> Vec<AddressTransactionsEntry> {
+        if !self.config.transaction_history_enabled {
+            return Vec::new();
+        }
+        get_transaction_history(
+            self.final_state.read().get_database(),
+            address,
+            start,
+            end,
+            limit,
+            self.config.thread_count,
+        )
+    }
+
     pub fn get_address_cycle_infos(&self, address: &Address) -> Vec<ExecutionAddressCycleInfo> {
         context_guard!(self).get_address_cycle_infos(address, self.config.periods_per_cycle)
     }
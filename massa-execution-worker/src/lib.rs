@@ -85,6 +85,7 @@ mod controller;
 mod execution;
 mod interface_impl;
 mod request_queue;
+mod reward_history;
 mod slot_sequencer;
 mod speculative_async_pool;
 mod speculative_executed_denunciations;
@@ -95,6 +96,7 @@ mod stats;
 /// Provide abstraction and implementations of a storage backend for the the
 /// dump-block feature
 pub mod storage_backend;
+mod transaction_history;
 mod worker;
 
 #[cfg(feature = "execution-trace")]
@@ -102,7 +104,6 @@ mod trace_history;
 
 mod execution_info;
 
-use massa_db_exports as _;
 pub use worker::start_execution_worker;
 
 #[cfg(any(
@@ -0,0 +1,98 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Optional node-local index of the rewards of each address at each final slot, stored in INDEX_CF.
+
+use massa_db_exports::{
+    DBBatch, MassaDirection, MassaIteratorMode, ShareableMassaDBController, INDEX_CF,
+    REWARD_HISTORY_DESER_ERROR, REWARD_HISTORY_PREFIX, REWARD_HISTORY_SER_ERROR,
+};
+use massa_execution_exports::RewardCredit;
+use massa_models::{
+    address::Address,
+    execution::{
+        AddressReward, AddressRewardsEntry, AddressRewardsEntryDeserializer,
+        AddressRewardsEntrySerializer,
+    },
+    prehash::PreHashMap,
+    slot::Slot,
+};
+use massa_serialization::{DeserializeError, Deserializer, Serializer};
+
+/// Adds the rewards credited at a final slot to the index batch of that slot
+///
+/// # Arguments
+/// * `slot`: final slot at which the rewards were credited
+/// * `credits`: rewards credited during the execution of the slot
+/// * `index_batch`: index batch written along with the final state changes of the slot
+pub(crate) fn apply_rewards_to_history_batch(
+    slot: Slot,
+    credits: &[RewardCredit],
+    index_batch: &mut DBBatch,
+) {
+    let mut rewards_by_address: PreHashMap<Address, Vec<AddressReward>> = PreHashMap::default();
+    for credit in credits {
+        rewards_by_address
+            .entry(credit.address)
+            .or_default()
+            .push(AddressReward {
+                kind: credit.kind,
+                amount: credit.amount,
+            });
+    }
+
+    let serializer = AddressRewardsEntrySerializer::new();
+    for (address, rewards) in rewards_by_address {
+        let mut value = Vec::new();
+        serializer
+            .serialize(&AddressRewardsEntry { slot, rewards }, &mut value)
+            .expect(REWARD_HISTORY_SER_ERROR);
+        index_batch.insert(reward_history_key(&address, &slot), Some(value));
+    }
+}
+
+/// Gets the recorded rewards of an address, in increasing slot order
+///
+/// # Arguments
+/// * `db`: final state database
+/// * `addr`: target address
+/// * `start`: first slot to return (inclusive)
+/// * `end`: last slot to return (inclusive)
+/// * `limit`: maximum number of entries to return
+/// * `thread_count`: number of threads, to deserialize the slots
+pub(crate) fn get_reward_history(
+    db: &ShareableMassaDBController,
+    addr: &Address,
+    start: Slot,
+    end: Slot,
+    limit: usize,
+    thread_count: u8,
+) -> Vec<AddressRewardsEntry> {
+    let db = db.read();
+    let deserializer = AddressRewardsEntryDeserializer::new(thread_count);
+
+    let start_key = reward_history_key(addr, &start);
+    let end_key = reward_history_key(addr, &end);
+    db.iterator_cf(
+        INDEX_CF,
+        MassaIteratorMode::From(&start_key, MassaDirection::Forward),
+    )
+    // keys between the start and end keys of the address are keys of the address
+    .take_while(|(key, _)| key <= &end_key)
+    .take(limit)
+    .map(|(_, value)| {
+        deserializer
+            .deserialize::<DeserializeError>(&value)
+            .expect(REWARD_HISTORY_DESER_ERROR)
+            .1
+    })
+    .collect()
+}
+
+/// Builds the INDEX_CF key of the rewards of an address at a given slot.
+/// Slots are serialized in a sortable way, so that iterating over the keys of an address yields increasing slots.
+fn reward_history_key(addr: &Address, slot: &Slot) -> Vec<u8> {
+    let mut key = REWARD_HISTORY_PREFIX.as_bytes().to_vec();
+    key.extend(addr.to_prefixed_bytes());
+    key.extend(slot.to_bytes_key());
+    key
+}
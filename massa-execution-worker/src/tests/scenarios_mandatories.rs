@@ -4,8 +4,8 @@ use massa_async_pool::{AsyncMessage, AsyncPool, AsyncPoolChanges, AsyncPoolConfi
 use massa_db_exports::{DBBatch, ShareableMassaDBController};
 use massa_executed_ops::{ExecutedDenunciations, ExecutedDenunciationsConfig};
 use massa_execution_exports::{
-    ExecutionConfig, ExecutionQueryRequest, ExecutionQueryRequestItem, ExecutionStackElement,
-    ReadOnlyExecutionRequest, ReadOnlyExecutionTarget,
+    ExecutionConfig, ExecutionQueryRequest, ExecutionQueryRequestItem, ExecutionQueryResponseItem,
+    ExecutionStackElement, ReadOnlyExecutionRequest, ReadOnlyExecutionTarget,
 };
 use massa_final_state::test_exports::get_initials;
 use massa_final_state::MockFinalStateController;
@@ -22,7 +22,7 @@ use massa_models::test_exports::gen_endorsements_for_denunciation;
 use massa_models::{address::Address, amount::Amount, slot::Slot};
 use massa_models::{
     denunciation::Denunciation,
-    execution::EventFilter,
+    execution::{AddressTransaction, EventFilter, RewardKind, TransactionKind},
     operation::{Operation, OperationSerializer, OperationType},
     secure_share::SecureShareContent,
 };
@@ -156,8 +156,12 @@ fn expect_finalize_deploy_and_call_blocks(
         .write()
         .expect_finalize()
         .times(1)
-        .with(predicate::eq(deploy_sc_slot), predicate::always())
-        .returning(move |_, changes| {
+        .with(
+            predicate::eq(deploy_sc_slot),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, changes, _| {
             let mut saved_bytecode = saved_bytecode_edit.write();
             if !changes.ledger_changes.get_bytecode_updates().is_empty() {
                 *saved_bytecode = Some(changes.ledger_changes.get_bytecode_updates()[0].clone());
@@ -169,8 +173,12 @@ fn expect_finalize_deploy_and_call_blocks(
             .write()
             .expect_finalize()
             .times(1)
-            .with(predicate::eq(call_sc_slot), predicate::always())
-            .returning(move |_, _| {
+            .with(
+                predicate::eq(call_sc_slot),
+                predicate::always(),
+                predicate::always(),
+            )
+            .returning(move |_, _, _| {
                 finalized_waitpoint_trigger_handle_2.trigger();
             });
     }
@@ -652,8 +660,12 @@ fn send_and_receive_async_message() {
         .write()
         .expect_finalize()
         .times(1)
-        .with(predicate::eq(Slot::new(1, 0)), predicate::always())
-        .returning(move |_, changes| {
+        .with(
+            predicate::eq(Slot::new(1, 0)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, changes, _| {
             {
                 let mut saved_bytecode = saved_bytecode_edit.write();
                 *saved_bytecode = Some(changes.ledger_changes.get_bytecode_updates()[0].clone());
@@ -677,8 +689,12 @@ fn send_and_receive_async_message() {
         .write()
         .expect_finalize()
         .times(1)
-        .with(predicate::eq(Slot::new(1, 1)), predicate::always())
-        .returning(move |_, changes| {
+        .with(
+            predicate::eq(Slot::new(1, 1)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, changes, _| {
             match changes.ledger_changes.0.get(&destination).unwrap() {
                 // sc has received the coins (0.0000001)
                 SetUpdateOrDelete::Update(change_sc_update) => {
@@ -810,8 +826,12 @@ fn cancel_async_message() {
         .write()
         .expect_finalize()
         .times(1)
-        .with(predicate::eq(Slot::new(1, 0)), predicate::always())
-        .returning(move |_, changes| {
+        .with(
+            predicate::eq(Slot::new(1, 0)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, changes, _| {
             {
                 let mut saved_bytecode = saved_bytecode_edit.write();
                 *saved_bytecode = Some(changes.ledger_changes.get_bytecode_updates()[0].clone());
@@ -836,8 +856,12 @@ fn cancel_async_message() {
         .write()
         .expect_finalize()
         .times(1)
-        .with(predicate::eq(Slot::new(1, 1)), predicate::always())
-        .returning(move |_, changes| {
+        .with(
+            predicate::eq(Slot::new(1, 1)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, changes, _| {
             match changes.ledger_changes.0.get(&sender_addr).unwrap() {
                 // at slot (1,1) msg was canceled so sender has received the coins (0.0000001)
                 // sender has received the coins (0.0000001)
@@ -1240,8 +1264,12 @@ fn send_and_receive_transaction() {
         .write()
         .expect_finalize()
         .times(1)
-        .with(predicate::eq(Slot::new(1, 0)), predicate::always())
-        .returning(move |_, changes| {
+        .with(
+            predicate::eq(Slot::new(1, 0)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, changes, _| {
             // 190 because 100 in the get_balance in the `final_state_boilerplate` and 90 from the transfer.
             assert_eq!(
                 changes
@@ -1319,8 +1347,12 @@ fn roll_buy() {
         .write()
         .expect_finalize()
         .times(1)
-        .with(predicate::eq(Slot::new(1, 0)), predicate::always())
-        .returning(move |_, changes| {
+        .with(
+            predicate::eq(Slot::new(1, 0)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, changes, _| {
             assert_eq!(changes.pos_changes.roll_changes.len(), 1);
             // 100 base + 1 bought
             assert_eq!(changes.pos_changes.roll_changes.get(&address), Some(&101));
@@ -1419,8 +1451,12 @@ fn roll_sell() {
         .write()
         .expect_finalize()
         .times(1)
-        .with(predicate::eq(Slot::new(3, 0)), predicate::always())
-        .returning(move |_, changes| {
+        .with(
+            predicate::eq(Slot::new(3, 0)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, changes, _| {
             let amount = changes
                 .ledger_changes
                 .get_balance_or_else(&address, || None)
@@ -1579,15 +1615,23 @@ fn auto_sell_on_missed_blocks() {
         .write()
         .expect_finalize()
         .times(1)
-        .with(predicate::eq(Slot::new(1, 0)), predicate::always())
-        .returning(move |_, _changes| {});
+        .with(
+            predicate::eq(Slot::new(1, 0)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, _changes, _| {});
 
     foreign_controllers
         .final_state
         .write()
         .expect_finalize()
-        .with(predicate::eq(Slot::new(1, 1)), predicate::always())
-        .returning(move |_, changes| {
+        .with(
+            predicate::eq(Slot::new(1, 1)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, changes, _| {
             println!("changes: {:?}", changes);
             let deferred_credits = changes
                 .pos_changes
@@ -1653,7 +1697,7 @@ fn roll_slash() {
         .final_state
         .write()
         .expect_finalize()
-        .returning(move |_, changes| {
+        .returning(move |_, changes, _| {
             let rolls = changes.pos_changes.roll_changes.get(&address).unwrap();
             // 97 sold and 3 slashed
             assert_eq!(rolls, &0);
@@ -1771,7 +1815,7 @@ fn roll_slash_2() {
         .final_state
         .write()
         .expect_finalize()
-        .returning(move |_, changes| {
+        .returning(move |_, changes, _| {
             let rolls = changes.pos_changes.roll_changes.get(&address).unwrap();
             // 100 sold
             assert_eq!(rolls, &0);
@@ -2072,8 +2116,12 @@ fn datastore_manipulations() {
         .write()
         .expect_finalize()
         .times(1)
-        .with(predicate::eq(Slot::new(1, 0)), predicate::always())
-        .returning(move |_, changes| {
+        .with(
+            predicate::eq(Slot::new(1, 0)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, changes, _| {
             let key_len = (key_a.len() + key_b.len()) as u64;
             let value_len = ([21, 0, 49].len() + [5, 12, 241].len()) as u64;
             let amount = changes
@@ -2461,8 +2509,12 @@ fn test_rewards() {
         .write()
         .expect_finalize()
         .times(1)
-        .with(predicate::eq(Slot::new(1, 0)), predicate::always())
-        .returning(move |_, changes| {
+        .with(
+            predicate::eq(Slot::new(1, 0)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, changes, _| {
             let block_credit_part = exec_cfg
                 .block_reward
                 .checked_div_u64(3 * (1 + (ENDORSEMENT_COUNT as u64)))
@@ -2496,8 +2548,12 @@ fn test_rewards() {
         .write()
         .expect_finalize()
         .times(1)
-        .with(predicate::eq(Slot::new(1, 1)), predicate::always())
-        .returning(move |_, changes| {
+        .with(
+            predicate::eq(Slot::new(1, 1)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, changes, _| {
             let block_credit_part_parent_in_thread = exec_cfg
                 .block_reward
                 .checked_div_u64(3 * (1 + (ENDORSEMENT_COUNT as u64)))
@@ -2588,6 +2644,260 @@ fn test_rewards() {
     finalized_waitpoint.wait();
 }
 
+#[test]
+fn test_reward_history() {
+    let exec_cfg = ExecutionConfig {
+        reward_history_enabled: true,
+        ..Default::default()
+    };
+    let mut foreign_controllers = ExecutionForeignControllers::new_with_mocks();
+    let finalized_waitpoint = WaitPoint::new();
+    let finalized_waitpoint_trigger_handle = finalized_waitpoint.get_trigger_handle();
+    let finalized_waitpoint_trigger_handle_2 = finalized_waitpoint.get_trigger_handle();
+    let endorsement_producer = KeyPair::generate(0).unwrap();
+    let endorsement_producer_address =
+        Address::from_public_key(&endorsement_producer.get_public_key());
+    let keypair = KeyPair::from_str(TEST_SK_1).unwrap();
+    let keypair_address = Address::from_public_key(&keypair.get_public_key());
+    let keypair2 = KeyPair::from_str(TEST_SK_2).unwrap();
+    let keypair2_address = Address::from_public_key(&keypair2.get_public_key());
+    selector_boilerplate(&mut foreign_controllers.selector_controller);
+    final_state_boilerplate(
+        &mut foreign_controllers.final_state,
+        foreign_controllers.db.clone(),
+        &foreign_controllers.selector_controller,
+        &mut foreign_controllers.ledger_controller,
+        None,
+        None,
+        None,
+    );
+    foreign_controllers
+        .final_state
+        .write()
+        .expect_get_database()
+        .return_const(foreign_controllers.db.clone());
+    let db = foreign_controllers.db.clone();
+    let db_2 = foreign_controllers.db.clone();
+    foreign_controllers
+        .final_state
+        .write()
+        .expect_finalize()
+        .times(1)
+        .with(
+            predicate::eq(Slot::new(1, 0)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |slot, _, index_batch| {
+            // the mocked final state only writes the index entries built by the execution
+            db.write()
+                .write_batch_with_index(DBBatch::new(), DBBatch::new(), index_batch, slot);
+            finalized_waitpoint_trigger_handle.trigger();
+        });
+    foreign_controllers
+        .final_state
+        .write()
+        .expect_finalize()
+        .times(1)
+        .with(
+            predicate::eq(Slot::new(1, 1)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |slot, _, index_batch| {
+            db_2.write()
+                .write_batch_with_index(DBBatch::new(), DBBatch::new(), index_batch, slot);
+            finalized_waitpoint_trigger_handle_2.trigger();
+        });
+    let mut universe = ExecutionTestUniverse::new(foreign_controllers, exec_cfg.clone());
+
+    // first block, fully endorsed by the endorsement producer
+    let block = ExecutionTestUniverse::create_block(
+        &keypair,
+        Slot::new(1, 0),
+        vec![],
+        vec![
+            ExecutionTestUniverse::create_endorsement(&endorsement_producer, Slot::new(1, 0));
+            ENDORSEMENT_COUNT as usize
+        ],
+        vec![],
+    );
+    universe.send_and_finalize(&keypair, block);
+    finalized_waitpoint.wait();
+
+    // second block, without endorsements.
+    // Once it is finalized, the rewards of the first one are recorded.
+    let block =
+        ExecutionTestUniverse::create_block(&keypair2, Slot::new(1, 1), vec![], vec![], vec![]);
+    universe.send_and_finalize(&keypair, block);
+    finalized_waitpoint.wait();
+
+    let block_credit_part = exec_cfg
+        .block_reward
+        .checked_div_u64(3 * (1 + (ENDORSEMENT_COUNT as u64)))
+        .expect("critical: block_credits checked_div factor is 0");
+    let get_rewards = |addr: Address| {
+        let response = universe
+            .module_controller
+            .query_state(ExecutionQueryRequest {
+                requests: vec![ExecutionQueryRequestItem::AddressRewardsFinal {
+                    addr,
+                    start: Slot::new(0, 0),
+                    end: Slot::new(1, 1),
+                    limit: 10,
+                }],
+            });
+        match response.responses.into_iter().next() {
+            Some(Ok(ExecutionQueryResponseItem::AddressRewards(entries))) => entries,
+            _ => panic!("unexpected response to the rewards query"),
+        }
+    };
+
+    // every endorsement of the first block is paid to the endorsement producer
+    let endorser_rewards = get_rewards(endorsement_producer_address);
+    assert_eq!(endorser_rewards.len(), 1);
+    assert_eq!(endorser_rewards[0].slot, Slot::new(1, 0));
+    assert_eq!(
+        endorser_rewards[0].rewards.len(),
+        ENDORSEMENT_COUNT as usize
+    );
+    assert!(
+        endorser_rewards[0]
+            .rewards
+            .iter()
+            .all(|reward| reward.kind == RewardKind::Endorsement
+                && reward.amount == block_credit_part)
+    );
+
+    // block creators are credited at the slot of their block
+    let creator_rewards = get_rewards(keypair_address);
+    assert_eq!(creator_rewards[0].slot, Slot::new(1, 0));
+    assert!(creator_rewards[0]
+        .rewards
+        .iter()
+        .any(|reward| reward.kind == RewardKind::BlockCreation));
+    let creator2_rewards = get_rewards(keypair2_address);
+    assert_eq!(creator2_rewards.len(), 1);
+    assert_eq!(creator2_rewards[0].slot, Slot::new(1, 1));
+    assert_eq!(
+        creator2_rewards[0].rewards[0].kind,
+        RewardKind::BlockCreation
+    );
+    assert_eq!(creator2_rewards[0].rewards[0].amount, exec_cfg.block_reward);
+}
+
+#[test]
+fn test_transaction_history() {
+    let exec_cfg = ExecutionConfig {
+        transaction_history_enabled: true,
+        ..Default::default()
+    };
+    let mut foreign_controllers = ExecutionForeignControllers::new_with_mocks();
+    let finalized_waitpoint = WaitPoint::new();
+    let finalized_waitpoint_trigger_handle = finalized_waitpoint.get_trigger_handle();
+    let keypair = KeyPair::from_str(TEST_SK_1).unwrap();
+    let sender_address = Address::from_public_key(&keypair.get_public_key());
+    let recipient_address =
+        Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+    selector_boilerplate(&mut foreign_controllers.selector_controller);
+    final_state_boilerplate(
+        &mut foreign_controllers.final_state,
+        foreign_controllers.db.clone(),
+        &foreign_controllers.selector_controller,
+        &mut foreign_controllers.ledger_controller,
+        None,
+        None,
+        None,
+    );
+    foreign_controllers
+        .final_state
+        .write()
+        .expect_get_database()
+        .return_const(foreign_controllers.db.clone());
+    let db = foreign_controllers.db.clone();
+    foreign_controllers
+        .final_state
+        .write()
+        .expect_finalize()
+        .times(1)
+        .with(
+            predicate::eq(Slot::new(1, 0)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |slot, _, index_batch| {
+            // the mocked final state only writes the index entries built by the execution
+            db.write()
+                .write_batch_with_index(DBBatch::new(), DBBatch::new(), index_batch, slot);
+            finalized_waitpoint_trigger_handle.trigger();
+        });
+    let mut universe = ExecutionTestUniverse::new(foreign_controllers, exec_cfg.clone());
+
+    // block containing a transaction from the block creator to the recipient
+    let operation = Operation::new_verifiable(
+        Operation {
+            fee: Amount::from_str("10").unwrap(),
+            expire_period: 10,
+            op: OperationType::Transaction {
+                recipient_address,
+                amount: Amount::from_str("90").unwrap(),
+            },
+        },
+        OperationSerializer::new(),
+        &keypair,
+        *CHAINID,
+    )
+    .unwrap();
+    universe.storage.store_operations(vec![operation.clone()]);
+    let block = ExecutionTestUniverse::create_block(
+        &keypair,
+        Slot::new(1, 0),
+        vec![operation.clone()],
+        vec![],
+        vec![],
+    );
+    universe.send_and_finalize(&keypair, block);
+    finalized_waitpoint.wait();
+
+    let get_transactions = |addr: Address| {
+        let response = universe
+            .module_controller
+            .query_state(ExecutionQueryRequest {
+                requests: vec![ExecutionQueryRequestItem::AddressTransactionsFinal {
+                    addr,
+                    start: Slot::new(0, 0),
+                    end: Slot::new(1, 1),
+                    limit: 10,
+                }],
+            });
+        match response.responses.into_iter().next() {
+            Some(Ok(ExecutionQueryResponseItem::AddressTransactions(entries))) => entries,
+            _ => panic!("unexpected response to the transactions query"),
+        }
+    };
+
+    // the transaction is recorded for both the sender and the recipient
+    let expected = AddressTransaction {
+        kind: TransactionKind::Transaction,
+        operation_id: Some(operation.id),
+        from: sender_address,
+        to: Some(recipient_address),
+        amount: Amount::from_str("90").unwrap(),
+        fee: Amount::from_str("10").unwrap(),
+        success: true,
+    };
+    for addr in [sender_address, recipient_address] {
+        let entries = get_transactions(addr);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].slot, Slot::new(1, 0));
+        assert_eq!(entries[0].transactions, vec![expected.clone()]);
+    }
+
+    // other addresses have no history
+    let other_address = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+    assert!(get_transactions(other_address).is_empty());
+}
+
 #[test]
 fn chain_id() {
     // setup the period duration
@@ -2888,8 +3198,12 @@ fn test_dump_block() {
         .write()
         .expect_finalize()
         .times(1)
-        .with(predicate::eq(Slot::new(1, 0)), predicate::always())
-        .returning(move |_, changes| {
+        .with(
+            predicate::eq(Slot::new(1, 0)),
+            predicate::always(),
+            predicate::always(),
+        )
+        .returning(move |_, changes, _| {
             // 190 because 100 in the get_balance in the `final_state_boilerplate` and 90 from the transfer.
             assert_eq!(
                 changes
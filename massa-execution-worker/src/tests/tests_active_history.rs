@@ -61,6 +61,8 @@ fn test_active_history_deferred_credits() {
         deferred_credits_execution: Default::default(),
         cancel_async_message_execution: Default::default(),
         auto_sell_execution: Default::default(),
+        reward_credits: Default::default(),
+        transactions: Default::default(),
     };
 
     let active_history = ActiveHistory(VecDeque::from([exec_output_1]));
@@ -65,6 +65,8 @@ impl ExecutionForeignControllers {
             max_versioning_elements_size: 100_000,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
 
         let db = Arc::new(RwLock::new(
@@ -0,0 +1,201 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Optional node-local index of the transactions of each address at each final slot, stored in INDEX_CF.
+
+#[cfg(feature = "execution-trace")]
+use crate::context::ExecutionContext;
+use massa_db_exports::{
+    DBBatch, MassaDirection, MassaIteratorMode, ShareableMassaDBController, INDEX_CF,
+    TRANSACTION_HISTORY_DESER_ERROR, TRANSACTION_HISTORY_PREFIX, TRANSACTION_HISTORY_SER_ERROR,
+};
+#[cfg(feature = "execution-trace")]
+use massa_execution_exports::AbiTrace;
+#[cfg(feature = "execution-trace")]
+use massa_models::operation::OperationId;
+use massa_models::{
+    address::Address,
+    amount::Amount,
+    execution::{
+        AddressTransaction, AddressTransactionsEntry, AddressTransactionsEntryDeserializer,
+        AddressTransactionsEntrySerializer, TransactionKind,
+    },
+    operation::{OperationType, SecureShareOperation},
+    prehash::PreHashMap,
+    slot::Slot,
+};
+use massa_serialization::{DeserializeError, Deserializer, Serializer};
+#[cfg(feature = "execution-trace")]
+use std::str::FromStr;
+
+/// Names of the ABIs transferring coins, in the execution traces
+#[cfg(feature = "execution-trace")]
+const TRANSFER_ABI_NAMES: [&str; 3] = [
+    "assembly_script_transfer_coins",
+    "assembly_script_transfer_coins_for",
+    "abi_transfer_coins",
+];
+
+/// Builds the transaction history record of an executed operation
+///
+/// # Arguments
+/// * `operation`: executed operation
+/// * `roll_price`: price of a roll, to compute the amount of roll operations
+/// * `success`: whether the execution of the operation succeeded
+pub(crate) fn operation_transaction(
+    operation: &SecureShareOperation,
+    roll_price: Amount,
+    success: bool,
+) -> AddressTransaction {
+    let (kind, to, amount) = match &operation.content.op {
+        OperationType::Transaction {
+            recipient_address,
+            amount,
+        } => (
+            TransactionKind::Transaction,
+            Some(*recipient_address),
+            *amount,
+        ),
+        OperationType::RollBuy { roll_count } => (
+            TransactionKind::RollBuy,
+            None,
+            roll_price
+                .checked_mul_u64(*roll_count)
+                .unwrap_or_else(Amount::zero),
+        ),
+        OperationType::RollSell { roll_count } => (
+            TransactionKind::RollSell,
+            None,
+            roll_price
+                .checked_mul_u64(*roll_count)
+                .unwrap_or_else(Amount::zero),
+        ),
+        OperationType::ExecuteSC { .. } => (TransactionKind::ExecuteSc, None, Amount::zero()),
+        OperationType::CallSC {
+            target_addr, coins, ..
+        } => (TransactionKind::CallSc, Some(*target_addr), *coins),
+    };
+    AddressTransaction {
+        kind,
+        operation_id: Some(operation.id),
+        from: operation.content_creator_address,
+        to,
+        amount,
+        fee: operation.content.fee,
+        success,
+    }
+}
+
+/// Records the coin transfers found in the execution traces of an operation or of an asynchronous message
+///
+/// # Arguments
+/// * `context`: execution context of the slot
+/// * `operation_id`: executed operation, None for an asynchronous message
+/// * `traces`: ABI call traces of the execution
+#[cfg(feature = "execution-trace")]
+pub(crate) fn record_internal_transfers(
+    context: &mut ExecutionContext,
+    operation_id: Option<OperationId>,
+    traces: &[AbiTrace],
+) {
+    let transfer_abi_names = TRANSFER_ABI_NAMES.map(String::from);
+    for trace in traces {
+        for transfer in trace.flatten_filter(&transfer_abi_names) {
+            let (from, to, amount) = transfer.parse_transfer();
+            let (Ok(from), Ok(to)) = (Address::from_str(&from), Address::from_str(&to)) else {
+                continue;
+            };
+            context.record_transaction(AddressTransaction {
+                kind: TransactionKind::InternalTransfer,
+                operation_id,
+                from,
+                to: Some(to),
+                amount: Amount::from_raw(amount),
+                fee: Amount::zero(),
+                success: true,
+            });
+        }
+    }
+}
+
+/// Adds the transactions executed at a final slot to the index batch of that slot, for their sender and their recipient
+///
+/// # Arguments
+/// * `slot`: final slot at which the transactions were executed
+/// * `transactions`: transactions executed during the slot
+/// * `index_batch`: index batch written along with the final state changes of the slot
+pub(crate) fn apply_transactions_to_history_batch(
+    slot: Slot,
+    transactions: &[AddressTransaction],
+    index_batch: &mut DBBatch,
+) {
+    let mut transactions_by_address: PreHashMap<Address, Vec<AddressTransaction>> =
+        PreHashMap::default();
+    for transaction in transactions {
+        transactions_by_address
+            .entry(transaction.from)
+            .or_default()
+            .push(transaction.clone());
+        if let Some(to) = transaction.to.filter(|to| to != &transaction.from) {
+            transactions_by_address
+                .entry(to)
+                .or_default()
+                .push(transaction.clone());
+        }
+    }
+
+    let serializer = AddressTransactionsEntrySerializer::new();
+    for (address, transactions) in transactions_by_address {
+        let mut value = Vec::new();
+        serializer
+            .serialize(&AddressTransactionsEntry { slot, transactions }, &mut value)
+            .expect(TRANSACTION_HISTORY_SER_ERROR);
+        index_batch.insert(transaction_history_key(&address, &slot), Some(value));
+    }
+}
+
+/// Gets the recorded transactions of an address, in increasing slot order
+///
+/// # Arguments
+/// * `db`: final state database
+/// * `addr`: target address
+/// * `start`: first slot to return (inclusive)
+/// * `end`: last slot to return (inclusive)
+/// * `limit`: maximum number of entries to return
+/// * `thread_count`: number of threads, to deserialize the slots
+pub(crate) fn get_transaction_history(
+    db: &ShareableMassaDBController,
+    addr: &Address,
+    start: Slot,
+    end: Slot,
+    limit: usize,
+    thread_count: u8,
+) -> Vec<AddressTransactionsEntry> {
+    let db = db.read();
+    let deserializer = AddressTransactionsEntryDeserializer::new(thread_count);
+
+    let start_key = transaction_history_key(addr, &start);
+    let end_key = transaction_history_key(addr, &end);
+    db.iterator_cf(
+        INDEX_CF,
+        MassaIteratorMode::From(&start_key, MassaDirection::Forward),
+    )
+    // keys between the start and end keys of the address are keys of the address
+    .take_while(|(key, _)| key <= &end_key)
+    .take(limit)
+    .map(|(_, value)| {
+        deserializer
+            .deserialize::<DeserializeError>(&value)
+            .expect(TRANSACTION_HISTORY_DESER_ERROR)
+            .1
+    })
+    .collect()
+}
+
+/// Builds the INDEX_CF key of the transactions of an address at a given slot.
+/// Slots are serialized in a sortable way, so that iterating over the keys of an address yields increasing slots.
+fn transaction_history_key(addr: &Address, slot: &Slot) -> Vec<u8> {
+    let mut key = TRANSACTION_HISTORY_PREFIX.as_bytes().to_vec();
+    key.extend(addr.to_prefixed_bytes());
+    key.extend(slot.to_bytes_key());
+    key
+}
@@ -3,6 +3,8 @@
 //! This file defines the factory settings
 
 use massa_time::MassaTime;
+use num::rational::Ratio;
+use std::path::PathBuf;
 
 /// Structure defining the settings of the factory
 #[derive(Debug, Clone)]
@@ -31,4 +33,10 @@ pub struct FactoryConfig {
     pub stop_production_when_zero_connections: bool,
     /// chain id
     pub chain_id: u64,
+    /// path of the record of every block and endorsement signed by the factories
+    pub slashing_protection_path: PathBuf,
+    /// maximal number of missed slots of the local stakers kept in memory
+    pub missed_slots_history_length: usize,
+    /// maximal ratio of missed blocks over a cycle before the rolls of an address are sold
+    pub max_miss_ratio: Ratio<u64>,
 }
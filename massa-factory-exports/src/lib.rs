@@ -9,11 +9,15 @@
 mod config;
 mod controller_traits;
 mod error;
+mod missed_slots;
+mod slashing_protection;
 mod types;
 
 pub use config::FactoryConfig;
 pub use controller_traits::FactoryManager;
 pub use error::*;
+pub use missed_slots::MissedSlots;
+pub use slashing_protection::*;
 pub use types::*;
 
 /// Tests utils
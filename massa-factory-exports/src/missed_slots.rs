@@ -0,0 +1,136 @@
+//! Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Record of the blocks and endorsements missed by the locally managed staking addresses
+
+use massa_models::{
+    address::Address,
+    prehash::PreHashMap,
+    slot::Slot,
+    stats::{MissedItem, MissedSlot, StakerMissStats},
+};
+use parking_lot::RwLock;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+};
+
+/// Number of cycles for which the statistics of the local stakers are kept
+const MISS_STATS_CYCLE_COUNT: usize = 5;
+
+/// Bounded record of the slots missed by the locally managed staking addresses.
+/// It is written by the factories and read by the API: clones share the same record.
+#[derive(Clone)]
+pub struct MissedSlots {
+    /// maximal number of missed slots kept in the history
+    history_length: usize,
+    /// cycle duration in periods
+    periods_per_cycle: u64,
+    /// shared content
+    inner: Arc<RwLock<MissedSlotsInner>>,
+}
+
+#[derive(Default)]
+struct MissedSlotsInner {
+    /// missed slots, from oldest to latest
+    history: VecDeque<MissedSlot>,
+    /// statistics of the latest cycles, by cycle and address
+    stats: BTreeMap<u64, PreHashMap<Address, StakerMissStats>>,
+}
+
+impl MissedSlotsInner {
+    /// Gets the statistics of an address at a given cycle, dropping the statistics of old cycles
+    fn stats_mut(&mut self, address: Address, cycle: u64) -> &mut StakerMissStats {
+        while self.stats.len() >= MISS_STATS_CYCLE_COUNT
+            && !self.stats.contains_key(&cycle)
+            && self.stats.keys().next().map_or(false, |c| *c < cycle)
+        {
+            self.stats.pop_first();
+        }
+        self.stats
+            .entry(cycle)
+            .or_default()
+            .entry(address)
+            .or_insert(StakerMissStats {
+                address,
+                cycle,
+                selected_block_count: 0,
+                missed_block_count: 0,
+                missed_endorsement_count: 0,
+            })
+    }
+}
+
+impl MissedSlots {
+    /// Creates an empty record
+    ///
+    /// # Arguments
+    /// * `history_length`: maximal number of missed slots kept in the history
+    /// * `periods_per_cycle`: cycle duration in periods
+    pub fn new(history_length: usize, periods_per_cycle: u64) -> Self {
+        MissedSlots {
+            history_length,
+            periods_per_cycle,
+            inner: Arc::new(RwLock::new(MissedSlotsInner::default())),
+        }
+    }
+
+    /// Records that a local address was selected to produce the block of a slot
+    pub fn record_selected_block(&self, address: Address, slot: Slot) {
+        let cycle = slot.get_cycle(self.periods_per_cycle);
+        self.inner
+            .write()
+            .stats_mut(address, cycle)
+            .selected_block_count += 1;
+    }
+
+    /// Records a missed slot
+    ///
+    /// # Return value
+    /// The updated statistics of the address over the cycle of the missed slot
+    pub fn record(&self, missed_slot: MissedSlot) -> StakerMissStats {
+        let cycle = missed_slot.slot.get_cycle(self.periods_per_cycle);
+        let mut inner = self.inner.write();
+        let stats = inner.stats_mut(missed_slot.address, cycle);
+        match missed_slot.item {
+            MissedItem::Block => stats.missed_block_count += 1,
+            MissedItem::Endorsement { .. } => stats.missed_endorsement_count += 1,
+        }
+        let stats = stats.clone();
+        if inner.history.len() >= self.history_length {
+            inner.history.pop_front();
+        }
+        if self.history_length > 0 {
+            inner.history.push_back(missed_slot);
+        }
+        stats
+    }
+
+    /// Gets the recorded missed slots, from oldest to latest
+    ///
+    /// # Arguments
+    /// * `addresses`: addresses to get the missed slots of, all of them if empty
+    pub fn get_missed_slots(&self, addresses: &[Address]) -> Vec<MissedSlot> {
+        self.inner
+            .read()
+            .history
+            .iter()
+            .filter(|missed| addresses.is_empty() || addresses.contains(&missed.address))
+            .cloned()
+            .collect()
+    }
+
+    /// Gets the statistics of the latest cycles, by increasing cycle
+    ///
+    /// # Arguments
+    /// * `addresses`: addresses to get the statistics of, all of them if empty
+    pub fn get_stats(&self, addresses: &[Address]) -> Vec<StakerMissStats> {
+        self.inner
+            .read()
+            .stats
+            .values()
+            .flat_map(|cycle_stats| cycle_stats.values())
+            .filter(|stats| addresses.is_empty() || addresses.contains(&stats.address))
+            .cloned()
+            .collect()
+    }
+}
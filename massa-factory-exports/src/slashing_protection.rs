@@ -0,0 +1,48 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! This file defines the records of the slashing protection database,
+//! and the format used to move them between nodes.
+
+use massa_models::{address::Address, slot::Slot};
+use serde::{Deserialize, Serialize};
+
+/// Current version of the slashing protection export format
+pub const SLASHING_PROTECTION_EXPORT_VERSION: u32 = 1;
+
+/// Kind of item signed by a factory.
+/// Signing two different items of the same kind for the same address and slot is a double-production.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[serde(rename_all = "snake_case")]
+pub enum SignedItemKind {
+    /// block header of the slot
+    Block,
+    /// endorsement at a given index of the slot
+    Endorsement {
+        /// endorsement index
+        index: u32,
+    },
+}
+
+/// An item signed by a factory
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct SlashingProtectionRecord {
+    /// address of the producer
+    pub address: Address,
+    /// slot of the item
+    pub slot: Slot,
+    /// kind of item
+    pub kind: SignedItemKind,
+    /// id of the signed block or endorsement
+    pub id: String,
+}
+
+/// Slashing protection export, used to move staking keys between nodes safely
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct SlashingProtectionExport {
+    /// version of the export format
+    pub version: u32,
+    /// chain id of the network the records were signed for
+    pub chain_id: u64,
+    /// signed items
+    pub records: Vec<SlashingProtectionRecord>,
+}
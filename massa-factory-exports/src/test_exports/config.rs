@@ -2,6 +2,7 @@
 
 use crate::FactoryConfig;
 use massa_time::MassaTime;
+use std::path::PathBuf;
 
 impl Default for FactoryConfig {
     fn default() -> Self {
@@ -19,6 +20,9 @@ impl Default for FactoryConfig {
             denunciation_expire_periods: DENUNCIATION_EXPIRE_PERIODS,
             stop_production_when_zero_connections: false,
             chain_id: *CHAINID,
+            slashing_protection_path: PathBuf::from("slashing_protection.jsonl"),
+            missed_slots_history_length: 1000,
+            max_miss_ratio: *POS_MISS_RATE_DEACTIVATION_THRESHOLD,
         }
     }
 }
@@ -1,31 +1,50 @@
 //! Copyright (c) 2022 MASSA LABS <info@massa.net>
 
+use crate::{missed_slots::MissRecorder, slashing_protection::SlashingProtection};
 use massa_channel::receiver::MassaReceiver;
-use massa_factory_exports::{FactoryChannels, FactoryConfig};
+use massa_factory_exports::{
+    FactoryChannels, FactoryConfig, SignedItemKind, SlashingProtectionRecord,
+};
 use massa_models::{
-    block::{Block, BlockSerializer},
+    address::Address,
+    block::{Block, BlockGraphStatus, BlockSerializer},
     block_header::{BlockHeader, BlockHeaderSerializer, SecuredHeader},
     block_id::BlockId,
     endorsement::SecureShareEndorsement,
+    error::ModelsError,
     operation::{compute_operations_hash, OperationIdSerializer},
     secure_share::SecureShareContent,
     slot::Slot,
+    stats::{MissReason, MissedItem},
     timeslots::{get_block_slot_timestamp, get_closest_slot_to_timestamp},
 };
 use massa_time::MassaTime;
-use massa_versioning::versioning::MipStore;
-use massa_wallet::Wallet;
-use parking_lot::RwLock;
-use std::{sync::Arc, thread, time::Instant};
-use tracing::{info, warn};
+use massa_versioning::{
+    block_header_factory::{BlockHeaderArgs, BlockHeaderFactory},
+    mip_votes::MipVotes,
+    versioning::MipStore,
+    versioning_factory::VersioningFactory,
+};
+use massa_wallet::Signer;
+use parking_lot::Mutex;
+use std::{collections::VecDeque, sync::Arc, thread, time::Instant};
+use tracing::{debug, info, warn};
 
 /// Structure gathering all elements needed by the factory thread
 pub(crate) struct BlockFactoryWorker {
     cfg: FactoryConfig,
-    wallet: Arc<RwLock<Wallet>>,
+    signer: Box<dyn Signer>,
     channels: FactoryChannels,
     factory_receiver: MassaReceiver<()>,
     mip_store: MipStore,
+    mip_votes: MipVotes,
+    block_header_factory: BlockHeaderFactory,
+    slashing_protection: Arc<Mutex<SlashingProtection>>,
+    miss_recorder: MissRecorder,
+    /// produced blocks whose inclusion in the blockclique is not settled yet
+    produced_blocks: VecDeque<(BlockId, Slot, Address)>,
+    /// processed slots whose draws were not available, checked once the selector answers again
+    slots_without_draws: VecDeque<Slot>,
     op_id_serializer: OperationIdSerializer,
 }
 
@@ -34,20 +53,35 @@ impl BlockFactoryWorker {
     /// needed by the factory worker thread.
     pub(crate) fn spawn(
         cfg: FactoryConfig,
-        wallet: Arc<RwLock<Wallet>>,
+        signer: Box<dyn Signer>,
         channels: FactoryChannels,
         factory_receiver: MassaReceiver<()>,
         mip_store: MipStore,
+        mip_votes: MipVotes,
+        slashing_protection: Arc<Mutex<SlashingProtection>>,
+        miss_recorder: MissRecorder,
     ) -> thread::JoinHandle<()> {
         thread::Builder::new()
             .name("block-factory".into())
             .spawn(|| {
+                let block_header_factory = BlockHeaderFactory {
+                    mip_store: mip_store.clone(),
+                    thread_count: cfg.thread_count,
+                    t0: cfg.t0,
+                    genesis_timestamp: cfg.genesis_timestamp,
+                };
                 let mut this = Self {
                     cfg,
-                    wallet,
+                    signer,
                     channels,
                     factory_receiver,
                     mip_store,
+                    mip_votes,
+                    block_header_factory,
+                    slashing_protection,
+                    miss_recorder,
+                    produced_blocks: VecDeque::new(),
+                    slots_without_draws: VecDeque::new(),
                     op_id_serializer: OperationIdSerializer::new(),
                 };
                 this.run();
@@ -125,26 +159,59 @@ impl BlockFactoryWorker {
         // get block producer address for that slot
         let block_producer_addr = match self.channels.selector.get_producer(slot) {
             Ok(addr) => addr,
+            Err(err) => {
+                // only warn when the draws stop being available, e.g. not at every slot of a bootstrap
+                if self.slots_without_draws.is_empty() {
+                    warn!(
+                        "block factory could not get selector draws for slot {}: {}",
+                        slot, err
+                    );
+                } else {
+                    debug!(
+                        "block factory could not get selector draws for slot {}: {}",
+                        slot, err
+                    );
+                }
+                if self.slots_without_draws.len() >= self.cfg.missed_slots_history_length {
+                    self.slots_without_draws.pop_front();
+                }
+                self.slots_without_draws.push_back(slot);
+                return;
+            }
+        };
+        self.record_no_draws();
+
+        // check if the block producer address is handled by the signer
+        let block_producer_public_key = match self.signer.get_public_key(&block_producer_addr) {
+            // the selected block producer is managed locally => continue to attempt block production
+            Ok(Some(public_key)) => public_key,
+            // the selected block producer is not managed locally => quit
+            Ok(None) => return,
             Err(err) => {
                 warn!(
-                    "block factory could not get selector draws for slot {}: {}",
-                    slot, err
+                    "block factory could not get the public key of {} for slot {}: {}",
+                    block_producer_addr, slot, err
                 );
                 return;
             }
         };
 
-        // check if the block producer address is handled by the wallet
-        let block_producer_keypair_ref = self.wallet.read();
-        let block_producer_keypair = if let Some(kp) =
-            block_producer_keypair_ref.find_associated_keypair(&block_producer_addr)
+        self.miss_recorder
+            .record_selected_block(block_producer_addr, slot);
+
+        // check that we did not already sign a block for that slot, even before a restart
+        if let Some(record) =
+            self.slashing_protection
+                .lock()
+                .get(&block_producer_addr, &slot, &SignedItemKind::Block)
         {
-            // the selected block producer is managed locally => continue to attempt block production
-            kp
-        } else {
-            // the selected block producer is not managed locally => quit
+            warn!(
+                "block factory will not produce block for slot {} with address {}: block {} was already signed",
+                slot, block_producer_addr, record.id
+            );
             return;
-        };
+        }
+
         let mut block_storage = self.channels.storage.clone_without_refs();
         {
             let block_lock = block_storage.read_blocks();
@@ -165,6 +232,12 @@ impl BlockFactoryWorker {
             if let Ok(stats) = self.channels.protocol.get_stats() {
                 if stats.1.is_empty() {
                     warn!("block factory could not produce block for slot {} because there are no connections", slot);
+                    self.miss_recorder.record(
+                        block_producer_addr,
+                        slot,
+                        MissedItem::Block,
+                        MissReason::NotConnected,
+                    );
                     return;
                 }
             }
@@ -210,36 +283,93 @@ impl BlockFactoryWorker {
 
         // create header
         let current_version = self.mip_store.get_network_version_current();
-        let announced_version = self.mip_store.get_network_version_to_announce();
-        let header: SecuredHeader = BlockHeader::new_verifiable::<BlockHeaderSerializer, BlockId>(
-            BlockHeader {
-                current_version,
-                announced_version,
-                slot,
-                parents: parents.into_iter().map(|(id, _period)| id).collect(),
-                operation_merkle_root: compute_operations_hash(&op_ids, &self.op_id_serializer),
-                endorsements,
-                denunciations: self.channels.pool.get_block_denunciations(&slot),
-            },
-            BlockHeaderSerializer::new(), // TODO reuse self.block_header_serializer
-            block_producer_keypair,
+        let announced_version = self
+            .mip_store
+            .get_network_version_to_announce_with_votes(&self.mip_votes.get_votes());
+        let header_args = BlockHeaderArgs {
+            current_version,
+            announced_version,
+            slot,
+            parents: parents.into_iter().map(|(id, _period)| id).collect(),
+            operation_merkle_root: compute_operations_hash(&op_ids, &self.op_id_serializer),
+            endorsements,
+            denunciations: self.channels.pool.get_block_denunciations(&slot),
+        };
+        let header_content = match self
+            .block_header_factory
+            .strategy_at(slot)
+            .and_then(|strategy| self.block_header_factory.create(&header_args, strategy))
+        {
+            Ok(header_content) => header_content,
+            Err(err) => {
+                warn!(
+                    "block factory could not create block header for slot {}: {}",
+                    slot, err
+                );
+                return;
+            }
+        };
+        let header: SecuredHeader = match BlockHeader::new_verifiable_with_signer(
+            header_content,
+            // TODO reuse self.block_header_serializer
+            BlockHeaderSerializer::new()
+                .with_version_at(Arc::new(self.block_header_factory.clone())),
+            block_producer_public_key,
             self.cfg.chain_id,
-        )
-        .expect("error while producing block header");
+            |serialized_content, _| {
+                self.signer
+                    .sign_block_header(&block_producer_addr, serialized_content)
+                    .map_err(|err| ModelsError::ErrorRaised(err.to_string()))
+            },
+        ) {
+            Ok(header) => header,
+            Err(err) => {
+                warn!(
+                    "block factory could not sign block header for slot {}: {}",
+                    slot, err
+                );
+                return;
+            }
+        };
         // create block
         let block_ = Block {
             header,
             operations: op_ids.into_iter().collect(),
         };
 
-        let block = Block::new_verifiable(
+        // the block is authenticated by its header: nothing more to sign
+        let block = Block::new_verifiable_with_signer::<_, BlockId, _>(
             block_,
             BlockSerializer::new(), // TODO reuse self.block_serializer
-            block_producer_keypair,
+            block_producer_public_key,
             self.cfg.chain_id,
+            |_, _| {
+                Err(ModelsError::ErrorRaised(
+                    "blocks are signed through their header".into(),
+                ))
+            },
         )
         .expect("error while producing block");
         let block_id = block.id;
+
+        // record the signed block before releasing it
+        if let Err(err) = self
+            .slashing_protection
+            .lock()
+            .record(SlashingProtectionRecord {
+                address: block_producer_addr,
+                slot,
+                kind: SignedItemKind::Block,
+                id: block_id.to_string(),
+            })
+        {
+            warn!(
+                "block factory discarded block {} at slot {}: {}",
+                block_id, slot, err
+            );
+            return;
+        }
+
         // store block in storage
         block_storage.store_block(block);
 
@@ -253,6 +383,89 @@ impl BlockFactoryWorker {
         self.channels
             .consensus
             .register_block(block_id, slot, block_storage, true);
+
+        // follow the block until its inclusion is settled
+        self.produced_blocks
+            .push_back((block_id, slot, block_producer_addr));
+    }
+
+    /// Records the processed slots whose draws were not available, now that the selector answers again,
+    /// at which a managed address was selected. The slots whose draws are still unknown are forgotten.
+    fn record_no_draws(&mut self) {
+        if self.slots_without_draws.is_empty() {
+            return;
+        }
+        info!("block factory got selector draws again");
+        for slot in std::mem::take(&mut self.slots_without_draws) {
+            let Ok(producer_addr) = self.channels.selector.get_producer(slot) else {
+                continue;
+            };
+            if let Ok(Some(_)) = self.signer.get_public_key(&producer_addr) {
+                self.miss_recorder
+                    .record_selected_block(producer_addr, slot);
+                self.miss_recorder.record(
+                    producer_addr,
+                    slot,
+                    MissedItem::Block,
+                    MissReason::NoDraws,
+                );
+            }
+        }
+    }
+
+    /// Records the slots skipped since the previous processed slot at which a managed address was selected
+    fn record_late_slots(&self, previous_slot: Slot, slot: Slot) {
+        let mut skipped_slot = previous_slot;
+        loop {
+            skipped_slot = match skipped_slot.get_next_slot(self.cfg.thread_count) {
+                Ok(next_slot) if next_slot < slot => next_slot,
+                _ => break,
+            };
+            // draws may not be available anymore for old slots
+            let Ok(producer_addr) = self.channels.selector.get_producer(skipped_slot) else {
+                continue;
+            };
+            if let Ok(Some(_)) = self.signer.get_public_key(&producer_addr) {
+                self.miss_recorder
+                    .record_selected_block(producer_addr, skipped_slot);
+                self.miss_recorder.record(
+                    producer_addr,
+                    skipped_slot,
+                    MissedItem::Block,
+                    MissReason::Late,
+                );
+            }
+        }
+    }
+
+    /// Checks whether the produced blocks made it into the blockclique.
+    /// Final blocks are forgotten, discarded ones are recorded as missed.
+    fn check_produced_blocks(&mut self) {
+        if self.produced_blocks.is_empty() {
+            return;
+        }
+        let block_ids: Vec<BlockId> = self.produced_blocks.iter().map(|(id, _, _)| *id).collect();
+        let statuses = self.channels.consensus.get_block_statuses(&block_ids);
+        let mut pending = VecDeque::with_capacity(self.produced_blocks.len());
+        for ((block_id, slot, address), status) in self.produced_blocks.drain(..).zip(statuses) {
+            match status {
+                BlockGraphStatus::Final => {}
+                BlockGraphStatus::Discarded | BlockGraphStatus::NotFound => {
+                    warn!(
+                        "block {} produced at slot {} by address {} was not included in the blockclique",
+                        block_id, slot, address
+                    );
+                    self.miss_recorder.record(
+                        address,
+                        slot,
+                        MissedItem::Block,
+                        MissReason::NotInBlockclique,
+                    );
+                }
+                _ => pending.push_back((block_id, slot, address)),
+            }
+        }
+        self.produced_blocks = pending;
     }
 
     /// main run loop of the block creator thread
@@ -267,6 +480,14 @@ impl BlockFactoryWorker {
                 break;
             }
 
+            // record the slots we woke up too late for
+            if let Some(prev_slot) = prev_slot {
+                self.record_late_slots(prev_slot, slot);
+            }
+
+            // follow up on the blocks produced earlier
+            self.check_produced_blocks();
+
             // process slot
             self.process_slot(slot);
 
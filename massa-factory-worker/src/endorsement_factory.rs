@@ -1,28 +1,36 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
+use crate::{missed_slots::MissRecorder, slashing_protection::SlashingProtection};
 use massa_channel::receiver::MassaReceiver;
-use massa_factory_exports::{FactoryChannels, FactoryConfig};
+use massa_factory_exports::{
+    FactoryChannels, FactoryConfig, SignedItemKind, SlashingProtectionRecord,
+};
 use massa_models::{
+    address::Address,
     block_id::BlockId,
     endorsement::{Endorsement, EndorsementSerializer, SecureShareEndorsement},
+    error::ModelsError,
     secure_share::SecureShareContent,
     slot::Slot,
+    stats::{MissReason, MissedItem},
     timeslots::{get_block_slot_timestamp, get_closest_slot_to_timestamp},
 };
-use massa_signature::KeyPair;
+use massa_signature::PublicKey;
 use massa_time::MassaTime;
-use massa_wallet::Wallet;
-use parking_lot::RwLock;
+use massa_wallet::Signer;
+use parking_lot::Mutex;
 use std::{sync::Arc, thread, time::Instant};
 use tracing::{debug, warn};
 
 /// Structure gathering all elements needed by the factory thread
 pub(crate) struct EndorsementFactoryWorker {
     cfg: FactoryConfig,
-    wallet: Arc<RwLock<Wallet>>,
+    signer: Box<dyn Signer>,
     channels: FactoryChannels,
     factory_receiver: MassaReceiver<()>,
     half_t0: MassaTime,
+    slashing_protection: Arc<Mutex<SlashingProtection>>,
+    miss_recorder: MissRecorder,
     endorsement_serializer: EndorsementSerializer,
 }
 
@@ -31,9 +39,11 @@ impl EndorsementFactoryWorker {
     /// needed by the factory worker thread.
     pub(crate) fn spawn(
         cfg: FactoryConfig,
-        wallet: Arc<RwLock<Wallet>>,
+        signer: Box<dyn Signer>,
         channels: FactoryChannels,
         factory_receiver: MassaReceiver<()>,
+        slashing_protection: Arc<Mutex<SlashingProtection>>,
+        miss_recorder: MissRecorder,
     ) -> thread::JoinHandle<()> {
         thread::Builder::new()
             .name("endorsement-factory".into())
@@ -44,9 +54,11 @@ impl EndorsementFactoryWorker {
                         .checked_div_u64(2)
                         .expect("could not compute half_t0"),
                     cfg,
-                    wallet,
+                    signer,
                     channels,
                     factory_receiver,
+                    slashing_protection,
+                    miss_recorder,
                     endorsement_serializer: EndorsementSerializer::new(),
                 };
                 this.run();
@@ -134,21 +146,40 @@ impl EndorsementFactoryWorker {
             }
         };
 
-        // get creators if they are managed by our wallet
-        let mut producers_indices: Vec<(KeyPair, usize)> = Vec::new();
+        // get creators if they are managed by our signer
+        let mut producers_indices: Vec<(Address, PublicKey, usize)> = Vec::new();
         {
-            let wallet = self.wallet.read();
+            let slashing_protection = self.slashing_protection.lock();
             for (index, producer_addr) in producer_addrs.into_iter().enumerate() {
-                // check if the block producer address is handled by the wallet
-                let producer_keypair =
-                    if let Some(kp) = wallet.find_associated_keypair(&producer_addr) {
-                        // the selected block producer is managed locally => continue to attempt endorsement production
-                        kp.clone()
-                    } else {
-                        // the selected block producer is not managed locally => continue
+                // check if the block producer address is handled by the signer
+                let producer_public_key = match self.signer.get_public_key(&producer_addr) {
+                    // the selected block producer is managed locally => continue to attempt endorsement production
+                    Ok(Some(public_key)) => public_key,
+                    // the selected block producer is not managed locally => continue
+                    Ok(None) => continue,
+                    Err(err) => {
+                        warn!(
+                            "endorsement factory could not get the public key of {} for slot {}: {}",
+                            producer_addr, slot, err
+                        );
                         continue;
-                    };
-                producers_indices.push((producer_keypair, index));
+                    }
+                };
+                // check that we did not already sign an endorsement at that index, even before a restart
+                if let Some(record) = slashing_protection.get(
+                    &producer_addr,
+                    &slot,
+                    &SignedItemKind::Endorsement {
+                        index: index as u32,
+                    },
+                ) {
+                    warn!(
+                        "endorsement factory will not produce endorsement at index {} for slot {} with address {}: endorsement {} was already signed",
+                        index, slot, producer_addr, record.id
+                    );
+                    continue;
+                }
+                producers_indices.push((producer_addr, producer_public_key, index));
             }
         }
 
@@ -163,6 +194,16 @@ impl EndorsementFactoryWorker {
             if let Ok(stats) = self.channels.protocol.get_stats() {
                 if stats.1.is_empty() {
                     warn!("endorsement factory could not produce endorsement for slot {} because there are no connections", slot);
+                    for (producer_addr, _, index) in producers_indices {
+                        self.miss_recorder.record(
+                            producer_addr,
+                            slot,
+                            MissedItem::Endorsement {
+                                index: index as u32,
+                            },
+                            MissReason::NotConnected,
+                        );
+                    }
                     return;
                 }
             }
@@ -177,18 +218,51 @@ impl EndorsementFactoryWorker {
         // produce endorsements
         let mut endorsements: Vec<SecureShareEndorsement> =
             Vec::with_capacity(producers_indices.len());
-        for (keypair, index) in producers_indices {
-            let endorsement = Endorsement::new_verifiable(
+        for (producer_addr, public_key, index) in producers_indices {
+            let endorsement: SecureShareEndorsement = match Endorsement::new_verifiable_with_signer(
                 Endorsement {
                     slot,
                     index: index as u32,
                     endorsed_block,
                 },
                 self.endorsement_serializer.clone(),
-                &keypair,
+                public_key,
                 self.cfg.chain_id,
-            )
-            .expect("could not create endorsement");
+                |serialized_content, _| {
+                    self.signer
+                        .sign_endorsement(&producer_addr, serialized_content)
+                        .map_err(|err| ModelsError::ErrorRaised(err.to_string()))
+                },
+            ) {
+                Ok(endorsement) => endorsement,
+                Err(err) => {
+                    warn!(
+                        "endorsement factory could not sign endorsement at index {} for slot {}: {}",
+                        index, slot, err
+                    );
+                    continue;
+                }
+            };
+
+            // record the signed endorsement before releasing it
+            if let Err(err) = self
+                .slashing_protection
+                .lock()
+                .record(SlashingProtectionRecord {
+                    address: endorsement.content_creator_address,
+                    slot,
+                    kind: SignedItemKind::Endorsement {
+                        index: index as u32,
+                    },
+                    id: endorsement.id.to_string(),
+                })
+            {
+                warn!(
+                    "endorsement factory discarded endorsement {} at slot {}: {}",
+                    endorsement.id, slot, err
+                );
+                continue;
+            }
 
             // log endorsement creation
             debug!(
@@ -199,6 +273,10 @@ impl EndorsementFactoryWorker {
             endorsements.push(endorsement);
         }
 
+        if endorsements.is_empty() {
+            return;
+        }
+
         // store endorsements
         let mut endo_storage = self.channels.storage.clone_without_refs();
         endo_storage.store_endorsements(endorsements);
@@ -211,6 +289,33 @@ impl EndorsementFactoryWorker {
         }
     }
 
+    /// Records the slots skipped since the previous processed slot at which a managed address was selected
+    fn record_late_slots(&self, previous_slot: Slot, slot: Slot) {
+        let mut skipped_slot = previous_slot;
+        loop {
+            skipped_slot = match skipped_slot.get_next_slot(self.cfg.thread_count) {
+                Ok(next_slot) if next_slot < slot => next_slot,
+                _ => break,
+            };
+            // draws may not be available anymore for old slots
+            let Ok(selection) = self.channels.selector.get_selection(skipped_slot) else {
+                continue;
+            };
+            for (index, producer_addr) in selection.endorsements.into_iter().enumerate() {
+                if let Ok(Some(_)) = self.signer.get_public_key(&producer_addr) {
+                    self.miss_recorder.record(
+                        producer_addr,
+                        skipped_slot,
+                        MissedItem::Endorsement {
+                            index: index as u32,
+                        },
+                        MissReason::Late,
+                    );
+                }
+            }
+        }
+    }
+
     /// main run loop of the endorsement creator thread
     fn run(&mut self) {
         let mut prev_slot = None;
@@ -223,6 +328,11 @@ impl EndorsementFactoryWorker {
                 break;
             }
 
+            // record the slots we woke up too late for
+            if let Some(prev_slot) = prev_slot {
+                self.record_late_slots(prev_slot, slot);
+            }
+
             // process slot
             self.process_slot(slot);
 
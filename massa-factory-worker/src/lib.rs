@@ -3,9 +3,12 @@
 mod block_factory;
 mod endorsement_factory;
 mod manager;
+mod missed_slots;
 mod run;
+mod slashing_protection;
 
 pub use run::start_factory;
+pub use slashing_protection::SlashingProtection;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,88 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+use massa_factory_exports::MissedSlots;
+use massa_metrics::MassaMetrics;
+use massa_models::{
+    address::Address,
+    slot::Slot,
+    stats::{MissReason, MissedItem, MissedSlot},
+};
+use num::rational::Ratio;
+use tracing::{debug, warn};
+
+/// Minimal number of blocks an address has to be selected for in a cycle before its miss ratio is warned about,
+/// so that a single early miss does not look like a ratio of 100%
+const MIN_SELECTED_BLOCKS_FOR_WARNING: u64 = 10;
+
+/// Records the slots missed by the local stakers in the shared record and in the metrics,
+/// and warns when an address gets close to having its rolls sold.
+#[derive(Clone)]
+pub(crate) struct MissRecorder {
+    missed_slots: MissedSlots,
+    massa_metrics: MassaMetrics,
+    /// maximal ratio of missed blocks over a cycle before the rolls of an address are sold
+    max_miss_ratio: Ratio<u64>,
+    /// ratio of missed blocks over a cycle above which a warning is emitted
+    warning_miss_ratio: Ratio<u64>,
+}
+
+impl MissRecorder {
+    pub(crate) fn new(
+        missed_slots: MissedSlots,
+        massa_metrics: MassaMetrics,
+        max_miss_ratio: Ratio<u64>,
+    ) -> Self {
+        MissRecorder {
+            missed_slots,
+            massa_metrics,
+            max_miss_ratio,
+            // warn when three quarters of the tolerated misses are reached
+            warning_miss_ratio: max_miss_ratio * Ratio::new(3, 4),
+        }
+    }
+
+    /// Records that a local address was selected to produce the block of a slot
+    pub(crate) fn record_selected_block(&self, address: Address, slot: Slot) {
+        self.missed_slots.record_selected_block(address, slot);
+    }
+
+    /// Records a block or an endorsement that a local address did not produce
+    pub(crate) fn record(
+        &self,
+        address: Address,
+        slot: Slot,
+        item: MissedItem,
+        reason: MissReason,
+    ) {
+        let missed_slot = MissedSlot {
+            address,
+            slot,
+            item,
+            reason,
+        };
+        debug!("{}", missed_slot);
+        let stats = self.missed_slots.record(missed_slot);
+
+        match item {
+            MissedItem::Block => self.massa_metrics.inc_missed_blocks_counter(),
+            MissedItem::Endorsement { .. } => {
+                self.massa_metrics.inc_missed_endorsements_counter();
+                return;
+            }
+        }
+
+        if stats.selected_block_count >= MIN_SELECTED_BLOCKS_FOR_WARNING
+            && Ratio::new(stats.missed_block_count, stats.selected_block_count)
+                >= self.warning_miss_ratio
+        {
+            warn!(
+                "address {} missed {} of the {} blocks it was selected for in cycle {}: its rolls will be sold if the ratio of missed blocks exceeds {}",
+                address,
+                stats.missed_block_count,
+                stats.selected_block_count,
+                stats.cycle,
+                self.max_miss_ratio
+            );
+        }
+    }
+}
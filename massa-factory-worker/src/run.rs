@@ -1,32 +1,57 @@
 //! Copyright (c) 2022 MASSA LABS <info@massa.net>
 
 use massa_channel::MassaChannel;
-use massa_versioning::versioning::MipStore;
-use parking_lot::RwLock;
+use massa_versioning::{mip_votes::MipVotes, versioning::MipStore};
+use parking_lot::Mutex;
 use std::sync::Arc;
 
 use crate::{
     block_factory::BlockFactoryWorker, endorsement_factory::EndorsementFactoryWorker,
-    manager::FactoryManagerImpl,
+    manager::FactoryManagerImpl, missed_slots::MissRecorder,
+    slashing_protection::SlashingProtection,
 };
-use massa_factory_exports::{FactoryChannels, FactoryConfig, FactoryManager};
-use massa_wallet::Wallet;
+use massa_factory_exports::{FactoryChannels, FactoryConfig, FactoryManager, MissedSlots};
+use massa_metrics::MassaMetrics;
+use massa_wallet::Signer;
 
 /// Start factory
 ///
 /// # Arguments
 /// * `cfg`: factory configuration
-/// * `wallet`: atomic reference to the node wallet
+/// * `signer`: signer of the staking addresses (node wallet or remote signer)
 /// * `channels`: channels to communicate with other modules
+/// * `mip_store`: MIP store, to get the network versions to set in block headers
+/// * `mip_votes`: votes of the node operator on MIPs, to choose the network version to announce
+/// * `missed_slots`: record of the slots missed by the staking addresses, shared with the API
+/// * `massa_metrics`: metrics of the node
 ///
 /// # Return value
 /// Returns a factory manager allowing to stop the workers cleanly.
+///
+/// # Panics
+/// If the slashing protection database cannot be opened: producing without it is unsafe.
 pub fn start_factory(
     cfg: FactoryConfig,
-    wallet: Arc<RwLock<Wallet>>,
+    signer: Box<dyn Signer>,
     channels: FactoryChannels,
     mip_store: MipStore,
+    mip_votes: MipVotes,
+    missed_slots: MissedSlots,
+    massa_metrics: MassaMetrics,
 ) -> Box<dyn FactoryManager> {
+    // open the record of signed blocks and endorsements, shared by both workers.
+    // Items older than the denunciation expiration can no longer be denounced: their records are dropped.
+    let slashing_protection = Arc::new(Mutex::new(
+        SlashingProtection::open(
+            &cfg.slashing_protection_path,
+            cfg.denunciation_expire_periods,
+        )
+        .expect("could not open the slashing protection database"),
+    ));
+
+    // record of the missed slots, shared by both workers
+    let miss_recorder = MissRecorder::new(missed_slots, massa_metrics, cfg.max_miss_ratio);
+
     // create block factory channel
     let (block_worker_tx, block_worker_rx) =
         MassaChannel::new("factory_block_worker".to_string(), None);
@@ -38,15 +63,24 @@ pub fn start_factory(
     // start block factory worker
     let block_worker_handle = BlockFactoryWorker::spawn(
         cfg.clone(),
-        wallet.clone(),
+        signer.clone(),
         channels.clone(),
         block_worker_rx,
         mip_store,
+        mip_votes,
+        slashing_protection.clone(),
+        miss_recorder.clone(),
     );
 
     // start endorsement factory worker
-    let endorsement_worker_handle =
-        EndorsementFactoryWorker::spawn(cfg, wallet, channels, endorsement_worker_rx);
+    let endorsement_worker_handle = EndorsementFactoryWorker::spawn(
+        cfg,
+        signer,
+        channels,
+        endorsement_worker_rx,
+        slashing_protection,
+        miss_recorder,
+    );
 
     // create factory manager
     let manager = FactoryManagerImpl {
@@ -0,0 +1,380 @@
+//! Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! On-disk record of the blocks and endorsements signed by the factories.
+//!
+//! Every signed item is appended (and synced) to a JSON lines file before being released,
+//! so that a restarted node never signs a second item for the same address, slot and kind.
+//! The file is locked while open so that two processes never sign from the same records.
+
+use massa_factory_exports::{
+    FactoryError, SignedItemKind, SlashingProtectionExport, SlashingProtectionRecord,
+    SLASHING_PROTECTION_EXPORT_VERSION,
+};
+use massa_models::{address::Address, block_id::BlockId, endorsement::EndorsementId, slot::Slot};
+use massa_wallet::{SignedItemsRecord, WalletError};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+use tracing::warn;
+
+/// Slashing protection database
+pub struct SlashingProtection {
+    /// lock file, locked for as long as the database is open
+    _lock: fd_lock::RwLock<File>,
+    /// file the records are appended to
+    file: File,
+    /// records indexed by (address, slot, kind)
+    records: BTreeMap<(Address, Slot, SignedItemKind), SlashingProtectionRecord>,
+}
+
+impl SlashingProtection {
+    /// Opens the database at `path`, creating it if it does not exist.
+    /// Fails if the database is already open, for instance by a running node.
+    ///
+    /// Records more than `retention_periods` periods older than the latest one are dropped.
+    pub fn open(path: &Path, retention_periods: u64) -> Result<Self, FactoryError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| io_error("could not create the directory of", path, err))?;
+        }
+
+        // lock the database
+        let lock_path = path.with_extension("lock");
+        let mut lock = fd_lock::RwLock::new(
+            OpenOptions::new()
+                .create(true)
+                .write(true)
+                .open(&lock_path)
+                .map_err(|err| io_error("could not open", &lock_path, err))?,
+        );
+        match lock.try_write() {
+            // the lock is released when the lock file is closed, that is when the database is dropped
+            Ok(guard) => std::mem::forget(guard),
+            Err(err) => {
+                return Err(FactoryError::SlashingProtectionError(format!(
+                    "could not lock {}, is it used by another process? {}",
+                    lock_path.display(),
+                    err
+                )))
+            }
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| io_error("could not open", path, err))?;
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)
+            .map_err(|err| io_error("could not read", path, err))?;
+
+        // A crash while appending can leave an incomplete last line.
+        // Its item was never released since records are synced before that: drop it.
+        let complete_len = content
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |pos| pos + 1);
+        let torn = complete_len < content.len();
+        if torn {
+            warn!("dropping the incomplete last record of {}", path.display());
+        }
+        let mut records = BTreeMap::new();
+        for line in content[..complete_len].split(|byte| *byte == b'\n') {
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            let record: SlashingProtectionRecord = serde_json::from_slice(line).map_err(|err| {
+                FactoryError::SlashingProtectionError(format!(
+                    "could not parse {}: {}",
+                    path.display(),
+                    err
+                ))
+            })?;
+            records.insert((record.address, record.slot, record.kind), record);
+        }
+
+        // drop the old records
+        let record_count = records.len();
+        if let Some(latest_period) = records.keys().map(|(_, slot, _)| slot.period).max() {
+            let min_period = latest_period.saturating_sub(retention_periods);
+            records.retain(|(_, slot, _), _| slot.period >= min_period);
+        }
+
+        let mut db = SlashingProtection {
+            _lock: lock,
+            file,
+            records,
+        };
+        if torn || db.records.len() < record_count {
+            db.rewrite(path)?;
+        }
+        Ok(db)
+    }
+
+    /// Gets the item signed by `address` at `slot` for `kind`, if any
+    pub fn get(
+        &self,
+        address: &Address,
+        slot: &Slot,
+        kind: &SignedItemKind,
+    ) -> Option<&SlashingProtectionRecord> {
+        self.records.get(&(*address, *slot, *kind))
+    }
+
+    /// Records a signed item. Must succeed before the item is released.
+    ///
+    /// Fails if a different item was already signed for the same address, slot and kind.
+    pub fn record(&mut self, record: SlashingProtectionRecord) -> Result<(), FactoryError> {
+        if let Some(existing) = self.get(&record.address, &record.slot, &record.kind) {
+            if existing.id == record.id {
+                return Ok(());
+            }
+            return Err(FactoryError::SlashingProtectionError(format!(
+                "{} already signed {} at slot {} for {:?}",
+                record.address, existing.id, record.slot, record.kind
+            )));
+        }
+        self.append(&record)?;
+        self.records
+            .insert((record.address, record.slot, record.kind), record);
+        Ok(())
+    }
+
+    /// Writes every record to `writer` in the export format
+    pub fn export<W: Write>(&self, writer: W, chain_id: u64) -> Result<usize, FactoryError> {
+        let mut records: Vec<SlashingProtectionRecord> = self.records.values().cloned().collect();
+        records.sort_by_key(|record| (record.slot, record.address));
+        let count = records.len();
+        serde_json::to_writer_pretty(
+            writer,
+            &SlashingProtectionExport {
+                version: SLASHING_PROTECTION_EXPORT_VERSION,
+                chain_id,
+                records,
+            },
+        )
+        .map_err(|err| {
+            FactoryError::SlashingProtectionError(format!("could not write export: {}", err))
+        })?;
+        Ok(count)
+    }
+
+    /// Adds the records of an export to the database.
+    /// Records already known for the same address, slot and kind are kept.
+    ///
+    /// # Returns
+    /// The number of added records
+    pub fn import<R: Read>(&mut self, reader: R, chain_id: u64) -> Result<usize, FactoryError> {
+        let export: SlashingProtectionExport = serde_json::from_reader(reader).map_err(|err| {
+            FactoryError::SlashingProtectionError(format!("could not parse export: {}", err))
+        })?;
+        if export.version != SLASHING_PROTECTION_EXPORT_VERSION {
+            return Err(FactoryError::SlashingProtectionError(format!(
+                "unsupported export version {} (expected {})",
+                export.version, SLASHING_PROTECTION_EXPORT_VERSION
+            )));
+        }
+        if export.chain_id != chain_id {
+            return Err(FactoryError::SlashingProtectionError(format!(
+                "export is for chain id {} but the node runs on chain id {}",
+                export.chain_id, chain_id
+            )));
+        }
+        let mut count = 0;
+        for record in export.records {
+            if self
+                .get(&record.address, &record.slot, &record.kind)
+                .is_none()
+            {
+                self.record(record)?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Replaces the file at `path` by the records in memory
+    fn rewrite(&mut self, path: &Path) -> Result<(), FactoryError> {
+        let mut content = Vec::new();
+        for record in self.records.values() {
+            content.extend(serialize_record(record)?);
+        }
+        // write a new file then move it over the old one, so that a crash leaves either of them
+        let tmp_path = path.with_extension("tmp");
+        File::create(&tmp_path)
+            .and_then(|mut file| {
+                file.write_all(&content)?;
+                file.sync_all()
+            })
+            .map_err(|err| io_error("could not write", &tmp_path, err))?;
+        std::fs::rename(&tmp_path, path).map_err(|err| io_error("could not replace", path, err))?;
+        self.file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| io_error("could not open", path, err))?;
+        Ok(())
+    }
+
+    /// Appends a record to the file and syncs it to disk
+    fn append(&mut self, record: &SlashingProtectionRecord) -> Result<(), FactoryError> {
+        let line = serialize_record(record)?;
+        self.file
+            .write_all(&line)
+            .and_then(|_| self.file.sync_data())
+            .map_err(|err| {
+                FactoryError::SlashingProtectionError(format!("could not write record: {}", err))
+            })
+    }
+}
+
+/// Lets a remote signing host refuse double-productions, whatever the nodes asking for signatures
+impl SignedItemsRecord for SlashingProtection {
+    fn record_block_header(
+        &mut self,
+        address: Address,
+        slot: Slot,
+        id: BlockId,
+    ) -> Result<(), WalletError> {
+        self.record(SlashingProtectionRecord {
+            address,
+            slot,
+            kind: SignedItemKind::Block,
+            id: id.to_string(),
+        })
+        .map_err(|err| WalletError::RemoteSignerError(err.to_string()))
+    }
+
+    fn record_endorsement(
+        &mut self,
+        address: Address,
+        slot: Slot,
+        index: u32,
+        id: EndorsementId,
+    ) -> Result<(), WalletError> {
+        self.record(SlashingProtectionRecord {
+            address,
+            slot,
+            kind: SignedItemKind::Endorsement { index },
+            id: id.to_string(),
+        })
+        .map_err(|err| WalletError::RemoteSignerError(err.to_string()))
+    }
+}
+
+/// Serializes a record as a line of the file
+fn serialize_record(record: &SlashingProtectionRecord) -> Result<Vec<u8>, FactoryError> {
+    let mut line = serde_json::to_vec(record).map_err(|err| {
+        FactoryError::SlashingProtectionError(format!("could not serialize record: {}", err))
+    })?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+/// Error of a file operation
+fn io_error(action: &str, path: &Path, err: std::io::Error) -> FactoryError {
+    FactoryError::SlashingProtectionError(format!("{} {}: {}", action, path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use massa_signature::KeyPair;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_slashing_protection() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("slashing_protection.jsonl");
+        let address = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let slot = Slot::new(3, 1);
+        let record = SlashingProtectionRecord {
+            address,
+            slot,
+            kind: SignedItemKind::Endorsement { index: 2 },
+            id: "E1".to_string(),
+        };
+
+        let mut db = SlashingProtection::open(&path, 10).unwrap();
+        assert!(db.get(&address, &slot, &record.kind).is_none());
+        db.record(record.clone()).unwrap();
+        // same item again is fine, a different item is refused
+        db.record(record.clone()).unwrap();
+        assert!(db
+            .record(SlashingProtectionRecord {
+                id: "E2".to_string(),
+                ..record.clone()
+            })
+            .is_err());
+        assert!(db.get(&address, &slot, &SignedItemKind::Block).is_none());
+
+        // records survive a restart
+        drop(db);
+        let db = SlashingProtection::open(&path, 10).unwrap();
+        assert_eq!(db.get(&address, &slot, &record.kind), Some(&record));
+
+        // export / import
+        let mut export = Vec::new();
+        assert_eq!(db.export(&mut export, 77).unwrap(), 1);
+        let mut other = SlashingProtection::open(&dir.path().join("other.jsonl"), 10).unwrap();
+        assert!(other.import(export.as_slice(), 78).is_err());
+        assert_eq!(other.import(export.as_slice(), 77).unwrap(), 1);
+        assert_eq!(other.import(export.as_slice(), 77).unwrap(), 0);
+        assert_eq!(other.get(&address, &slot, &record.kind), Some(&record));
+    }
+
+    #[test]
+    fn test_slashing_protection_recovery() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("slashing_protection.jsonl");
+        let address = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let record = |period: u64| SlashingProtectionRecord {
+            address,
+            slot: Slot::new(period, 0),
+            kind: SignedItemKind::Block,
+            id: format!("B{}", period),
+        };
+
+        let mut db = SlashingProtection::open(&path, 10).unwrap();
+        // the database cannot be opened twice
+        assert!(SlashingProtection::open(&path, 10).is_err());
+        for period in [1, 5, 20] {
+            db.record(record(period)).unwrap();
+        }
+        drop(db);
+
+        // a crash in the middle of an append leaves an incomplete line
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"address":"#).unwrap();
+        drop(file);
+
+        // the incomplete line and the records older than 10 periods before the latest are dropped
+        let mut db = SlashingProtection::open(&path, 10).unwrap();
+        assert!(db
+            .get(&address, &Slot::new(1, 0), &SignedItemKind::Block)
+            .is_none());
+        assert!(db
+            .get(&address, &Slot::new(5, 0), &SignedItemKind::Block)
+            .is_none());
+        assert_eq!(
+            db.get(&address, &Slot::new(20, 0), &SignedItemKind::Block),
+            Some(&record(20))
+        );
+        db.record(record(21)).unwrap();
+        drop(db);
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        let db = SlashingProtection::open(&path, 10).unwrap();
+        assert_eq!(
+            db.get(&address, &Slot::new(21, 0), &SignedItemKind::Block),
+            Some(&record(21))
+        );
+
+        // a corrupted complete line is an error
+        drop(db);
+        std::fs::write(&path, "{}\n").unwrap();
+        assert!(SlashingProtection::open(&path, 10).is_err());
+    }
+}
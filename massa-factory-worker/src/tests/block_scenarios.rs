@@ -1,4 +1,4 @@
-use std::{str::FromStr, sync::Arc};
+use std::{str::FromStr, sync::Arc, time::Duration};
 
 use super::BlockTestFactory;
 use massa_consensus_exports::MockConsensusController;
@@ -12,9 +12,10 @@ use massa_models::{
     operation::{Operation, OperationSerializer, OperationType},
     secure_share::SecureShareContent,
     slot::Slot,
+    stats::{MissReason, MissedItem},
 };
 use massa_pool_exports::MockPoolController;
-use massa_pos_exports::MockSelectorController;
+use massa_pos_exports::{MockSelectorController, PosError};
 use massa_signature::KeyPair;
 use massa_storage::Storage;
 use parking_lot::{Condvar, Mutex};
@@ -187,3 +188,57 @@ fn basic_creation_with_operation() {
     }
     test_factory.stop();
 }
+
+/// Records a missed block for the managed address when the draws of a slot were not available,
+/// once they show that it was selected.
+#[test]
+#[serial]
+fn missed_slot_without_draws() {
+    let keypair = KeyPair::generate(0).unwrap();
+    let staking_address = Address::from_public_key(&keypair.get_public_key());
+    let other_address = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+    let mut selector_controller = Box::new(MockSelectorController::new());
+    let first_slot: Arc<Mutex<Option<Slot>>> = Default::default();
+    selector_controller
+        .expect_get_producer()
+        .returning(move |slot| {
+            let mut first_slot = first_slot.lock();
+            match *first_slot {
+                // the draws of the first processed slot are not available at first
+                None => {
+                    *first_slot = Some(slot);
+                    Err(PosError::CycleUnavailable(0))
+                }
+                // they then show that the managed address was selected
+                Some(first_slot) if first_slot == slot => Ok(staking_address),
+                Some(_) => Ok(other_address),
+            }
+        });
+    let mut test_factory = BlockTestFactory::new(
+        &keypair,
+        Storage::create_root(),
+        Box::new(MockConsensusController::new()),
+        selector_controller,
+        Box::new(MockPoolController::new()),
+    );
+
+    // wait for the next slot to be processed
+    let mut missed_slots = Vec::new();
+    for _ in 0..50 {
+        missed_slots = test_factory.missed_slots.get_missed_slots(&[]);
+        if !missed_slots.is_empty() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    test_factory.stop();
+
+    assert_eq!(missed_slots.len(), 1);
+    let missed_slot = missed_slots.first().expect("no missed slot was recorded");
+    assert_eq!(missed_slot.address, staking_address);
+    assert_eq!(missed_slot.item, MissedItem::Block);
+    assert_eq!(missed_slot.reason, MissReason::NoDraws);
+
+    let stats = test_factory.missed_slots.get_stats(&[staking_address]);
+    assert!(stats.iter().any(|stats| stats.missed_block_count == 1));
+}
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use super::EndorsementTestFactory;
+use crate::slashing_protection::SlashingProtection;
 use massa_consensus_exports::MockConsensusController;
+use massa_factory_exports::{SignedItemKind, SlashingProtectionRecord};
 use massa_hash::Hash;
 use massa_models::{
     address::Address,
@@ -16,6 +18,7 @@ use massa_signature::KeyPair;
 use massa_storage::Storage;
 use parking_lot::{Condvar, Mutex};
 use serial_test::serial;
+use tempfile::TempDir;
 
 /// Creates a basic empty block with the factory.
 #[test]
@@ -94,3 +97,95 @@ fn basic_creation() {
     }
     test_factory.stop();
 }
+
+/// Endorsement indices signed before a restart, as recorded in the slashing protection database, are not signed again.
+#[test]
+#[serial]
+fn skip_endorsements_signed_before_restart() {
+    let default_panic = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_panic(info);
+        std::process::exit(1);
+    }));
+    let keypair = KeyPair::generate(0).unwrap();
+    let storage = Storage::create_root();
+    let staking_address = Address::from_public_key(&keypair.get_public_key());
+    let parent = BlockId::generate_from_hash(Hash::compute_from("test".as_bytes()));
+
+    // endorsements 0 and 1 of slot (1, 0) were signed before the restart
+    let slashing_protection_dir = TempDir::new().unwrap();
+    let mut slashing_protection = SlashingProtection::open(
+        &slashing_protection_dir.path().join("records.jsonl"),
+        u64::MAX,
+    )
+    .unwrap();
+    for index in [0, 1] {
+        slashing_protection
+            .record(SlashingProtectionRecord {
+                address: staking_address,
+                slot: Slot::new(1, 0),
+                kind: SignedItemKind::Endorsement { index },
+                id: format!("E{}", index),
+            })
+            .unwrap();
+    }
+    drop(slashing_protection);
+
+    let pair = Arc::new((Mutex::new(false), Condvar::new()));
+    let pair2 = pair.clone();
+    let mut consensus_controller = Box::new(MockConsensusController::new());
+    consensus_controller
+        .expect_get_latest_blockclique_block_at_slot()
+        .times(1)
+        .returning(move |_| parent);
+    let mut selector_controller = Box::new(MockSelectorController::new());
+    selector_controller
+        .expect_get_selection()
+        .times(1)
+        .returning(move |slot| {
+            assert_eq!(slot, Slot::new(1, 0));
+            Ok(Selection {
+                producer: staking_address,
+                endorsements: vec![staking_address; ENDORSEMENT_COUNT as usize],
+            })
+        });
+    let mut pool_controller = Box::new(MockPoolController::new());
+    pool_controller
+        .expect_add_endorsements()
+        .times(1)
+        .returning(|_| {});
+    let mut protocol_controller = Box::new(MockProtocolController::new());
+    protocol_controller
+        .expect_propagate_endorsements()
+        .times(1)
+        .returning(move |storage| {
+            let endorsements = storage.read_endorsements();
+            let mut indices: Vec<u32> = storage
+                .get_endorsement_refs()
+                .iter()
+                .map(|id| endorsements.get(id).unwrap().content.index)
+                .collect();
+            indices.sort_unstable();
+            assert_eq!(indices, (2..ENDORSEMENT_COUNT).collect::<Vec<_>>());
+            let (lock, cvar) = &*pair2;
+            let mut started = lock.lock();
+            *started = true;
+            cvar.notify_one();
+            Ok(())
+        });
+    let mut test_factory = EndorsementTestFactory::new_with_slashing_protection(
+        &keypair,
+        storage,
+        consensus_controller,
+        selector_controller,
+        pool_controller,
+        protocol_controller,
+        slashing_protection_dir,
+    );
+    let (lock, cvar) = &*pair;
+    let mut started = lock.lock();
+    if !*started {
+        cvar.wait(&mut started);
+    }
+    test_factory.stop();
+}
@@ -2,15 +2,23 @@ use massa_channel::sender::MassaSender;
 use massa_channel::MassaChannel;
 use massa_consensus_exports::MockConsensusController;
 use massa_models::config::MIP_STORE_STATS_BLOCK_CONSIDERED;
+use massa_versioning::mip_votes::MipVotes;
 use massa_versioning::versioning::MipStatsConfig;
 use massa_versioning::versioning::MipStore;
 use num::rational::Ratio;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::sync::Arc;
 use std::thread::JoinHandle;
+use std::time::Duration;
+use tempfile::TempDir;
 
-use massa_factory_exports::{test_exports::create_empty_block, FactoryChannels, FactoryConfig};
-use massa_models::{address::Address, block_id::BlockId, prehash::PreHashMap, slot::Slot};
+use massa_factory_exports::{
+    test_exports::create_empty_block, FactoryChannels, FactoryConfig, MissedSlots,
+};
+use massa_metrics::MassaMetrics;
+use massa_models::{
+    address::Address, block_id::BlockId, config::THREAD_COUNT, prehash::PreHashMap, slot::Slot,
+};
 use massa_pool_exports::MockPoolController;
 use massa_pos_exports::MockSelectorController;
 use massa_protocol_exports::MockProtocolController;
@@ -19,7 +27,10 @@ use massa_storage::Storage;
 
 use crate::block_factory::BlockFactoryWorker;
 use crate::endorsement_factory::EndorsementFactoryWorker;
+use crate::missed_slots::MissRecorder;
+use crate::slashing_protection::SlashingProtection;
 use massa_wallet::test_exports::create_test_wallet;
+use massa_wallet::LocalSigner;
 
 /// This structure store all information and links to creates tests for the factory.
 pub struct BlockTestFactory {
@@ -28,6 +39,8 @@ pub struct BlockTestFactory {
     _genesis_blocks: Vec<(BlockId, u64)>,
     pub(crate) _storage: Storage,
     _keypair: KeyPair,
+    _slashing_protection_dir: TempDir,
+    pub(crate) missed_slots: MissedSlots,
 }
 
 impl BlockTestFactory {
@@ -75,11 +88,25 @@ impl BlockTestFactory {
         let mip_store =
             MipStore::try_from(([], mip_stats_config)).expect("Cannot create an empty MIP store");
 
+        let slashing_protection_dir = TempDir::new().unwrap();
+        let slashing_protection = Arc::new(Mutex::new(
+            SlashingProtection::open(
+                &slashing_protection_dir.path().join("records.jsonl"),
+                factory_config.denunciation_expire_periods,
+            )
+            .unwrap(),
+        ));
+
+        let missed_slots = MissedSlots::new(
+            factory_config.missed_slots_history_length,
+            factory_config.periods_per_cycle,
+        );
+
         let wallet = create_test_wallet(Some(accounts));
         let (tx, rx) = MassaChannel::new(String::from("test_block_factory"), None);
         let join_handle = BlockFactoryWorker::spawn(
             factory_config.clone(),
-            Arc::new(RwLock::new(wallet)),
+            Box::new(LocalSigner::new(Arc::new(RwLock::new(wallet)))),
             FactoryChannels {
                 selector: selector_controller,
                 consensus: consensus_controller,
@@ -89,6 +116,13 @@ impl BlockTestFactory {
             },
             rx,
             mip_store,
+            MipVotes::load(
+                &slashing_protection_dir.path().join("mip_votes.json"),
+                Default::default(),
+            )
+            .unwrap(),
+            slashing_protection,
+            create_miss_recorder(&factory_config, missed_slots.clone()),
         );
 
         BlockTestFactory {
@@ -97,6 +131,8 @@ impl BlockTestFactory {
             _genesis_blocks: genesis_blocks,
             _storage: storage,
             _keypair: default_keypair.clone(),
+            _slashing_protection_dir: slashing_protection_dir,
+            missed_slots,
         }
     }
 
@@ -114,6 +150,8 @@ pub struct EndorsementTestFactory {
     _genesis_blocks: Vec<(BlockId, u64)>,
     pub(crate) _storage: Storage,
     _keypair: KeyPair,
+    _slashing_protection_dir: TempDir,
+    pub(crate) missed_slots: MissedSlots,
 }
 
 impl EndorsementTestFactory {
@@ -124,12 +162,34 @@ impl EndorsementTestFactory {
     /// Returns
     /// - `TestFactory`: the structure that will be used to manage the tests
     pub fn new(
+        default_keypair: &KeyPair,
+        storage: Storage,
+        consensus_controller: Box<MockConsensusController>,
+        selector_controller: Box<MockSelectorController>,
+        pool_controller: Box<MockPoolController>,
+        protocol_controller: Box<MockProtocolController>,
+    ) -> EndorsementTestFactory {
+        EndorsementTestFactory::new_with_slashing_protection(
+            default_keypair,
+            storage,
+            consensus_controller,
+            selector_controller,
+            pool_controller,
+            protocol_controller,
+            TempDir::new().unwrap(),
+        )
+    }
+
+    /// Same as `new`, with the slashing protection database in `slashing_protection_dir`:
+    /// its records are those of a node that signed before a restart
+    pub fn new_with_slashing_protection(
         default_keypair: &KeyPair,
         mut storage: Storage,
         consensus_controller: Box<MockConsensusController>,
         selector_controller: Box<MockSelectorController>,
         pool_controller: Box<MockPoolController>,
         protocol_controller: Box<MockProtocolController>,
+        slashing_protection_dir: TempDir,
     ) -> EndorsementTestFactory {
         let mut factory_config = FactoryConfig::default();
         factory_config.genesis_timestamp = factory_config
@@ -149,11 +209,24 @@ impl EndorsementTestFactory {
 
         accounts.insert(producer_address, producer_keypair.clone());
 
+        let slashing_protection = Arc::new(Mutex::new(
+            SlashingProtection::open(
+                &slashing_protection_dir.path().join("records.jsonl"),
+                factory_config.denunciation_expire_periods,
+            )
+            .unwrap(),
+        ));
+
+        let missed_slots = MissedSlots::new(
+            factory_config.missed_slots_history_length,
+            factory_config.periods_per_cycle,
+        );
+
         let wallet = create_test_wallet(Some(accounts));
         let (tx, rx) = MassaChannel::new(String::from("test_block_factory"), None);
         let join_handle = EndorsementFactoryWorker::spawn(
             factory_config.clone(),
-            Arc::new(RwLock::new(wallet)),
+            Box::new(LocalSigner::new(Arc::new(RwLock::new(wallet)))),
             FactoryChannels {
                 selector: selector_controller,
                 consensus: consensus_controller,
@@ -162,6 +235,8 @@ impl EndorsementTestFactory {
                 storage: storage.clone_without_refs(),
             },
             rx,
+            slashing_protection,
+            create_miss_recorder(&factory_config, missed_slots.clone()),
         );
 
         EndorsementTestFactory {
@@ -170,6 +245,8 @@ impl EndorsementTestFactory {
             _genesis_blocks: genesis_blocks,
             _storage: storage,
             _keypair: default_keypair.clone(),
+            _slashing_protection_dir: slashing_protection_dir,
+            missed_slots,
         }
     }
 
@@ -180,3 +257,14 @@ impl EndorsementTestFactory {
         }
     }
 }
+
+/// Creates a miss recorder writing to the given record, with disabled metrics
+fn create_miss_recorder(factory_config: &FactoryConfig, missed_slots: MissedSlots) -> MissRecorder {
+    let (massa_metrics, _) = MassaMetrics::new(
+        false,
+        "0.0.0.0:9898".parse().unwrap(),
+        THREAD_COUNT,
+        Duration::from_secs(1),
+    );
+    MissRecorder::new(missed_slots, massa_metrics, factory_config.max_miss_ratio)
+}
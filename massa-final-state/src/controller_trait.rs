@@ -33,8 +33,11 @@ pub trait FinalStateController: Send + Sync {
     /// Applies changes to the execution state at a given slot, and settles that slot forever.
     /// Once this is called, the state is attached at the output of the provided slot.
     ///
+    /// `index_batch` holds node-local index entries (INDEX_CF) built by the caller for this slot:
+    /// they are written in the same atomic write as the changes.
+    ///
     /// Panics if the new slot is not the one coming just after the current one.
-    fn finalize(&mut self, slot: Slot, changes: StateChanges);
+    fn finalize(&mut self, slot: Slot, changes: StateChanges, index_batch: DBBatch);
 
     /// After bootstrap or load from disk, recompute all the caches.
     fn recompute_caches(&mut self);
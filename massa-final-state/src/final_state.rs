@@ -423,7 +423,12 @@ impl FinalState {
         Ok(())
     }
 
-    fn _finalize(&mut self, slot: Slot, changes: StateChanges) -> AnyResult<()> {
+    fn _finalize(
+        &mut self,
+        slot: Slot,
+        changes: StateChanges,
+        index_batch: DBBatch,
+    ) -> AnyResult<()> {
         let cur_slot = self.db.read().get_change_id()?;
         // check slot consistency
         let next_slot = cur_slot.get_next_slot(self.config.thread_count)?;
@@ -449,6 +454,12 @@ impl FinalState {
 
         // do not panic above, it might just mean that the lookback cycle is not available
         // bootstrap again instead
+        let mut db_index_batch = index_batch;
+        self.ledger.apply_changes_to_history_batch(
+            &changes.ledger_changes,
+            slot,
+            &mut db_index_batch,
+        );
         self.ledger
             .apply_changes_to_batch(changes.ledger_changes, &mut db_batch);
         self.executed_ops
@@ -490,7 +501,7 @@ impl FinalState {
 
         self.db
             .write()
-            .write_batch(db_batch, db_versioning_batch, Some(slot));
+            .write_batch_with_index(db_batch, db_versioning_batch, db_index_batch, slot);
 
         let final_state_hash = self.db.read().get_xof_db_hash();
 
@@ -794,8 +805,8 @@ impl FinalStateController for FinalState {
             .map_err(|err| FinalStateError::PosError(err.to_string()))
     }
 
-    fn finalize(&mut self, slot: Slot, changes: StateChanges) {
-        self._finalize(slot, changes).unwrap()
+    fn finalize(&mut self, slot: Slot, changes: StateChanges, index_batch: DBBatch) {
+        self._finalize(slot, changes, index_batch).unwrap()
     }
 
     fn get_execution_trail_hash(&self) -> Hash {
@@ -965,6 +976,7 @@ mod test {
             initial_ledger_path: massa_node_base.join("base_config/initial_ledger.json"),
             max_key_length: MAX_DATASTORE_KEY_LENGTH,
             max_datastore_value_length: MAX_DATASTORE_VALUE_LENGTH,
+            address_history_enabled: false,
         };
         let async_pool_config = AsyncPoolConfig {
             max_length: MAX_ASYNC_POOL_LENGTH,
@@ -1030,6 +1042,8 @@ mod test {
             max_versioning_elements_size: 100,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let db = Arc::new(RwLock::new(
             Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>
@@ -1117,7 +1131,7 @@ mod test {
         let ok_next_slot = Slot::new(0, 1);
         let changes = get_state_changes();
 
-        let res = fstate._finalize(wrong_next_slot, changes.clone());
+        let res = fstate._finalize(wrong_next_slot, changes.clone(), DBBatch::new());
         assert!(res
             .err()
             .unwrap()
@@ -1127,7 +1141,7 @@ mod test {
         assert_eq!(fstate.get_slot(), initial_slot);
 
         // This should also fail because there is no initial cycle (required by POS state)
-        let res = fstate._finalize(ok_next_slot, changes.clone());
+        let res = fstate._finalize(ok_next_slot, changes.clone(), DBBatch::new());
 
         assert!(res.is_err());
         match res {
@@ -1145,7 +1159,7 @@ mod test {
 
         let mut batch = DBBatch::new();
         fstate.pos_state.create_initial_cycle(&mut batch);
-        let res = fstate._finalize(ok_next_slot, changes);
+        let res = fstate._finalize(ok_next_slot, changes, DBBatch::new());
         assert!(res.is_ok());
         assert_eq!(fstate.get_slot(), ok_next_slot);
     }
@@ -1161,7 +1175,7 @@ mod test {
         let changes = get_state_changes();
         let mut batch = DBBatch::new();
         fstate.pos_state.create_initial_cycle(&mut batch);
-        let res = fstate._finalize(ok_next_slot, changes);
+        let res = fstate._finalize(ok_next_slot, changes, DBBatch::new());
         assert!(res.is_ok());
         assert_eq!(fstate.get_slot(), ok_next_slot);
 
@@ -1221,7 +1235,7 @@ mod test {
         let changes = get_state_changes();
         let mut batch = DBBatch::new();
         fstate.pos_state.create_initial_cycle(&mut batch);
-        let res = fstate._finalize(ok_next_slot, changes);
+        let res = fstate._finalize(ok_next_slot, changes, DBBatch::new());
         assert!(res.is_ok());
         assert_eq!(fstate.get_slot(), ok_next_slot);
 
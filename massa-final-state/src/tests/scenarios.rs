@@ -41,6 +41,8 @@ fn create_final_state(temp_dir: &TempDir, reset_final_state: bool) -> Arc<RwLock
         max_versioning_elements_size: 100_000,
         thread_count,
         max_ledger_backups: 10,
+        state_proofs_enabled: false,
+        index_history_periods: 1000,
     };
     let db = Arc::new(RwLock::new(
         Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>
@@ -56,6 +58,7 @@ fn create_final_state(temp_dir: &TempDir, reset_final_state: bool) -> Arc<RwLock
             initial_ledger_path: "".into(),
             max_key_length: MAX_DATASTORE_KEY_LENGTH,
             max_datastore_value_length: MAX_DATASTORE_VALUE_LENGTH,
+            address_history_enabled: false,
         },
         async_pool_config: AsyncPoolConfig {
             thread_count,
@@ -210,7 +213,7 @@ fn test_final_state() {
         );
         state_changes.ledger_changes = ledger_changes;
 
-        fs.write().finalize(slot, state_changes);
+        fs.write().finalize(slot, state_changes, DBBatch::new());
 
         hash = fs.read().db.read().get_xof_db_hash();
 
@@ -0,0 +1,277 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Middleware checking the API keys, method allow-lists and rate limits of the gRPC requests.
+//! See `massa_api_exports::access` for the access rules.
+//!
+//! Methods are named in snake case in the allow-lists (e.g. `get_status` for `GetStatus`). A request counts as one
+//! call when it is opened, and each message sent by the client after the first one (on a stream) as another call.
+//! The gas of the read-only calls is counted by their handler, using the [`AccessGrant`] added to the request extensions.
+
+use crate::error::GrpcError;
+use futures_util::future::BoxFuture;
+use futures_util::{ready, Stream};
+use hyper::body::{Bytes, HttpBody};
+use hyper::header::{HeaderMap, AUTHORIZATION};
+use hyper::{Body, Request, Response};
+use massa_api_exports::access::{AccessClient, AccessConfig, AccessControl, AccessError};
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tonic::body::BoxBody;
+use tonic::transport::server::{TcpConnectInfo, TlsConnectInfo};
+use tower::{Layer, Service};
+use tracing::debug;
+
+/// Prefix of the paths of the Massa gRPC services: the other services (health, reflection) are not checked
+const MASSA_SERVICES_PATH_PREFIX: &str = "/massa.";
+
+/// Length of the header of a gRPC message: compression flag and big-endian message length
+const GRPC_MESSAGE_HEADER_LEN: usize = 5;
+
+/// Access granted to a request, used by its handler to consume the read-only gas budget of the client
+#[derive(Clone)]
+pub(crate) struct AccessGrant {
+    control: Arc<AccessControl>,
+    client: AccessClient,
+}
+
+impl AccessGrant {
+    /// Consume read-only execution gas from the budget of the client
+    pub(crate) fn consume_read_only_gas(&self, gas: u64) -> Result<(), GrpcError> {
+        Ok(self.control.consume_read_only_gas(&self.client, gas)?)
+    }
+
+    /// Consume calls from the budget of the client
+    fn consume_requests(&self, count: u64) -> Result<(), GrpcError> {
+        Ok(self.control.consume_requests(&self.client, count)?)
+    }
+}
+
+/// Layer adding the access control to a gRPC server, forwarding every request as such if it is disabled
+#[derive(Clone)]
+pub(crate) struct AccessLayer {
+    control: Option<Arc<AccessControl>>,
+}
+
+impl AccessLayer {
+    /// Create the access control layer of a server, the IP of the TCP connections being known
+    pub(crate) fn new(config: &AccessConfig) -> Result<Self, AccessError> {
+        let control = if config.enabled {
+            Some(Arc::new(AccessControl::new(config.clone(), true)?))
+        } else {
+            None
+        };
+        Ok(AccessLayer { control })
+    }
+}
+
+impl<S> Layer<S> for AccessLayer {
+    type Service = AccessService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessService {
+            inner,
+            control: self.control.clone(),
+        }
+    }
+}
+
+/// Service checking the access rules before forwarding the requests to the gRPC services
+#[derive(Clone)]
+pub(crate) struct AccessService<S> {
+    inner: S,
+    control: Option<Arc<AccessControl>>,
+}
+
+impl<S, B> Service<Request<Body>> for AccessService<S>
+where
+    S: Service<Request<Body>, Response = Response<B>> + Clone + Send + 'static,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<tonic::codegen::StdError>,
+{
+    type Response = Response<BoxBody>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<Body>) -> Self::Future {
+        // the ready service must be the one called: keep it and leave a fresh clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let path = request.uri().path();
+        if let Some(control) = self
+            .control
+            .as_ref()
+            .filter(|_| path.starts_with(MASSA_SERVICES_PATH_PREFIX))
+        {
+            let method = path
+                .rsplit('/')
+                .next()
+                .map(to_snake_case)
+                .unwrap_or_default();
+            let headers = request.headers();
+            let credential = AccessControl::credential(
+                header_str(headers, AUTHORIZATION.as_str()),
+                header_str(headers, "x-api-key"),
+            );
+            let ip = match &control.config().client_ip_header {
+                Some(header) => client_ip(headers, header),
+                None => remote_ip(&request),
+            };
+            match control.authorize(credential, ip, Some(std::slice::from_ref(&method))) {
+                Ok(client) => {
+                    let grant = AccessGrant {
+                        control: control.clone(),
+                        client,
+                    };
+                    request.extensions_mut().insert(grant.clone());
+                    let (parts, body) = request.into_parts();
+                    let body = Body::wrap_stream(MeteredBody {
+                        body,
+                        grant,
+                        counter: MessageCounter::default(),
+                        free_messages: 1,
+                        failed: false,
+                    });
+                    request = Request::from_parts(parts, body);
+                }
+                Err(err) => {
+                    debug!("refused gRPC request: {}", err);
+                    let status = tonic::Status::from(GrpcError::from(err));
+                    return Box::pin(async move { Ok(status.to_http()) });
+                }
+            }
+        }
+
+        Box::pin(async move {
+            let response = inner.call(request).await?;
+            Ok(response.map(tonic::body::boxed))
+        })
+    }
+}
+
+/// Request body counting each message after the first one as a call of the client
+struct MeteredBody {
+    body: Body,
+    grant: AccessGrant,
+    counter: MessageCounter,
+    /// messages already counted when the request was authorized
+    free_messages: u64,
+    /// whether the rate limit was exceeded, ending the body
+    failed: bool,
+}
+
+impl Stream for MeteredBody {
+    type Item = Result<Bytes, Box<dyn std::error::Error + Send + Sync>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.failed {
+            return Poll::Ready(None);
+        }
+        let chunk = match ready!(Pin::new(&mut this.body).poll_data(cx)) {
+            Some(Ok(chunk)) => chunk,
+            Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+            None => return Poll::Ready(None),
+        };
+        let messages = this.counter.count(&chunk);
+        let counted = messages.saturating_sub(this.free_messages);
+        this.free_messages = this.free_messages.saturating_sub(messages);
+        if counted > 0 {
+            if let Err(err) = this.grant.consume_requests(counted) {
+                debug!("refused gRPC message: {}", err);
+                this.failed = true;
+                return Poll::Ready(Some(Err(Box::new(tonic::Status::from(err)))));
+            }
+        }
+        Poll::Ready(Some(Ok(chunk)))
+    }
+}
+
+/// Counter of the gRPC messages starting in the successive chunks of a body
+#[derive(Default)]
+pub(crate) struct MessageCounter {
+    /// bytes of the header of the next message received so far
+    header: Vec<u8>,
+    /// bytes of the current message not received yet
+    remaining: usize,
+}
+
+impl MessageCounter {
+    /// Count the messages whose header ends in a chunk
+    pub(crate) fn count(&mut self, mut chunk: &[u8]) -> u64 {
+        let mut count = 0;
+        while !chunk.is_empty() {
+            if self.remaining > 0 {
+                let len = self.remaining.min(chunk.len());
+                self.remaining -= len;
+                chunk = &chunk[len..];
+                continue;
+            }
+            let len = (GRPC_MESSAGE_HEADER_LEN - self.header.len()).min(chunk.len());
+            self.header.extend_from_slice(&chunk[..len]);
+            chunk = &chunk[len..];
+            if self.header.len() == GRPC_MESSAGE_HEADER_LEN {
+                let length = [
+                    self.header[1],
+                    self.header[2],
+                    self.header[3],
+                    self.header[4],
+                ];
+                self.remaining = u32::from_be_bytes(length) as usize;
+                self.header.clear();
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+/// Get the client IP from a header set by a trusted load balancer (the first one of a forwarding chain)
+fn client_ip(headers: &HeaderMap, header: &str) -> Option<IpAddr> {
+    header_str(headers, header)?
+        .split(',')
+        .next()
+        .and_then(|ip| ip.trim().parse().ok())
+}
+
+/// Get the IP of the connection of a request, with or without TLS
+fn remote_ip(request: &Request<Body>) -> Option<IpAddr> {
+    let extensions = request.extensions();
+    extensions
+        .get::<TcpConnectInfo>()
+        .or_else(|| {
+            extensions
+                .get::<TlsConnectInfo<TcpConnectInfo>>()
+                .map(|info| info.get_ref())
+        })
+        .and_then(|info| info.remote_addr())
+        .map(|addr| addr.ip())
+}
+
+/// Convert a gRPC method name to snake case (e.g. `GetMipStatus` to `get_mip_status`)
+fn to_snake_case(method: &str) -> String {
+    let mut snake_case = String::with_capacity(method.len() + 4);
+    for (index, c) in method.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if index > 0 {
+                snake_case.push('_');
+            }
+            snake_case.push(c.to_ascii_lowercase());
+        } else {
+            snake_case.push(c);
+        }
+    }
+    snake_case
+}
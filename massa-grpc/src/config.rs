@@ -1,5 +1,6 @@
 // Copyright (c) 2023 MASSA LABS <info@massa.net>
 
+use massa_api_exports::access::AccessConfig;
 use massa_models::amount::Amount;
 use massa_signature::KeyPair;
 use massa_time::MassaTime;
@@ -136,6 +137,8 @@ pub struct GrpcConfig {
     pub chain_id: u64,
     /// minimal fees
     pub minimal_fees: Amount,
+    /// access control of the server (API keys and rate limits)
+    pub access: AccessConfig,
 }
 
 /// gRPC API configuration.
@@ -4,6 +4,7 @@ use std::error::Error;
 
 use displaydoc::Display;
 
+use massa_api_exports::access::AccessError;
 use massa_consensus_exports::error::ConsensusError;
 use massa_execution_exports::ExecutionError;
 use massa_hash::MassaHashError;
@@ -45,6 +46,8 @@ pub enum GrpcError {
     InvalidArgument(String),
     /// Not implemented error: {0}
     Unimplemented(String),
+    /// Access error: {0}
+    AccessError(#[from] AccessError),
 }
 
 impl From<GrpcError> for tonic::Status {
@@ -64,6 +67,12 @@ impl From<GrpcError> for tonic::Status {
             GrpcError::ReflectionError(e) => tonic::Status::internal(e.to_string()),
             GrpcError::InvalidArgument(e) => tonic::Status::invalid_argument(e),
             GrpcError::Unimplemented(e) => tonic::Status::unimplemented(e),
+            GrpcError::AccessError(e) => match e {
+                AccessError::Unauthorized => tonic::Status::unauthenticated(e.to_string()),
+                AccessError::Forbidden(_) => tonic::Status::permission_denied(e.to_string()),
+                AccessError::RateLimited => tonic::Status::resource_exhausted(e.to_string()),
+                AccessError::UnknownClientIp => tonic::Status::unauthenticated(e.to_string()),
+            },
         }
     }
 }
@@ -0,0 +1,172 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Methods of the gRPC API that are not defined by [massa_proto_rs] yet.
+//!
+//! They are served next to the generated services, by the `massa.api.v1.PublicServiceExt`
+//! and `massa.api.v1.PrivateServiceExt` services defined in `proto/massa/api/v1/ext.proto`.
+//! Their messages are defined by the [massa_sdk::grpc_ext] module, shared with the clients,
+//! until that file is moved to massa-proto-rs. Until then, the reflection service does not
+//! list these services: clients need the `.proto` file to call them.
+
+use crate::error::GrpcError;
+use crate::private::{get_mip_status_with_votes, set_mip_vote};
+use crate::public::{get_addresses_draws, get_async_messages, get_datastore_entries_page};
+use crate::server::{MassaPrivateGrpc, MassaPublicGrpc};
+use hyper::Body;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tonic::body::BoxBody;
+use tonic::codec::ProstCodec;
+use tonic::codegen::{empty_body, http, BoxFuture, Service};
+use tonic::transport::NamedService;
+
+pub use massa_sdk::grpc_ext::{
+    AddressDraws, AsyncMessageEntry, CycleDraws, DatastoreKeyValue, EndorsementSlot,
+    GetAddressesDrawsRequest, GetAddressesDrawsResponse, GetAsyncMessagesRequest,
+    GetAsyncMessagesResponse, GetDatastoreEntriesPageRequest, GetDatastoreEntriesPageResponse,
+    GetMipStatusWithVotesRequest, GetMipStatusWithVotesResponse, MipStatusWithVote, MipVoteKind,
+    SetMipVoteRequest, SetMipVoteResponse,
+};
+
+/// Server of the extension methods of a gRPC API
+pub struct ExtServer<T> {
+    inner: Arc<T>,
+    max_decoding_message_size: usize,
+    max_encoding_message_size: usize,
+}
+
+impl<T> Clone for ExtServer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            max_decoding_message_size: self.max_decoding_message_size,
+            max_encoding_message_size: self.max_encoding_message_size,
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> ExtServer<T> {
+    /// Creates a new server of the extension methods, with the message size limits of the generated services
+    pub fn new(
+        inner: T,
+        max_decoding_message_size: usize,
+        max_encoding_message_size: usize,
+    ) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            max_decoding_message_size,
+            max_encoding_message_size,
+        }
+    }
+
+    /// Answers a unary request with the given handler
+    fn unary<Req, Resp>(
+        &self,
+        req: http::Request<Body>,
+        handler: fn(&T, tonic::Request<Req>) -> Result<Resp, GrpcError>,
+    ) -> BoxFuture<http::Response<BoxBody>, Infallible>
+    where
+        Req: prost::Message + Default + Send + 'static,
+        Resp: prost::Message + Send + 'static,
+    {
+        let service = UnaryHandler {
+            inner: self.inner.clone(),
+            handler,
+        };
+        let mut grpc = tonic::server::Grpc::new(ProstCodec::<Resp, Req>::default())
+            .apply_max_message_size_config(
+                Some(self.max_decoding_message_size),
+                Some(self.max_encoding_message_size),
+            );
+        Box::pin(async move { Ok(grpc.unary(service, req).await) })
+    }
+}
+
+/// Unary method of an extension service
+struct UnaryHandler<T, Req, Resp> {
+    inner: Arc<T>,
+    handler: fn(&T, tonic::Request<Req>) -> Result<Resp, GrpcError>,
+}
+
+impl<T, Req, Resp> tonic::server::UnaryService<Req> for UnaryHandler<T, Req, Resp>
+where
+    T: Send + Sync + 'static,
+    Req: Send + 'static,
+    Resp: Send + 'static,
+{
+    type Response = Resp;
+    type Future = BoxFuture<tonic::Response<Resp>, tonic::Status>;
+
+    fn call(&mut self, request: tonic::Request<Req>) -> Self::Future {
+        let result = (self.handler)(&self.inner, request)
+            .map(tonic::Response::new)
+            .map_err(tonic::Status::from);
+        Box::pin(std::future::ready(result))
+    }
+}
+
+/// Answers the requests to unknown methods with the `UNIMPLEMENTED` gRPC status
+fn unimplemented() -> BoxFuture<http::Response<BoxBody>, Infallible> {
+    Box::pin(async move {
+        Ok(http::Response::builder()
+            .status(200)
+            .header("grpc-status", "12")
+            .header("content-type", "application/grpc")
+            .body(empty_body())
+            .expect("critical: invalid unimplemented gRPC response"))
+    })
+}
+
+impl Service<http::Request<Body>> for ExtServer<MassaPublicGrpc> {
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<Body>) -> Self::Future {
+        match req.uri().path() {
+            "/massa.api.v1.PublicServiceExt/GetDatastoreEntriesPage" => {
+                self.unary(req, get_datastore_entries_page)
+            }
+            "/massa.api.v1.PublicServiceExt/GetAddressesDraws" => {
+                self.unary(req, get_addresses_draws)
+            }
+            "/massa.api.v1.PublicServiceExt/GetAsyncMessages" => {
+                self.unary(req, get_async_messages)
+            }
+            _ => unimplemented(),
+        }
+    }
+}
+
+impl NamedService for ExtServer<MassaPublicGrpc> {
+    const NAME: &'static str = "massa.api.v1.PublicServiceExt";
+}
+
+impl Service<http::Request<Body>> for ExtServer<MassaPrivateGrpc> {
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<Body>) -> Self::Future {
+        match req.uri().path() {
+            "/massa.api.v1.PrivateServiceExt/GetMipStatusWithVotes" => {
+                self.unary(req, get_mip_status_with_votes)
+            }
+            "/massa.api.v1.PrivateServiceExt/SetMipVote" => self.unary(req, set_mip_vote),
+            _ => unimplemented(),
+        }
+    }
+}
+
+impl NamedService for ExtServer<MassaPrivateGrpc> {
+    const NAME: &'static str = "massa.api.v1.PrivateServiceExt";
+}
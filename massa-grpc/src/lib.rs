@@ -7,7 +7,9 @@
 //!
 //! ## **Structure**
 //!
+//! * `access.rs`: checks the API keys and rate limits of the requests.
 //! * `api.rs`: implements gRPC service methods without streams.
+//! * `ext.rs`: serves the gRPC methods that are not defined by [massa_proto_rs] yet.
 //! * `handler.rs`: defines the logic for handling incoming gRPC requests.
 //! * `server`: initializes the gRPC service and serve It.
 //! * `stream/`: contains the gRPC streaming methods implementations files.
@@ -26,10 +28,14 @@ use tonic_web as _;
 #[cfg(feature = "execution-trace")]
 use serde_json as _;
 
+/// API keys and rate limits
+mod access;
 /// gRPC configuration
 pub mod config;
 /// models error
 pub mod error;
+/// gRPC methods not defined by massa-proto-rs yet
+pub mod ext;
 /// gRPC API implementation
 pub mod handler;
 /// business code for node management methods
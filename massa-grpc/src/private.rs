@@ -4,6 +4,10 @@ use std::net::IpAddr;
 use std::str::FromStr;
 
 use crate::error::GrpcError;
+use crate::ext::{
+    GetMipStatusWithVotesRequest, GetMipStatusWithVotesResponse, MipStatusWithVote, MipVoteKind,
+    SetMipVoteRequest, SetMipVoteResponse,
+};
 use crate::server::MassaPrivateGrpc;
 use massa_execution_exports::ExecutionQueryRequest;
 use massa_hash::Hash;
@@ -16,6 +20,7 @@ use massa_proto_rs::massa::model::v1 as grpc_model;
 use massa_protocol_exports::{PeerConnectionType, PeerId};
 use massa_signature::KeyPair;
 use massa_time::MassaTime;
+use massa_versioning::mip_votes::MipVote;
 use tracing::warn;
 // use massa_proto_rs::massa::model::v1 "add_to_bootstrap_blacklist"as grpc_model;
 
@@ -213,6 +218,70 @@ pub(crate) fn get_mip_status(
     })
 }
 
+/// Get the status of the MIPs known by the node, with the votes of the node operator
+pub(crate) fn get_mip_status_with_votes(
+    grpc: &MassaPrivateGrpc,
+    _request: tonic::Request<GetMipStatusWithVotesRequest>,
+) -> Result<GetMipStatusWithVotesResponse, GrpcError> {
+    let votes = grpc.mip_votes.get_votes();
+    let entries = grpc
+        .mip_store
+        .get_mip_status()
+        .iter()
+        .map(|(mip_info, state_id)| {
+            let vote = match votes.get(&mip_info.name) {
+                Some(MipVote::For) => MipVoteKind::For,
+                Some(MipVote::Against) => MipVoteKind::Against,
+                None => MipVoteKind::Unspecified,
+            };
+            MipStatusWithVote {
+                status: Some(grpc_model::MipStatusEntry {
+                    mip_info: Some(grpc_model::MipInfo::from(mip_info)),
+                    state_id: i32::from(grpc_model::ComponentStateId::from(state_id)),
+                }),
+                vote: vote as i32,
+            }
+        })
+        .collect();
+
+    Ok(GetMipStatusWithVotesResponse { entries })
+}
+
+/// Set or remove the vote of the node operator on a MIP
+pub(crate) fn set_mip_vote(
+    grpc: &MassaPrivateGrpc,
+    request: tonic::Request<SetMipVoteRequest>,
+) -> Result<SetMipVoteResponse, GrpcError> {
+    let request = request.into_inner();
+    let vote = match MipVoteKind::try_from(request.vote) {
+        Ok(MipVoteKind::Unspecified) => None,
+        Ok(MipVoteKind::For) => Some(MipVote::For),
+        Ok(MipVoteKind::Against) => Some(MipVote::Against),
+        Err(_) => {
+            return Err(GrpcError::InvalidArgument(format!(
+                "invalid vote {}",
+                request.vote
+            )))
+        }
+    };
+    if !grpc
+        .mip_store
+        .get_mip_status()
+        .keys()
+        .any(|mip_info| mip_info.name == request.name)
+    {
+        return Err(GrpcError::InvalidArgument(format!(
+            "unknown MIP {}",
+            request.name
+        )));
+    }
+    grpc.mip_votes
+        .set_vote(request.name, vote)
+        .map_err(|err| GrpcError::InternalServerError(err.to_string()))?;
+
+    Ok(SetMipVoteResponse {})
+}
+
 /// Allow everyone to bootstrap from the node by removing bootstrap whitelist configuration file
 pub(crate) fn allow_everyone_to_bootstrap(
     _grpc: &MassaPrivateGrpc,
@@ -1,16 +1,26 @@
 // Copyright (c) 2023 MASSA LABS <info@massa.net>
 
+use crate::access::AccessGrant;
 use crate::error::GrpcError;
+use crate::ext::{
+    AddressDraws, AsyncMessageEntry, CycleDraws, DatastoreKeyValue, EndorsementSlot,
+    GetAddressesDrawsRequest, GetAddressesDrawsResponse, GetAsyncMessagesRequest,
+    GetAsyncMessagesResponse, GetDatastoreEntriesPageRequest, GetDatastoreEntriesPageResponse,
+};
 use crate::server::MassaPublicGrpc;
 use crate::{EndorsementDraw, SlotDraw, SlotRange};
 
 use itertools::{izip, Itertools};
+use massa_async_pool::{AsyncMessageFilter, AsyncMessageId};
 use massa_execution_exports::mapping_grpc::{
     to_event_filter, to_execution_query_response, to_querystate_filter,
 };
 use massa_execution_exports::{
-    ExecutionQueryRequest, ExecutionStackElement, ReadOnlyExecutionRequest, ReadOnlyExecutionTarget,
+    ExecutionQueryError, ExecutionQueryRequest, ExecutionQueryRequestItem,
+    ExecutionQueryResponseItem, ExecutionStackElement, ReadOnlyExecutionRequest,
+    ReadOnlyExecutionTarget,
 };
+use massa_ledger_exports::{DatastoreDirection, DatastoreRange};
 use massa_models::address::Address;
 use massa_models::amount::Amount;
 use massa_models::block::{Block, BlockGraphStatus};
@@ -27,7 +37,8 @@ use massa_proto_rs::massa::model::v1::{self as grpc_model, read_only_execution_c
 use massa_serialization::{DeserializeError, Deserializer};
 use massa_time::MassaTime;
 use massa_versioning::versioning_factory::{FactoryStrategy, VersioningFactory};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Bound;
 use std::str::FromStr;
 
 #[cfg(feature = "execution-trace")]
@@ -46,11 +57,17 @@ pub(crate) fn execute_read_only_call(
     grpc: &MassaPublicGrpc,
     request: tonic::Request<grpc_api::ExecuteReadOnlyCallRequest>,
 ) -> Result<grpc_api::ExecuteReadOnlyCallResponse, GrpcError> {
+    let access_grant = request.extensions().get::<AccessGrant>().cloned();
     let call: grpc_model::ReadOnlyExecutionCall = request
         .into_inner()
         .call
         .ok_or_else(|| GrpcError::InvalidArgument("no call provided".to_string()))?;
 
+    // count the gas of the call in the rate limits of the client
+    if let Some(access_grant) = access_grant {
+        access_grant.consume_read_only_gas(call.max_gas)?;
+    }
+
     let caller_address = match call.caller_address {
         Some(addr) => Address::from_str(&addr)?,
         None => {
@@ -279,6 +296,89 @@ pub(crate) fn get_datastore_entries(
     })
 }
 
+/// Get a page of the datastore entries of an address
+pub(crate) fn get_datastore_entries_page(
+    grpc: &MassaPublicGrpc,
+    request: tonic::Request<GetDatastoreEntriesPageRequest>,
+) -> Result<GetDatastoreEntriesPageResponse, GrpcError> {
+    let inner_req = request.into_inner();
+    let max_entries = grpc.grpc_config.max_datastore_entries_per_request;
+
+    let limit = inner_req.limit.map_or(max_entries, u64::from);
+    if limit > max_entries {
+        return Err(GrpcError::InvalidArgument(format!(
+            "too many datastore entries requested. Only a maximum of {} datastore entries are returned per request",
+            max_entries
+        )));
+    }
+    let limit = limit as usize;
+
+    let addr = Address::from_str(&inner_req.address)?;
+    let mut range = DatastoreRange {
+        prefix: inner_req.prefix,
+        start_key: inner_req
+            .start_key
+            .map_or(Bound::Unbounded, Bound::Included),
+        end_key: inner_req.end_key.map_or(Bound::Unbounded, Bound::Excluded),
+        direction: if inner_req.reverse {
+            DatastoreDirection::Reverse
+        } else {
+            DatastoreDirection::Forward
+        },
+    };
+    if let Some(cursor) = inner_req.cursor {
+        range = range.after(cursor);
+    }
+
+    // fetch one extra entry to know whether there is a next page
+    let query_limit = limit.saturating_add(1);
+    let query = if inner_req.is_final {
+        ExecutionQueryRequestItem::AddressDatastoreEntriesFinal {
+            addr,
+            range,
+            limit: query_limit,
+        }
+    } else {
+        ExecutionQueryRequestItem::AddressDatastoreEntriesCandidate {
+            addr,
+            range,
+            limit: query_limit,
+        }
+    };
+
+    let response = grpc
+        .execution_controller
+        .query_state(ExecutionQueryRequest {
+            requests: vec![query],
+        });
+    let mut entries = match response.responses.into_iter().next() {
+        Some(Ok(ExecutionQueryResponseItem::DatastoreEntries(entries))) => entries,
+        // the address has no ledger entry: its datastore is empty
+        Some(Err(ExecutionQueryError::NotFound(_))) => Vec::new(),
+        Some(Err(err)) => return Err(GrpcError::InternalServerError(err.to_string())),
+        Some(Ok(_)) | None => {
+            return Err(GrpcError::InternalServerError(
+                "unexpected response to datastore entries query".to_string(),
+            ))
+        }
+    };
+
+    let next_cursor = if entries.len() > limit {
+        entries.truncate(limit);
+        entries.last().map(|(key, _value)| key.clone())
+    } else {
+        None
+    };
+
+    Ok(GetDatastoreEntriesPageResponse {
+        entries: entries
+            .into_iter()
+            .map(|(key, value)| DatastoreKeyValue { key, value })
+            .collect(),
+        next_cursor,
+    })
+}
+
 /// Get endorsements
 pub(crate) fn get_endorsements(
     grpc: &MassaPublicGrpc,
@@ -910,6 +1010,146 @@ pub(crate) fn get_selector_draws(
     })
 }
 
+/// Get a page of the upcoming draws of a list of addresses
+pub(crate) fn get_addresses_draws(
+    grpc: &MassaPublicGrpc,
+    request: tonic::Request<GetAddressesDrawsRequest>,
+) -> Result<GetAddressesDrawsResponse, GrpcError> {
+    let inner_req = request.into_inner();
+    let max_addresses = grpc.grpc_config.max_addresses_per_request;
+
+    let limit = inner_req.limit.unwrap_or(max_addresses);
+    if limit > max_addresses {
+        return Err(GrpcError::InvalidArgument(format!(
+            "too many addresses requested. Only the draws of a maximum of {} addresses are returned per request",
+            max_addresses
+        )));
+    }
+    if let (Some(start_cycle), Some(end_cycle)) = (inner_req.start_cycle, inner_req.end_cycle) {
+        if start_cycle > end_cycle {
+            return Err(GrpcError::InvalidArgument(format!(
+                "invalid cycle range: start cycle {} is after end cycle {}",
+                start_cycle, end_cycle
+            )));
+        }
+    }
+
+    // only upcoming draws are returned
+    let cur_slot = get_latest_block_slot_at_timestamp(
+        grpc.grpc_config.thread_count,
+        grpc.grpc_config.t0,
+        grpc.grpc_config.genesis_timestamp,
+        MassaTime::now(),
+    )?
+    .unwrap_or_else(|| Slot::new(0, 0));
+    let start_cycle = std::cmp::max(
+        inner_req.start_cycle.unwrap_or(0),
+        cur_slot.get_cycle(grpc.grpc_config.periods_per_cycle),
+    );
+    let end_cycle = inner_req.end_cycle.unwrap_or(u64::MAX);
+
+    let offset = inner_req.offset.unwrap_or(0) as usize;
+    let mut draws = Vec::new();
+    for address in inner_req.addresses.iter().skip(offset).take(limit as usize) {
+        let cycles = grpc
+            .selector_controller
+            .get_address_draws(&Address::from_str(address)?, start_cycle..=end_cycle)
+            .map_err(|err| GrpcError::InternalServerError(err.to_string()))?
+            .into_iter()
+            .map(|cycle_draws| CycleDraws {
+                cycle: cycle_draws.cycle,
+                block_draws: cycle_draws
+                    .block_slots
+                    .into_iter()
+                    .filter(|slot| *slot >= cur_slot)
+                    .map(Into::into)
+                    .collect(),
+                endorsement_draws: cycle_draws
+                    .endorsement_slots
+                    .into_iter()
+                    .filter(|indexed| indexed.slot >= cur_slot)
+                    .map(|indexed| EndorsementSlot {
+                        slot: Some(indexed.slot.into()),
+                        index: indexed.index as u32,
+                    })
+                    .collect(),
+            })
+            .filter(|cycle_draws| {
+                !cycle_draws.block_draws.is_empty() || !cycle_draws.endorsement_draws.is_empty()
+            })
+            .collect();
+        draws.push(AddressDraws {
+            address: address.clone(),
+            cycles,
+        });
+    }
+
+    let next_offset = offset.saturating_add(limit as usize);
+    Ok(GetAddressesDrawsResponse {
+        draws,
+        next_offset: (next_offset < inner_req.addresses.len()).then_some(next_offset as u32),
+    })
+}
+
+/// Get the pending asynchronous messages matching a filter, in the final and candidate pools
+pub(crate) fn get_async_messages(
+    grpc: &MassaPublicGrpc,
+    request: tonic::Request<GetAsyncMessagesRequest>,
+) -> Result<GetAsyncMessagesResponse, GrpcError> {
+    let inner_req = request.into_inner();
+    let parse_address = |address: Option<String>| {
+        address
+            .map(|address| Address::from_str(&address))
+            .transpose()
+    };
+    let filter = AsyncMessageFilter {
+        sender: parse_address(inner_req.sender)?,
+        destination: parse_address(inner_req.destination)?,
+        validity_start: inner_req.validity_start.map(Slot::from),
+        validity_end: inner_req.validity_end.map(Slot::from),
+        trigger_address: parse_address(inner_req.trigger_address)?,
+        can_be_executed: inner_req.can_be_executed,
+    };
+
+    let response = grpc
+        .execution_controller
+        .query_state(ExecutionQueryRequest {
+            requests: vec![
+                ExecutionQueryRequestItem::AsyncMessagesFinal(filter.clone()),
+                ExecutionQueryRequestItem::AsyncMessagesCandidate(filter),
+            ],
+        });
+    let mut responses = response
+        .responses
+        .into_iter()
+        .map(|response| match response {
+            Ok(ExecutionQueryResponseItem::AsyncMessages(messages)) => Ok(messages),
+            Ok(_) => Err(GrpcError::InternalServerError(
+                "unexpected response to asynchronous messages query".to_string(),
+            )),
+            Err(err) => Err(GrpcError::InternalServerError(err.to_string())),
+        });
+    let (Some(final_messages), Some(candidate_messages)) = (responses.next(), responses.next())
+    else {
+        return Err(GrpcError::InternalServerError(
+            "missing response to asynchronous messages query".to_string(),
+        ));
+    };
+
+    // merge the final and candidate views, keeping the pool priority order
+    let mut messages: BTreeMap<AsyncMessageId, AsyncMessageEntry> = BTreeMap::new();
+    for (id, msg) in final_messages? {
+        messages.entry(id).or_default().final_message = Some(msg.into());
+    }
+    for (id, msg) in candidate_messages? {
+        messages.entry(id).or_default().candidate_message = Some(msg.into());
+    }
+
+    Ok(GetAsyncMessagesResponse {
+        messages: messages.into_values().collect(),
+    })
+}
+
 //  Get status
 pub(crate) fn get_status(
     grpc: &MassaPublicGrpc,
@@ -3,14 +3,16 @@
 use massa_bootstrap::white_black_list::SharedWhiteBlackList;
 use massa_models::node::NodeId;
 use massa_versioning::keypair_factory::KeyPairFactory;
-use massa_versioning::versioning::MipStore;
+use massa_versioning::{mip_votes::MipVotes, versioning::MipStore};
 use parking_lot::RwLock;
 use std::convert::Infallible;
 use std::path::Path;
 use std::sync::{Arc, Condvar, Mutex};
 
+use crate::access::AccessLayer;
 use crate::config::{GrpcConfig, ServiceName};
 use crate::error::GrpcError;
+use crate::ext::ExtServer;
 use futures_util::FutureExt;
 use hyper::service::Service;
 use hyper::{Body, Method, Request, Response};
@@ -62,6 +64,8 @@ pub struct MassaPrivateGrpc {
     pub node_id: NodeId,
     /// database for all MIP info
     pub mip_store: MipStore,
+    /// votes of the node operator on MIPs
+    pub mip_votes: MipVotes,
     /// node version
     pub version: massa_models::version::Version,
     /// white/black list of bootstrap
@@ -71,6 +75,11 @@ pub struct MassaPrivateGrpc {
 impl MassaPrivateGrpc {
     /// Start the gRPC PRIVATE API
     pub async fn serve(self, config: &GrpcConfig) -> Result<StopHandle, GrpcError> {
+        let ext_service = ExtServer::new(
+            self.clone(),
+            config.max_decoding_message_size,
+            config.max_encoding_message_size,
+        );
         let mut service = PrivateServiceServer::new(self)
             .max_decoding_message_size(config.max_decoding_message_size)
             .max_encoding_message_size(config.max_encoding_message_size);
@@ -87,7 +96,7 @@ impl MassaPrivateGrpc {
             };
         }
 
-        serve(service, config).await
+        serve(service, ext_service, config).await
     }
 }
 
@@ -127,6 +136,11 @@ pub struct MassaPublicGrpc {
 impl MassaPublicGrpc {
     /// Start the gRPC PUBLIC API
     pub async fn serve(self, config: &GrpcConfig) -> Result<StopHandle, GrpcError> {
+        let ext_service = ExtServer::new(
+            self.clone(),
+            config.max_decoding_message_size,
+            config.max_encoding_message_size,
+        );
         let mut service = PublicServiceServer::new(self)
             .max_decoding_message_size(config.max_decoding_message_size)
             .max_encoding_message_size(config.max_encoding_message_size);
@@ -142,7 +156,7 @@ impl MassaPublicGrpc {
                 service = service.send_compressed(CompressionEncoding::Gzip);
             };
         }
-        serve(service, config).await
+        serve(service, ext_service, config).await
     }
 }
 
@@ -170,8 +184,12 @@ async fn massa_service_status(mut reporter: HealthReporter) {
         .await;
 }
 
-// Configure and start the gRPC API with the given service
-async fn serve<S>(service: S, config: &GrpcConfig) -> Result<StopHandle, GrpcError>
+// Configure and start the gRPC API with the given service and the service of its extension methods
+async fn serve<S, E>(
+    service: S,
+    ext_service: E,
+    config: &GrpcConfig,
+) -> Result<StopHandle, GrpcError>
 where
     S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
         + NamedService
@@ -179,6 +197,12 @@ where
         + Send
         + 'static,
     S::Future: Send + 'static,
+    E: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
+        + NamedService
+        + Clone
+        + Send
+        + 'static,
+    E::Future: Send + 'static,
 {
     let (shutdown_send, shutdown_recv) = oneshot::channel::<()>();
 
@@ -260,6 +284,9 @@ where
         None
     };
 
+    // API keys and rate limits of the Massa services
+    let access = AccessLayer::new(&config.access)?;
+
     if config.accept_http1 {
         if config.enable_cors {
             let cors = CorsLayer::new()
@@ -273,9 +300,11 @@ where
                 .accept_http1(true)
                 .layer(cors)
                 .layer(GrpcWebLayer::new())
+                .layer(access)
                 .add_optional_service(reflection_service_opt)
                 .add_optional_service(health_service_opt)
-                .add_service(service);
+                .add_service(service)
+                .add_service(ext_service);
 
             tokio::spawn(
                 router_with_http1.serve_with_shutdown(config.bind, shutdown_recv.map(drop)),
@@ -284,9 +313,11 @@ where
             let router_with_http1 = server_builder
                 .accept_http1(true)
                 .layer(GrpcWebLayer::new())
+                .layer(access)
                 .add_optional_service(reflection_service_opt)
                 .add_optional_service(health_service_opt)
-                .add_service(service);
+                .add_service(service)
+                .add_service(ext_service);
 
             tokio::spawn(
                 router_with_http1.serve_with_shutdown(config.bind, shutdown_recv.map(drop)),
@@ -294,9 +325,11 @@ where
         }
     } else {
         let router = server_builder
+            .layer(access)
             .add_optional_service(reflection_service_opt)
             .add_optional_service(health_service_opt)
-            .add_service(service);
+            .add_service(service)
+            .add_service(ext_service);
 
         tokio::spawn(router.serve_with_shutdown(config.bind, shutdown_recv.map(drop)));
     }
@@ -4,12 +4,15 @@ use crate::error::{match_for_io_error, GrpcError};
 use crate::server::MassaPublicGrpc;
 use futures_util::StreamExt;
 use massa_models::block::{BlockDeserializer, BlockDeserializerArgs, SecureShareBlock};
+use massa_models::block_header::BlockHeaderVersionAt;
 use massa_models::error::ModelsError;
 use massa_models::secure_share::SecureShareDeserializer;
 use massa_proto_rs::massa::api::v1 as grpc_api;
 use massa_serialization::{DeserializeError, Deserializer};
+use massa_versioning::block_header_factory::BlockHeaderFactory;
 use std::io::ErrorKind;
 use std::pin::Pin;
+use std::sync::Arc;
 use tokio::sync::mpsc::Sender;
 use tonic::Request;
 use tracing::{error, warn};
@@ -35,6 +38,12 @@ pub(crate) async fn send_blocks(
     let protocol_command_sender = grpc.protocol_controller.clone();
     let config = grpc.grpc_config.clone();
     let storage = grpc.storage.clone_without_refs();
+    let block_header_version_at: Arc<dyn BlockHeaderVersionAt> = Arc::new(BlockHeaderFactory {
+        mip_store: grpc.keypair_factory.mip_store.clone(),
+        thread_count: config.thread_count,
+        t0: config.t0,
+        genesis_timestamp: config.genesis_timestamp,
+    });
 
     // Create a channel to handle communication with the client
     let (tx, rx) = tokio::sync::mpsc::channel(config.max_channel_size);
@@ -65,6 +74,7 @@ pub(crate) async fn send_blocks(
                             .max_denunciations_per_block_header,
                         last_start_period: Some(config.last_start_period),
                         chain_id: config.chain_id,
+                        block_header_version_at: Some(block_header_version_at.clone()),
                     };
                     // Deserialize and verify received block in the incoming message
                     match SecureShareDeserializer::new(
@@ -0,0 +1,29 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+use crate::access::MessageCounter;
+
+/// gRPC framing of a message with the given payload
+fn message(payload: &[u8]) -> Vec<u8> {
+    let mut message = vec![0];
+    message.extend((payload.len() as u32).to_be_bytes());
+    message.extend(payload);
+    message
+}
+
+#[test]
+fn count_messages() {
+    // several messages in a single chunk
+    let mut counter = MessageCounter::default();
+    let mut chunk = message(b"first");
+    chunk.extend(message(b""));
+    chunk.extend(message(b"third"));
+    assert_eq!(counter.count(&chunk), 3);
+
+    // messages split across chunks, including in their headers
+    let mut counter = MessageCounter::default();
+    let mut body = message(b"first message");
+    body.extend(message(b"second message"));
+    let counts: Vec<u64> = body.chunks(3).map(|chunk| counter.count(chunk)).collect();
+    assert_eq!(counts.iter().sum::<u64>(), 2);
+    assert_eq!(counts[1], 1);
+}
@@ -111,6 +111,7 @@ pub(crate) fn grpc_public_service(addr: &SocketAddr) -> MassaPublicGrpc {
         max_query_items_per_request: 50,
         chain_id: *CHAINID,
         minimal_fees: Amount::zero(),
+        access: Default::default(),
     };
 
     let mip_stats_config = MipStatsConfig {
@@ -3,6 +3,8 @@
 #[cfg(test)]
 pub mod mock;
 
+#[cfg(test)]
+mod access;
 #[cfg(test)]
 mod public;
 #[cfg(test)]
@@ -1,16 +1,22 @@
 // Copyright (c) 2023 MASSA LABS <info@massa.net>
 
+use crate::ext::{
+    GetAddressesDrawsRequest, GetAddressesDrawsResponse, GetDatastoreEntriesPageRequest,
+    GetDatastoreEntriesPageResponse,
+};
 use crate::tests::mock::grpc_public_service;
 use massa_consensus_exports::MockConsensusController;
-use massa_execution_exports::{EventStore, MockExecutionController};
+use massa_execution_exports::{
+    EventStore, ExecutionQueryError, ExecutionQueryResponseItem, MockExecutionController,
+};
 use massa_models::address::Address;
 use massa_models::block::BlockGraphStatus;
 use massa_models::block_id::BlockId;
 use massa_models::config::VERSION;
-use massa_models::slot::Slot;
+use massa_models::slot::{IndexedSlot, Slot};
 use massa_models::stats::ExecutionStats;
 use massa_pool_exports::MockPoolController;
-use massa_pos_exports::{MockSelectorController, Selection};
+use massa_pos_exports::{AddressCycleDraws, MockSelectorController, Selection};
 use massa_proto_rs::massa::api::v1::get_datastore_entry_filter::Filter;
 use massa_proto_rs::massa::api::v1::public_service_client::PublicServiceClient;
 use massa_proto_rs::massa::api::v1::{
@@ -336,6 +342,221 @@ async fn get_datastore_entries() {
     stop_handle.stop();
 }
 
+#[tokio::test]
+async fn get_datastore_entries_page() {
+    let addr: SocketAddr = "[::]:4034".parse().unwrap();
+    let mut public_server = grpc_public_service(&addr);
+
+    let mut exec_ctrl = Box::new(MockExecutionController::new());
+    exec_ctrl.expect_query_state().returning(|req| {
+        let response = match &req.requests[0] {
+            massa_execution_exports::ExecutionQueryRequestItem::AddressDatastoreEntriesFinal {
+                limit,
+                ..
+            } => {
+                // one more entry than the requested page size
+                assert_eq!(*limit, 3);
+                Ok(ExecutionQueryResponseItem::DatastoreEntries(vec![
+                    (vec![1], vec![10]),
+                    (vec![2], vec![20]),
+                    (vec![3], vec![30]),
+                ]))
+            }
+            _ => Err(ExecutionQueryError::NotFound("address".to_string())),
+        };
+        massa_execution_exports::ExecutionQueryResponse {
+            responses: vec![response],
+            candidate_cursor: Slot::new(1, 2),
+            final_cursor: Slot::new(1, 7),
+            final_state_fingerprint: massa_hash::Hash::compute_from(&Vec::new()),
+        }
+    });
+
+    public_server.execution_controller = exec_ctrl;
+    let config = public_server.grpc_config.clone();
+
+    let stop_handle = public_server.serve(&config).await.unwrap();
+    let channel = tonic::transport::Channel::from_shared(format!(
+        "grpc://localhost:{}",
+        addr.to_string().split(':').last().unwrap()
+    ))
+    .unwrap()
+    .connect()
+    .await
+    .unwrap();
+    let mut client = tonic::client::Grpc::new(channel);
+    let path = tonic::codegen::http::uri::PathAndQuery::from_static(
+        "/massa.api.v1.PublicServiceExt/GetDatastoreEntriesPage",
+    );
+    let request = GetDatastoreEntriesPageRequest {
+        address: "AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x".to_string(),
+        limit: Some(2),
+        is_final: true,
+        ..Default::default()
+    };
+
+    client.ready().await.unwrap();
+    let page: GetDatastoreEntriesPageResponse = client
+        .unary(
+            tonic::Request::new(request.clone()),
+            path.clone(),
+            tonic::codec::ProstCodec::default(),
+        )
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(page.entries.len(), 2);
+    assert_eq!(page.entries[1].value, vec![20]);
+    assert_eq!(page.next_cursor, Some(vec![2]));
+
+    // an address without ledger entry has an empty datastore
+    client.ready().await.unwrap();
+    let page: GetDatastoreEntriesPageResponse = client
+        .unary(
+            tonic::Request::new(GetDatastoreEntriesPageRequest {
+                is_final: false,
+                ..request.clone()
+            }),
+            path.clone(),
+            tonic::codec::ProstCodec::default(),
+        )
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(page.entries.is_empty());
+    assert_eq!(page.next_cursor, None);
+
+    // pages can't be larger than the per request maximum
+    client.ready().await.unwrap();
+    let result: Result<tonic::Response<GetDatastoreEntriesPageResponse>, _> = client
+        .unary(
+            tonic::Request::new(GetDatastoreEntriesPageRequest {
+                limit: Some(u32::MAX),
+                ..request
+            }),
+            path,
+            tonic::codec::ProstCodec::default(),
+        )
+        .await;
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+
+    stop_handle.stop();
+}
+
+#[tokio::test]
+async fn get_addresses_draws() {
+    let addr: SocketAddr = "[::]:4035".parse().unwrap();
+    let mut public_server = grpc_public_service(&addr);
+
+    let mut selector_ctrl = Box::new(MockSelectorController::new());
+    selector_ctrl
+        .expect_get_address_draws()
+        .returning(|_address, cycle_range| {
+            assert_eq!(*cycle_range.end(), u64::MAX);
+            Ok(vec![AddressCycleDraws {
+                cycle: 1_000_000,
+                // past draws are not returned
+                block_slots: vec![Slot::new(1, 0), Slot::new(u64::MAX - 1, 0)],
+                endorsement_slots: vec![IndexedSlot {
+                    slot: Slot::new(u64::MAX - 1, 1),
+                    index: 4,
+                }],
+            }])
+        });
+    public_server.selector_controller = selector_ctrl;
+    let config = public_server.grpc_config.clone();
+
+    let stop_handle = public_server.serve(&config).await.unwrap();
+    let channel = tonic::transport::Channel::from_shared(format!(
+        "grpc://localhost:{}",
+        addr.to_string().split(':').last().unwrap()
+    ))
+    .unwrap()
+    .connect()
+    .await
+    .unwrap();
+    let mut client = tonic::client::Grpc::new(channel);
+    let path = tonic::codegen::http::uri::PathAndQuery::from_static(
+        "/massa.api.v1.PublicServiceExt/GetAddressesDraws",
+    );
+    let request = GetAddressesDrawsRequest {
+        addresses: vec![
+            "AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x".to_string(),
+            "AU1nHnddh6N4BybVGMKR9SWzoJKpabSaYVhezs96MwEp3NLD2DyW".to_string(),
+            "AU12ZmAhr2pVwMM7iiMBb6A7mBi5VrCXVh8gM6Z889WmhcqNdNddk".to_string(),
+        ],
+        limit: Some(2),
+        ..Default::default()
+    };
+
+    client.ready().await.unwrap();
+    let page: GetAddressesDrawsResponse = client
+        .unary(
+            tonic::Request::new(request.clone()),
+            path.clone(),
+            tonic::codec::ProstCodec::default(),
+        )
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(page.draws.len(), 2);
+    assert_eq!(page.draws[1].address, request.addresses[1]);
+    let cycle_draws = &page.draws[0].cycles[0];
+    assert_eq!(cycle_draws.cycle, 1_000_000);
+    assert_eq!(cycle_draws.block_draws.len(), 1);
+    assert_eq!(cycle_draws.endorsement_draws[0].index, 4);
+    assert_eq!(page.next_offset, Some(2));
+
+    // last page
+    client.ready().await.unwrap();
+    let page: GetAddressesDrawsResponse = client
+        .unary(
+            tonic::Request::new(GetAddressesDrawsRequest {
+                offset: page.next_offset,
+                ..request.clone()
+            }),
+            path.clone(),
+            tonic::codec::ProstCodec::default(),
+        )
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(page.draws.len(), 1);
+    assert_eq!(page.draws[0].address, request.addresses[2]);
+    assert_eq!(page.next_offset, None);
+
+    // pages can't be larger than the per request maximum
+    client.ready().await.unwrap();
+    let result: Result<tonic::Response<GetAddressesDrawsResponse>, _> = client
+        .unary(
+            tonic::Request::new(GetAddressesDrawsRequest {
+                limit: Some(u32::MAX),
+                ..request.clone()
+            }),
+            path.clone(),
+            tonic::codec::ProstCodec::default(),
+        )
+        .await;
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+
+    // the cycle range can't be reversed
+    client.ready().await.unwrap();
+    let result: Result<tonic::Response<GetAddressesDrawsResponse>, _> = client
+        .unary(
+            tonic::Request::new(GetAddressesDrawsRequest {
+                start_cycle: Some(10),
+                end_cycle: Some(9),
+                ..request
+            }),
+            path,
+            tonic::codec::ProstCodec::default(),
+        )
+        .await;
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+
+    stop_handle.stop();
+}
+
 #[tokio::test]
 async fn execute_read_only_call() {
     let addr: SocketAddr = "[::]:4007".parse().unwrap();
@@ -362,6 +583,8 @@ async fn execute_read_only_call() {
                     deferred_credits_execution: vec![],
                     cancel_async_message_execution: vec![],
                     auto_sell_execution: vec![],
+                    reward_credits: vec![],
+                    transactions: vec![],
                 },
                 gas_cost: 100,
                 call_result: "toto".as_bytes().to_vec(),
@@ -1075,6 +1075,8 @@ async fn new_slot_execution_outputs() {
         deferred_credits_execution: vec![],
         cancel_async_message_execution: vec![],
         auto_sell_execution: vec![],
+        reward_credits: vec![],
+        transactions: vec![],
     };
 
     let (tx_request, rx) = tokio::sync::mpsc::channel(10);
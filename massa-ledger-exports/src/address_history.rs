@@ -0,0 +1,340 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! This file defines the structures recorded by the optional address history index of the `FinalLedger`
+
+use massa_models::amount::{Amount, AmountDeserializer, AmountSerializer};
+use massa_models::bytecode::{Bytecode, BytecodeDeserializer, BytecodeSerializer};
+use massa_models::config::{MAX_DATASTORE_KEY_LENGTH, MAX_DATASTORE_VALUE_LENGTH};
+use massa_models::serialization::{VecU8Deserializer, VecU8Serializer};
+use massa_models::slot::{Slot, SlotDeserializer, SlotSerializer};
+use massa_serialization::{
+    BoolDeserializer, BoolSerializer, Deserializer, OptionDeserializer, OptionSerializer,
+    SerializeError, Serializer, U64VarIntDeserializer, U64VarIntSerializer,
+};
+use nom::error::{context, ContextError, ParseError};
+use nom::multi::length_count;
+use nom::sequence::tuple;
+use nom::{IResult, Parser};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use std::collections::BTreeMap;
+use std::ops::Bound::{Excluded, Included};
+
+/// Maximum number of datastore changes recorded in an `AddressHistoryEntry`
+pub const ADDRESS_HISTORY_MAX_DATASTORE_CHANGES: u64 = 1000;
+
+/// Maximum total size in bytes of the datastore keys and values recorded in an `AddressHistoryEntry`,
+/// enough for a change between two values of the maximum length
+pub const ADDRESS_HISTORY_MAX_DATASTORE_SIZE: u64 =
+    2 * (MAX_DATASTORE_KEY_LENGTH as u64 + MAX_DATASTORE_VALUE_LENGTH);
+
+/// Values of a ledger sub-entry before and after a change.
+/// `None` means that the sub-entry did not exist.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ValueChange<T> {
+    /// value before the change
+    pub before: Option<T>,
+    /// value after the change
+    pub after: Option<T>,
+}
+
+impl<T: PartialEq> ValueChange<T> {
+    /// Creates a `ValueChange`, or returns `None` if the value did not change
+    pub fn new(before: Option<T>, after: Option<T>) -> Option<Self> {
+        if before == after {
+            None
+        } else {
+            Some(ValueChange { before, after })
+        }
+    }
+}
+
+/// Changes applied to the ledger entry of an address at a given final slot
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct AddressHistoryEntry {
+    /// slot at which the changes were finalized
+    pub slot: Slot,
+    /// balance change, if any
+    pub balance: Option<ValueChange<Amount>>,
+    /// bytecode change, if any
+    pub bytecode: Option<ValueChange<Bytecode>>,
+    /// changed datastore entries, by key
+    #[serde_as(as = "Vec<(_, _)>")]
+    pub datastore: BTreeMap<Vec<u8>, ValueChange<Vec<u8>>>,
+    /// whether datastore changes were left out, above `ADDRESS_HISTORY_MAX_DATASTORE_CHANGES`
+    /// or `ADDRESS_HISTORY_MAX_DATASTORE_SIZE`
+    pub datastore_truncated: bool,
+}
+
+impl AddressHistoryEntry {
+    /// Returns true if the entry does not record any change
+    pub fn is_empty(&self) -> bool {
+        self.balance.is_none()
+            && self.bytecode.is_none()
+            && self.datastore.is_empty()
+            && !self.datastore_truncated
+    }
+}
+
+/// Serializer for `ValueChange`
+pub struct ValueChangeSerializer<T, ST: Serializer<T>> {
+    option_serializer: OptionSerializer<T, ST>,
+}
+
+impl<T, ST: Serializer<T>> ValueChangeSerializer<T, ST> {
+    /// Creates a new `ValueChangeSerializer`
+    pub fn new(value_serializer: ST) -> Self {
+        Self {
+            option_serializer: OptionSerializer::new(value_serializer),
+        }
+    }
+}
+
+impl<T, ST: Serializer<T>> Serializer<ValueChange<T>> for ValueChangeSerializer<T, ST> {
+    fn serialize(
+        &self,
+        value: &ValueChange<T>,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), SerializeError> {
+        self.option_serializer.serialize(&value.before, buffer)?;
+        self.option_serializer.serialize(&value.after, buffer)?;
+        Ok(())
+    }
+}
+
+/// Deserializer for `ValueChange`
+pub struct ValueChangeDeserializer<T: Clone, DT: Deserializer<T>> {
+    option_deserializer: OptionDeserializer<T, DT>,
+}
+
+impl<T: Clone, DT: Deserializer<T>> ValueChangeDeserializer<T, DT> {
+    /// Creates a new `ValueChangeDeserializer`
+    pub fn new(value_deserializer: DT) -> Self {
+        Self {
+            option_deserializer: OptionDeserializer::new(value_deserializer),
+        }
+    }
+}
+
+impl<T: Clone, DT: Deserializer<T>> Deserializer<ValueChange<T>>
+    for ValueChangeDeserializer<T, DT>
+{
+    fn deserialize<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
+        &self,
+        buffer: &'a [u8],
+    ) -> IResult<&'a [u8], ValueChange<T>, E> {
+        context(
+            "Failed ValueChange deserialization",
+            tuple((
+                context("Failed before deserialization", |input| {
+                    self.option_deserializer.deserialize(input)
+                }),
+                context("Failed after deserialization", |input| {
+                    self.option_deserializer.deserialize(input)
+                }),
+            )),
+        )
+        .map(|(before, after)| ValueChange { before, after })
+        .parse(buffer)
+    }
+}
+
+/// Serializer for `AddressHistoryEntry`
+pub struct AddressHistoryEntrySerializer {
+    slot_serializer: SlotSerializer,
+    balance_serializer:
+        OptionSerializer<ValueChange<Amount>, ValueChangeSerializer<Amount, AmountSerializer>>,
+    bytecode_serializer: OptionSerializer<
+        ValueChange<Bytecode>,
+        ValueChangeSerializer<Bytecode, BytecodeSerializer>,
+    >,
+    u64_serializer: U64VarIntSerializer,
+    key_serializer: VecU8Serializer,
+    datastore_value_serializer: ValueChangeSerializer<Vec<u8>, VecU8Serializer>,
+    bool_serializer: BoolSerializer,
+}
+
+impl AddressHistoryEntrySerializer {
+    /// Creates a new `AddressHistoryEntrySerializer`
+    pub fn new() -> Self {
+        Self {
+            slot_serializer: SlotSerializer::new(),
+            balance_serializer: OptionSerializer::new(ValueChangeSerializer::new(
+                AmountSerializer::new(),
+            )),
+            bytecode_serializer: OptionSerializer::new(ValueChangeSerializer::new(
+                BytecodeSerializer::new(),
+            )),
+            u64_serializer: U64VarIntSerializer::new(),
+            key_serializer: VecU8Serializer::new(),
+            datastore_value_serializer: ValueChangeSerializer::new(VecU8Serializer::new()),
+            bool_serializer: BoolSerializer::new(),
+        }
+    }
+}
+
+impl Default for AddressHistoryEntrySerializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Serializer<AddressHistoryEntry> for AddressHistoryEntrySerializer {
+    fn serialize(
+        &self,
+        value: &AddressHistoryEntry,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), SerializeError> {
+        self.slot_serializer.serialize(&value.slot, buffer)?;
+        self.balance_serializer.serialize(&value.balance, buffer)?;
+        self.bytecode_serializer
+            .serialize(&value.bytecode, buffer)?;
+        let entry_count = value.datastore.len() as u64;
+        if entry_count > ADDRESS_HISTORY_MAX_DATASTORE_CHANGES {
+            return Err(SerializeError::GeneralError(format!(
+                "too many datastore entries in AddressHistoryEntry: {}",
+                entry_count
+            )));
+        }
+        self.u64_serializer.serialize(&entry_count, buffer)?;
+        for (key, value_change) in value.datastore.iter() {
+            self.key_serializer.serialize(key, buffer)?;
+            self.datastore_value_serializer
+                .serialize(value_change, buffer)?;
+        }
+        self.bool_serializer
+            .serialize(&value.datastore_truncated, buffer)?;
+        Ok(())
+    }
+}
+
+/// Deserializer for `AddressHistoryEntry`
+pub struct AddressHistoryEntryDeserializer {
+    slot_deserializer: SlotDeserializer,
+    balance_deserializer: OptionDeserializer<
+        ValueChange<Amount>,
+        ValueChangeDeserializer<Amount, AmountDeserializer>,
+    >,
+    bytecode_deserializer: OptionDeserializer<
+        ValueChange<Bytecode>,
+        ValueChangeDeserializer<Bytecode, BytecodeDeserializer>,
+    >,
+    length_deserializer: U64VarIntDeserializer,
+    key_deserializer: VecU8Deserializer,
+    datastore_value_deserializer: ValueChangeDeserializer<Vec<u8>, VecU8Deserializer>,
+    bool_deserializer: BoolDeserializer,
+}
+
+impl AddressHistoryEntryDeserializer {
+    /// Creates a new `AddressHistoryEntryDeserializer`
+    pub fn new(
+        thread_count: u8,
+        max_datastore_key_length: u8,
+        max_datastore_value_length: u64,
+    ) -> Self {
+        Self {
+            slot_deserializer: SlotDeserializer::new(
+                (Included(u64::MIN), Included(u64::MAX)),
+                (Included(0), Excluded(thread_count)),
+            ),
+            balance_deserializer: OptionDeserializer::new(ValueChangeDeserializer::new(
+                AmountDeserializer::new(Included(Amount::MIN), Included(Amount::MAX)),
+            )),
+            bytecode_deserializer: OptionDeserializer::new(ValueChangeDeserializer::new(
+                BytecodeDeserializer::new(max_datastore_value_length),
+            )),
+            length_deserializer: U64VarIntDeserializer::new(
+                Included(u64::MIN),
+                Included(ADDRESS_HISTORY_MAX_DATASTORE_CHANGES),
+            ),
+            key_deserializer: VecU8Deserializer::new(
+                Included(u64::MIN),
+                Included(max_datastore_key_length as u64),
+            ),
+            datastore_value_deserializer: ValueChangeDeserializer::new(VecU8Deserializer::new(
+                Included(u64::MIN),
+                Included(max_datastore_value_length),
+            )),
+            bool_deserializer: BoolDeserializer::new(),
+        }
+    }
+}
+
+impl Deserializer<AddressHistoryEntry> for AddressHistoryEntryDeserializer {
+    /// ## Example
+    /// ```
+    /// use massa_serialization::{Deserializer, Serializer, DeserializeError};
+    /// use massa_models::{amount::Amount, slot::Slot};
+    /// use massa_ledger_exports::{AddressHistoryEntry, AddressHistoryEntrySerializer, AddressHistoryEntryDeserializer, ValueChange};
+    /// use std::collections::BTreeMap;
+    /// use std::str::FromStr;
+    ///
+    /// let mut datastore = BTreeMap::new();
+    /// datastore.insert(b"key".to_vec(), ValueChange { before: None, after: Some(vec![1, 2, 3]) });
+    /// let entry = AddressHistoryEntry {
+    ///     slot: Slot::new(1, 2),
+    ///     balance: ValueChange::new(Some(Amount::from_str("1").unwrap()), None),
+    ///     bytecode: None,
+    ///     datastore,
+    ///     datastore_truncated: false,
+    /// };
+    /// let mut serialized = Vec::new();
+    /// AddressHistoryEntrySerializer::new().serialize(&entry, &mut serialized).unwrap();
+    /// let (rest, deserialized) = AddressHistoryEntryDeserializer::new(32, 255, 10000)
+    ///     .deserialize::<DeserializeError>(&serialized)
+    ///     .unwrap();
+    /// assert!(rest.is_empty());
+    /// assert_eq!(entry, deserialized);
+    /// ```
+    fn deserialize<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
+        &self,
+        buffer: &'a [u8],
+    ) -> IResult<&'a [u8], AddressHistoryEntry, E> {
+        context(
+            "Failed AddressHistoryEntry deserialization",
+            tuple((
+                context("Failed slot deserialization", |input| {
+                    self.slot_deserializer.deserialize(input)
+                }),
+                context("Failed balance deserialization", |input| {
+                    self.balance_deserializer.deserialize(input)
+                }),
+                context("Failed bytecode deserialization", |input| {
+                    self.bytecode_deserializer.deserialize(input)
+                }),
+                context(
+                    "Failed datastore deserialization",
+                    length_count(
+                        context("Failed length deserialization", |input| {
+                            self.length_deserializer.deserialize(input)
+                        }),
+                        |input| {
+                            tuple((
+                                context("Failed key deserialization", |input| {
+                                    self.key_deserializer.deserialize(input)
+                                }),
+                                context("Failed value deserialization", |input| {
+                                    self.datastore_value_deserializer.deserialize(input)
+                                }),
+                            ))(input)
+                        },
+                    ),
+                ),
+                context("Failed datastore_truncated deserialization", |input| {
+                    self.bool_deserializer.deserialize(input)
+                }),
+            )),
+        )
+        .map(
+            |(slot, balance, bytecode, datastore, datastore_truncated)| AddressHistoryEntry {
+                slot,
+                balance,
+                bytecode,
+                datastore: datastore.into_iter().collect(),
+                datastore_truncated,
+            },
+        )
+        .parse(buffer)
+    }
+}
@@ -15,4 +15,6 @@ pub struct LedgerConfig {
     pub max_key_length: u8,
     /// max datastore value length
     pub max_datastore_value_length: u64,
+    /// record the before/after values of every final ledger change, per address
+    pub address_history_enabled: bool,
 }
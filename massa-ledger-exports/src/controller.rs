@@ -1,7 +1,7 @@
-use massa_models::{address::Address, amount::Amount, bytecode::Bytecode};
+use massa_models::{address::Address, amount::Amount, bytecode::Bytecode, slot::Slot};
 use std::collections::BTreeSet;
 
-use crate::{LedgerChanges, LedgerError};
+use crate::{AddressHistoryEntry, DatastoreRange, LedgerChanges, LedgerEntry, LedgerError};
 use massa_db_exports::DBBatch;
 
 #[cfg(feature = "test-exports")]
@@ -46,6 +46,37 @@ pub trait LedgerController: Send + Sync {
     /// A `BTreeSet` of the datastore keys
     fn get_datastore_keys(&self, addr: &Address, prefix: &[u8]) -> Option<BTreeSet<Vec<u8>>>;
 
+    /// Get a page of the datastore entries of a given address.
+    ///
+    /// # Arguments
+    /// * `addr`: target address
+    /// * `range`: range of keys to query, and iteration direction
+    /// * `limit`: maximum number of entries to return
+    ///
+    /// # Returns
+    /// The key/value pairs in the order given by the range direction, or `None` if the ledger entry was not found
+    #[allow(clippy::type_complexity)]
+    fn get_datastore_entries(
+        &self,
+        addr: &Address,
+        range: &DatastoreRange,
+        limit: usize,
+    ) -> Option<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// Get a page of full ledger entries, in increasing address order.
+    ///
+    /// # Arguments
+    /// * `after`: only return the addresses strictly after this one, or `None` to start from the first address
+    /// * `limit`: maximum number of entries to return
+    ///
+    /// # Returns
+    /// The addresses with their entries, the page is the last one if it has less than `limit` entries
+    fn get_ledger_entries(
+        &self,
+        after: Option<Address>,
+        limit: usize,
+    ) -> Vec<(Address, LedgerEntry)>;
+
     /// Reset the ledger
     ///
     /// USED FOR BOOTSTRAP ONLY
@@ -53,6 +84,32 @@ pub trait LedgerController: Send + Sync {
 
     fn apply_changes_to_batch(&mut self, changes: LedgerChanges, ledger_batch: &mut DBBatch);
 
+    /// Records in `index_batch` the before/after values of every ledger sub-entry modified by `changes` at `slot`.
+    /// Must be called before `changes` are written to the database.
+    /// Does nothing if the address history index is disabled.
+    fn apply_changes_to_history_batch(
+        &self,
+        changes: &LedgerChanges,
+        slot: Slot,
+        index_batch: &mut DBBatch,
+    );
+
+    /// Gets the recorded ledger changes of an address, in increasing slot order.
+    ///
+    /// # Arguments
+    /// * `addr`: target address
+    /// * `start`: first slot to return (inclusive), or `None` to start from the oldest record
+    /// * `limit`: maximum number of entries to return
+    ///
+    /// # Returns
+    /// The history entries, empty if the address history index is disabled
+    fn get_address_history(
+        &self,
+        addr: &Address,
+        start: Option<Slot>,
+        limit: usize,
+    ) -> Vec<AddressHistoryEntry>;
+
     /// Deserializes the key and value, useful after bootstrap
     fn is_key_value_valid(&self, serialized_key: &[u8], serialized_value: &[u8]) -> bool;
 
@@ -0,0 +1,143 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! This file defines the structures used to query a range of datastore entries
+
+use massa_models::datastore::get_prefix_bounds;
+use serde::{Deserialize, Serialize};
+use std::ops::{Bound, RangeBounds};
+
+/// Order in which datastore entries are returned
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+pub enum DatastoreDirection {
+    /// increasing key order
+    #[default]
+    Forward,
+    /// decreasing key order
+    Reverse,
+}
+
+/// Range of datastore keys of an address
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct DatastoreRange {
+    /// only keys starting with this prefix are part of the range
+    pub prefix: Vec<u8>,
+    /// lower bound of the range
+    pub start_key: Bound<Vec<u8>>,
+    /// upper bound of the range
+    pub end_key: Bound<Vec<u8>>,
+    /// order in which the entries are iterated
+    pub direction: DatastoreDirection,
+}
+
+impl Default for DatastoreRange {
+    fn default() -> Self {
+        DatastoreRange {
+            prefix: Vec::new(),
+            start_key: Bound::Unbounded,
+            end_key: Bound::Unbounded,
+            direction: DatastoreDirection::Forward,
+        }
+    }
+}
+
+impl DatastoreRange {
+    /// Returns the lower and upper bounds of the range, taking the prefix into account
+    pub fn bounds(&self) -> (Bound<Vec<u8>>, Bound<Vec<u8>>) {
+        let (prefix_start, prefix_end) = get_prefix_bounds(&self.prefix);
+        (
+            max_lower_bound(prefix_start, self.start_key.clone()),
+            min_upper_bound(prefix_end, self.end_key.clone()),
+        )
+    }
+
+    /// Returns true if `key` is part of the range
+    pub fn contains(&self, key: &[u8]) -> bool {
+        key.starts_with(&self.prefix)
+            && (self.start_key.as_ref(), self.end_key.as_ref()).contains(&key.to_vec())
+    }
+
+    /// Returns the range of the next page, given the last key of the current page
+    pub fn after(&self, last_key: Vec<u8>) -> Self {
+        let mut range = self.clone();
+        match self.direction {
+            DatastoreDirection::Forward => {
+                range.start_key = max_lower_bound(range.start_key, Bound::Excluded(last_key))
+            }
+            DatastoreDirection::Reverse => {
+                range.end_key = min_upper_bound(range.end_key, Bound::Excluded(last_key))
+            }
+        }
+        range
+    }
+}
+
+/// Returns the most restrictive of two lower bounds
+fn max_lower_bound(a: Bound<Vec<u8>>, b: Bound<Vec<u8>>) -> Bound<Vec<u8>> {
+    let keep_a = match (&a, &b) {
+        (Bound::Unbounded, _) => false,
+        (_, Bound::Unbounded) => true,
+        (Bound::Included(x) | Bound::Excluded(x), Bound::Included(y) | Bound::Excluded(y)) => {
+            x > y || (x == y && matches!(a, Bound::Excluded(_)))
+        }
+    };
+    if keep_a {
+        a
+    } else {
+        b
+    }
+}
+
+/// Returns the most restrictive of two upper bounds
+fn min_upper_bound(a: Bound<Vec<u8>>, b: Bound<Vec<u8>>) -> Bound<Vec<u8>> {
+    let keep_a = match (&a, &b) {
+        (Bound::Unbounded, _) => false,
+        (_, Bound::Unbounded) => true,
+        (Bound::Included(x) | Bound::Excluded(x), Bound::Included(y) | Bound::Excluded(y)) => {
+            x < y || (x == y && matches!(a, Bound::Excluded(_)))
+        }
+    };
+    if keep_a {
+        a
+    } else {
+        b
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_datastore_range_bounds() {
+        let range = DatastoreRange {
+            prefix: b"ab".to_vec(),
+            start_key: Bound::Included(b"a".to_vec()),
+            end_key: Bound::Excluded(b"abc".to_vec()),
+            direction: DatastoreDirection::Forward,
+        };
+        assert_eq!(
+            range.bounds(),
+            (
+                Bound::Included(b"ab".to_vec()),
+                Bound::Excluded(b"abc".to_vec())
+            )
+        );
+        assert!(range.contains(b"ab"));
+        assert!(range.contains(b"abb"));
+        assert!(!range.contains(b"abc"));
+        assert!(!range.contains(b"aa"));
+
+        let next = range.after(b"abb".to_vec());
+        assert_eq!(next.start_key, Bound::Excluded(b"abb".to_vec()));
+        assert!(!next.contains(b"abb"));
+
+        let reverse = DatastoreRange {
+            direction: DatastoreDirection::Reverse,
+            ..range
+        };
+        let next = reverse.after(b"abb".to_vec());
+        assert_eq!(next.end_key, Bound::Excluded(b"abb".to_vec()));
+        assert!(next.contains(b"ab"));
+        assert!(!next.contains(b"abb"));
+    }
+}
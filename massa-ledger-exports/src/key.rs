@@ -139,8 +139,8 @@ impl Key {
     }
 }
 
-/// Gives the general prefix of the datastore of an address while respecting a provided key prefix
-pub fn datastore_prefix_from_address(address: &Address, prefix: &[u8]) -> Vec<u8> {
+/// Gives the prefix of all the keys of the ledger entry of an address
+pub fn ledger_prefix_from_address(address: &Address) -> Vec<u8> {
     let mut res_prefix = LEDGER_PREFIX.as_bytes().to_vec();
     U64VarIntSerializer::new()
         .serialize(&KEY_VERSION, &mut res_prefix)
@@ -148,6 +148,12 @@ pub fn datastore_prefix_from_address(address: &Address, prefix: &[u8]) -> Vec<u8
     AddressSerializer::new()
         .serialize(address, &mut res_prefix)
         .unwrap();
+    res_prefix
+}
+
+/// Gives the general prefix of the datastore of an address while respecting a provided key prefix
+pub fn datastore_prefix_from_address(address: &Address, prefix: &[u8]) -> Vec<u8> {
+    let mut res_prefix = ledger_prefix_from_address(address);
     res_prefix.push(DATASTORE_IDENT);
     res_prefix.extend(prefix);
     res_prefix
@@ -0,0 +1,172 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! This file defines the format of ledger export files.
+//!
+//! A ledger export is a JSON lines file: the first line is a `LedgerExportHeader`,
+//! and every following line is a `LedgerExportEntry`.
+//! This allows writing and reading ledgers of any size without holding them in memory.
+
+use crate::{LedgerEntry, LedgerError};
+use massa_models::{address::Address, slot::Slot};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Lines, Write};
+
+/// Current version of the ledger export format
+pub const LEDGER_EXPORT_VERSION: u32 = 1;
+
+/// First line of a ledger export file
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct LedgerExportHeader {
+    /// version of the export format
+    pub version: u32,
+    /// chain id of the exported network
+    pub chain_id: u64,
+    /// final slot (change id of the database) at which the ledger was exported
+    pub change_id: Slot,
+}
+
+/// Ledger entry of an address, one per line of a ledger export file
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct LedgerExportEntry {
+    /// address of the entry
+    pub address: Address,
+    /// balance, bytecode and datastore of the address
+    #[serde(flatten)]
+    pub entry: LedgerEntry,
+}
+
+/// Writes a ledger export file, one entry at a time
+pub struct LedgerExportWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> LedgerExportWriter<W> {
+    /// Creates a new writer and writes the header
+    pub fn new(mut writer: W, header: &LedgerExportHeader) -> Result<Self, LedgerError> {
+        write_line(&mut writer, header)?;
+        Ok(LedgerExportWriter { writer })
+    }
+
+    /// Writes the entry of an address
+    pub fn write_entry(&mut self, entry: &LedgerExportEntry) -> Result<(), LedgerError> {
+        write_line(&mut self.writer, entry)
+    }
+
+    /// Flushes the written entries and returns the underlying writer
+    pub fn finish(mut self) -> Result<W, LedgerError> {
+        self.writer.flush().map_err(|err| {
+            LedgerError::FileError(format!("error writing ledger export: {}", err))
+        })?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads a ledger export file, iterating over its entries
+pub struct LedgerExportReader<R: BufRead> {
+    header: LedgerExportHeader,
+    lines: Lines<R>,
+}
+
+impl<R: BufRead> LedgerExportReader<R> {
+    /// Creates a new reader, reading and checking the header
+    pub fn new(reader: R) -> Result<Self, LedgerError> {
+        let mut lines = reader.lines();
+        let header: LedgerExportHeader = match lines.next() {
+            Some(line) => parse_line(line)?,
+            None => {
+                return Err(LedgerError::FileError(
+                    "empty ledger export file".to_string(),
+                ))
+            }
+        };
+        if header.version != LEDGER_EXPORT_VERSION {
+            return Err(LedgerError::FileError(format!(
+                "unsupported ledger export version {} (expected {})",
+                header.version, LEDGER_EXPORT_VERSION
+            )));
+        }
+        Ok(LedgerExportReader { header, lines })
+    }
+
+    /// Header of the export
+    pub fn header(&self) -> &LedgerExportHeader {
+        &self.header
+    }
+}
+
+impl<R: BufRead> Iterator for LedgerExportReader<R> {
+    type Item = Result<LedgerExportEntry, LedgerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines.next().map(parse_line)
+    }
+}
+
+/// Serializes a value on a single line
+fn write_line<W: Write, T: Serialize>(writer: &mut W, value: &T) -> Result<(), LedgerError> {
+    serde_json::to_writer(&mut *writer, value).map_err(|err| {
+        LedgerError::FileError(format!("error serializing ledger export: {}", err))
+    })?;
+    writer
+        .write_all(b"\n")
+        .map_err(|err| LedgerError::FileError(format!("error writing ledger export: {}", err)))
+}
+
+/// Parses a line read from a ledger export
+fn parse_line<T: for<'de> Deserialize<'de>>(
+    line: std::io::Result<String>,
+) -> Result<T, LedgerError> {
+    let line = line
+        .map_err(|err| LedgerError::FileError(format!("error reading ledger export: {}", err)))?;
+    serde_json::from_str(&line)
+        .map_err(|err| LedgerError::FileError(format!("error parsing ledger export: {}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use massa_models::{amount::Amount, bytecode::Bytecode};
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_ledger_export_roundtrip() {
+        let header = LedgerExportHeader {
+            version: LEDGER_EXPORT_VERSION,
+            chain_id: 77,
+            change_id: Slot::new(10, 3),
+        };
+        let entry = LedgerExportEntry {
+            address: Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x")
+                .unwrap(),
+            entry: LedgerEntry {
+                balance: Amount::from_str("42").unwrap(),
+                bytecode: Bytecode(vec![1, 2, 3]),
+                datastore: BTreeMap::from([(vec![4], vec![5, 6])]),
+            },
+        };
+
+        let mut writer = LedgerExportWriter::new(Vec::new(), &header).unwrap();
+        writer.write_entry(&entry).unwrap();
+        writer.write_entry(&entry).unwrap();
+        let bytes = writer.finish().unwrap();
+        assert_eq!(bytes.iter().filter(|b| **b == b'\n').count(), 3);
+
+        let reader = LedgerExportReader::new(bytes.as_slice()).unwrap();
+        assert_eq!(reader.header(), &header);
+        let entries = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(entries, vec![entry.clone(), entry]);
+
+        // unsupported version
+        let mut bytes = Vec::new();
+        write_line(
+            &mut bytes,
+            &LedgerExportHeader {
+                version: LEDGER_EXPORT_VERSION + 1,
+                ..header
+            },
+        )
+        .unwrap();
+        assert!(LedgerExportReader::new(bytes.as_slice()).is_err());
+    }
+}
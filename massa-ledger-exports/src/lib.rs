@@ -2,21 +2,30 @@
 //!
 //! TODO
 
+mod address_history;
 mod config;
 mod controller;
+mod datastore_range;
 mod error;
 mod key;
 mod ledger_changes;
 mod ledger_entry;
+mod ledger_export;
 mod mapping_grpc;
 mod types;
 
+pub use address_history::{
+    AddressHistoryEntry, AddressHistoryEntryDeserializer, AddressHistoryEntrySerializer,
+    ValueChange, ValueChangeDeserializer, ValueChangeSerializer,
+    ADDRESS_HISTORY_MAX_DATASTORE_CHANGES, ADDRESS_HISTORY_MAX_DATASTORE_SIZE,
+};
 pub use config::LedgerConfig;
 pub use controller::LedgerController;
+pub use datastore_range::{DatastoreDirection, DatastoreRange};
 pub use error::LedgerError;
 pub use key::{
-    datastore_prefix_from_address, Key, KeyDeserializer, KeySerializer, KeyType, BALANCE_IDENT,
-    BYTECODE_IDENT, DATASTORE_IDENT, VERSION_IDENT,
+    datastore_prefix_from_address, ledger_prefix_from_address, Key, KeyDeserializer, KeySerializer,
+    KeyType, BALANCE_IDENT, BYTECODE_IDENT, DATASTORE_IDENT, VERSION_IDENT,
 };
 pub use ledger_changes::{
     DatastoreUpdateDeserializer, DatastoreUpdateSerializer, LedgerChanges,
@@ -24,6 +33,10 @@ pub use ledger_changes::{
     LedgerEntryUpdateDeserializer, LedgerEntryUpdateSerializer,
 };
 pub use ledger_entry::{LedgerEntry, LedgerEntryDeserializer, LedgerEntrySerializer};
+pub use ledger_export::{
+    LedgerExportEntry, LedgerExportHeader, LedgerExportReader, LedgerExportWriter,
+    LEDGER_EXPORT_VERSION,
+};
 pub use types::{
     Applicable, SetOrDelete, SetOrKeep, SetOrKeepDeserializer, SetOrKeepSerializer,
     SetUpdateOrDelete, SetUpdateOrDeleteDeserializer, SetUpdateOrDeleteSerializer,
@@ -5,12 +5,14 @@
 use crate::ledger_db::{LedgerDB, LedgerSubEntry};
 use massa_db_exports::{DBBatch, ShareableMassaDBController};
 use massa_ledger_exports::{
-    LedgerChanges, LedgerConfig, LedgerController, LedgerEntry, LedgerError,
+    AddressHistoryEntry, DatastoreRange, LedgerChanges, LedgerConfig, LedgerController,
+    LedgerEntry, LedgerError,
 };
 use massa_models::{
     address::Address,
     amount::{Amount, AmountDeserializer},
     bytecode::{Bytecode, BytecodeDeserializer},
+    slot::Slot,
 };
 use massa_serialization::{DeserializeError, Deserializer};
 use std::collections::{BTreeSet, HashMap};
@@ -142,6 +144,31 @@ impl LedgerController for FinalLedger {
         self.sorted_ledger.get_datastore_keys(addr, prefix)
     }
 
+    /// Get a page of the datastore entries of a given address.
+    ///
+    /// # Returns
+    /// The key/value pairs in the order given by the range direction, or `None` if the ledger entry was not found
+    fn get_datastore_entries(
+        &self,
+        addr: &Address,
+        range: &DatastoreRange,
+        limit: usize,
+    ) -> Option<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.sorted_ledger.get_datastore_entries(addr, range, limit)
+    }
+
+    /// Get a page of full ledger entries, in increasing address order.
+    ///
+    /// # Returns
+    /// At most `limit` addresses strictly after `after`, with their entries
+    fn get_ledger_entries(
+        &self,
+        after: Option<Address>,
+        limit: usize,
+    ) -> Vec<(Address, LedgerEntry)> {
+        self.sorted_ledger.get_ledger_entries(after, limit)
+    }
+
     /// Reset the disk ledger.
     ///
     /// USED FOR BOOTSTRAP ONLY
@@ -155,6 +182,32 @@ impl LedgerController for FinalLedger {
             .apply_changes_to_batch(changes, ledger_batch);
     }
 
+    /// Records the before/after values of the changed sub-entries, if the address history is enabled
+    fn apply_changes_to_history_batch(
+        &self,
+        changes: &LedgerChanges,
+        slot: Slot,
+        index_batch: &mut DBBatch,
+    ) {
+        if self.config.address_history_enabled {
+            self.sorted_ledger
+                .apply_changes_to_history_batch(changes, slot, index_batch);
+        }
+    }
+
+    /// Gets the recorded ledger changes of an address, in increasing slot order
+    fn get_address_history(
+        &self,
+        addr: &Address,
+        start: Option<Slot>,
+        limit: usize,
+    ) -> Vec<AddressHistoryEntry> {
+        if !self.config.address_history_enabled {
+            return Vec::new();
+        }
+        self.sorted_ledger.get_address_history(addr, start, limit)
+    }
+
     /// Deserializes the key and value, useful after bootstrap
     fn is_key_value_valid(&self, serialized_key: &[u8], serialized_value: &[u8]) -> bool {
         self.sorted_ledger
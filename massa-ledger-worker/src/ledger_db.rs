@@ -3,12 +3,13 @@
 //! Module to interact with the disk ledger
 
 use massa_db_exports::{
-    DBBatch, MassaDirection, MassaIteratorMode, ShareableMassaDBController, CRUD_ERROR,
-    KEY_SER_ERROR, LEDGER_PREFIX, STATE_CF,
+    DBBatch, MassaDirection, MassaIteratorMode, ShareableMassaDBController,
+    ADDRESS_HISTORY_DESER_ERROR, ADDRESS_HISTORY_PREFIX, ADDRESS_HISTORY_SER_ERROR, CRUD_ERROR,
+    INDEX_CF, KEY_DESER_ERROR, KEY_SER_ERROR, LEDGER_PREFIX, STATE_CF,
 };
 use massa_ledger_exports::*;
 use massa_models::amount::AmountDeserializer;
-use massa_models::bytecode::BytecodeDeserializer;
+use massa_models::bytecode::{Bytecode, BytecodeDeserializer};
 use massa_models::datastore::get_prefix_bounds;
 use massa_models::{
     address::Address, amount::AmountSerializer, bytecode::BytecodeSerializer, slot::Slot,
@@ -16,11 +17,11 @@ use massa_models::{
 use massa_serialization::{
     DeserializeError, Deserializer, Serializer, U64VarIntDeserializer, U64VarIntSerializer,
 };
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Debug;
 
 use massa_models::amount::Amount;
-use std::ops::Bound;
+use std::ops::{Bound, RangeBounds};
 
 /// Ledger sub entry enum
 pub enum LedgerSubEntry {
@@ -61,6 +62,8 @@ pub struct LedgerDB {
     bytecode_deserializer: BytecodeDeserializer,
     max_datastore_value_length: u64,
     max_datastore_key_length: u8,
+    history_entry_serializer: AddressHistoryEntrySerializer,
+    history_entry_deserializer: AddressHistoryEntryDeserializer,
 }
 
 impl Debug for LedgerDB {
@@ -100,6 +103,12 @@ impl LedgerDB {
             ),
             max_datastore_value_length,
             max_datastore_key_length,
+            history_entry_serializer: AddressHistoryEntrySerializer::new(),
+            history_entry_deserializer: AddressHistoryEntryDeserializer::new(
+                thread_count,
+                max_datastore_key_length,
+                max_datastore_value_length,
+            ),
         }
     }
 
@@ -149,6 +158,67 @@ impl LedgerDB {
         }
     }
 
+    /// Records the before/after values of every sub-entry modified by `changes` at `slot`.
+    /// Must be called before the changes are written, as the "before" values are read from the database.
+    ///
+    /// # Arguments
+    /// * changes: ledger changes about to be applied
+    /// * slot: final slot at which the changes are applied
+    /// * index_batch: the batch to write the history entries to
+    pub fn apply_changes_to_history_batch(
+        &self,
+        changes: &LedgerChanges,
+        slot: Slot,
+        index_batch: &mut DBBatch,
+    ) {
+        for (addr, change) in changes.0.iter() {
+            let entry = self.get_history_entry(addr, change, slot);
+            if entry.is_empty() {
+                continue;
+            }
+            let mut value = Vec::new();
+            self.history_entry_serializer
+                .serialize(&entry, &mut value)
+                .expect(ADDRESS_HISTORY_SER_ERROR);
+            self.db.read().put_or_update_entry_value(
+                index_batch,
+                address_history_key(addr, Some(&slot)),
+                &value,
+            );
+        }
+    }
+
+    /// Get the recorded history of a given address, in increasing slot order.
+    ///
+    /// # Arguments
+    /// * `addr`: associated address
+    /// * `start`: first slot to return (inclusive), or `None` to start from the oldest record
+    /// * `limit`: maximum number of entries to return
+    pub fn get_address_history(
+        &self,
+        addr: &Address,
+        start: Option<Slot>,
+        limit: usize,
+    ) -> Vec<AddressHistoryEntry> {
+        let db = self.db.read();
+
+        let prefix = address_history_key(addr, None);
+        let start_key = address_history_key(addr, start.as_ref());
+        db.iterator_cf(
+            INDEX_CF,
+            MassaIteratorMode::From(&start_key, MassaDirection::Forward),
+        )
+        .take_while(|(key, _)| key.starts_with(&prefix))
+        .take(limit)
+        .map(|(_, value)| {
+            self.history_entry_deserializer
+                .deserialize::<DeserializeError>(&value)
+                .expect(ADDRESS_HISTORY_DESER_ERROR)
+                .1
+        })
+        .collect()
+    }
+
     /// Get the given sub-entry of a given address.
     ///
     /// # Arguments
@@ -210,6 +280,145 @@ impl LedgerDB {
         )
     }
 
+    /// Get a page of the datastore entries of a given address.
+    ///
+    /// # Returns
+    /// The key/value pairs in the order given by the range direction, or `None` if the address does not exist
+    pub fn get_datastore_entries(
+        &self,
+        addr: &Address,
+        range: &DatastoreRange,
+        limit: usize,
+    ) -> Option<Vec<(Vec<u8>, Vec<u8>)>> {
+        let db = self.db.read();
+
+        // check if address exists, return None if it does not
+        {
+            let key = LedgerSubEntry::Balance.derive_key(addr);
+            let mut serialized_key = Vec::new();
+            self.key_serializer_db
+                .serialize(&key, &mut serialized_key)
+                .expect(KEY_SER_ERROR);
+            db.get_cf(STATE_CF, serialized_key).expect(CRUD_ERROR)?;
+        }
+
+        // datastore keys are serialized right after the address datastore prefix, so the order is preserved
+        let address_prefix = datastore_prefix_from_address(addr, &[]);
+        let to_db_bound = |bound: Bound<Vec<u8>>| match bound {
+            Bound::Included(key) => Bound::Included(datastore_prefix_from_address(addr, &key)),
+            Bound::Excluded(key) => Bound::Excluded(datastore_prefix_from_address(addr, &key)),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let (start, end) = range.bounds();
+        let db_range = (to_db_bound(start), to_db_bound(end));
+        let in_range = |key: &Vec<u8>| key.starts_with(&address_prefix) && db_range.contains(key);
+
+        let entries = match range.direction {
+            DatastoreDirection::Forward => {
+                let seek_key = match &db_range.0 {
+                    Bound::Included(key) | Bound::Excluded(key) => key.clone(),
+                    Bound::Unbounded => address_prefix.clone(),
+                };
+                db.iterator_cf(
+                    STATE_CF,
+                    MassaIteratorMode::From(&seek_key, MassaDirection::Forward),
+                )
+                .skip_while(|(key, _)| !in_range(key) && key == &seek_key)
+                .take_while(|(key, _)| in_range(key))
+                .take(limit)
+                .collect::<Vec<_>>()
+            }
+            DatastoreDirection::Reverse => {
+                let seek_key = match &db_range.1 {
+                    Bound::Included(key) | Bound::Excluded(key) => Some(key.clone()),
+                    Bound::Unbounded => end_prefix(&address_prefix),
+                };
+                let mode = match &seek_key {
+                    Some(key) => MassaIteratorMode::From(key, MassaDirection::Reverse),
+                    None => MassaIteratorMode::End,
+                };
+                db.iterator_cf(STATE_CF, mode)
+                    .skip_while(|(key, _)| !in_range(key) && Some(key) == seek_key.as_ref())
+                    .take_while(|(key, _)| in_range(key))
+                    .take(limit)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        Some(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key[address_prefix.len()..].to_vec(), value))
+                .collect(),
+        )
+    }
+
+    /// Get a page of full ledger entries, in increasing address order.
+    ///
+    /// # Returns
+    /// At most `limit` addresses strictly after `after` (or from the first address), with their entries
+    pub fn get_ledger_entries(
+        &self,
+        after: Option<Address>,
+        limit: usize,
+    ) -> Vec<(Address, LedgerEntry)> {
+        let db = self.db.read();
+
+        // all the keys of an address share the address prefix, so the next address starts at its end bound
+        let seek_key = match after {
+            Some(addr) => match get_prefix_bounds(&ledger_prefix_from_address(&addr)).1 {
+                Bound::Excluded(key) => key,
+                _ => return Vec::new(),
+            },
+            None => LEDGER_PREFIX.as_bytes().to_vec(),
+        };
+
+        let mut entries: Vec<(Address, LedgerEntry)> = Vec::new();
+        for (serialized_key, value) in db.iterator_cf(
+            STATE_CF,
+            MassaIteratorMode::From(&seek_key, MassaDirection::Forward),
+        ) {
+            if !serialized_key.starts_with(LEDGER_PREFIX.as_bytes()) {
+                break;
+            }
+            let (_rest, key) = self
+                .key_deserializer_db
+                .deserialize::<DeserializeError>(&serialized_key)
+                .expect(KEY_DESER_ERROR);
+
+            if entries.last().map(|(addr, _)| addr) != Some(&key.address) {
+                if entries.len() >= limit {
+                    break;
+                }
+                entries.push((key.address, LedgerEntry::default()));
+            }
+            let Some((_, entry)) = entries.last_mut() else {
+                break;
+            };
+            match key.key_type {
+                KeyType::VERSION => {}
+                KeyType::BALANCE => {
+                    entry.balance = self
+                        .amount_deserializer
+                        .deserialize::<DeserializeError>(&value)
+                        .expect("critical: invalid balance format")
+                        .1;
+                }
+                KeyType::BYTECODE => {
+                    entry.bytecode = self
+                        .bytecode_deserializer
+                        .deserialize::<DeserializeError>(&value)
+                        .expect("critical: invalid bytecode format")
+                        .1;
+                }
+                KeyType::DATASTORE(datastore_key) => {
+                    entry.datastore.insert(datastore_key, value);
+                }
+            }
+        }
+        entries
+    }
+
     pub fn reset(&self) {
         self.db.write().delete_prefix(LEDGER_PREFIX, STATE_CF, None);
     }
@@ -471,6 +680,179 @@ impl LedgerDB {
     }
 }
 
+// Address history helpers
+impl LedgerDB {
+    /// Compute the history entry of an address for a change that is about to be applied.
+    /// The recorded datastore changes are bounded: the entry is marked as truncated when some are left out.
+    fn get_history_entry(
+        &self,
+        addr: &Address,
+        change: &SetUpdateOrDelete<LedgerEntry, LedgerEntryUpdate>,
+        slot: Slot,
+    ) -> AddressHistoryEntry {
+        let mut entry = AddressHistoryEntry {
+            slot,
+            balance: None,
+            bytecode: None,
+            datastore: BTreeMap::new(),
+            datastore_truncated: false,
+        };
+        let mut datastore = HistoryDatastoreChanges::default();
+        match change {
+            SetUpdateOrDelete::Set(new_entry) => {
+                entry.balance = ValueChange::new(self.read_balance(addr), Some(new_entry.balance));
+                entry.bytecode =
+                    ValueChange::new(self.read_bytecode(addr), Some(new_entry.bytecode.clone()));
+                for (key, value) in new_entry.datastore.iter() {
+                    if datastore.is_full() {
+                        break;
+                    }
+                    let before = self.get_sub_entry(addr, LedgerSubEntry::Datastore(key.clone()));
+                    if let Some(value_change) = ValueChange::new(before, Some(value.clone())) {
+                        datastore.push(key.clone(), value_change);
+                    }
+                }
+                // the old keys absent from the new entry are deleted
+                self.for_each_datastore_entry(addr, |key, value| {
+                    if !new_entry.datastore.contains_key(&key) {
+                        datastore.push(
+                            key,
+                            ValueChange {
+                                before: Some(value),
+                                after: None,
+                            },
+                        );
+                    }
+                    !datastore.is_full()
+                });
+            }
+            SetUpdateOrDelete::Update(entry_update) => {
+                if let SetOrKeep::Set(balance) = &entry_update.balance {
+                    entry.balance = ValueChange::new(self.read_balance(addr), Some(*balance));
+                }
+                if let SetOrKeep::Set(bytecode) = &entry_update.bytecode {
+                    entry.bytecode =
+                        ValueChange::new(self.read_bytecode(addr), Some(bytecode.clone()));
+                }
+                for (key, update) in entry_update.datastore.iter() {
+                    if datastore.is_full() {
+                        break;
+                    }
+                    let before = self.get_sub_entry(addr, LedgerSubEntry::Datastore(key.clone()));
+                    let after = match update {
+                        SetOrDelete::Set(value) => Some(value.clone()),
+                        SetOrDelete::Delete => None,
+                    };
+                    if let Some(value_change) = ValueChange::new(before, after) {
+                        datastore.push(key.clone(), value_change);
+                    }
+                }
+            }
+            SetUpdateOrDelete::Delete => {
+                entry.balance = ValueChange::new(self.read_balance(addr), None);
+                entry.bytecode = ValueChange::new(self.read_bytecode(addr), None);
+                self.for_each_datastore_entry(addr, |key, value| {
+                    datastore.push(
+                        key,
+                        ValueChange {
+                            before: Some(value),
+                            after: None,
+                        },
+                    );
+                    !datastore.is_full()
+                });
+            }
+        }
+        entry.datastore = datastore.changes;
+        entry.datastore_truncated = datastore.truncated;
+        entry
+    }
+
+    /// Read the current balance of an address
+    fn read_balance(&self, addr: &Address) -> Option<Amount> {
+        self.get_sub_entry(addr, LedgerSubEntry::Balance)
+            .map(|bytes| {
+                self.amount_deserializer
+                    .deserialize::<DeserializeError>(&bytes)
+                    .expect("critical: invalid balance format")
+                    .1
+            })
+    }
+
+    /// Read the current bytecode of an address
+    fn read_bytecode(&self, addr: &Address) -> Option<Bytecode> {
+        self.get_sub_entry(addr, LedgerSubEntry::Bytecode)
+            .map(|bytes| {
+                self.bytecode_deserializer
+                    .deserialize::<DeserializeError>(&bytes)
+                    .expect("critical: invalid bytecode format")
+                    .1
+            })
+    }
+
+    /// Call `f` on the current datastore entries of an address, in key order, until it returns false
+    fn for_each_datastore_entry(
+        &self,
+        addr: &Address,
+        mut f: impl FnMut(Vec<u8>, Vec<u8>) -> bool,
+    ) {
+        let db = self.db.read();
+
+        let key_prefix = datastore_prefix_from_address(addr, &[]);
+        let end_prefix = end_prefix(&key_prefix);
+        for (key, value) in db
+            .iterator_cf(
+                STATE_CF,
+                MassaIteratorMode::From(&key_prefix, MassaDirection::Forward),
+            )
+            .take_while(|(key, _)| match &end_prefix {
+                Some(end) => key < end,
+                None => true,
+            })
+        {
+            let (_rest, key) = self
+                .key_deserializer_db
+                .deserialize::<DeserializeError>(&key)
+                .expect("could not deserialize datastore key from state db");
+            if let KeyType::DATASTORE(datastore_key) = key.key_type {
+                if !f(datastore_key, value) {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Datastore changes recorded in a history entry, bounded in number and size
+#[derive(Default)]
+struct HistoryDatastoreChanges {
+    changes: BTreeMap<Vec<u8>, ValueChange<Vec<u8>>>,
+    size: u64,
+    truncated: bool,
+}
+
+impl HistoryDatastoreChanges {
+    /// Record a change, or leave it out and mark the changes as truncated if it does not fit
+    fn push(&mut self, key: Vec<u8>, change: ValueChange<Vec<u8>>) {
+        let size = (key.len()
+            + change.before.as_ref().map_or(0, Vec::len)
+            + change.after.as_ref().map_or(0, Vec::len)) as u64;
+        if self.changes.len() as u64 >= ADDRESS_HISTORY_MAX_DATASTORE_CHANGES
+            || self.size.saturating_add(size) > ADDRESS_HISTORY_MAX_DATASTORE_SIZE
+        {
+            self.truncated = true;
+            return;
+        }
+        self.size += size;
+        self.changes.insert(key, change);
+    }
+
+    /// Whether the maximum number of changes is reached and others were left out: the next ones can be skipped
+    fn is_full(&self) -> bool {
+        self.truncated && self.changes.len() as u64 >= ADDRESS_HISTORY_MAX_DATASTORE_CHANGES
+    }
+}
+
 // test helpers
 impl LedgerDB {
     /// Get every address and their corresponding balance.
@@ -554,6 +936,18 @@ fn end_prefix(prefix: &[u8]) -> Option<Vec<u8>> {
     }
 }
 
+/// Builds the INDEX_CF key of the history entry of an address at a given slot.
+/// Slots are serialized in a sortable way, so that iterating over the prefix yields increasing slots.
+/// If `slot` is `None`, returns the key prefix shared by every history entry of the address.
+fn address_history_key(addr: &Address, slot: Option<&Slot>) -> Vec<u8> {
+    let mut key = ADDRESS_HISTORY_PREFIX.as_bytes().to_vec();
+    key.extend(addr.to_prefixed_bytes());
+    if let Some(slot) = slot {
+        key.extend(slot.to_bytes_key());
+    }
+    key
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -601,6 +995,8 @@ mod tests {
             max_final_state_elements_size: 100_000,
             max_versioning_elements_size: 100_000,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
             thread_count: 32,
         };
 
@@ -675,6 +1071,217 @@ mod tests {
         assert!(ledger_db.get_entire_datastore(&addr).is_empty());
     }
 
+    /// Functional test of the address history index of `LedgerDB`
+    #[test]
+    fn test_address_history() {
+        let addr = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let (ledger_db, _data) = init_test_ledger(addr);
+
+        let mut datastore_update = BTreeMap::new();
+        datastore_update.insert(b"1".to_vec(), SetOrDelete::Set(b"z".to_vec()));
+        datastore_update.insert(b"2".to_vec(), SetOrDelete::Delete);
+        let mut changes = LedgerChanges::default();
+        changes.0.insert(
+            addr,
+            SetUpdateOrDelete::Update(LedgerEntryUpdate {
+                balance: SetOrKeep::Set(Amount::from_str("30").unwrap()),
+                bytecode: SetOrKeep::Keep,
+                datastore: datastore_update,
+            }),
+        );
+        let mut deletion = LedgerChanges::default();
+        deletion.0.insert(addr, SetUpdateOrDelete::Delete);
+
+        // record and apply both changes
+        for (slot, changes) in [(Slot::new(1, 0), changes), (Slot::new(2, 0), deletion)] {
+            let mut index_batch = DBBatch::new();
+            ledger_db.apply_changes_to_history_batch(&changes, slot, &mut index_batch);
+            let mut batch = DBBatch::new();
+            ledger_db.apply_changes_to_batch(changes, &mut batch);
+            ledger_db.db.write().write_batch_with_index(
+                batch,
+                Default::default(),
+                index_batch,
+                slot,
+            );
+        }
+
+        let history = ledger_db.get_address_history(&addr, None, 10);
+        assert_eq!(history.len(), 2);
+
+        assert_eq!(history[0].slot, Slot::new(1, 0));
+        assert_eq!(
+            history[0].balance,
+            ValueChange::new(
+                Some(Amount::from_str("21").unwrap()),
+                Some(Amount::from_str("30").unwrap())
+            )
+        );
+        assert!(history[0].bytecode.is_none());
+        assert_eq!(
+            history[0].datastore.get(&b"1".to_vec()),
+            ValueChange::new(Some(b"a".to_vec()), Some(b"z".to_vec())).as_ref()
+        );
+        assert_eq!(
+            history[0].datastore.get(&b"2".to_vec()),
+            ValueChange::new(Some(b"b".to_vec()), None).as_ref()
+        );
+        assert_eq!(history[0].datastore.len(), 2);
+
+        assert_eq!(history[1].slot, Slot::new(2, 0));
+        assert_eq!(
+            history[1].balance,
+            ValueChange::new(Some(Amount::from_str("30").unwrap()), None)
+        );
+        assert_eq!(
+            history[1].datastore.keys().cloned().collect::<Vec<_>>(),
+            vec![b"1".to_vec(), b"3".to_vec()]
+        );
+
+        // paging
+        assert_eq!(ledger_db.get_address_history(&addr, None, 1), history[..1]);
+        assert_eq!(
+            ledger_db.get_address_history(&addr, Some(Slot::new(2, 0)), 10),
+            history[1..]
+        );
+
+        // the index does not change the state hash
+        assert_eq!(
+            HashXof(*STATE_HASH_INITIAL_BYTES),
+            ledger_db.db.read().get_xof_db_hash()
+        );
+    }
+
+    /// The datastore changes recorded in an address history entry are bounded
+    #[test]
+    fn test_address_history_truncated() {
+        let addr = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let (ledger_db, _data) = init_test_ledger(addr);
+
+        let datastore_update = (0..=ADDRESS_HISTORY_MAX_DATASTORE_CHANGES)
+            .map(|index| (index.to_be_bytes().to_vec(), SetOrDelete::Set(vec![1])))
+            .collect();
+        let mut changes = LedgerChanges::default();
+        changes.0.insert(
+            addr,
+            SetUpdateOrDelete::Update(LedgerEntryUpdate {
+                balance: SetOrKeep::Keep,
+                bytecode: SetOrKeep::Keep,
+                datastore: datastore_update,
+            }),
+        );
+        let slot = Slot::new(1, 0);
+        let mut index_batch = DBBatch::new();
+        ledger_db.apply_changes_to_history_batch(&changes, slot, &mut index_batch);
+        ledger_db.db.write().write_batch_with_index(
+            DBBatch::new(),
+            Default::default(),
+            index_batch,
+            slot,
+        );
+
+        let history = ledger_db.get_address_history(&addr, None, 10);
+        assert_eq!(history.len(), 1);
+        assert_eq!(
+            history[0].datastore.len() as u64,
+            ADDRESS_HISTORY_MAX_DATASTORE_CHANGES
+        );
+        assert!(history[0].datastore_truncated);
+    }
+
+    /// Test of the datastore pagination of `LedgerDB`
+    #[test]
+    fn test_datastore_entries() {
+        let addr = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let (ledger_db, data) = init_test_ledger(addr);
+        let all_entries: Vec<(Vec<u8>, Vec<u8>)> = data.into_iter().collect();
+
+        // whole datastore
+        let range = DatastoreRange::default();
+        assert_eq!(
+            ledger_db.get_datastore_entries(&addr, &range, 10),
+            Some(all_entries.clone())
+        );
+
+        // forward pages
+        let page = ledger_db.get_datastore_entries(&addr, &range, 2).unwrap();
+        assert_eq!(page, all_entries[..2]);
+        let next_range = range.after(page[1].0.clone());
+        assert_eq!(
+            ledger_db.get_datastore_entries(&addr, &next_range, 2),
+            Some(all_entries[2..].to_vec())
+        );
+
+        // reverse pages
+        let range = DatastoreRange {
+            direction: DatastoreDirection::Reverse,
+            ..Default::default()
+        };
+        let page = ledger_db.get_datastore_entries(&addr, &range, 2).unwrap();
+        assert_eq!(page, vec![all_entries[2].clone(), all_entries[1].clone()]);
+        let next_range = range.after(page[1].0.clone());
+        assert_eq!(
+            ledger_db.get_datastore_entries(&addr, &next_range, 2),
+            Some(all_entries[..1].to_vec())
+        );
+
+        // key range
+        let range = DatastoreRange {
+            start_key: Bound::Excluded(b"1".to_vec()),
+            end_key: Bound::Included(b"2".to_vec()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ledger_db.get_datastore_entries(&addr, &range, 10),
+            Some(all_entries[1..2].to_vec())
+        );
+
+        // unknown address
+        let other_addr = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        assert_eq!(
+            ledger_db.get_datastore_entries(&other_addr, &DatastoreRange::default(), 10),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ledger_entries() {
+        let addr = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let (ledger_db, data) = init_test_ledger(addr);
+
+        let other_addr = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let other_entry = LedgerEntry {
+            balance: Amount::from_str("7").unwrap(),
+            bytecode: Bytecode(vec![1, 2, 3]),
+            ..Default::default()
+        };
+        let mut batch = DBBatch::new();
+        ledger_db.put_entry(&other_addr, other_entry.clone(), &mut batch);
+        ledger_db
+            .db
+            .write()
+            .write_batch(batch, Default::default(), None);
+
+        let entries = ledger_db.get_ledger_entries(None, 10);
+        assert_eq!(entries.len(), 2);
+        let expected_entry = LedgerEntry {
+            balance: Amount::from_str("21").unwrap(),
+            datastore: data,
+            ..Default::default()
+        };
+        assert!(entries.contains(&(addr, expected_entry)));
+        assert!(entries.contains(&(other_addr, other_entry)));
+
+        // pages
+        let first_page = ledger_db.get_ledger_entries(None, 1);
+        assert_eq!(first_page, entries[..1]);
+        let second_page = ledger_db.get_ledger_entries(Some(first_page[0].0), 1);
+        assert_eq!(second_page, entries[1..]);
+        assert!(ledger_db
+            .get_ledger_entries(Some(second_page[0].0), 1)
+            .is_empty());
+    }
+
     #[test]
     fn test_end_prefix() {
         assert_eq!(end_prefix(&[5, 6, 7]), Some(vec![5, 6, 8]));
@@ -22,6 +22,8 @@ impl Default for FinalLedger {
             max_versioning_elements_size: 100_000,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let db = MassaDB::new(db_config);
         let db = LedgerDB::new(
@@ -142,6 +142,10 @@ pub struct MassaMetrics {
     /// counter of operations for final slot
     operations_final_counter: IntCounter,
 
+    // blocks and endorsements missed by the local stakers
+    missed_blocks_counter: IntCounter,
+    missed_endorsements_counter: IntCounter,
+
     // block_cache
     block_cache_checked_headers_size: IntGauge,
     block_cache_blocks_known_by_peer: IntGauge,
@@ -399,6 +403,18 @@ impl MassaMetrics {
         let operations_final_counter =
             IntCounter::new("operations_final_counter", "total final operations").unwrap();
 
+        let missed_blocks_counter = IntCounter::new(
+            "missed_blocks_counter",
+            "total blocks missed by the local stakers",
+        )
+        .unwrap();
+
+        let missed_endorsements_counter = IntCounter::new(
+            "missed_endorsements_counter",
+            "total endorsements missed by the local stakers",
+        )
+        .unwrap();
+
         let block_slot_delay = Histogram::with_opts(
             prometheus::HistogramOpts::new("block_slot_delay", "block slot delay").buckets(vec![
                 0.100, 0.250, 0.500, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0,
@@ -437,6 +453,8 @@ impl MassaMetrics {
                 let _ = prometheus::register(Box::new(peernet_total_bytes_received.clone()));
                 let _ = prometheus::register(Box::new(peernet_total_bytes_sent.clone()));
                 let _ = prometheus::register(Box::new(operations_final_counter.clone()));
+                let _ = prometheus::register(Box::new(missed_blocks_counter.clone()));
+                let _ = prometheus::register(Box::new(missed_endorsements_counter.clone()));
                 let _ = prometheus::register(Box::new(stakers.clone()));
                 let _ = prometheus::register(Box::new(rolls.clone()));
                 let _ = prometheus::register(Box::new(know_peers.clone()));
@@ -493,6 +511,8 @@ impl MassaMetrics {
                 active_in_connections,
                 active_out_connections,
                 operations_final_counter,
+                missed_blocks_counter,
+                missed_endorsements_counter,
                 block_cache_checked_headers_size,
                 block_cache_blocks_known_by_peer,
                 operation_cache_checked_operations,
@@ -618,6 +638,14 @@ impl MassaMetrics {
         self.operations_final_counter.inc_by(diff);
     }
 
+    pub fn inc_missed_blocks_counter(&self) {
+        self.missed_blocks_counter.inc();
+    }
+
+    pub fn inc_missed_endorsements_counter(&self) {
+        self.missed_endorsements_counter.inc();
+    }
+
     pub fn set_known_peers(&self, nb: usize) {
         self.protocol_known_peers.set(nb as i64);
     }
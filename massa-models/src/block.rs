@@ -15,6 +15,7 @@ use crate::{
     // slot::{Slot, SlotDeserializer, SlotSerializer},
 };
 // use massa_hash::{Hash, HashDeserializer};
+use massa_hash::Hash;
 use massa_serialization::{
     // DeserializeError,
     Deserializer,
@@ -41,8 +42,11 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Formatter;
 // use std::ops::Bound::{Excluded, Included};
 // use std::str::FromStr;
-use crate::block_header::{BlockHeader, BlockHeaderDeserializer, SecuredHeader};
+use crate::block_header::{
+    BlockHeader, BlockHeaderDeserializer, BlockHeaderVersionAt, SecuredHeader,
+};
 use crate::block_id::BlockId;
+use std::sync::Arc;
 
 /// block
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,6 +88,31 @@ impl SecureShareContent for Block {
         })
     }
 
+    fn new_verifiable_with_signer<SC, U, F>(
+        self,
+        content_serializer: SC,
+        _public_key: PublicKey,
+        _chain_id: u64,
+        _sign: F,
+    ) -> Result<SecureShare<Self, U>, ModelsError>
+    where
+        SC: Serializer<Self>,
+        U: Id,
+        F: FnOnce(&[u8], &Hash) -> Result<Signature, ModelsError>,
+    {
+        // the block is authenticated by the signature of its header
+        let mut content_serialized = Vec::new();
+        content_serializer.serialize(&self, &mut content_serialized)?;
+        Ok(SecureShare {
+            signature: self.header.signature,
+            content_creator_pub_key: self.header.content_creator_pub_key,
+            content_creator_address: self.header.content_creator_address,
+            id: U::new(*self.header.id.get_hash()),
+            content: self,
+            serialized_data: content_serialized,
+        })
+    }
+
     fn serialize(
         _signature: &Signature,
         _creator_public_key: &PublicKey,
@@ -229,6 +258,8 @@ pub struct BlockDeserializerArgs {
     pub last_start_period: Option<u64>,
     /// chain id
     pub chain_id: u64,
+    /// selects the layout of the block headers, version 0 at every slot if `None`
+    pub block_header_version_at: Option<Arc<dyn BlockHeaderVersionAt>>,
 }
 
 /// Deserializer for `Block`
@@ -240,17 +271,18 @@ pub struct BlockDeserializer {
 impl BlockDeserializer {
     /// Creates a new `BlockDeserializer`
     pub fn new(args: BlockDeserializerArgs) -> Self {
+        let mut header_deserializer = BlockHeaderDeserializer::new(
+            args.thread_count,
+            args.endorsement_count,
+            args.max_denunciations_per_block_header,
+            args.last_start_period,
+            args.chain_id,
+        );
+        if let Some(version_at) = args.block_header_version_at {
+            header_deserializer = header_deserializer.with_version_at(version_at);
+        }
         BlockDeserializer {
-            header_deserializer: SecureShareDeserializer::new(
-                BlockHeaderDeserializer::new(
-                    args.thread_count,
-                    args.endorsement_count,
-                    args.max_denunciations_per_block_header,
-                    args.last_start_period,
-                    args.chain_id,
-                ),
-                args.chain_id,
-            ),
+            header_deserializer: SecureShareDeserializer::new(header_deserializer, args.chain_id),
             op_ids_deserializer: OperationIdsDeserializer::new(args.max_operations_per_block),
         }
     }
@@ -325,7 +357,8 @@ impl Deserializer<Block> for BlockDeserializer {
     ///     endorsement_count: 9,
     ///     max_denunciations_per_block_header: 10,
     ///     last_start_period: Some(0),
-    ///     chain_id: *CHAINID
+    ///     chain_id: *CHAINID,
+    ///     block_header_version_at: None,
     /// };
     /// let (rest, res_block) = BlockDeserializer::new(args).deserialize::<DeserializeError>(&mut buffer).unwrap();
     ///
@@ -536,6 +569,7 @@ mod test {
             max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
             last_start_period: Some(0),
             chain_id: *CHAINID,
+            block_header_version_at: None,
         };
         let (rest, res_block): (&[u8], SecureShareBlock) =
             SecureShareDeserializer::new(BlockDeserializer::new(args), *CHAINID)
@@ -627,6 +661,7 @@ mod test {
             max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
             last_start_period: Some(0),
             chain_id: *CHAINID,
+            block_header_version_at: None,
         };
         let (rest, res_block): (&[u8], SecureShareBlock) =
             SecureShareDeserializer::new(BlockDeserializer::new(args), *CHAINID)
@@ -723,6 +758,7 @@ mod test {
             max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
             last_start_period: Some(0),
             chain_id: *CHAINID,
+            block_header_version_at: None,
         };
         let res: Result<(&[u8], SecureShareBlock), _> =
             SecureShareDeserializer::new(BlockDeserializer::new(args), *CHAINID)
@@ -782,6 +818,7 @@ mod test {
             max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
             last_start_period: Some(0),
             chain_id: *CHAINID,
+            block_header_version_at: None,
         };
         let res: Result<(&[u8], SecureShareBlock), _> =
             SecureShareDeserializer::new(BlockDeserializer::new(args), *CHAINID)
@@ -836,6 +873,7 @@ mod test {
             max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
             last_start_period: Some(0),
             chain_id: *CHAINID,
+            block_header_version_at: None,
         };
         let res: Result<(&[u8], SecureShareBlock), _> =
             SecureShareDeserializer::new(BlockDeserializer::new(args), *CHAINID)
@@ -893,6 +931,7 @@ mod test {
             max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
             last_start_period: Some(0),
             chain_id: *CHAINID,
+            block_header_version_at: None,
         };
         let res: Result<(&[u8], SecureShareBlock), _> =
             SecureShareDeserializer::new(BlockDeserializer::new(args), *CHAINID)
@@ -974,6 +1013,7 @@ mod test {
             max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
             last_start_period: Some(0),
             chain_id: *CHAINID,
+            block_header_version_at: None,
         };
         let (_, res): (&[u8], SecureShareBlock) =
             SecureShareDeserializer::new(BlockDeserializer::new(args), *CHAINID)
@@ -1033,6 +1073,7 @@ mod test {
             max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
             last_start_period: Some(0),
             chain_id: *CHAINID,
+            block_header_version_at: None,
         };
         let res: Result<(&[u8], SecureShareBlock), _> =
             SecureShareDeserializer::new(BlockDeserializer::new(args), *CHAINID)
@@ -1105,6 +1146,7 @@ mod test {
             max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
             last_start_period: Some(0),
             chain_id: *CHAINID,
+            block_header_version_at: None,
         };
         let res: Result<(&[u8], SecureShareBlock), _> =
             SecureShareDeserializer::new(BlockDeserializer::new(args), *CHAINID)
@@ -1182,6 +1224,7 @@ mod test {
             max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
             last_start_period: Some(0),
             chain_id: *CHAINID,
+            block_header_version_at: None,
         };
         let res: Result<(&[u8], SecureShareBlock), _> =
             SecureShareDeserializer::new(BlockDeserializer::new(args), *CHAINID)
@@ -1272,6 +1315,7 @@ mod test {
             max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
             last_start_period: Some(0),
             chain_id: *CHAINID,
+            block_header_version_at: None,
         };
         let res: Result<(&[u8], SecureShareBlock), _> =
             SecureShareDeserializer::new(BlockDeserializer::new(args), *CHAINID)
@@ -4,14 +4,15 @@ use crate::endorsement::{
     Endorsement, EndorsementDeserializerLW, EndorsementId, EndorsementSerializer,
     EndorsementSerializerLW, SecureShareEndorsement,
 };
+use crate::error::ModelsError;
 use crate::secure_share::{
-    SecureShare, SecureShareContent, SecureShareDeserializer, SecureShareSerializer,
+    Id, SecureShare, SecureShareContent, SecureShareDeserializer, SecureShareSerializer,
 };
 use crate::slot::{Slot, SlotDeserializer, SlotSerializer};
 use massa_hash::{Hash, HashDeserializer};
 use massa_serialization::{
-    Deserializer, OptionDeserializer, OptionSerializer, SerializeError, Serializer,
-    U32VarIntDeserializer, U32VarIntSerializer,
+    DeserializeError, Deserializer, OptionDeserializer, OptionSerializer, SerializeError,
+    Serializer, U32VarIntDeserializer, U32VarIntSerializer,
 };
 use massa_signature::PublicKey;
 use nom::branch::alt;
@@ -24,6 +25,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::Bound::{Excluded, Included};
 use std::collections::HashSet;
 use std::fmt::Formatter;
+use std::sync::Arc;
 
 /// block header
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,11 +104,53 @@ pub type SecuredHeader = SecureShare<BlockHeader, BlockId>;
 impl SecureShareContent for BlockHeader {
     /// compute the signed hash
     fn compute_signed_hash(&self, public_key: &PublicKey, content_hash: &Hash) -> Hash {
-        let mut signed_data: Vec<u8> = Vec::new();
-        signed_data.extend(public_key.to_bytes());
-        signed_data.extend(BlockHeaderDenunciationData::new(self.slot).to_bytes());
-        signed_data.extend(content_hash.to_bytes());
-        Hash::compute_from(&signed_data)
+        compute_signed_hash_at(public_key, self.slot, content_hash)
+    }
+}
+
+/// Hash signed by the creator of a header of `slot`
+fn compute_signed_hash_at(public_key: &PublicKey, slot: Slot, content_hash: &Hash) -> Hash {
+    let mut signed_data: Vec<u8> = Vec::new();
+    signed_data.extend(public_key.to_bytes());
+    signed_data.extend(BlockHeaderDenunciationData::new(slot).to_bytes());
+    signed_data.extend(content_hash.to_bytes());
+    Hash::compute_from(&signed_data)
+}
+
+impl BlockHeader {
+    /// Computes the slot, the id and the hash signed by the creator of a header from its serialized content.
+    ///
+    /// Only the slot is deserialized: it comes first in every layout,
+    /// so the layout active at the slot does not need to be known (e.g. by remote signers).
+    pub fn compute_signed_hash_from_serialized(
+        serialized_content: &[u8],
+        public_key: &PublicKey,
+        thread_count: u8,
+    ) -> Result<(Slot, BlockId, Hash), ModelsError> {
+        let network_versions_deserializer =
+            U32VarIntDeserializer::new(Included(0), Included(u32::MAX));
+        let opt_deserializer =
+            OptionDeserializer::new(U32VarIntDeserializer::new(Included(0), Included(u32::MAX)));
+        let slot_deserializer = SlotDeserializer::new(
+            (Included(0), Included(u64::MAX)),
+            (Included(0), Excluded(thread_count)),
+        );
+        let (_, (_, _, slot)) = tuple((
+            |input| network_versions_deserializer.deserialize::<DeserializeError>(input),
+            |input| opt_deserializer.deserialize(input),
+            |input| slot_deserializer.deserialize(input),
+        ))
+        .parse(serialized_content)
+        .map_err(|err| ModelsError::DeserializeError(err.to_string()))?;
+        // same as `SecureShareContent::compute_hash`
+        let mut hash_data = public_key.to_bytes();
+        hash_data.extend(serialized_content);
+        let content_hash = Hash::compute_from(&hash_data);
+        Ok((
+            slot,
+            BlockId::new(content_hash),
+            compute_signed_hash_at(public_key, slot, &content_hash),
+        ))
     }
 }
 
@@ -130,7 +174,24 @@ impl SecuredHeader {
     }
 }
 
+/// Gives the version of the block header layout used at a slot,
+/// i.e. the version of the block component that is active at the slot according to the MIP store
+pub trait BlockHeaderVersionAt: Send + Sync {
+    /// Get the version of the block header layout used at a slot
+    fn block_header_version_at(&self, slot: Slot) -> u32;
+}
+
+/// Get the block header layout version of a slot, version 0 if no versioning is provided
+fn get_block_header_version(version_at: &Option<Arc<dyn BlockHeaderVersionAt>>, slot: Slot) -> u32 {
+    version_at
+        .as_ref()
+        .map_or(0, |version_at| version_at.block_header_version_at(slot))
+}
+
 /// Serializer for `BlockHeader`
+///
+/// The network versions and the slot come first in every layout,
+/// so that the layout of the rest of the header can be selected from the slot.
 pub struct BlockHeaderSerializer {
     slot_serializer: SlotSerializer,
     endorsement_serializer: SecureShareSerializer,
@@ -139,10 +200,11 @@ pub struct BlockHeaderSerializer {
     u32_serializer: U32VarIntSerializer,
     opt_serializer: OptionSerializer<u32, U32VarIntSerializer>,
     block_id_serializer: BlockIdSerializer,
+    version_at: Option<Arc<dyn BlockHeaderVersionAt>>,
 }
 
 impl BlockHeaderSerializer {
-    /// Creates a new `BlockHeaderSerializer`
+    /// Creates a new `BlockHeaderSerializer`, using the layout of version 0 at every slot
     pub fn new() -> Self {
         Self {
             slot_serializer: SlotSerializer::new(),
@@ -152,8 +214,62 @@ impl BlockHeaderSerializer {
             endorsement_content_serializer: EndorsementSerializerLW::new(),
             denunciation_serializer: DenunciationSerializer::new(),
             block_id_serializer: BlockIdSerializer::new(),
+            version_at: None,
         }
     }
+
+    /// Select the layout of the headers from the block header version active at their slot
+    pub fn with_version_at(mut self, version_at: Arc<dyn BlockHeaderVersionAt>) -> Self {
+        self.version_at = Some(version_at);
+        self
+    }
+
+    /// Serialize the fields following the slot with the layout of version 0
+    fn serialize_v0(
+        &self,
+        value: &BlockHeader,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), SerializeError> {
+        // parents (note: there should be none if slot period=0)
+        if value.parents.is_empty() {
+            buffer.push(0);
+        } else {
+            buffer.push(1);
+        }
+        for parent_h in value.parents.iter() {
+            self.block_id_serializer.serialize(parent_h, buffer)?;
+        }
+
+        // operations merkle root
+        buffer.extend(value.operation_merkle_root.to_bytes());
+
+        self.u32_serializer.serialize(
+            &value.endorsements.len().try_into().map_err(|err| {
+                SerializeError::GeneralError(format!("too many endorsements: {}", err))
+            })?,
+            buffer,
+        )?;
+
+        for endorsement in value.endorsements.iter() {
+            self.endorsement_serializer.serialize_with(
+                &self.endorsement_content_serializer,
+                endorsement,
+                buffer,
+            )?;
+        }
+        self.u32_serializer.serialize(
+            &value.denunciations.len().try_into().map_err(|err| {
+                SerializeError::GeneralError(format!("too many denunciations: {}", err))
+            })?,
+            buffer,
+        )?;
+        for denunciation in value.denunciations.iter() {
+            self.denunciation_serializer
+                .serialize(denunciation, buffer)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for BlockHeaderSerializer {
@@ -223,45 +339,13 @@ impl Serializer<BlockHeader> for BlockHeaderSerializer {
         // slot
         self.slot_serializer.serialize(&value.slot, buffer)?;
 
-        // parents (note: there should be none if slot period=0)
-        if value.parents.is_empty() {
-            buffer.push(0);
-        } else {
-            buffer.push(1);
+        match get_block_header_version(&self.version_at, value.slot) {
+            0 => self.serialize_v0(value, buffer),
+            version => Err(SerializeError::GeneralError(format!(
+                "unimplemented block header version: {}",
+                version
+            ))),
         }
-        for parent_h in value.parents.iter() {
-            self.block_id_serializer.serialize(parent_h, buffer)?;
-        }
-
-        // operations merkle root
-        buffer.extend(value.operation_merkle_root.to_bytes());
-
-        self.u32_serializer.serialize(
-            &value.endorsements.len().try_into().map_err(|err| {
-                SerializeError::GeneralError(format!("too many endorsements: {}", err))
-            })?,
-            buffer,
-        )?;
-
-        for endorsement in value.endorsements.iter() {
-            self.endorsement_serializer.serialize_with(
-                &self.endorsement_content_serializer,
-                endorsement,
-                buffer,
-            )?;
-        }
-        self.u32_serializer.serialize(
-            &value.denunciations.len().try_into().map_err(|err| {
-                SerializeError::GeneralError(format!("too many denunciations: {}", err))
-            })?,
-            buffer,
-        )?;
-        for denunciation in value.denunciations.iter() {
-            self.denunciation_serializer
-                .serialize(denunciation, buffer)?;
-        }
-
-        Ok(())
     }
 }
 
@@ -280,10 +364,11 @@ pub struct BlockHeaderDeserializer {
     opt_deserializer: OptionDeserializer<u32, U32VarIntDeserializer>,
     block_id_deserializer: BlockIdDeserializer,
     chain_id: u64,
+    version_at: Option<Arc<dyn BlockHeaderVersionAt>>,
 }
 
 impl BlockHeaderDeserializer {
-    /// Creates a new `BlockHeaderDeserializer`
+    /// Creates a new `BlockHeaderDeserializer`, using the layout of version 0 at every slot
     /// If last_start_period is Some(lsp), then the deserializer will check for valid (non)-genesis blocks
     pub fn new(
         thread_count: u8,
@@ -324,8 +409,15 @@ impl BlockHeaderDeserializer {
             endorsement_count,
             last_start_period,
             chain_id,
+            version_at: None,
         }
     }
+
+    /// Select the layout of the headers from the block header version active at their slot
+    pub fn with_version_at(mut self, version_at: Arc<dyn BlockHeaderVersionAt>) -> Self {
+        self.version_at = Some(version_at);
+        self
+    }
 }
 
 impl Deserializer<BlockHeader> for BlockHeaderDeserializer {
@@ -384,26 +476,51 @@ impl Deserializer<BlockHeader> for BlockHeaderDeserializer {
     /// BlockHeaderSerializer::new().serialize(&deserialized_header, &mut buffer2).unwrap();
     /// assert_eq!(buffer, buffer2);
     /// ```
-    #[allow(clippy::type_complexity)]
     fn deserialize<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
         &self,
         buffer: &'a [u8],
     ) -> IResult<&'a [u8], BlockHeader, E> {
-        let (rest, (current_version, announced_version, slot, parents, operation_merkle_root)): (
-            &[u8],
-            (u32, Option<u32>, Slot, Vec<BlockId>, Hash),
-        ) = context("Failed BlockHeader deserialization", |input| {
-            let (rest, (current_version, announced_version, slot, parents)) = tuple((
-                context("Failed current_version deserialization", |input| {
-                    self.network_versions_deserializer.deserialize(input)
-                }),
-                context("Failed announced_version deserialization", |input| {
-                    self.opt_deserializer.deserialize(input)
-                }),
-                context("Failed slot deserialization", |input| {
-                    self.slot_deserializer.deserialize(input)
-                }),
-                context(
+        let (rest, (current_version, announced_version, slot)) =
+            context("Failed BlockHeader deserialization", |input| {
+                tuple((
+                    context("Failed current_version deserialization", |input| {
+                        self.network_versions_deserializer.deserialize(input)
+                    }),
+                    context("Failed announced_version deserialization", |input| {
+                        self.opt_deserializer.deserialize(input)
+                    }),
+                    context("Failed slot deserialization", |input| {
+                        self.slot_deserializer.deserialize(input)
+                    }),
+                ))
+                .parse(input)
+            })
+            .parse(buffer)?;
+
+        match get_block_header_version(&self.version_at, slot) {
+            0 => self.deserialize_v0(rest, current_version, announced_version, slot),
+            _ => Err(nom::Err::Failure(ContextError::add_context(
+                rest,
+                "Unknown block header version",
+                ParseError::from_error_kind(rest, nom::error::ErrorKind::Fail),
+            ))),
+        }
+    }
+}
+
+impl BlockHeaderDeserializer {
+    /// Deserialize the fields following the slot with the layout of version 0
+    #[allow(clippy::type_complexity)]
+    fn deserialize_v0<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
+        &self,
+        buffer: &'a [u8],
+        current_version: u32,
+        announced_version: Option<u32>,
+        slot: Slot,
+    ) -> IResult<&'a [u8], BlockHeader, E> {
+        let (rest, (parents, operation_merkle_root)): (&[u8], (Vec<BlockId>, Hash)) =
+            context("Failed BlockHeader deserialization", |input| {
+                let (rest, parents) = context(
                     "Failed parents deserialization",
                     alt((
                         preceded(tag(&[0]), |input| Ok((input, Vec::new()))),
@@ -417,39 +534,35 @@ impl Deserializer<BlockHeader> for BlockHeaderDeserializer {
                             ),
                         ),
                     )),
-                ),
-            ))
-            .parse(input)?;
-
-            // validate the parent/slot invariants before moving on to other fields
-            if let Some(last_start_period) = self.last_start_period {
-                if slot.period == last_start_period && !parents.is_empty() {
-                    return Err(nom::Err::Failure(ContextError::add_context(
-                        rest,
-                        "Genesis block cannot contain parents",
-                        ParseError::from_error_kind(rest, nom::error::ErrorKind::Fail),
-                    )));
-                } else if slot.period != last_start_period
-                    && parents.len() != self.thread_count as usize
-                {
-                    return Err(nom::Err::Failure(ContextError::add_context(
-                        rest,
-                        "Non-genesis block must have same numbers of parents as threads count",
-                        ParseError::from_error_kind(rest, nom::error::ErrorKind::Fail),
-                    )));
+                )
+                .parse(input)?;
+
+                // validate the parent/slot invariants before moving on to other fields
+                if let Some(last_start_period) = self.last_start_period {
+                    if slot.period == last_start_period && !parents.is_empty() {
+                        return Err(nom::Err::Failure(ContextError::add_context(
+                            rest,
+                            "Genesis block cannot contain parents",
+                            ParseError::from_error_kind(rest, nom::error::ErrorKind::Fail),
+                        )));
+                    } else if slot.period != last_start_period
+                        && parents.len() != self.thread_count as usize
+                    {
+                        return Err(nom::Err::Failure(ContextError::add_context(
+                            rest,
+                            "Non-genesis block must have same numbers of parents as threads count",
+                            ParseError::from_error_kind(rest, nom::error::ErrorKind::Fail),
+                        )));
+                    }
                 }
-            }
 
-            let (rest, merkle) = context("Failed operation_merkle_root", |input| {
-                self.hash_deserializer.deserialize(input)
+                let (rest, merkle) = context("Failed operation_merkle_root", |input| {
+                    self.hash_deserializer.deserialize(input)
+                })
+                .parse(rest)?;
+                Ok((rest, (parents, merkle)))
             })
-            .parse(rest)?;
-            Ok((
-                rest,
-                (current_version, announced_version, slot, parents, merkle),
-            ))
-        })
-        .parse(buffer)?;
+            .parse(buffer)?;
 
         if parents.is_empty() {
             let res = BlockHeader {
@@ -725,6 +838,52 @@ mod test {
         assert_eq!(block_header_1, block_header_der);
     }
 
+    #[test]
+    fn test_block_header_version_at_ser_der() {
+        // block header version 1 from period 10
+        struct TestVersionAt;
+        impl BlockHeaderVersionAt for TestVersionAt {
+            fn block_header_version_at(&self, slot: Slot) -> u32 {
+                u32::from(slot.period >= 10)
+            }
+        }
+
+        let mut block_header = BlockHeader {
+            current_version: 0,
+            announced_version: None,
+            slot: Slot::new(0, 1),
+            parents: vec![],
+            operation_merkle_root: Hash::compute_from("mno".as_bytes()),
+            endorsements: vec![],
+            denunciations: vec![],
+        };
+        let ser = BlockHeaderSerializer::new().with_version_at(Arc::new(TestVersionAt));
+        let der = BlockHeaderDeserializer::new(
+            THREAD_COUNT,
+            ENDORSEMENT_COUNT,
+            MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
+            None,
+            *CHAINID,
+        )
+        .with_version_at(Arc::new(TestVersionAt));
+
+        // version 0 layout before the activation
+        let mut buffer = Vec::new();
+        ser.serialize(&block_header, &mut buffer).unwrap();
+        let (rem, block_header_der) = der.deserialize::<DeserializeError>(&buffer).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(block_header, block_header_der);
+
+        // no layout is implemented for version 1 yet
+        block_header.slot = Slot::new(10, 1);
+        assert!(ser.serialize(&block_header, &mut Vec::new()).is_err());
+        let mut buffer = Vec::new();
+        BlockHeaderSerializer::new()
+            .serialize(&block_header, &mut buffer)
+            .unwrap();
+        assert!(der.deserialize::<DeserializeError>(&buffer).is_err());
+    }
+
     #[test]
     fn test_verify_sig_batch() {
         let (_slot, _keypair, secured_header_1, secured_header_2, secured_header_3) =
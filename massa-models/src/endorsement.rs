@@ -265,6 +265,30 @@ impl SecureShareContent for Endorsement {
     }
 }
 
+impl Endorsement {
+    /// Deserializes an endorsement and computes its id and the hash signed by its creator,
+    /// for signers that are only given the serialized content (e.g. remote signers)
+    pub fn compute_signed_hash_from_serialized(
+        serialized_content: &[u8],
+        public_key: &PublicKey,
+        chain_id: u64,
+        thread_count: u8,
+        endorsement_count: u32,
+    ) -> Result<(Endorsement, EndorsementId, Hash), ModelsError> {
+        let (rest, endorsement) = EndorsementDeserializer::new(thread_count, endorsement_count)
+            .deserialize::<DeserializeError>(serialized_content)
+            .map_err(|err| ModelsError::DeserializeError(err.to_string()))?;
+        if !rest.is_empty() {
+            return Err(ModelsError::DeserializeError(
+                "trailing bytes after the endorsement".to_string(),
+            ));
+        }
+        let content_hash = endorsement.compute_hash(serialized_content, public_key, chain_id);
+        let signed_hash = endorsement.compute_signed_hash(public_key, &content_hash);
+        Ok((endorsement, EndorsementId::new(content_hash), signed_hash))
+    }
+}
+
 /// Serializer for `Endorsement`
 #[derive(Clone)]
 pub struct EndorsementSerializer {
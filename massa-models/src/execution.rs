@@ -1,7 +1,25 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
-use crate::{address::Address, operation::OperationId, slot::Slot};
+use crate::{
+    address::{Address, AddressDeserializer, AddressSerializer},
+    amount::{Amount, AmountDeserializer, AmountSerializer},
+    operation::{OperationId, OperationIdDeserializer, OperationIdSerializer},
+    slot::{Slot, SlotDeserializer, SlotSerializer},
+};
+use massa_serialization::{
+    BoolDeserializer, BoolSerializer, Deserializer, OptionDeserializer, OptionSerializer,
+    SerializeError, Serializer, U32VarIntDeserializer, U32VarIntSerializer, U64VarIntDeserializer,
+    U64VarIntSerializer,
+};
+use nom::{
+    error::{context, ContextError, ParseError},
+    multi::length_count,
+    sequence::tuple,
+    IResult, Parser,
+};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
 use serde::{Deserialize, Serialize};
+use std::ops::Bound::{Excluded, Included};
 
 /// filter used when retrieving SC output events
 #[derive(Default, Debug, Deserialize, Clone, Serialize)]
@@ -29,3 +47,483 @@ pub struct EventFilter {
     /// None means both
     pub is_error: Option<bool>,
 }
+
+/// Kind of a reward recorded by the reward history index
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, IntoPrimitive, TryFromPrimitive,
+)]
+#[serde(rename_all = "snake_case")]
+#[repr(u32)]
+pub enum RewardKind {
+    /// block reward and fees remaining for the block creator once endorsements are paid
+    BlockCreation = 0,
+    /// share of the block reward paid to the creator of an included endorsement
+    Endorsement = 1,
+    /// share of the block reward paid to the creator of the endorsed block
+    EndorsedBlock = 2,
+    /// deferred credits (from roll sells) paid out at the slot
+    DeferredCredits = 3,
+    /// coins lost (rolls and deferred credits) because of a denunciation
+    Slashing = 4,
+}
+
+impl std::fmt::Display for RewardKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RewardKind::BlockCreation => write!(f, "block creation"),
+            RewardKind::Endorsement => write!(f, "endorsement"),
+            RewardKind::EndorsedBlock => write!(f, "endorsed block"),
+            RewardKind::DeferredCredits => write!(f, "deferred credits"),
+            RewardKind::Slashing => write!(f, "slashing"),
+        }
+    }
+}
+
+/// A reward of an address at a given slot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressReward {
+    /// kind of reward
+    pub kind: RewardKind,
+    /// credited (or lost, for slashing) amount
+    pub amount: Amount,
+}
+
+/// Rewards of an address at a given final slot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressRewardsEntry {
+    /// slot at which the rewards were finalized
+    pub slot: Slot,
+    /// rewards, in execution order
+    pub rewards: Vec<AddressReward>,
+}
+
+/// Serializer for `AddressRewardsEntry`
+pub struct AddressRewardsEntrySerializer {
+    slot_serializer: SlotSerializer,
+    u64_serializer: U64VarIntSerializer,
+    kind_serializer: U32VarIntSerializer,
+    amount_serializer: AmountSerializer,
+}
+
+impl AddressRewardsEntrySerializer {
+    /// Creates a new `AddressRewardsEntrySerializer`
+    pub fn new() -> Self {
+        Self {
+            slot_serializer: SlotSerializer::new(),
+            u64_serializer: U64VarIntSerializer::new(),
+            kind_serializer: U32VarIntSerializer::new(),
+            amount_serializer: AmountSerializer::new(),
+        }
+    }
+}
+
+impl Default for AddressRewardsEntrySerializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Serializer<AddressRewardsEntry> for AddressRewardsEntrySerializer {
+    fn serialize(
+        &self,
+        value: &AddressRewardsEntry,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), SerializeError> {
+        self.slot_serializer.serialize(&value.slot, buffer)?;
+        let reward_count: u64 = value.rewards.len().try_into().map_err(|err| {
+            SerializeError::GeneralError(format!(
+                "too many rewards in AddressRewardsEntry: {}",
+                err
+            ))
+        })?;
+        self.u64_serializer.serialize(&reward_count, buffer)?;
+        for reward in value.rewards.iter() {
+            self.kind_serializer
+                .serialize(&u32::from(reward.kind), buffer)?;
+            self.amount_serializer.serialize(&reward.amount, buffer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Deserializer for `AddressRewardsEntry`
+pub struct AddressRewardsEntryDeserializer {
+    slot_deserializer: SlotDeserializer,
+    length_deserializer: U64VarIntDeserializer,
+    kind_deserializer: U32VarIntDeserializer,
+    amount_deserializer: AmountDeserializer,
+}
+
+impl AddressRewardsEntryDeserializer {
+    /// Creates a new `AddressRewardsEntryDeserializer`
+    pub fn new(thread_count: u8) -> Self {
+        Self {
+            slot_deserializer: SlotDeserializer::new(
+                (Included(u64::MIN), Included(u64::MAX)),
+                (Included(0), Excluded(thread_count)),
+            ),
+            length_deserializer: U64VarIntDeserializer::new(Included(u64::MIN), Included(u64::MAX)),
+            kind_deserializer: U32VarIntDeserializer::new(Included(u32::MIN), Included(u32::MAX)),
+            amount_deserializer: AmountDeserializer::new(
+                Included(Amount::MIN),
+                Included(Amount::MAX),
+            ),
+        }
+    }
+}
+
+impl Deserializer<AddressRewardsEntry> for AddressRewardsEntryDeserializer {
+    /// ## Example
+    /// ```
+    /// use massa_serialization::{Deserializer, Serializer, DeserializeError};
+    /// use massa_models::{amount::Amount, slot::Slot};
+    /// use massa_models::execution::{
+    ///     AddressReward, AddressRewardsEntry, AddressRewardsEntryDeserializer,
+    ///     AddressRewardsEntrySerializer, RewardKind,
+    /// };
+    /// use std::str::FromStr;
+    ///
+    /// let entry = AddressRewardsEntry {
+    ///     slot: Slot::new(1, 2),
+    ///     rewards: vec![
+    ///         AddressReward { kind: RewardKind::BlockCreation, amount: Amount::from_str("1").unwrap() },
+    ///         AddressReward { kind: RewardKind::Slashing, amount: Amount::from_str("0.5").unwrap() },
+    ///     ],
+    /// };
+    /// let mut serialized = Vec::new();
+    /// AddressRewardsEntrySerializer::new().serialize(&entry, &mut serialized).unwrap();
+    /// let (rest, deserialized) = AddressRewardsEntryDeserializer::new(32)
+    ///     .deserialize::<DeserializeError>(&serialized)
+    ///     .unwrap();
+    /// assert!(rest.is_empty());
+    /// assert_eq!(entry, deserialized);
+    /// ```
+    fn deserialize<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
+        &self,
+        buffer: &'a [u8],
+    ) -> IResult<&'a [u8], AddressRewardsEntry, E> {
+        context(
+            "Failed AddressRewardsEntry deserialization",
+            tuple((
+                context("Failed slot deserialization", |input| {
+                    self.slot_deserializer.deserialize(input)
+                }),
+                length_count::<&[u8], AddressReward, u64, E, _, _>(
+                    context("Failed length deserialization", |input| {
+                        self.length_deserializer.deserialize(input)
+                    }),
+                    context(
+                        "Failed reward deserialization",
+                        tuple((
+                            context("Failed kind deserialization", |input| {
+                                let (rest, kind) = self.kind_deserializer.deserialize(input)?;
+                                let kind = RewardKind::try_from(kind).map_err(|_| {
+                                    nom::Err::Error(ParseError::from_error_kind(
+                                        input,
+                                        nom::error::ErrorKind::Digit,
+                                    ))
+                                })?;
+                                Ok((rest, kind))
+                            }),
+                            context("Failed amount deserialization", |input| {
+                                self.amount_deserializer.deserialize(input)
+                            }),
+                        )),
+                    )
+                    .map(|(kind, amount)| AddressReward { kind, amount }),
+                ),
+            )),
+        )
+        .map(|(slot, rewards)| AddressRewardsEntry { slot, rewards })
+        .parse(buffer)
+    }
+}
+
+/// Kind of a transaction recorded by the transaction history index
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, IntoPrimitive, TryFromPrimitive,
+)]
+#[serde(rename_all = "snake_case")]
+#[repr(u32)]
+pub enum TransactionKind {
+    /// coin transfer operation
+    Transaction = 0,
+    /// roll buy operation, the amount is the price of the rolls
+    RollBuy = 1,
+    /// roll sell operation, the amount is the price of the rolls, credited later as deferred credits
+    RollSell = 2,
+    /// bytecode execution operation, the coins it moves are recorded as internal transfers
+    ExecuteSc = 3,
+    /// smart contract call operation, the amount is the coins sent to the called smart contract
+    CallSc = 4,
+    /// coin transfer made by a smart contract, only recorded if the node records execution traces
+    InternalTransfer = 5,
+}
+
+impl std::fmt::Display for TransactionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransactionKind::Transaction => write!(f, "transaction"),
+            TransactionKind::RollBuy => write!(f, "roll buy"),
+            TransactionKind::RollSell => write!(f, "roll sell"),
+            TransactionKind::ExecuteSc => write!(f, "execute sc"),
+            TransactionKind::CallSc => write!(f, "call sc"),
+            TransactionKind::InternalTransfer => write!(f, "internal transfer"),
+        }
+    }
+}
+
+/// An operation or a coin transfer executed at a given slot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressTransaction {
+    /// kind of transaction
+    pub kind: TransactionKind,
+    /// operation at the origin of the transaction, None for the internal transfers of asynchronous messages
+    pub operation_id: Option<OperationId>,
+    /// operation creator, or source of the internal transfer
+    pub from: Address,
+    /// transaction recipient, called smart contract or destination of the internal transfer, None for other operations
+    pub to: Option<Address>,
+    /// transferred (or spent, for roll operations) amount
+    pub amount: Amount,
+    /// fee paid by the operation creator, zero for internal transfers
+    pub fee: Amount,
+    /// whether the operation (or the transfer) succeeded. The fee of a failed operation is still paid
+    pub success: bool,
+}
+
+impl std::fmt::Display for AddressTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} from {}", self.kind, self.from)?;
+        if let Some(to) = &self.to {
+            write!(f, " to {}", to)?;
+        }
+        write!(f, ": {} (fee: {})", self.amount, self.fee)?;
+        if let Some(operation_id) = &self.operation_id {
+            write!(f, ", operation {}", operation_id)?;
+        }
+        if !self.success {
+            write!(f, ", failed")?;
+        }
+        Ok(())
+    }
+}
+
+/// Transactions involving an address at a given final slot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressTransactionsEntry {
+    /// slot at which the transactions were finalized
+    pub slot: Slot,
+    /// transactions, in execution order
+    pub transactions: Vec<AddressTransaction>,
+}
+
+/// Serializer for `AddressTransactionsEntry`
+pub struct AddressTransactionsEntrySerializer {
+    slot_serializer: SlotSerializer,
+    u64_serializer: U64VarIntSerializer,
+    kind_serializer: U32VarIntSerializer,
+    operation_id_serializer: OptionSerializer<OperationId, OperationIdSerializer>,
+    address_serializer: AddressSerializer,
+    to_serializer: OptionSerializer<Address, AddressSerializer>,
+    amount_serializer: AmountSerializer,
+    bool_serializer: BoolSerializer,
+}
+
+impl AddressTransactionsEntrySerializer {
+    /// Creates a new `AddressTransactionsEntrySerializer`
+    pub fn new() -> Self {
+        Self {
+            slot_serializer: SlotSerializer::new(),
+            u64_serializer: U64VarIntSerializer::new(),
+            kind_serializer: U32VarIntSerializer::new(),
+            operation_id_serializer: OptionSerializer::new(OperationIdSerializer::new()),
+            address_serializer: AddressSerializer::new(),
+            to_serializer: OptionSerializer::new(AddressSerializer::new()),
+            amount_serializer: AmountSerializer::new(),
+            bool_serializer: BoolSerializer::new(),
+        }
+    }
+}
+
+impl Default for AddressTransactionsEntrySerializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Serializer<AddressTransactionsEntry> for AddressTransactionsEntrySerializer {
+    fn serialize(
+        &self,
+        value: &AddressTransactionsEntry,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), SerializeError> {
+        self.slot_serializer.serialize(&value.slot, buffer)?;
+        let transaction_count: u64 = value.transactions.len().try_into().map_err(|err| {
+            SerializeError::GeneralError(format!(
+                "too many transactions in AddressTransactionsEntry: {}",
+                err
+            ))
+        })?;
+        self.u64_serializer.serialize(&transaction_count, buffer)?;
+        for transaction in value.transactions.iter() {
+            self.kind_serializer
+                .serialize(&u32::from(transaction.kind), buffer)?;
+            self.operation_id_serializer
+                .serialize(&transaction.operation_id, buffer)?;
+            self.address_serializer
+                .serialize(&transaction.from, buffer)?;
+            self.to_serializer.serialize(&transaction.to, buffer)?;
+            self.amount_serializer
+                .serialize(&transaction.amount, buffer)?;
+            self.amount_serializer.serialize(&transaction.fee, buffer)?;
+            self.bool_serializer
+                .serialize(&transaction.success, buffer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Deserializer for `AddressTransactionsEntry`
+pub struct AddressTransactionsEntryDeserializer {
+    slot_deserializer: SlotDeserializer,
+    length_deserializer: U64VarIntDeserializer,
+    kind_deserializer: U32VarIntDeserializer,
+    operation_id_deserializer: OptionDeserializer<OperationId, OperationIdDeserializer>,
+    address_deserializer: AddressDeserializer,
+    to_deserializer: OptionDeserializer<Address, AddressDeserializer>,
+    amount_deserializer: AmountDeserializer,
+    bool_deserializer: BoolDeserializer,
+}
+
+impl AddressTransactionsEntryDeserializer {
+    /// Creates a new `AddressTransactionsEntryDeserializer`
+    pub fn new(thread_count: u8) -> Self {
+        Self {
+            slot_deserializer: SlotDeserializer::new(
+                (Included(u64::MIN), Included(u64::MAX)),
+                (Included(0), Excluded(thread_count)),
+            ),
+            length_deserializer: U64VarIntDeserializer::new(Included(u64::MIN), Included(u64::MAX)),
+            kind_deserializer: U32VarIntDeserializer::new(Included(u32::MIN), Included(u32::MAX)),
+            operation_id_deserializer: OptionDeserializer::new(OperationIdDeserializer::new()),
+            address_deserializer: AddressDeserializer::new(),
+            to_deserializer: OptionDeserializer::new(AddressDeserializer::new()),
+            amount_deserializer: AmountDeserializer::new(
+                Included(Amount::MIN),
+                Included(Amount::MAX),
+            ),
+            bool_deserializer: BoolDeserializer::new(),
+        }
+    }
+}
+
+impl Deserializer<AddressTransactionsEntry> for AddressTransactionsEntryDeserializer {
+    /// ## Example
+    /// ```
+    /// use massa_serialization::{Deserializer, Serializer, DeserializeError};
+    /// use massa_models::{address::Address, amount::Amount, slot::Slot};
+    /// use massa_models::execution::{
+    ///     AddressTransaction, AddressTransactionsEntry, AddressTransactionsEntryDeserializer,
+    ///     AddressTransactionsEntrySerializer, TransactionKind,
+    /// };
+    /// use std::str::FromStr;
+    ///
+    /// let from = Address::from_str("AU12fZLkHnLED3okr8Lduyty7dz9ZKkd24xMCc2JJWPcdmfn2eUEx").unwrap();
+    /// let to = Address::from_str("AS12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap();
+    /// let entry = AddressTransactionsEntry {
+    ///     slot: Slot::new(1, 2),
+    ///     transactions: vec![
+    ///         AddressTransaction {
+    ///             kind: TransactionKind::CallSc,
+    ///             operation_id: None,
+    ///             from,
+    ///             to: Some(to),
+    ///             amount: Amount::from_str("1").unwrap(),
+    ///             fee: Amount::from_str("0.01").unwrap(),
+    ///             success: false,
+    ///         },
+    ///         AddressTransaction {
+    ///             kind: TransactionKind::RollBuy,
+    ///             operation_id: None,
+    ///             from,
+    ///             to: None,
+    ///             amount: Amount::from_str("100").unwrap(),
+    ///             fee: Amount::zero(),
+    ///             success: true,
+    ///         },
+    ///     ],
+    /// };
+    /// let mut serialized = Vec::new();
+    /// AddressTransactionsEntrySerializer::new().serialize(&entry, &mut serialized).unwrap();
+    /// let (rest, deserialized) = AddressTransactionsEntryDeserializer::new(32)
+    ///     .deserialize::<DeserializeError>(&serialized)
+    ///     .unwrap();
+    /// assert!(rest.is_empty());
+    /// assert_eq!(entry, deserialized);
+    /// ```
+    fn deserialize<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
+        &self,
+        buffer: &'a [u8],
+    ) -> IResult<&'a [u8], AddressTransactionsEntry, E> {
+        context(
+            "Failed AddressTransactionsEntry deserialization",
+            tuple((
+                context("Failed slot deserialization", |input| {
+                    self.slot_deserializer.deserialize(input)
+                }),
+                length_count::<&[u8], AddressTransaction, u64, E, _, _>(
+                    context("Failed length deserialization", |input| {
+                        self.length_deserializer.deserialize(input)
+                    }),
+                    context(
+                        "Failed transaction deserialization",
+                        tuple((
+                            context("Failed kind deserialization", |input| {
+                                let (rest, kind) = self.kind_deserializer.deserialize(input)?;
+                                let kind = TransactionKind::try_from(kind).map_err(|_| {
+                                    nom::Err::Error(ParseError::from_error_kind(
+                                        input,
+                                        nom::error::ErrorKind::Digit,
+                                    ))
+                                })?;
+                                Ok((rest, kind))
+                            }),
+                            context("Failed operation_id deserialization", |input| {
+                                self.operation_id_deserializer.deserialize(input)
+                            }),
+                            context("Failed from deserialization", |input| {
+                                self.address_deserializer.deserialize(input)
+                            }),
+                            context("Failed to deserialization", |input| {
+                                self.to_deserializer.deserialize(input)
+                            }),
+                            context("Failed amount deserialization", |input| {
+                                self.amount_deserializer.deserialize(input)
+                            }),
+                            context("Failed fee deserialization", |input| {
+                                self.amount_deserializer.deserialize(input)
+                            }),
+                            context("Failed success deserialization", |input| {
+                                self.bool_deserializer.deserialize(input)
+                            }),
+                        )),
+                    )
+                    .map(
+                        |(kind, operation_id, from, to, amount, fee, success)| AddressTransaction {
+                            kind,
+                            operation_id,
+                            from,
+                            to,
+                            amount,
+                            fee,
+                            success,
+                        },
+                    ),
+                ),
+            )),
+        )
+        .map(|(slot, transactions)| AddressTransactionsEntry { slot, transactions })
+        .parse(buffer)
+    }
+}
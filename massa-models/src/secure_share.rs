@@ -97,6 +97,38 @@ where
         })
     }
 
+    /// Same as `new_verifiable`, but the signature is applied by `sign`,
+    /// given the serialized content and the hash to sign,
+    /// so that the key-pair does not need to be known (e.g. remote signers).
+    fn new_verifiable_with_signer<Ser, ID, F>(
+        self,
+        content_serializer: Ser,
+        public_key: PublicKey,
+        chain_id: u64,
+        sign: F,
+    ) -> Result<SecureShare<Self, ID>, ModelsError>
+    where
+        Ser: Serializer<Self>,
+        ID: Id,
+        F: FnOnce(&[u8], &Hash) -> Result<Signature, ModelsError>,
+    {
+        let mut content_serialized = Vec::new();
+        content_serializer.serialize(&self, &mut content_serialized)?;
+        let hash = Self::compute_hash(&self, &content_serialized, &public_key, chain_id);
+        let signature = sign(
+            &content_serialized,
+            &self.compute_signed_hash(&public_key, &hash),
+        )?;
+        Ok(SecureShare {
+            signature,
+            content_creator_address: Address::from_public_key(&public_key),
+            content_creator_pub_key: public_key,
+            content: self,
+            serialized_data: content_serialized,
+            id: ID::new(hash),
+        })
+    }
+
     /// Compute hash
     fn compute_hash(
         &self,
@@ -1,6 +1,6 @@
 //! Copyright (c) 2022 MASSA LABS <info@massa.net>
 
-use crate::slot::Slot;
+use crate::{address::Address, slot::Slot};
 use massa_time::MassaTime;
 use serde::{Deserialize, Serialize};
 use std::fmt::Formatter;
@@ -130,3 +130,100 @@ impl std::fmt::Display for PoolStats {
         Ok(())
     }
 }
+
+/// Reason why a locally managed staking address did not produce at a slot it was selected for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MissReason {
+    /// the node had no connections and was configured not to produce in that case
+    NotConnected,
+    /// the selector draws of the slot were not available yet when it had to be produced
+    NoDraws,
+    /// the factory woke up after the slot had already passed
+    Late,
+    /// the block was produced but did not make it into the blockclique
+    NotInBlockclique,
+}
+
+impl std::fmt::Display for MissReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MissReason::NotConnected => write!(f, "not connected"),
+            MissReason::NoDraws => write!(f, "no draws yet"),
+            MissReason::Late => write!(f, "late"),
+            MissReason::NotInBlockclique => write!(f, "not in blockclique"),
+        }
+    }
+}
+
+/// Kind of item that was not produced
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MissedItem {
+    /// block of the slot
+    Block,
+    /// endorsement at a given index of the slot
+    Endorsement {
+        /// endorsement index
+        index: u32,
+    },
+}
+
+/// A block or endorsement that a locally managed staking address missed
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct MissedSlot {
+    /// address that was selected
+    pub address: Address,
+    /// slot of the missed item
+    pub slot: Slot,
+    /// missed item
+    pub item: MissedItem,
+    /// reason observed by the factory
+    pub reason: MissReason,
+}
+
+impl std::fmt::Display for MissedSlot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.item {
+            MissedItem::Block => write!(f, "{} missed block at slot {}", self.address, self.slot)?,
+            MissedItem::Endorsement { index } => write!(
+                f,
+                "{} missed endorsement {} at slot {}",
+                self.address, index, self.slot
+            )?,
+        }
+        write!(f, " ({})", self.reason)
+    }
+}
+
+/// Block production statistics of a locally managed staking address over a cycle, as seen by the factory
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct StakerMissStats {
+    /// staking address
+    pub address: Address,
+    /// cycle of the statistics
+    pub cycle: u64,
+    /// number of slots the address was selected to produce a block at
+    pub selected_block_count: u64,
+    /// number of those blocks that were missed
+    pub missed_block_count: u64,
+    /// number of endorsements that were missed
+    pub missed_endorsement_count: u64,
+}
+
+impl std::fmt::Display for StakerMissStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Address {} at cycle {}:", self.address, self.cycle)?;
+        writeln!(
+            f,
+            "\tMissed blocks: {}/{}",
+            self.missed_block_count, self.selected_block_count
+        )?;
+        writeln!(
+            f,
+            "\tMissed endorsements: {}",
+            self.missed_endorsement_count
+        )?;
+        Ok(())
+    }
+}
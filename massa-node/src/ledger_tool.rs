@@ -0,0 +1,210 @@
+//! Offline ledger export / import, used to fork the state of a network into a local test network
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context;
+use clap::Subcommand;
+use massa_db_exports::{MassaDBConfig, MassaDBController};
+use massa_db_worker::MassaDB;
+use massa_ledger_exports::{
+    DatastoreRange, LedgerConfig, LedgerController, LedgerEntry, LedgerExportEntry,
+    LedgerExportHeader, LedgerExportReader, LedgerExportWriter, LEDGER_EXPORT_VERSION,
+};
+use massa_ledger_worker::FinalLedger;
+use massa_models::address::Address;
+use massa_models::config::{
+    CHAINID, MAX_BOOTSTRAP_FINAL_STATE_PARTS_SIZE, MAX_BOOTSTRAP_VERSIONING_ELEMENTS_SIZE,
+    MAX_DATASTORE_KEY_LENGTH, MAX_DATASTORE_VALUE_LENGTH, THREAD_COUNT,
+};
+use parking_lot::RwLock;
+use serde::ser::{SerializeMap, Serializer};
+
+use crate::settings::SETTINGS;
+
+/// Number of ledger entries read from the database at once during an export
+const EXPORT_PAGE_SIZE: usize = 1000;
+
+/// Ledger tools, run instead of the node
+#[derive(Subcommand)]
+pub enum LedgerCommand {
+    /// Export the final ledger of the disk ledger db to a file, then exit.
+    /// The db is opened read-only: stop the node to export a consistent state.
+    ExportLedger {
+        /// Path of the export file to create
+        #[arg(long)]
+        output: PathBuf,
+        /// Only export these addresses (comma separated). All addresses are exported by default
+        #[arg(long = "addresses", value_delimiter = ',')]
+        addresses: Vec<Address>,
+    },
+    /// Build an initial ledger file from a ledger export, then exit
+    ImportLedger {
+        /// Path of the export file to read
+        #[arg(long)]
+        input: PathBuf,
+        /// Path of the initial ledger file to create
+        #[arg(long)]
+        output: PathBuf,
+        /// Only import these addresses (comma separated). All addresses are imported by default
+        #[arg(long = "addresses", value_delimiter = ',')]
+        addresses: Vec<Address>,
+    },
+}
+
+/// Runs a ledger command
+pub fn run_ledger_command(command: LedgerCommand) -> anyhow::Result<()> {
+    match command {
+        LedgerCommand::ExportLedger { output, addresses } => export_ledger(&output, &addresses),
+        LedgerCommand::ImportLedger {
+            input,
+            output,
+            addresses,
+        } => import_ledger(&input, &output, &addresses),
+    }
+}
+
+/// Exports the final ledger at the current change id of the disk ledger db
+fn export_ledger(output: &Path, addresses: &[Address]) -> anyhow::Result<()> {
+    let db_config = MassaDBConfig {
+        path: SETTINGS.ledger.disk_ledger_path.clone(),
+        max_history_length: SETTINGS.ledger.final_history_length,
+        max_final_state_elements_size: MAX_BOOTSTRAP_FINAL_STATE_PARTS_SIZE.try_into().unwrap(),
+        max_versioning_elements_size: MAX_BOOTSTRAP_VERSIONING_ELEMENTS_SIZE.try_into().unwrap(),
+        thread_count: THREAD_COUNT,
+        max_ledger_backups: SETTINGS.ledger.max_ledger_backups,
+        // the export only reads the state, the state proof tree is left untouched
+        state_proofs_enabled: false,
+        index_history_periods: SETTINGS.ledger.index_history_periods,
+    };
+    let db = MassaDB::open_read_only(db_config).with_context(|| {
+        format!(
+            "could not open the disk ledger at {}",
+            SETTINGS.ledger.disk_ledger_path.display()
+        )
+    })?;
+    let db = Arc::new(RwLock::new(
+        Box::new(db) as Box<(dyn MassaDBController + 'static)>
+    ));
+    let ledger_config = LedgerConfig {
+        thread_count: THREAD_COUNT,
+        initial_ledger_path: SETTINGS.ledger.initial_ledger_path.clone(),
+        max_key_length: MAX_DATASTORE_KEY_LENGTH,
+        max_datastore_value_length: MAX_DATASTORE_VALUE_LENGTH,
+        address_history_enabled: false,
+    };
+    let ledger = FinalLedger::new(ledger_config, db.clone());
+
+    let change_id = db
+        .read()
+        .get_change_id()
+        .context("could not read the change id of the disk ledger")?;
+    let header = LedgerExportHeader {
+        version: LEDGER_EXPORT_VERSION,
+        chain_id: *CHAINID,
+        change_id,
+    };
+    let file =
+        File::create(output).with_context(|| format!("could not create {}", output.display()))?;
+    let mut writer = LedgerExportWriter::new(BufWriter::new(file), &header)?;
+
+    let mut count = 0;
+    if addresses.is_empty() {
+        let mut after = None;
+        loop {
+            let entries = ledger.get_ledger_entries(after, EXPORT_PAGE_SIZE);
+            for (address, entry) in &entries {
+                writer.write_entry(&LedgerExportEntry {
+                    address: *address,
+                    entry: entry.clone(),
+                })?;
+            }
+            count += entries.len();
+            match entries.last() {
+                Some((address, _)) if entries.len() == EXPORT_PAGE_SIZE => after = Some(*address),
+                _ => break,
+            }
+        }
+    } else {
+        for address in addresses {
+            let (Some(balance), Some(datastore)) = (
+                ledger.get_balance(address),
+                ledger.get_datastore_entries(address, &DatastoreRange::default(), usize::MAX),
+            ) else {
+                println!("Address {} not found in the ledger, skipped", address);
+                continue;
+            };
+            writer.write_entry(&LedgerExportEntry {
+                address: *address,
+                entry: LedgerEntry {
+                    balance,
+                    bytecode: ledger.get_bytecode(address).unwrap_or_default(),
+                    datastore: datastore.into_iter().collect(),
+                },
+            })?;
+            count += 1;
+        }
+    }
+    writer.finish()?;
+
+    println!(
+        "Exported {} ledger entries at slot {} to {}",
+        count,
+        change_id,
+        output.display()
+    );
+    Ok(())
+}
+
+/// Builds an initial ledger file from a ledger export.
+/// Entries are written one at a time, in the order of the export.
+fn import_ledger(input: &Path, output: &Path, addresses: &[Address]) -> anyhow::Result<()> {
+    let file = File::open(input).with_context(|| format!("could not open {}", input.display()))?;
+    let reader = LedgerExportReader::new(BufReader::new(file))?;
+    let header = reader.header().clone();
+
+    let file =
+        File::create(output).with_context(|| format!("could not create {}", output.display()))?;
+    let mut serializer = serde_json::Serializer::pretty(BufWriter::new(file));
+    let mut initial_ledger = serializer
+        .serialize_map(None)
+        .context("could not write the initial ledger")?;
+
+    // only the addresses are kept in memory, to reject an export listing an address twice
+    let mut imported_addresses = HashSet::new();
+    for export_entry in reader {
+        let export_entry = export_entry?;
+        if !addresses.is_empty() && !addresses.contains(&export_entry.address) {
+            continue;
+        }
+        if !imported_addresses.insert(export_entry.address) {
+            anyhow::bail!(
+                "address {} is exported several times in {}",
+                export_entry.address,
+                input.display()
+            );
+        }
+        initial_ledger
+            .serialize_entry(&export_entry.address, &export_entry.entry)
+            .context("could not write the initial ledger")?;
+    }
+    initial_ledger
+        .end()
+        .context("could not write the initial ledger")?;
+    serializer
+        .into_inner()
+        .into_inner()
+        .context("could not write the initial ledger")?;
+
+    println!(
+        "Imported {} ledger entries exported at slot {} (chain id {}) to {}",
+        imported_addresses.len(),
+        header.change_id,
+        header.chain_id,
+        output.display()
+    );
+    Ok(())
+}
@@ -5,17 +5,23 @@
 #![warn(unused_crate_dependencies)]
 extern crate massa_logging;
 
+use crate::ledger_tool::{run_ledger_command, LedgerCommand};
+use crate::mip_simulator_tool::{run_mip_simulator_command, MipSimulatorCommand};
 #[cfg(feature = "op_spammer")]
 use crate::operation_injector::start_operation_injector;
+use crate::remote_signer_tool::{
+    read_remote_signer_secret, run_remote_signer_command, RemoteSignerCommand,
+};
 use crate::settings::SETTINGS;
+use crate::slashing_protection_tool::{run_slashing_protection_command, SlashingProtectionCommand};
 use crate::survey::MassaSurvey;
 
 use cfg_if::cfg_if;
-use clap::{crate_version, Parser};
+use clap::{crate_version, Parser, Subcommand};
 use crossbeam_channel::TryRecvError;
 use dialoguer::Password;
 use massa_api::{ApiServer, ApiV2, Private, Public, RpcServer, StopHandle, API};
-use massa_api_exports::config::APIConfig;
+use massa_api_exports::{access::AccessControl, config::APIConfig};
 use massa_async_pool::AsyncPoolConfig;
 use massa_bootstrap::BootstrapError;
 use massa_bootstrap::{
@@ -45,7 +51,7 @@ use massa_execution_worker::storage_backend::FileStorageBackend;
 #[cfg(all(feature = "dump-block", feature = "db_storage_backend"))]
 use massa_execution_worker::storage_backend::RocksDBStorageBackend;
 
-use massa_factory_exports::{FactoryChannels, FactoryConfig, FactoryManager};
+use massa_factory_exports::{FactoryChannels, FactoryConfig, FactoryManager, MissedSlots};
 use massa_factory_worker::start_factory;
 use massa_final_state::{FinalState, FinalStateConfig, FinalStateController};
 use massa_grpc::config::{GrpcConfig, ServiceName};
@@ -102,9 +108,10 @@ use massa_signature::KeyPair;
 use massa_storage::Storage;
 use massa_time::MassaTime;
 use massa_versioning::keypair_factory::KeyPairFactory;
-use massa_versioning::mips::get_mip_list;
+use massa_versioning::mip_votes::MipVotes;
+use massa_versioning::mips::get_mip_list_with;
 use massa_versioning::versioning::{MipStatsConfig, MipStore};
-use massa_wallet::Wallet;
+use massa_wallet::{LocalSigner, RemoteSigner, Signer, Wallet};
 use num::rational::Ratio;
 use parking_lot::RwLock;
 use settings::GrpcSettings;
@@ -120,9 +127,13 @@ use tokio::sync::broadcast;
 use tracing::{debug, error, info, warn};
 use tracing_subscriber::filter::{filter_fn, LevelFilter};
 
+mod ledger_tool;
+mod mip_simulator_tool;
 #[cfg(feature = "op_spammer")]
 mod operation_injector;
+mod remote_signer_tool;
 mod settings;
+mod slashing_protection_tool;
 mod survey;
 
 async fn launch(
@@ -163,6 +174,7 @@ async fn launch(
         initial_ledger_path: SETTINGS.ledger.initial_ledger_path.clone(),
         max_key_length: MAX_DATASTORE_KEY_LENGTH,
         max_datastore_value_length: MAX_DATASTORE_VALUE_LENGTH,
+        address_history_enabled: SETTINGS.ledger.address_history_enabled,
     };
     let async_pool_config = AsyncPoolConfig {
         max_length: MAX_ASYNC_POOL_LENGTH,
@@ -239,9 +251,13 @@ async fn launch(
         max_versioning_elements_size: MAX_BOOTSTRAP_VERSIONING_ELEMENTS_SIZE.try_into().unwrap(),
         thread_count: THREAD_COUNT,
         max_ledger_backups: SETTINGS.ledger.max_ledger_backups,
+        state_proofs_enabled: SETTINGS.ledger.state_proofs_enabled,
+        index_history_periods: SETTINGS.ledger.index_history_periods,
     };
+    let db = MassaDB::try_new(db_config)
+        .unwrap_or_else(|err| panic!("critical: could not open the ledger database: {}", err));
     let db = Arc::new(RwLock::new(
-        Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>
+        Box::new(db) as Box<(dyn MassaDBController + 'static)>
     ));
 
     // Create final ledger
@@ -297,7 +313,8 @@ async fn launch(
                 // The resulting MIP store will likely be updated by the bootstrap process in order
                 // to get the latest information for the MIP store (new states, votes...)
 
-                let mip_list = get_mip_list();
+                let mip_list = get_mip_list_with(&SETTINGS.versioning.mips)
+                    .expect("invalid MIP definition in the versioning settings");
                 debug!("MIP list: {:?}", mip_list);
                 let mip_store = MipStore::try_from((mip_list, mip_stats_config))
                     .expect("mip store creation failed");
@@ -316,6 +333,16 @@ async fn launch(
     ));
 
     let mip_store = final_state.read().get_mip_store().clone();
+    let mip_votes = MipVotes::load(
+        &SETTINGS.versioning.mip_votes_path,
+        SETTINGS
+            .versioning
+            .mip_votes
+            .iter()
+            .map(|mip_vote| (mip_vote.name.clone(), mip_vote.vote))
+            .collect(),
+    )
+    .expect("could not load the MIP votes");
 
     let bootstrap_config: BootstrapConfig = BootstrapConfig {
         bootstrap_list: SETTINGS.bootstrap.bootstrap_list.clone(),
@@ -377,6 +404,7 @@ async fn launch(
         final_state.clone(),
         DefaultConnector,
         *VERSION,
+        T0,
         *GENESIS_TIMESTAMP,
         *END_TIMESTAMP,
         args.restart_from_snapshot_at_period,
@@ -518,6 +546,8 @@ async fn launch(
             .broadcast_slot_execution_traces_channel_capacity,
         max_execution_traces_slot_limit: SETTINGS.execution.execution_traces_limit,
         block_dump_folder_path,
+        reward_history_enabled: SETTINGS.execution.reward_history_enabled,
+        transaction_history_enabled: SETTINGS.execution.transaction_history_enabled,
     };
 
     let execution_channels = ExecutionChannels {
@@ -804,7 +834,15 @@ async fn launch(
             .factory
             .stop_production_when_zero_connections,
         chain_id: *CHAINID,
+        slashing_protection_path: SETTINGS.factory.slashing_protection_path.clone(),
+        missed_slots_history_length: SETTINGS.factory.missed_slots_history_length,
+        max_miss_ratio: *POS_MISS_RATE_DEACTIVATION_THRESHOLD,
     };
+    // slots missed by the staking addresses, recorded by the factory and exposed by the private API
+    let missed_slots = MissedSlots::new(
+        factory_config.missed_slots_history_length,
+        factory_config.periods_per_cycle,
+    );
     let factory_channels = FactoryChannels {
         selector: selector_controller.clone(),
         consensus: consensus_controller.clone(),
@@ -812,11 +850,26 @@ async fn launch(
         protocol: protocol_controller.clone(),
         storage: shared_storage.clone(),
     };
+    let staking_signer: Box<dyn Signer> = match &SETTINGS.factory.remote_signer {
+        Some(remote_signer) => Box::new(RemoteSigner::new(
+            remote_signer
+                .endpoint
+                .parse()
+                .expect("invalid remote signer endpoint"),
+            read_remote_signer_secret(&remote_signer.secret_path)
+                .expect("could not read the remote signer secret"),
+            remote_signer.timeout.to_duration(),
+        )),
+        None => Box::new(LocalSigner::new(node_wallet.clone())),
+    };
     let factory_manager = start_factory(
         factory_config,
-        node_wallet.clone(),
+        staking_signer,
         factory_channels,
         mip_store.clone(),
+        mip_votes.clone(),
+        missed_slots.clone(),
+        massa_metrics.clone(),
     );
 
     let bootstrap_manager = bootstrap_config.listen_addr.map(|addr| {
@@ -879,6 +932,20 @@ async fn launch(
         chain_id: *CHAINID,
         deferred_credits_delta: SETTINGS.api.deferred_credits_delta,
         minimal_fees: SETTINGS.pool.minimal_fees,
+        // a single access control, so that the public and API v2 servers share the budgets of the clients.
+        // The API servers give the IP of the connections.
+        access: SETTINGS.api.access.enabled.then(|| {
+            Arc::new(
+                AccessControl::new(SETTINGS.api.access.clone(), true)
+                    .expect("invalid API access control settings"),
+            )
+        }),
+        private_access: SETTINGS.api.private_access.enabled.then(|| {
+            Arc::new(
+                AccessControl::new(SETTINGS.api.private_access.clone(), true)
+                    .expect("invalid private API access control settings"),
+            )
+        }),
     };
 
     // spawn Massa API
@@ -968,6 +1035,7 @@ async fn launch(
             protocol_config: protocol_config.clone(),
             node_id,
             mip_store: mip_store.clone(),
+            mip_votes: mip_votes.clone(),
             version: *VERSION,
             stop_cv: sig_int_toggled.clone(),
             node_wallet: node_wallet.clone(),
@@ -1006,6 +1074,9 @@ async fn launch(
         api_config.clone(),
         sig_int_toggled,
         node_wallet,
+        missed_slots,
+        mip_store.clone(),
+        mip_votes,
     );
     let api_private_handle = api_private
         .serve(&SETTINGS.api.bind_private, &api_config)
@@ -1175,6 +1246,7 @@ fn configure_grpc(
         client_private_key_path: settings.client_private_key_path.clone(),
         chain_id: *CHAINID,
         minimal_fees,
+        access: settings.access.clone(),
     }
 }
 
@@ -1272,6 +1344,19 @@ async fn stop(
     // note that FinalLedger gets destroyed as soon as its Arc count goes to zero
 }
 
+/// Tools run instead of the node
+#[derive(Subcommand)]
+enum NodeCommand {
+    #[command(flatten)]
+    Ledger(LedgerCommand),
+    #[command(flatten)]
+    SlashingProtection(SlashingProtectionCommand),
+    #[command(flatten)]
+    RemoteSigner(RemoteSignerCommand),
+    #[command(flatten)]
+    MipSimulator(MipSimulatorCommand),
+}
+
 #[derive(Parser)]
 #[command(version = crate_version!())]
 struct Args {
@@ -1286,6 +1371,10 @@ struct Args {
     #[arg(long = "restart-from-snapshot-at-period")]
     restart_from_snapshot_at_period: Option<u64>,
 
+    /// Tools, run instead of the node
+    #[command(subcommand)]
+    command: Option<NodeCommand>,
+
     #[cfg(feature = "op_spammer")]
     /// number of operations
     #[arg(
@@ -1338,7 +1427,24 @@ fn load_wallet(
 }
 
 fn main() -> anyhow::Result<()> {
-    let args = Args::parse();
+    let mut args = Args::parse();
+
+    match args.command.take() {
+        Some(NodeCommand::Ledger(command)) => return run_ledger_command(command),
+        Some(NodeCommand::SlashingProtection(command)) => {
+            return run_slashing_protection_command(command)
+        }
+        Some(NodeCommand::RemoteSigner(command)) => {
+            let wallet = load_wallet(
+                args.password.clone(),
+                &SETTINGS.factory.staking_wallet_path,
+                *CHAINID,
+            )?;
+            return run_remote_signer_command(command, wallet);
+        }
+        Some(NodeCommand::MipSimulator(command)) => return run_mip_simulator_command(command),
+        None => {}
+    }
 
     let tokio_rt = tokio::runtime::Builder::new_multi_thread()
         .thread_name_fn(|| {
@@ -0,0 +1,60 @@
+//! Offline simulation of the MIP deployments, used to check governance proposals before deployment
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use clap::Subcommand;
+use massa_versioning::mip_simulator::{simulate_mips, AnnouncementSample};
+use massa_versioning::mips::{get_mip_list_with, MipDefinition};
+use serde::de::DeserializeOwned;
+
+use crate::settings::SETTINGS;
+
+/// MIP simulation tools, run instead of the node
+#[derive(Subcommand)]
+pub enum MipSimulatorCommand {
+    /// Simulate the deployment of MIPs from the network versions announced over time,
+    /// print the state transitions and the activation time of each MIP, then exit.
+    SimulateMips {
+        /// Path of a JSON file listing the announcement samples:
+        /// `[{"timestamp": <millis>, "announced": {"<network version>": <percentage of the latest blocks>}}]`
+        #[arg(long)]
+        samples: PathBuf,
+        /// Path of a JSON file listing MIP definitions, in the format of the `versioning.mips` setting,
+        /// simulated along with the built-in MIPs. Defaults to the MIPs of this node.
+        #[arg(long)]
+        mips: Option<PathBuf>,
+    },
+}
+
+/// Runs a MIP simulation command
+pub fn run_mip_simulator_command(command: MipSimulatorCommand) -> anyhow::Result<()> {
+    match command {
+        MipSimulatorCommand::SimulateMips { samples, mips } => {
+            let samples: Vec<AnnouncementSample> = read_json(&samples)?;
+            let mip_list = match mips {
+                Some(path) => {
+                    let definitions: Vec<MipDefinition> = read_json(&path)?;
+                    get_mip_list_with(&definitions)?
+                }
+                None => get_mip_list_with(&SETTINGS.versioning.mips)?,
+            };
+            if mip_list.is_empty() {
+                println!("No MIP to simulate");
+            }
+            for simulation in simulate_mips(&mip_list, &samples) {
+                print!("{}", simulation);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Reads a JSON file
+fn read_json<T: DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    let file = File::open(path).with_context(|| format!("could not open {}", path.display()))?;
+    serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("could not parse {}", path.display()))
+}
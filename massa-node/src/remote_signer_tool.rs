@@ -0,0 +1,72 @@
+//! Remote signer: serves the staking keys of this node's wallet to nodes configured with `factory.remote_signer`
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context;
+use clap::Subcommand;
+use massa_factory_worker::SlashingProtection;
+use massa_models::config::DENUNCIATION_EXPIRE_PERIODS;
+use massa_wallet::{
+    serve_remote_signer, LocalSigner, RemoteSignerEndpoint, RemoteSignerListener, Wallet,
+};
+use parking_lot::{Mutex, RwLock};
+
+/// Remote signer tools, run instead of the node
+#[derive(Subcommand)]
+pub enum RemoteSignerCommand {
+    /// Serve signature requests with the keys of the staking wallet, instead of running the node
+    RunRemoteSigner {
+        /// Where to listen: tcp://host:port or unix:///path/to/socket
+        #[arg(long)]
+        listen: String,
+        /// File containing the secret shared with the nodes
+        #[arg(long)]
+        secret_path: PathBuf,
+        /// Record of every block and endorsement signed by this host, consulted before signing
+        #[arg(long)]
+        slashing_protection_path: PathBuf,
+    },
+}
+
+/// Runs a remote signer command
+pub fn run_remote_signer_command(
+    command: RemoteSignerCommand,
+    wallet: Arc<RwLock<Wallet>>,
+) -> anyhow::Result<()> {
+    match command {
+        RemoteSignerCommand::RunRemoteSigner {
+            listen,
+            secret_path,
+            slashing_protection_path,
+        } => {
+            let endpoint: RemoteSignerEndpoint = listen.parse()?;
+            let secret = read_remote_signer_secret(&secret_path)?;
+            let slashing_protection =
+                SlashingProtection::open(&slashing_protection_path, DENUNCIATION_EXPIRE_PERIODS)
+                    .with_context(|| {
+                        format!("could not open {}", slashing_protection_path.display())
+                    })?;
+            let listener = RemoteSignerListener::bind(&endpoint)?;
+            println!("Remote signer listening on {}", endpoint);
+            serve_remote_signer(
+                listener,
+                Box::new(LocalSigner::new(wallet)),
+                secret,
+                Arc::new(Mutex::new(slashing_protection)),
+            )?;
+            Ok(())
+        }
+    }
+}
+
+/// Reads a remote signer secret file, ignoring surrounding whitespace
+pub fn read_remote_signer_secret(path: &Path) -> anyhow::Result<Vec<u8>> {
+    let secret = std::fs::read_to_string(path)
+        .with_context(|| format!("could not read the remote signer secret {}", path.display()))?;
+    let secret = secret.trim();
+    if secret.is_empty() {
+        anyhow::bail!("the remote signer secret {} is empty", path.display());
+    }
+    Ok(secret.as_bytes().to_vec())
+}
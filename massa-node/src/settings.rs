@@ -3,10 +3,12 @@
 //! Build here the default node settings from the configuration file toml
 use std::{collections::HashMap, path::PathBuf};
 
+use massa_api_exports::access::AccessConfig;
 use massa_bootstrap::IpType;
 use massa_models::{amount::Amount, config::build_massa_settings, node::NodeId};
 use massa_protocol_exports::PeerCategoryInfo;
 use massa_time::MassaTime;
+use massa_versioning::{mip_votes::MipVote, mips::MipDefinition};
 use serde::Deserialize;
 use std::net::{IpAddr, SocketAddr};
 
@@ -37,6 +39,8 @@ pub struct ExecutionSettings {
     /// slot execution traces channel capacity
     pub broadcast_slot_execution_traces_channel_capacity: usize,
     pub execution_traces_limit: usize,
+    pub reward_history_enabled: bool,
+    pub transaction_history_enabled: bool,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -52,6 +56,9 @@ pub struct LedgerSettings {
     pub initial_deferred_credits_path: Option<PathBuf>,
     pub ledger_backup_periods_interval: u64,
     pub max_ledger_backups: u64,
+    pub address_history_enabled: bool,
+    pub state_proofs_enabled: bool,
+    pub index_history_periods: u64,
 }
 
 /// Bootstrap configuration.
@@ -88,6 +95,23 @@ pub struct FactorySettings {
     pub staking_wallet_path: PathBuf,
     /// stop the production in case we are not connected to anyone
     pub stop_production_when_zero_connections: bool,
+    /// Record of every block and endorsement signed by the node, to prevent double-production
+    pub slashing_protection_path: PathBuf,
+    /// Number of blocks and endorsements missed by the staking addresses kept in memory
+    pub missed_slots_history_length: usize,
+    /// Remote signer holding the staking keys. The staking wallet is used if absent
+    pub remote_signer: Option<RemoteSignerSettings>,
+}
+
+/// Remote signer settings
+#[derive(Debug, Deserialize, Clone)]
+pub struct RemoteSignerSettings {
+    /// where the signer listens: tcp://host:port or unix:///path/to/socket
+    pub endpoint: String,
+    /// file containing the secret shared with the signer
+    pub secret_path: PathBuf,
+    /// timeout of the requests to the signer
+    pub timeout: MassaTime,
 }
 
 /// Pool configuration, read from a file configuration
@@ -129,6 +153,8 @@ pub struct APISettings {
     // whether to broadcast for blocks, endorsement and operations
     pub enable_broadcast: bool,
     pub deferred_credits_delta: MassaTime,
+    pub access: AccessConfig,
+    pub private_access: AccessConfig,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -368,6 +394,8 @@ pub struct GrpcSettings {
     pub client_certificate_path: PathBuf,
     /// client private key path
     pub client_private_key_path: PathBuf,
+    /// access control of the server
+    pub access: AccessConfig,
 }
 
 /// gRPC API settings.
@@ -383,6 +411,23 @@ pub struct GrpcApiSettings {
 pub struct VersioningSettings {
     // Warn user to update its node if we reach this percentage for announced network versions
     pub(crate) mip_stats_warn_announced_version: u32,
+    // MIPs supported by the node in addition to the built-in ones, for private networks
+    #[serde(default)]
+    pub(crate) mips: Vec<MipDefinition>,
+    // File where the votes on MIPs are persisted
+    pub(crate) mip_votes_path: PathBuf,
+    // Votes on MIPs, used until votes are changed through the private API
+    #[serde(default)]
+    pub(crate) mip_votes: Vec<MipVoteSettings>,
+}
+
+/// Vote of the node operator on a MIP
+#[derive(Debug, Deserialize, Clone)]
+pub struct MipVoteSettings {
+    /// MIP name
+    pub(crate) name: String,
+    /// for or against
+    pub(crate) vote: MipVote,
 }
 
 #[derive(Debug, Deserialize, Clone)]
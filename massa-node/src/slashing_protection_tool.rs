@@ -0,0 +1,75 @@
+//! Offline export / import of the slashing protection database, used to move staking keys between nodes
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use clap::Subcommand;
+use massa_factory_worker::SlashingProtection;
+use massa_models::config::{CHAINID, DENUNCIATION_EXPIRE_PERIODS};
+
+use crate::settings::SETTINGS;
+
+/// Slashing protection tools, run instead of the node
+#[derive(Subcommand)]
+pub enum SlashingProtectionCommand {
+    /// Export the record of the blocks and endorsements signed by this node to a file, then exit.
+    /// The node must be stopped.
+    ExportSlashingProtection {
+        /// Path of the export file to create
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Add the records of an export file to the slashing protection database of this node, then exit.
+    /// The node must be stopped.
+    ImportSlashingProtection {
+        /// Path of the export file to read
+        #[arg(long)]
+        input: PathBuf,
+    },
+}
+
+/// Runs a slashing protection command
+pub fn run_slashing_protection_command(command: SlashingProtectionCommand) -> anyhow::Result<()> {
+    let path = &SETTINGS.factory.slashing_protection_path;
+    let mut db = SlashingProtection::open(path, DENUNCIATION_EXPIRE_PERIODS)
+        .with_context(|| format!("could not open {}", path.display()))?;
+    match command {
+        SlashingProtectionCommand::ExportSlashingProtection { output } => {
+            export_slashing_protection(&db, &output)
+        }
+        SlashingProtectionCommand::ImportSlashingProtection { input } => {
+            import_slashing_protection(&mut db, &input)
+        }
+    }
+}
+
+/// Exports every record of the database
+fn export_slashing_protection(db: &SlashingProtection, output: &Path) -> anyhow::Result<()> {
+    let file =
+        File::create(output).with_context(|| format!("could not create {}", output.display()))?;
+    let mut writer = BufWriter::new(file);
+    let count = db.export(&mut writer, *CHAINID)?;
+    writer
+        .flush()
+        .with_context(|| format!("could not write {}", output.display()))?;
+    println!(
+        "Exported {} slashing protection records to {}",
+        count,
+        output.display()
+    );
+    Ok(())
+}
+
+/// Adds the records of an export to the database
+fn import_slashing_protection(db: &mut SlashingProtection, input: &Path) -> anyhow::Result<()> {
+    let file = File::open(input).with_context(|| format!("could not open {}", input.display()))?;
+    let count = db.import(BufReader::new(file), *CHAINID)?;
+    println!(
+        "Imported {} new slashing protection records from {}",
+        count,
+        input.display()
+    );
+    Ok(())
+}
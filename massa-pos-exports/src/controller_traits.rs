@@ -7,7 +7,11 @@ use std::collections::BTreeMap;
 
 use crate::PosResult;
 use massa_hash::Hash;
-use massa_models::{address::Address, prehash::PreHashSet, slot::Slot};
+use massa_models::{
+    address::Address,
+    prehash::PreHashSet,
+    slot::{IndexedSlot, Slot},
+};
 
 #[cfg(feature = "test-exports")]
 use std::collections::{HashMap, VecDeque};
@@ -21,6 +25,17 @@ pub struct Selection {
     pub producer: Address,
 }
 
+/// Draws of an address in a cycle
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressCycleDraws {
+    /// cycle of the draws
+    pub cycle: u64,
+    /// slots at which the address is selected to produce the block, in increasing order
+    pub block_slots: Vec<Slot>,
+    /// slots and indices at which the address is selected to produce an endorsement, in increasing order
+    pub endorsement_slots: Vec<IndexedSlot>,
+}
+
 #[cfg(feature = "test-exports")]
 use std::sync::Arc;
 
@@ -62,6 +77,19 @@ pub trait SelectorController: Send + Sync {
         restrict_to_addresses: Option<&'a PreHashSet<Address>>,
     ) -> PosResult<BTreeMap<Slot, Selection>>;
 
+    /// Get the draws of an address, using the index of the draws by address
+    /// # Arguments
+    /// * `address`: drawn address
+    /// * `cycle_range`: cycles to get the draws for (only available cycles are considered)
+    ///
+    /// # Returns
+    /// The draws of the cycles in which the address is selected at least once, in increasing cycle order
+    fn get_address_draws(
+        &self,
+        address: &Address,
+        cycle_range: std::ops::RangeInclusive<u64>,
+    ) -> PosResult<Vec<AddressCycleDraws>>;
+
     /// Returns a boxed clone of self.
     /// Useful to allow cloning `Box<dyn SelectorController>`.
     fn clone_box(&self) -> Box<dyn SelectorController>;
@@ -17,9 +17,9 @@ mod pos_final_state;
 mod settings;
 
 pub use config::PoSConfig;
+pub use controller_traits::{AddressCycleDraws, Selection, SelectorController, SelectorManager};
 #[cfg(any(test, feature = "test-exports"))]
 pub use controller_traits::{MockSelectorController, MockSelectorControllerWrapper};
-pub use controller_traits::{Selection, SelectorController, SelectorManager};
 pub use cycle_info::*;
 pub use deferred_credits::*;
 pub use error::*;
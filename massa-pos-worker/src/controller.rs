@@ -8,7 +8,9 @@ use std::collections::BTreeMap;
 use crate::{Command, DrawCachePtr};
 use massa_hash::Hash;
 use massa_models::{address::Address, prehash::PreHashSet, slot::Slot};
-use massa_pos_exports::{PosError, PosResult, Selection, SelectorController, SelectorManager};
+use massa_pos_exports::{
+    AddressCycleDraws, PosError, PosResult, Selection, SelectorController, SelectorManager,
+};
 #[cfg(feature = "test-exports")]
 use std::collections::{HashMap, VecDeque};
 use std::sync::mpsc::SyncSender;
@@ -159,6 +161,36 @@ impl SelectorController for SelectorControllerImpl {
 
         // get the selections
         let mut res = BTreeMap::new();
+        if let Some(restrict_to_addrs) = restrict_to_addresses {
+            // only visit the slots at which the addresses are drawn, using the index by address
+            let cycle_begin = slot_begin.get_cycle(self.periods_per_cycle);
+            let cycle_end = slot_end_included.get_cycle(self.periods_per_cycle);
+            for cycle in cycle_begin..=cycle_end {
+                let cycle_draws = cache.get(cycle).ok_or(PosError::CycleUnavailable(cycle))?;
+                for address in restrict_to_addrs {
+                    let Some(address_draws) = cycle_draws.address_draws.get(address) else {
+                        continue;
+                    };
+                    let slots = address_draws.block_slots.iter().chain(
+                        address_draws
+                            .endorsement_slots
+                            .iter()
+                            .map(|indexed_slot| &indexed_slot.slot),
+                    );
+                    for slot in slots {
+                        if *slot < slot_begin || *slot > slot_end_included {
+                            continue;
+                        }
+                        let slot_selection = cycle_draws
+                            .draws
+                            .get(slot)
+                            .ok_or(PosError::CycleUnavailable(cycle))?;
+                        res.insert(*slot, slot_selection.clone());
+                    }
+                }
+            }
+            return Ok(res);
+        }
         let mut slot = slot_begin;
         while slot <= slot_end_included {
             let cycle = slot.get_cycle(self.periods_per_cycle);
@@ -166,18 +198,7 @@ impl SelectorController for SelectorControllerImpl {
                 .get(cycle)
                 .and_then(|selections| selections.draws.get(&slot))
                 .ok_or(PosError::CycleUnavailable(cycle))?;
-            if let Some(restrict_to_addrs) = restrict_to_addresses {
-                if restrict_to_addrs.contains(&slot_selection.producer)
-                    || slot_selection
-                        .endorsements
-                        .iter()
-                        .any(|a| restrict_to_addrs.contains(a))
-                {
-                    res.insert(slot, slot_selection.clone());
-                }
-            } else {
-                res.insert(slot, slot_selection.clone());
-            }
+            res.insert(slot, slot_selection.clone());
             slot = match slot.get_next_slot(self.thread_count) {
                 Ok(s) => s,
                 Err(_) => break,
@@ -186,6 +207,29 @@ impl SelectorController for SelectorControllerImpl {
         Ok(res)
     }
 
+    /// Get the draws of an address in the available cycles of a cycle range
+    /// # Arguments
+    /// * `address`: drawn address
+    /// * `cycle_range`: target cycles (from included, to included)
+    fn get_address_draws(
+        &self,
+        address: &Address,
+        cycle_range: std::ops::RangeInclusive<u64>,
+    ) -> PosResult<Vec<AddressCycleDraws>> {
+        // take lock
+        let (_cache_cv, cache_lock) = &*self.cache;
+        let cache_guard = cache_lock.read();
+        let cache = cache_guard.as_ref().map_err(|err| err.clone())?;
+
+        // the cache is sorted by increasing cycle
+        Ok(cache
+            .0
+            .iter()
+            .filter(|cycle_draws| cycle_range.contains(&cycle_draws.cycle))
+            .filter_map(|cycle_draws| cycle_draws.address_draws.get(address).cloned())
+            .collect())
+    }
+
     /// Returns a boxed clone of self.
     /// Allows cloning `Box<dyn SelectorController>`,
     /// see `massa-pos-exports/controller_traits.rs`
@@ -1,7 +1,11 @@
 use crate::CycleDraws;
 use massa_hash::Hash;
-use massa_models::{address::Address, slot::Slot};
-use massa_pos_exports::{PosError, PosResult, Selection, SelectorConfig};
+use massa_models::{
+    address::Address,
+    prehash::PreHashMap,
+    slot::{IndexedSlot, Slot},
+};
+use massa_pos_exports::{AddressCycleDraws, PosError, PosResult, Selection, SelectorConfig};
 use rand::{distributions::Distribution, SeedableRng};
 use rand_distr::WeightedAliasIndex;
 use rand_xoshiro::Xoshiro256PlusPlus;
@@ -57,6 +61,7 @@ pub(crate) fn perform_draws(
         draws: HashMap::with_capacity(
             (cfg.periods_per_cycle as usize) * (cfg.thread_count as usize),
         ),
+        address_draws: PreHashMap::default(),
     };
 
     let mut five_first_slots: Vec<(Slot, Selection)> = Vec::new();
@@ -83,6 +88,29 @@ pub(crate) fn perform_draws(
             five_first_slots.push((cur_slot, selection.clone()));
             count += 1;
         }
+        // index the draws by address, slots are drawn in increasing order
+        let new_address_draws = || AddressCycleDraws {
+            cycle,
+            ..Default::default()
+        };
+        cycle_draws
+            .address_draws
+            .entry(selection.producer)
+            .or_insert_with(new_address_draws)
+            .block_slots
+            .push(cur_slot);
+        for (index, endorser) in selection.endorsements.iter().enumerate() {
+            cycle_draws
+                .address_draws
+                .entry(*endorser)
+                .or_insert_with(new_address_draws)
+                .endorsement_slots
+                .push(IndexedSlot {
+                    slot: cur_slot,
+                    index,
+                });
+        }
+
         // add to draws
         cycle_draws.draws.insert(cur_slot, selection);
 
@@ -8,8 +8,8 @@ mod draw;
 mod worker;
 
 use massa_hash::Hash;
-use massa_models::{address::Address, slot::Slot};
-use massa_pos_exports::{PosResult, Selection};
+use massa_models::{address::Address, prehash::PreHashMap, slot::Slot};
+use massa_pos_exports::{AddressCycleDraws, PosResult, Selection};
 
 use parking_lot::{Condvar, Mutex, RwLock, RwLockReadGuard};
 use std::{
@@ -83,6 +83,8 @@ pub(crate) struct CycleDraws {
     pub cycle: u64,
     /// cache of draws
     pub draws: HashMap<Slot, Selection>,
+    /// draws indexed by address
+    pub address_draws: PreHashMap<Address, AddressCycleDraws>,
 }
 
 /// Structure of the shared pointer to the computed draws, or error if the draw system failed.
@@ -2,6 +2,7 @@ use massa_hash::Hash;
 use massa_models::address::Address;
 use massa_models::config::PERIODS_PER_CYCLE;
 use massa_models::config::THREAD_COUNT;
+use massa_models::prehash::PreHashSet;
 use massa_models::slot::Slot;
 use massa_pos_exports::PosError;
 use massa_pos_exports::SelectorConfig;
@@ -79,6 +80,77 @@ fn test_standalone_selection() {
     manager.stop();
 }
 
+#[test]
+fn test_address_draws_index() {
+    // initialize the selector configuration and the test inputs
+    let cfg = SelectorConfig::default();
+    let address =
+        Address::from_str("AU12Cyu2f7C7isA3ADAhoNuq9ZUFPKP24jmiGj3sh9D1pHoAWKDYY").unwrap();
+    let mut lookback_rolls: BTreeMap<Address, u64> = std::collections::BTreeMap::new();
+    lookback_rolls.insert(address, 1);
+    lookback_rolls.insert(
+        Address::from_str("AU12BTfZ7k1z6PsLEUZeHYNirz6WJ3NdrWto9H4TkVpkV9xE2TJg2").unwrap(),
+        3,
+    );
+    let mut seed_bytes = [0u8; 16];
+    thread_rng().fill_bytes(&mut seed_bytes);
+    let lookback_seed = Hash::compute_from(&seed_bytes);
+
+    // start the selector thread and compute the draws of cycle 0
+    let (mut manager, controller) = start_selector_worker(cfg).unwrap();
+    controller
+        .feed_cycle(0, lookback_rolls, lookback_seed)
+        .unwrap();
+    controller.wait_for_draws(0).unwrap();
+
+    // scan all the draws of a range, then keep the ones involving the address
+    let range = Slot::new(3, 0)..=Slot::new(PERIODS_PER_CYCLE - 4, THREAD_COUNT - 1);
+    let expected: BTreeMap<_, _> = controller
+        .get_available_selections_in_range(range.clone(), None)
+        .unwrap()
+        .into_iter()
+        .filter(|(_slot, selection)| {
+            selection.producer == address || selection.endorsements.contains(&address)
+        })
+        .collect();
+
+    // the index gives the same result
+    let mut addresses = PreHashSet::default();
+    addresses.insert(address);
+    let indexed = controller
+        .get_available_selections_in_range(range.clone(), Some(&addresses))
+        .unwrap();
+    assert_eq!(indexed, expected);
+
+    // and the draws of the address match
+    let address_draws = controller.get_address_draws(&address, 0..=5).unwrap();
+    assert_eq!(address_draws.len(), 1);
+    assert_eq!(address_draws[0].cycle, 0);
+    let in_range_block_slots: Vec<Slot> = address_draws[0]
+        .block_slots
+        .iter()
+        .filter(|slot| range.contains(slot))
+        .copied()
+        .collect();
+    let expected_block_slots: Vec<Slot> = expected
+        .iter()
+        .filter(|(_slot, selection)| selection.producer == address)
+        .map(|(slot, _selection)| *slot)
+        .collect();
+    assert_eq!(in_range_block_slots, expected_block_slots);
+    for indexed_slot in &address_draws[0].endorsement_slots {
+        let selection = controller.get_selection(indexed_slot.slot).unwrap();
+        assert_eq!(selection.endorsements[indexed_slot.index], address);
+    }
+    assert!(controller
+        .get_address_draws(&address, 1..=5)
+        .unwrap()
+        .is_empty());
+
+    // stop worker
+    manager.stop();
+}
+
 #[test]
 fn test_invalid_roll_distribution() {
     // initialize the selector configuration and the test inputs
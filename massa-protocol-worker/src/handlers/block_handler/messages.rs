@@ -1,5 +1,5 @@
 use massa_models::{
-    block_header::{BlockHeader, BlockHeaderDeserializer, SecuredHeader},
+    block_header::{BlockHeader, BlockHeaderDeserializer, BlockHeaderVersionAt, SecuredHeader},
     block_id::{BlockId, BlockIdDeserializer, BlockIdSerializer},
     operation::{
         OperationId, OperationIdSerializer, OperationIdsDeserializer, OperationsDeserializer,
@@ -16,7 +16,7 @@ use nom::{
     IResult, Parser,
 };
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use std::ops::Bound::Included;
+use std::{ops::Bound::Included, sync::Arc};
 
 /// Request block data
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -218,20 +218,26 @@ pub struct BlockMessageDeserializerArgs {
     pub max_denunciations_in_block_header: u32,
     pub last_start_period: Option<u64>,
     pub chain_id: u64,
+    /// selects the layout of the block headers, version 0 at every slot if `None`
+    pub block_header_version_at: Option<Arc<dyn BlockHeaderVersionAt>>,
 }
 
 impl BlockMessageDeserializer {
     pub fn new(args: BlockMessageDeserializerArgs) -> Self {
+        let mut block_header_deserializer = BlockHeaderDeserializer::new(
+            args.thread_count,
+            args.endorsement_count,
+            args.max_denunciations_in_block_header,
+            args.last_start_period,
+            args.chain_id,
+        );
+        if let Some(version_at) = args.block_header_version_at {
+            block_header_deserializer = block_header_deserializer.with_version_at(version_at);
+        }
         Self {
             id_deserializer: U64VarIntDeserializer::new(Included(0), Included(u64::MAX)),
             block_header_deserializer: SecureShareDeserializer::new(
-                BlockHeaderDeserializer::new(
-                    args.thread_count,
-                    args.endorsement_count,
-                    args.max_denunciations_in_block_header,
-                    args.last_start_period,
-                    args.chain_id,
-                ),
+                block_header_deserializer,
                 args.chain_id,
             ),
             block_id_deserializer: BlockIdDeserializer::new(),
@@ -394,6 +400,7 @@ mod tests {
                 max_denunciations_in_block_header: 1,
                 last_start_period: None,
                 chain_id: *CHAINID,
+                block_header_version_at: None,
             });
         let (rest, deserialized_message) = deserializer
             .deserialize::<DeserializeError>(&buffer)
@@ -481,6 +488,7 @@ mod tests {
                 max_denunciations_in_block_header: 1,
                 last_start_period: None,
                 chain_id: *CHAINID,
+                block_header_version_at: None,
             });
         deserializer
             .deserialize::<DeserializeError>(&buffer)
@@ -499,6 +507,7 @@ mod tests {
                 max_denunciations_in_block_header: 1,
                 last_start_period: None,
                 chain_id: *CHAINID,
+                block_header_version_at: None,
             });
         let (rest, deserialized_message) = deserializer
             .deserialize::<DeserializeError>(&buffer)
@@ -1,5 +1,6 @@
 use std::{
     collections::{HashMap, HashSet},
+    sync::Arc,
     thread::JoinHandle,
     time::Instant,
 };
@@ -46,7 +47,7 @@ use massa_protocol_exports::{ProtocolConfig, ProtocolError};
 use massa_serialization::{DeserializeError, Deserializer, Serializer};
 use massa_storage::Storage;
 use massa_time::TimeError;
-use massa_versioning::versioning::MipStore;
+use massa_versioning::{block_header_factory::BlockHeaderFactory, versioning::MipStore};
 use rand::thread_rng;
 use rand::{seq::SliceRandom, Rng};
 use tracing::{debug, info, warn};
@@ -130,6 +131,12 @@ impl RetrievalThread {
                 max_denunciations_in_block_header: self.config.max_denunciations_in_block_header,
                 last_start_period: Some(self.config.last_start_period),
                 chain_id: self.config.chain_id,
+                block_header_version_at: Some(Arc::new(BlockHeaderFactory {
+                    mip_store: self.mip_store.clone(),
+                    thread_count: self.config.thread_count,
+                    t0: self.config.t0,
+                    genesis_timestamp: self.config.genesis_timestamp,
+                })),
             });
 
         let tick_update_metrics = tick(self.massa_metrics.tick_delay);
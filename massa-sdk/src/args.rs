@@ -0,0 +1,574 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Encoding and decoding of smart contract function arguments in the `Args` format of the
+//! AssemblyScript smart contract SDK.
+//!
+//! Numbers are little endian, `string`, `bytes` and `address` values are prefixed by their byte
+//! length (u32) and arrays by the byte length of their serialized elements (u32).
+//!
+//! The text form of an argument is `type:value`, for example `u64:100`, `string:"foo"`,
+//! `address:AU12...`, `bytes:0x0aff` or `array<u64>:[1,2,3]`.
+
+use massa_models::address::Address;
+use num::BigUint;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Byte size of a serialized `u256`
+const U256_SIZE: usize = 32;
+
+/// Error when encoding or decoding arguments
+#[non_exhaustive]
+#[derive(Error, Debug)]
+pub enum ArgsError {
+    /// Unknown argument type
+    #[error("unknown argument type: {0}")]
+    UnknownType(String),
+    /// Invalid value for an argument type
+    #[error("invalid {0} value: {1}")]
+    InvalidValue(ArgType, String),
+    /// Not enough bytes to decode an argument
+    #[error("not enough bytes to decode a {0} argument")]
+    NotEnoughBytes(ArgType),
+    /// Bytes left after decoding all the arguments
+    #[error("{0} bytes left after decoding the arguments")]
+    TrailingBytes(usize),
+}
+
+/// Type of an argument
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgType {
+    /// boolean, one byte
+    Bool,
+    /// unsigned 8 bits integer
+    U8,
+    /// unsigned 16 bits integer
+    U16,
+    /// unsigned 32 bits integer
+    U32,
+    /// unsigned 64 bits integer
+    U64,
+    /// unsigned 128 bits integer
+    U128,
+    /// unsigned 256 bits integer
+    U256,
+    /// signed 8 bits integer
+    I8,
+    /// signed 16 bits integer
+    I16,
+    /// signed 32 bits integer
+    I32,
+    /// signed 64 bits integer
+    I64,
+    /// signed 128 bits integer
+    I128,
+    /// 32 bits float
+    F32,
+    /// 64 bits float
+    F64,
+    /// UTF-8 string
+    String,
+    /// byte array
+    Bytes,
+    /// address, encoded as a string
+    Address,
+    /// array of arguments of the same type
+    Array(Box<ArgType>),
+}
+
+impl FromStr for ArgType {
+    type Err = ArgsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "bool" => ArgType::Bool,
+            "u8" => ArgType::U8,
+            "u16" => ArgType::U16,
+            "u32" => ArgType::U32,
+            "u64" => ArgType::U64,
+            "u128" => ArgType::U128,
+            "u256" => ArgType::U256,
+            "i8" => ArgType::I8,
+            "i16" => ArgType::I16,
+            "i32" => ArgType::I32,
+            "i64" => ArgType::I64,
+            "i128" => ArgType::I128,
+            "f32" => ArgType::F32,
+            "f64" => ArgType::F64,
+            "string" => ArgType::String,
+            "bytes" => ArgType::Bytes,
+            "address" => ArgType::Address,
+            _ => match s.strip_prefix("array<").and_then(|s| s.strip_suffix('>')) {
+                Some(item_type) => ArgType::Array(Box::new(item_type.parse()?)),
+                None => return Err(ArgsError::UnknownType(s.to_string())),
+            },
+        })
+    }
+}
+
+impl Display for ArgType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgType::Bool => write!(f, "bool"),
+            ArgType::U8 => write!(f, "u8"),
+            ArgType::U16 => write!(f, "u16"),
+            ArgType::U32 => write!(f, "u32"),
+            ArgType::U64 => write!(f, "u64"),
+            ArgType::U128 => write!(f, "u128"),
+            ArgType::U256 => write!(f, "u256"),
+            ArgType::I8 => write!(f, "i8"),
+            ArgType::I16 => write!(f, "i16"),
+            ArgType::I32 => write!(f, "i32"),
+            ArgType::I64 => write!(f, "i64"),
+            ArgType::I128 => write!(f, "i128"),
+            ArgType::F32 => write!(f, "f32"),
+            ArgType::F64 => write!(f, "f64"),
+            ArgType::String => write!(f, "string"),
+            ArgType::Bytes => write!(f, "bytes"),
+            ArgType::Address => write!(f, "address"),
+            ArgType::Array(item_type) => write!(f, "array<{}>", item_type),
+        }
+    }
+}
+
+/// Value of an argument
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgValue {
+    /// boolean
+    Bool(bool),
+    /// unsigned 8 bits integer
+    U8(u8),
+    /// unsigned 16 bits integer
+    U16(u16),
+    /// unsigned 32 bits integer
+    U32(u32),
+    /// unsigned 64 bits integer
+    U64(u64),
+    /// unsigned 128 bits integer
+    U128(u128),
+    /// unsigned 256 bits integer
+    U256(BigUint),
+    /// signed 8 bits integer
+    I8(i8),
+    /// signed 16 bits integer
+    I16(i16),
+    /// signed 32 bits integer
+    I32(i32),
+    /// signed 64 bits integer
+    I64(i64),
+    /// signed 128 bits integer
+    I128(i128),
+    /// 32 bits float
+    F32(f32),
+    /// 64 bits float
+    F64(f64),
+    /// UTF-8 string
+    String(String),
+    /// byte array
+    Bytes(Vec<u8>),
+    /// address
+    Address(Address),
+    /// array of values of the given item type
+    Array(ArgType, Vec<ArgValue>),
+}
+
+impl ArgValue {
+    /// Type of the value
+    pub fn arg_type(&self) -> ArgType {
+        match self {
+            ArgValue::Bool(_) => ArgType::Bool,
+            ArgValue::U8(_) => ArgType::U8,
+            ArgValue::U16(_) => ArgType::U16,
+            ArgValue::U32(_) => ArgType::U32,
+            ArgValue::U64(_) => ArgType::U64,
+            ArgValue::U128(_) => ArgType::U128,
+            ArgValue::U256(_) => ArgType::U256,
+            ArgValue::I8(_) => ArgType::I8,
+            ArgValue::I16(_) => ArgType::I16,
+            ArgValue::I32(_) => ArgType::I32,
+            ArgValue::I64(_) => ArgType::I64,
+            ArgValue::I128(_) => ArgType::I128,
+            ArgValue::F32(_) => ArgType::F32,
+            ArgValue::F64(_) => ArgType::F64,
+            ArgValue::String(_) => ArgType::String,
+            ArgValue::Bytes(_) => ArgType::Bytes,
+            ArgValue::Address(_) => ArgType::Address,
+            ArgValue::Array(item_type, _) => ArgType::Array(Box::new(item_type.clone())),
+        }
+    }
+
+    /// Parse the text form of a value of the given type (without the `type:` prefix)
+    pub fn parse(arg_type: &ArgType, value: &str) -> Result<ArgValue, ArgsError> {
+        let invalid = || ArgsError::InvalidValue(arg_type.clone(), value.to_string());
+        Ok(match arg_type {
+            ArgType::Bool => ArgValue::Bool(value.parse().map_err(|_| invalid())?),
+            ArgType::U8 => ArgValue::U8(value.parse().map_err(|_| invalid())?),
+            ArgType::U16 => ArgValue::U16(value.parse().map_err(|_| invalid())?),
+            ArgType::U32 => ArgValue::U32(value.parse().map_err(|_| invalid())?),
+            ArgType::U64 => ArgValue::U64(value.parse().map_err(|_| invalid())?),
+            ArgType::U128 => ArgValue::U128(value.parse().map_err(|_| invalid())?),
+            ArgType::U256 => {
+                let value = BigUint::from_str(value).map_err(|_| invalid())?;
+                if value.bits() > (U256_SIZE * 8) as u64 {
+                    return Err(invalid());
+                }
+                ArgValue::U256(value)
+            }
+            ArgType::I8 => ArgValue::I8(value.parse().map_err(|_| invalid())?),
+            ArgType::I16 => ArgValue::I16(value.parse().map_err(|_| invalid())?),
+            ArgType::I32 => ArgValue::I32(value.parse().map_err(|_| invalid())?),
+            ArgType::I64 => ArgValue::I64(value.parse().map_err(|_| invalid())?),
+            ArgType::I128 => ArgValue::I128(value.parse().map_err(|_| invalid())?),
+            ArgType::F32 => ArgValue::F32(value.parse().map_err(|_| invalid())?),
+            ArgType::F64 => ArgValue::F64(value.parse().map_err(|_| invalid())?),
+            ArgType::String => ArgValue::String(unquote(value).ok_or_else(invalid)?),
+            ArgType::Bytes => ArgValue::Bytes(decode_hex(value).ok_or_else(invalid)?),
+            ArgType::Address => ArgValue::Address(value.parse().map_err(|_| invalid())?),
+            ArgType::Array(item_type) => {
+                let items = value
+                    .trim()
+                    .strip_prefix('[')
+                    .and_then(|value| value.strip_suffix(']'))
+                    .ok_or_else(invalid)?;
+                ArgValue::Array(
+                    *item_type.clone(),
+                    split_array_items(items)
+                        .ok_or_else(invalid)?
+                        .into_iter()
+                        .map(|item| ArgValue::parse(item_type, item))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+        })
+    }
+
+    /// Append the serialized value to a buffer
+    pub fn serialize(&self, buffer: &mut Vec<u8>) {
+        match self {
+            ArgValue::Bool(value) => buffer.push(u8::from(*value)),
+            ArgValue::U8(value) => buffer.push(*value),
+            ArgValue::U16(value) => buffer.extend(value.to_le_bytes()),
+            ArgValue::U32(value) => buffer.extend(value.to_le_bytes()),
+            ArgValue::U64(value) => buffer.extend(value.to_le_bytes()),
+            ArgValue::U128(value) => buffer.extend(value.to_le_bytes()),
+            ArgValue::U256(value) => {
+                let mut bytes = value.to_bytes_le();
+                bytes.resize(U256_SIZE, 0);
+                buffer.extend(bytes);
+            }
+            ArgValue::I8(value) => buffer.extend(value.to_le_bytes()),
+            ArgValue::I16(value) => buffer.extend(value.to_le_bytes()),
+            ArgValue::I32(value) => buffer.extend(value.to_le_bytes()),
+            ArgValue::I64(value) => buffer.extend(value.to_le_bytes()),
+            ArgValue::I128(value) => buffer.extend(value.to_le_bytes()),
+            ArgValue::F32(value) => buffer.extend(value.to_le_bytes()),
+            ArgValue::F64(value) => buffer.extend(value.to_le_bytes()),
+            ArgValue::String(value) => serialize_with_length(value.as_bytes(), buffer),
+            ArgValue::Bytes(value) => serialize_with_length(value, buffer),
+            ArgValue::Address(value) => serialize_with_length(value.to_string().as_bytes(), buffer),
+            ArgValue::Array(_, items) => {
+                let mut items_buffer = Vec::new();
+                for item in items {
+                    item.serialize(&mut items_buffer);
+                }
+                serialize_with_length(&items_buffer, buffer);
+            }
+        }
+    }
+
+    /// Decode a value of the given type at the beginning of `bytes`, returns the value and the remaining bytes
+    pub fn deserialize<'a>(
+        arg_type: &ArgType,
+        bytes: &'a [u8],
+    ) -> Result<(ArgValue, &'a [u8]), ArgsError> {
+        let invalid =
+            |value: &[u8]| ArgsError::InvalidValue(arg_type.clone(), format!("{:?}", value));
+        macro_rules! from_le_bytes {
+            ($variant:ident, $number:ty) => {{
+                let (value, rest) = take(arg_type, bytes, std::mem::size_of::<$number>())?;
+                (
+                    ArgValue::$variant(<$number>::from_le_bytes(value.try_into().unwrap())),
+                    rest,
+                )
+            }};
+        }
+        Ok(match arg_type {
+            ArgType::Bool => {
+                let (value, rest) = take(arg_type, bytes, 1)?;
+                match value[0] {
+                    0 => (ArgValue::Bool(false), rest),
+                    1 => (ArgValue::Bool(true), rest),
+                    _ => return Err(invalid(value)),
+                }
+            }
+            ArgType::U8 => from_le_bytes!(U8, u8),
+            ArgType::U16 => from_le_bytes!(U16, u16),
+            ArgType::U32 => from_le_bytes!(U32, u32),
+            ArgType::U64 => from_le_bytes!(U64, u64),
+            ArgType::U128 => from_le_bytes!(U128, u128),
+            ArgType::U256 => {
+                let (value, rest) = take(arg_type, bytes, U256_SIZE)?;
+                (ArgValue::U256(BigUint::from_bytes_le(value)), rest)
+            }
+            ArgType::I8 => from_le_bytes!(I8, i8),
+            ArgType::I16 => from_le_bytes!(I16, i16),
+            ArgType::I32 => from_le_bytes!(I32, i32),
+            ArgType::I64 => from_le_bytes!(I64, i64),
+            ArgType::I128 => from_le_bytes!(I128, i128),
+            ArgType::F32 => from_le_bytes!(F32, f32),
+            ArgType::F64 => from_le_bytes!(F64, f64),
+            ArgType::String => {
+                let (value, rest) = take_with_length(arg_type, bytes)?;
+                let value = String::from_utf8(value.to_vec()).map_err(|_| invalid(value))?;
+                (ArgValue::String(value), rest)
+            }
+            ArgType::Bytes => {
+                let (value, rest) = take_with_length(arg_type, bytes)?;
+                (ArgValue::Bytes(value.to_vec()), rest)
+            }
+            ArgType::Address => {
+                let (value, rest) = take_with_length(arg_type, bytes)?;
+                let address = std::str::from_utf8(value)
+                    .ok()
+                    .and_then(|address| Address::from_str(address).ok())
+                    .ok_or_else(|| invalid(value))?;
+                (ArgValue::Address(address), rest)
+            }
+            ArgType::Array(item_type) => {
+                let (mut items_bytes, rest) = take_with_length(arg_type, bytes)?;
+                let mut items = Vec::new();
+                while !items_bytes.is_empty() {
+                    let (item, items_rest) = ArgValue::deserialize(item_type, items_bytes)?;
+                    items.push(item);
+                    items_bytes = items_rest;
+                }
+                (ArgValue::Array(*item_type.clone(), items), rest)
+            }
+        })
+    }
+
+    /// Text form of the value, without the `type:` prefix
+    fn fmt_value(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgValue::Bool(value) => write!(f, "{}", value),
+            ArgValue::U8(value) => write!(f, "{}", value),
+            ArgValue::U16(value) => write!(f, "{}", value),
+            ArgValue::U32(value) => write!(f, "{}", value),
+            ArgValue::U64(value) => write!(f, "{}", value),
+            ArgValue::U128(value) => write!(f, "{}", value),
+            ArgValue::U256(value) => write!(f, "{}", value),
+            ArgValue::I8(value) => write!(f, "{}", value),
+            ArgValue::I16(value) => write!(f, "{}", value),
+            ArgValue::I32(value) => write!(f, "{}", value),
+            ArgValue::I64(value) => write!(f, "{}", value),
+            ArgValue::I128(value) => write!(f, "{}", value),
+            ArgValue::F32(value) => write!(f, "{}", value),
+            ArgValue::F64(value) => write!(f, "{}", value),
+            ArgValue::String(value) => write!(f, "{:?}", value),
+            ArgValue::Bytes(value) => {
+                write!(f, "0x")?;
+                value.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+            }
+            ArgValue::Address(value) => write!(f, "{}", value),
+            ArgValue::Array(_, items) => {
+                write!(f, "[")?;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    item.fmt_value(f)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl FromStr for ArgValue {
+    type Err = ArgsError;
+
+    /// Parse the `type:value` text form of an argument
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((arg_type, value)) => ArgValue::parse(&arg_type.parse()?, value),
+            None => Err(ArgsError::UnknownType(s.to_string())),
+        }
+    }
+}
+
+impl Display for ArgValue {
+    /// Display the `type:value` text form of an argument
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:", self.arg_type())?;
+        self.fmt_value(f)
+    }
+}
+
+/// Serialize arguments in the `Args` format
+pub fn serialize_args(values: &[ArgValue]) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    for value in values {
+        value.serialize(&mut buffer);
+    }
+    buffer
+}
+
+/// Decode arguments of the given types serialized in the `Args` format, all the bytes must be consumed
+pub fn deserialize_args(bytes: &[u8], types: &[ArgType]) -> Result<Vec<ArgValue>, ArgsError> {
+    let mut rest = bytes;
+    let mut values = Vec::with_capacity(types.len());
+    for arg_type in types {
+        let (value, value_rest) = ArgValue::deserialize(arg_type, rest)?;
+        values.push(value);
+        rest = value_rest;
+    }
+    if !rest.is_empty() {
+        return Err(ArgsError::TrailingBytes(rest.len()));
+    }
+    Ok(values)
+}
+
+fn serialize_with_length(bytes: &[u8], buffer: &mut Vec<u8>) {
+    buffer.extend((bytes.len() as u32).to_le_bytes());
+    buffer.extend(bytes);
+}
+
+fn take<'a>(
+    arg_type: &ArgType,
+    bytes: &'a [u8],
+    size: usize,
+) -> Result<(&'a [u8], &'a [u8]), ArgsError> {
+    if bytes.len() < size {
+        return Err(ArgsError::NotEnoughBytes(arg_type.clone()));
+    }
+    Ok(bytes.split_at(size))
+}
+
+fn take_with_length<'a>(
+    arg_type: &ArgType,
+    bytes: &'a [u8],
+) -> Result<(&'a [u8], &'a [u8]), ArgsError> {
+    let (length, rest) = take(arg_type, bytes, std::mem::size_of::<u32>())?;
+    take(
+        arg_type,
+        rest,
+        u32::from_le_bytes(length.try_into().unwrap()) as usize,
+    )
+}
+
+/// Remove the optional double quotes around a string, and unescape its escaped characters
+fn unquote(value: &str) -> Option<String> {
+    let Some(inner) = value.strip_prefix('"') else {
+        return Some(value.to_string());
+    };
+    let inner = inner.strip_suffix('"')?;
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'n' => unquoted.push('\n'),
+                'r' => unquoted.push('\r'),
+                't' => unquoted.push('\t'),
+                c => unquoted.push(c),
+            },
+            '"' => return None,
+            c => unquoted.push(c),
+        }
+    }
+    Some(unquoted)
+}
+
+/// Split the items of an array text form on the commas outside of quotes and nested arrays
+fn split_array_items(items: &str) -> Option<Vec<&str>> {
+    if items.trim().is_empty() {
+        return Some(Vec::new());
+    }
+    let mut result = Vec::new();
+    let mut depth = 0usize;
+    let mut in_quotes = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (index, c) in items.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            '[' if !in_quotes => depth += 1,
+            ']' if !in_quotes => depth = depth.checked_sub(1)?,
+            ',' if !in_quotes && depth == 0 => {
+                result.push(items[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    if in_quotes || depth != 0 {
+        return None;
+    }
+    result.push(items[start..].trim());
+    Some(result)
+}
+
+/// Decodes a hexadecimal string, optionally prefixed with `0x`
+pub fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.trim_start_matches("0x");
+    if !hex.is_ascii() || hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_args_round_trip() {
+        let values = [
+            "bool:true",
+            "u8:255",
+            "u64:100",
+            "u128:340282366920938463463374607431768211455",
+            "u256:115792089237316195423570985008687907853269984665640564039457584007913129639935",
+            "i32:-42",
+            "f64:1.5",
+            "string:\"foo \\\"bar\\\"\"",
+            "bytes:0x0aff",
+            "address:AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x",
+            "array<u16>:[1,2,3]",
+            "array<string>:[\"a,b\",\"c\"]",
+            "array<array<u8>>:[[1],[]]",
+        ]
+        .iter()
+        .map(|value| value.parse::<ArgValue>().unwrap())
+        .collect::<Vec<_>>();
+        let bytes = serialize_args(&values);
+        // u64 values are 8 bytes little endian, strings are prefixed with their u32 byte length
+        assert_eq!(
+            serialize_args(&["u64:100".parse().unwrap(), "string:foo".parse().unwrap()]),
+            vec![100, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, b'f', b'o', b'o']
+        );
+        let types = values.iter().map(ArgValue::arg_type).collect::<Vec<_>>();
+        assert_eq!(deserialize_args(&bytes, &types).unwrap(), values);
+        for value in &values {
+            assert_eq!(&value.to_string().parse::<ArgValue>().unwrap(), value);
+        }
+        assert!(matches!(
+            deserialize_args(&bytes[..bytes.len() - 1], &types),
+            Err(ArgsError::NotEnoughBytes(_))
+        ));
+        assert!(matches!(
+            "u8:256".parse::<ArgValue>(),
+            Err(ArgsError::InvalidValue(ArgType::U8, _))
+        ));
+        assert!(matches!(
+            "foo".parse::<ArgValue>(),
+            Err(ArgsError::UnknownType(_))
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) 2023 MASSA LABS <info@massa.net>
 
 use massa_time::MassaTime;
+use std::path::PathBuf;
 
 /// Client common settings.
 /// the client common settings
@@ -45,3 +46,31 @@ pub struct WsConfig {
     /// Max number of redirections.
     pub max_redirections: usize,
 }
+
+/// gRPC client TLS settings, matching the TLS settings of the node gRPC server.
+/// the gRPC client TLS settings
+#[derive(Debug, Clone, Default)]
+pub struct GrpcTlsConfig {
+    /// whether to enable TLS.
+    pub enable_tls: bool,
+    /// whether to enable mTLS (requires `enable_tls` to be true).
+    pub enable_mtls: bool,
+    /// domain name checked against the server certificate.
+    pub domain_name: String,
+    /// path of the certificate authority root used to check the server certificate.
+    pub certificate_authority_root_path: PathBuf,
+    /// path of the client certificate (mTLS only).
+    pub client_certificate_path: PathBuf,
+    /// path of the client private key (mTLS only).
+    pub client_private_key_path: PathBuf,
+}
+
+/// gRPC client settings.
+/// the gRPC client settings
+#[derive(Debug, Clone, Default)]
+pub struct GrpcConfig {
+    /// TLS settings of the public service.
+    pub public: GrpcTlsConfig,
+    /// TLS settings of the private service.
+    pub private: GrpcTlsConfig,
+}
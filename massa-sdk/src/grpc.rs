@@ -0,0 +1,315 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Typed clients for the public and private gRPC services of a node.
+//!
+//! Every unary method takes the proto request and returns the proto response, every streaming
+//! method takes a stream of requests and returns the stream of responses sent by the node.
+//! The connection can be secured with TLS or mTLS, using the certificates generated by the node
+//! (see the [`crate::cert_manager`] module).
+//!
+//! The methods of the `PublicServiceExt` and `PrivateServiceExt` services, not defined by
+//! [massa_proto_rs] yet, take and return the messages of the [`crate::grpc_ext`] module.
+
+use crate::grpc_ext;
+use crate::{ClientError, GrpcTlsConfig};
+use http::uri::PathAndQuery;
+use massa_proto_rs::massa::api::v1 as grpc_api;
+use massa_proto_rs::massa::api::v1::private_service_client::PrivateServiceClient;
+use massa_proto_rs::massa::api::v1::public_service_client::PublicServiceClient;
+use std::net::SocketAddr;
+use tonic::client::Grpc;
+use tonic::codec::ProstCodec;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Identity};
+use tonic::{Code, IntoStreamingRequest, Request, Status, Streaming};
+
+/// Open a channel to a gRPC server, using TLS (and mTLS) if enabled in the configuration
+pub async fn connect_channel(
+    addr: SocketAddr,
+    tls_config: &GrpcTlsConfig,
+) -> Result<Channel, ClientError> {
+    let endpoint = if tls_config.enable_tls {
+        let ca_cert = std::fs::read_to_string(&tls_config.certificate_authority_root_path)?;
+        let mut tls = ClientTlsConfig::new()
+            .ca_certificate(Certificate::from_pem(ca_cert))
+            .domain_name(tls_config.domain_name.clone());
+        if tls_config.enable_mtls {
+            let cert = std::fs::read_to_string(&tls_config.client_certificate_path)?;
+            let key = std::fs::read_to_string(&tls_config.client_private_key_path)?;
+            tls = tls.identity(Identity::from_pem(cert, key));
+        }
+        Channel::from_shared(format!("https://{}", addr))?.tls_config(tls)?
+    } else {
+        Channel::from_shared(format!("grpc://{}", addr))?
+    };
+    Ok(endpoint.connect().await?)
+}
+
+/// Generate the typed wrappers of the unary methods of a gRPC service client
+macro_rules! unary_methods {
+    ($($(#[$doc:meta])* $name:ident($request:ident) -> $response:ident;)*) => {
+        $(
+            $(#[$doc])*
+            pub async fn $name(
+                &self,
+                request: grpc_api::$request,
+            ) -> Result<grpc_api::$response, Status> {
+                self.client
+                    .clone()
+                    .$name(request)
+                    .await
+                    .map(|response| response.into_inner())
+            }
+        )*
+    };
+}
+
+/// Generate the typed wrappers of the streaming methods of a gRPC service client
+macro_rules! streaming_methods {
+    ($($(#[$doc:meta])* $name:ident($request:ident) -> $response:ident;)*) => {
+        $(
+            $(#[$doc])*
+            pub async fn $name(
+                &self,
+                requests: impl IntoStreamingRequest<Message = grpc_api::$request>,
+            ) -> Result<Streaming<grpc_api::$response>, Status> {
+                self.client
+                    .clone()
+                    .$name(requests)
+                    .await
+                    .map(|response| response.into_inner())
+            }
+        )*
+    };
+}
+
+/// Generate the typed wrappers of the unary methods of a gRPC extension service
+macro_rules! ext_unary_methods {
+    ($service:literal, $($(#[$doc:meta])* $name:ident($method:literal, $request:ident) -> $response:ident;)*) => {
+        $(
+            $(#[$doc])*
+            pub async fn $name(
+                &self,
+                request: grpc_ext::$request,
+            ) -> Result<grpc_ext::$response, Status> {
+                ext_unary(
+                    &self.ext,
+                    concat!("/massa.api.v1.", $service, "/", $method),
+                    request,
+                )
+                .await
+            }
+        )*
+    };
+}
+
+/// Call a unary method of a gRPC extension service
+async fn ext_unary<Req, Resp>(
+    ext: &Grpc<Channel>,
+    path: &'static str,
+    request: Req,
+) -> Result<Resp, Status>
+where
+    Req: prost::Message + Send + Sync + 'static,
+    Resp: prost::Message + Default + Send + Sync + 'static,
+{
+    let mut ext = ext.clone();
+    ext.ready()
+        .await
+        .map_err(|e| Status::new(Code::Unknown, format!("Service was not ready: {}", e)))?;
+    ext.unary(
+        Request::new(request),
+        PathAndQuery::from_static(path),
+        ProstCodec::<Req, Resp>::default(),
+    )
+    .await
+    .map(|response| response.into_inner())
+}
+
+/// Typed client of the public gRPC service
+#[derive(Clone)]
+pub struct GrpcPublicClient {
+    client: PublicServiceClient<Channel>,
+    ext: Grpc<Channel>,
+}
+
+impl GrpcPublicClient {
+    /// Connect to the public gRPC service of a node
+    pub async fn connect(
+        addr: SocketAddr,
+        tls_config: &GrpcTlsConfig,
+    ) -> Result<GrpcPublicClient, ClientError> {
+        Ok(GrpcPublicClient::new(
+            connect_channel(addr, tls_config).await?,
+        ))
+    }
+
+    /// Create a client from an already opened channel
+    pub fn new(channel: Channel) -> GrpcPublicClient {
+        GrpcPublicClient {
+            client: PublicServiceClient::new(channel.clone()),
+            ext: Grpc::new(channel),
+        }
+    }
+
+    /// Get the raw service client, e.g. to set compression or message size limits
+    pub fn inner(&self) -> &PublicServiceClient<Channel> {
+        &self.client
+    }
+
+    unary_methods! {
+        /// Execute a read-only call (bytecode or function call)
+        execute_read_only_call(ExecuteReadOnlyCallRequest) -> ExecuteReadOnlyCallResponse;
+        /// Get the ABI call stacks of operations (requires the execution traces on the node)
+        get_operation_abi_call_stacks(GetOperationAbiCallStacksRequest) -> GetOperationAbiCallStacksResponse;
+        /// Get the ABI call stacks of slots (requires the execution traces on the node)
+        get_slot_abi_call_stacks(GetSlotAbiCallStacksRequest) -> GetSlotAbiCallStacksResponse;
+        /// Get the transfers of slots (requires the execution traces on the node)
+        get_slot_transfers(GetSlotTransfersRequest) -> GetSlotTransfersResponse;
+        /// Get blocks by ids
+        get_blocks(GetBlocksRequest) -> GetBlocksResponse;
+        /// Get datastore entries
+        get_datastore_entries(GetDatastoreEntriesRequest) -> GetDatastoreEntriesResponse;
+        /// Get endorsements by ids
+        get_endorsements(GetEndorsementsRequest) -> GetEndorsementsResponse;
+        /// Get the stakers and their rolls
+        get_stakers(GetStakersRequest) -> GetStakersResponse;
+        /// Get the node status
+        get_status(GetStatusRequest) -> GetStatusResponse;
+        /// Get the best parents for the next block
+        get_next_block_best_parents(GetNextBlockBestParentsRequest) -> GetNextBlockBestParentsResponse;
+        /// Get operations by ids
+        get_operations(GetOperationsRequest) -> GetOperationsResponse;
+        /// Get smart contract execution events
+        get_sc_execution_events(GetScExecutionEventsRequest) -> GetScExecutionEventsResponse;
+        /// Get the selector draws
+        get_selector_draws(GetSelectorDrawsRequest) -> GetSelectorDrawsResponse;
+        /// Get the transactions throughput
+        get_transactions_throughput(GetTransactionsThroughputRequest) -> GetTransactionsThroughputResponse;
+        /// Query the execution state
+        query_state(QueryStateRequest) -> QueryStateResponse;
+        /// Search blocks
+        search_blocks(SearchBlocksRequest) -> SearchBlocksResponse;
+        /// Search endorsements
+        search_endorsements(SearchEndorsementsRequest) -> SearchEndorsementsResponse;
+        /// Search operations
+        search_operations(SearchOperationsRequest) -> SearchOperationsResponse;
+    }
+
+    streaming_methods! {
+        /// Stream the new blocks
+        new_blocks(NewBlocksRequest) -> NewBlocksResponse;
+        /// Stream the new endorsements
+        new_endorsements(NewEndorsementsRequest) -> NewEndorsementsResponse;
+        /// Stream the new filled blocks
+        new_filled_blocks(NewFilledBlocksRequest) -> NewFilledBlocksResponse;
+        /// Stream the new operations
+        new_operations(NewOperationsRequest) -> NewOperationsResponse;
+        /// Stream the new slot execution outputs
+        new_slot_execution_outputs(NewSlotExecutionOutputsRequest) -> NewSlotExecutionOutputsResponse;
+        /// Stream the new slot transfers (requires the execution traces on the node)
+        new_slot_transfers(NewSlotTransfersRequest) -> NewSlotTransfersResponse;
+        /// Stream the new slot ABI call stacks (requires the execution traces on the node)
+        new_slot_abi_call_stacks(NewSlotAbiCallStacksRequest) -> NewSlotAbiCallStacksResponse;
+        /// Send blocks to the node
+        send_blocks(SendBlocksRequest) -> SendBlocksResponse;
+        /// Send endorsements to the node
+        send_endorsements(SendEndorsementsRequest) -> SendEndorsementsResponse;
+        /// Send operations to the node
+        send_operations(SendOperationsRequest) -> SendOperationsResponse;
+        /// Stream the transactions throughput
+        transactions_throughput(TransactionsThroughputRequest) -> TransactionsThroughputResponse;
+    }
+
+    ext_unary_methods! {
+        "PublicServiceExt",
+        /// Get a page of the datastore entries of an address
+        get_datastore_entries_page("GetDatastoreEntriesPage", GetDatastoreEntriesPageRequest) -> GetDatastoreEntriesPageResponse;
+        /// Get a page of the upcoming draws of a list of addresses
+        get_addresses_draws("GetAddressesDraws", GetAddressesDrawsRequest) -> GetAddressesDrawsResponse;
+        /// Get the pending asynchronous messages matching a filter, in the final and candidate pools
+        get_async_messages("GetAsyncMessages", GetAsyncMessagesRequest) -> GetAsyncMessagesResponse;
+    }
+}
+
+/// Typed client of the private gRPC service
+#[derive(Clone)]
+pub struct GrpcPrivateClient {
+    client: PrivateServiceClient<Channel>,
+    ext: Grpc<Channel>,
+}
+
+impl GrpcPrivateClient {
+    /// Connect to the private gRPC service of a node
+    pub async fn connect(
+        addr: SocketAddr,
+        tls_config: &GrpcTlsConfig,
+    ) -> Result<GrpcPrivateClient, ClientError> {
+        Ok(GrpcPrivateClient::new(
+            connect_channel(addr, tls_config).await?,
+        ))
+    }
+
+    /// Create a client from an already opened channel
+    pub fn new(channel: Channel) -> GrpcPrivateClient {
+        GrpcPrivateClient {
+            client: PrivateServiceClient::new(channel.clone()),
+            ext: Grpc::new(channel),
+        }
+    }
+
+    /// Get the raw service client, e.g. to set compression or message size limits
+    pub fn inner(&self) -> &PrivateServiceClient<Channel> {
+        &self.client
+    }
+
+    unary_methods! {
+        /// Add IP addresses to the bootstrap blacklist
+        add_to_bootstrap_blacklist(AddToBootstrapBlacklistRequest) -> AddToBootstrapBlacklistResponse;
+        /// Add IP addresses to the bootstrap whitelist
+        add_to_bootstrap_whitelist(AddToBootstrapWhitelistRequest) -> AddToBootstrapWhitelistResponse;
+        /// Add IP addresses to the peers whitelist
+        add_to_peers_whitelist(AddToPeersWhitelistRequest) -> AddToPeersWhitelistResponse;
+        /// Add staking secret keys
+        add_staking_secret_keys(AddStakingSecretKeysRequest) -> AddStakingSecretKeysResponse;
+        /// Ban nodes by ids
+        ban_nodes_by_ids(BanNodesByIdsRequest) -> BanNodesByIdsResponse;
+        /// Ban nodes by IP addresses
+        ban_nodes_by_ips(BanNodesByIpsRequest) -> BanNodesByIpsResponse;
+        /// Get the bootstrap blacklist
+        get_bootstrap_blacklist(GetBootstrapBlacklistRequest) -> GetBootstrapBlacklistResponse;
+        /// Get the bootstrap whitelist
+        get_bootstrap_whitelist(GetBootstrapWhitelistRequest) -> GetBootstrapWhitelistResponse;
+        /// Get the status of the MIPs
+        get_mip_status(GetMipStatusRequest) -> GetMipStatusResponse;
+        /// Allow everyone to bootstrap from the node
+        allow_everyone_to_bootstrap(AllowEveryoneToBootstrapRequest) -> AllowEveryoneToBootstrapResponse;
+        /// Get the node status
+        get_node_status(GetNodeStatusRequest) -> GetNodeStatusResponse;
+        /// Get the peers whitelist
+        get_peers_whitelist(GetPeersWhitelistRequest) -> GetPeersWhitelistResponse;
+        /// Remove IP addresses from the bootstrap blacklist
+        remove_from_bootstrap_blacklist(RemoveFromBootstrapBlacklistRequest) -> RemoveFromBootstrapBlacklistResponse;
+        /// Remove IP addresses from the bootstrap whitelist
+        remove_from_bootstrap_whitelist(RemoveFromBootstrapWhitelistRequest) -> RemoveFromBootstrapWhitelistResponse;
+        /// Remove IP addresses from the peers whitelist
+        remove_from_peers_whitelist(RemoveFromPeersWhitelistRequest) -> RemoveFromPeersWhitelistResponse;
+        /// Remove staking addresses
+        remove_staking_addresses(RemoveStakingAddressesRequest) -> RemoveStakingAddressesResponse;
+        /// Sign messages with the node key
+        sign_messages(SignMessagesRequest) -> SignMessagesResponse;
+        /// Gracefully stop the node
+        shutdown_gracefully(ShutdownGracefullyRequest) -> ShutdownGracefullyResponse;
+        /// Unban nodes by ids
+        unban_nodes_by_ids(UnbanNodesByIdsRequest) -> UnbanNodesByIdsResponse;
+        /// Unban nodes by IP addresses
+        unban_nodes_by_ips(UnbanNodesByIpsRequest) -> UnbanNodesByIpsResponse;
+    }
+
+    ext_unary_methods! {
+        "PrivateServiceExt",
+        /// Get the status of the MIPs known by the node, with the votes of the node operator
+        get_mip_status_with_votes("GetMipStatusWithVotes", GetMipStatusWithVotesRequest) -> GetMipStatusWithVotesResponse;
+        /// Set or remove the vote of the node operator on a MIP
+        set_mip_vote("SetMipVote", SetMipVoteRequest) -> SetMipVoteResponse;
+    }
+}
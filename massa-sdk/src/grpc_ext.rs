@@ -0,0 +1,220 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Messages of the gRPC methods served by the node but not defined by [massa_proto_rs] yet.
+//!
+//! They belong to the `massa.api.v1.PublicServiceExt` and `massa.api.v1.PrivateServiceExt`
+//! services described in `massa-grpc/proto/massa/api/v1/ext.proto`, and are written by hand
+//! from that file with the same names and field numbers. The node serves them and the
+//! clients of the [`crate::grpc`] module call them.
+
+use massa_proto_rs::massa::model::v1 as grpc_model;
+
+/// Request of a page of the datastore entries of an address
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetDatastoreEntriesPageRequest {
+    /// Address owning the datastore
+    #[prost(string, tag = "1")]
+    pub address: String,
+    /// Only return keys starting with this prefix
+    #[prost(bytes = "vec", tag = "2")]
+    pub prefix: Vec<u8>,
+    /// First key of the range (included)
+    #[prost(bytes = "vec", optional, tag = "3")]
+    pub start_key: Option<Vec<u8>>,
+    /// End of the range (excluded)
+    #[prost(bytes = "vec", optional, tag = "4")]
+    pub end_key: Option<Vec<u8>>,
+    /// `next_cursor` returned by the previous page, if any
+    #[prost(bytes = "vec", optional, tag = "5")]
+    pub cursor: Option<Vec<u8>>,
+    /// Return the entries in decreasing key order
+    #[prost(bool, tag = "6")]
+    pub reverse: bool,
+    /// Maximum number of entries to return
+    #[prost(uint32, optional, tag = "7")]
+    pub limit: Option<u32>,
+    /// Query the final datastore instead of the candidate one
+    #[prost(bool, tag = "8")]
+    pub is_final: bool,
+}
+
+/// Datastore key/value pair
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DatastoreKeyValue {
+    /// Datastore key
+    #[prost(bytes = "vec", tag = "1")]
+    pub key: Vec<u8>,
+    /// Datastore value
+    #[prost(bytes = "vec", tag = "2")]
+    pub value: Vec<u8>,
+}
+
+/// Page of datastore entries
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetDatastoreEntriesPageResponse {
+    /// Entries of the page, in the requested order
+    #[prost(message, repeated, tag = "1")]
+    pub entries: Vec<DatastoreKeyValue>,
+    /// Cursor to pass to get the next page, absent if this is the last one
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub next_cursor: Option<Vec<u8>>,
+}
+
+/// Request of a page of the upcoming draws of a list of addresses
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetAddressesDrawsRequest {
+    /// Addresses to get the draws of
+    #[prost(string, repeated, tag = "1")]
+    pub addresses: Vec<String>,
+    /// First cycle (included), the current cycle if absent
+    #[prost(uint64, optional, tag = "2")]
+    pub start_cycle: Option<u64>,
+    /// Last cycle (included), the last drawn cycle if absent
+    #[prost(uint64, optional, tag = "3")]
+    pub end_cycle: Option<u64>,
+    /// Index in `addresses` of the first address of the page, 0 if absent
+    #[prost(uint32, optional, tag = "4")]
+    pub offset: Option<u32>,
+    /// Maximum number of addresses in the page
+    #[prost(uint32, optional, tag = "5")]
+    pub limit: Option<u32>,
+}
+
+/// Endorsement draw of an address
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct EndorsementSlot {
+    /// Slot of the endorsement
+    #[prost(message, optional, tag = "1")]
+    pub slot: Option<grpc_model::Slot>,
+    /// Index of the endorsement in the slot
+    #[prost(uint32, tag = "2")]
+    pub index: u32,
+}
+
+/// Upcoming draws of an address in a cycle
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CycleDraws {
+    /// Cycle
+    #[prost(uint64, tag = "1")]
+    pub cycle: u64,
+    /// Slots of the blocks to produce, in increasing order
+    #[prost(message, repeated, tag = "2")]
+    pub block_draws: Vec<grpc_model::Slot>,
+    /// Endorsements to produce, in increasing slot order
+    #[prost(message, repeated, tag = "3")]
+    pub endorsement_draws: Vec<EndorsementSlot>,
+}
+
+/// Upcoming draws of an address
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AddressDraws {
+    /// Address
+    #[prost(string, tag = "1")]
+    pub address: String,
+    /// Draws of the cycles in which the address is selected, in increasing cycle order
+    #[prost(message, repeated, tag = "2")]
+    pub cycles: Vec<CycleDraws>,
+}
+
+/// Page of address draws
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetAddressesDrawsResponse {
+    /// Draws of the addresses of the page, in request order
+    #[prost(message, repeated, tag = "1")]
+    pub draws: Vec<AddressDraws>,
+    /// Offset to pass to get the next page, absent if this is the last one
+    #[prost(uint32, optional, tag = "2")]
+    pub next_offset: Option<u32>,
+}
+
+/// Request of the pending asynchronous messages matching a filter
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetAsyncMessagesRequest {
+    /// Only messages sent by this address
+    #[prost(string, optional, tag = "1")]
+    pub sender: Option<String>,
+    /// Only messages targeting this address
+    #[prost(string, optional, tag = "2")]
+    pub destination: Option<String>,
+    /// Only messages whose validity range ends after this slot (included)
+    #[prost(message, optional, tag = "3")]
+    pub validity_start: Option<grpc_model::Slot>,
+    /// Only messages whose validity range starts before this slot (excluded)
+    #[prost(message, optional, tag = "4")]
+    pub validity_end: Option<grpc_model::Slot>,
+    /// Only messages whose trigger watches this address
+    #[prost(string, optional, tag = "5")]
+    pub trigger_address: Option<String>,
+    /// Only messages whose `can_be_executed` flag has this value
+    #[prost(bool, optional, tag = "6")]
+    pub can_be_executed: Option<bool>,
+}
+
+/// Pending asynchronous message, in the final and candidate pools
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AsyncMessageEntry {
+    /// Message in the final pool, absent if it is not there or does not match the filter
+    #[prost(message, optional, tag = "1")]
+    pub final_message: Option<grpc_model::AsyncMessage>,
+    /// Message in the candidate pool, absent if it is not there or does not match the filter
+    #[prost(message, optional, tag = "2")]
+    pub candidate_message: Option<grpc_model::AsyncMessage>,
+}
+
+/// Pending asynchronous messages matching a filter
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetAsyncMessagesResponse {
+    /// Messages, in pool priority order
+    #[prost(message, repeated, tag = "1")]
+    pub messages: Vec<AsyncMessageEntry>,
+}
+
+/// Vote of the node operator on a MIP
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum MipVoteKind {
+    /// No vote
+    Unspecified = 0,
+    /// Announce the MIP in priority while it is started or locked in
+    For = 1,
+    /// Never announce the MIP
+    Against = 2,
+}
+
+/// Request of the status of the MIPs known by the node, with the votes of the node operator
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetMipStatusWithVotesRequest {}
+
+/// Status of a MIP with the vote of the node operator
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MipStatusWithVote {
+    /// MIP info and state
+    #[prost(message, optional, tag = "1")]
+    pub status: Option<grpc_model::MipStatusEntry>,
+    /// Vote of the node operator
+    #[prost(enumeration = "MipVoteKind", tag = "2")]
+    pub vote: i32,
+}
+
+/// Status of the MIPs known by the node, with the votes of the node operator
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetMipStatusWithVotesResponse {
+    /// Status of each MIP
+    #[prost(message, repeated, tag = "1")]
+    pub entries: Vec<MipStatusWithVote>,
+}
+
+/// Request to set or remove the vote of the node operator on a MIP
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SetMipVoteRequest {
+    /// MIP name
+    #[prost(string, tag = "1")]
+    pub name: String,
+    /// Vote, `MIP_VOTE_KIND_UNSPECIFIED` to remove the vote
+    #[prost(enumeration = "MipVoteKind", tag = "2")]
+    pub vote: i32,
+}
+
+/// Response to a MIP vote
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SetMipVoteResponse {}
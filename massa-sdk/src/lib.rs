@@ -4,6 +4,7 @@
 #![warn(missing_docs)]
 #![warn(unused_crate_dependencies)]
 
+use grpc::{GrpcPrivateClient, GrpcPublicClient};
 use http::header::HeaderName;
 use jsonrpsee::core::client::{ClientT, IdKind, Subscription, SubscriptionClientT};
 use jsonrpsee::http_client::transport::HttpBackend;
@@ -17,15 +18,23 @@ use jsonrpsee_ws_client as _;
 use massa_api_exports::page::PagedVecV2;
 use massa_api_exports::ApiRequest;
 use massa_api_exports::{
-    address::AddressInfo,
+    address::{
+        AddressDrawsPage, AddressDrawsRequest, AddressInfo, AddressRewardsPage,
+        AddressRewardsRequest, AddressTransactionsPage, AddressTransactionsRequest,
+    },
+    async_message::AsyncMessageOutput,
     block::{BlockInfo, BlockSummary},
     datastore::{DatastoreEntryInput, DatastoreEntryOutput},
     endorsement::EndorsementInfo,
     execution::{ExecuteReadOnlyResponse, ReadOnlyBytecodeExecution, ReadOnlyCall, Transfer},
-    node::NodeStatus,
+    ledger::{
+        AddressHistoryPage, AddressHistoryRequest, LedgerEntryProof, LedgerEntryProofRequest,
+    },
+    node::{MipStatus, MipVoteInput, MissedSlotsInfo, NodeStatus},
     operation::{OperationInfo, OperationInput},
     TimeInterval,
 };
+use massa_async_pool::AsyncMessageFilter;
 use massa_models::secure_share::SecureShare;
 use massa_models::slot::Slot;
 use massa_models::{
@@ -43,15 +52,18 @@ use massa_models::{
     prehash::{PreHashMap, PreHashSet},
     version::Version,
 };
-use massa_proto_rs::massa::api::v1::private_service_client::PrivateServiceClient;
-use massa_proto_rs::massa::api::v1::public_service_client::PublicServiceClient;
 use std::net::{IpAddr, SocketAddr};
 use std::str::FromStr;
 use thiserror::Error;
 
+pub mod args;
 pub mod cert_manager;
 mod config;
+pub mod grpc;
+pub mod grpc_ext;
 pub use config::ClientConfig;
+pub use config::GrpcConfig;
+pub use config::GrpcTlsConfig;
 pub use config::HttpConfig;
 pub use config::WsConfig;
 
@@ -64,6 +76,9 @@ pub enum ClientError {
     /// Connection error
     #[error("Cannot connect to grpc server: {0}")]
     Connect(#[from] tonic::transport::Error),
+    /// TLS certificates reading error
+    #[error("Cannot read grpc TLS certificates: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 /// Client
@@ -73,23 +88,29 @@ pub struct Client {
     /// private component
     pub private: RpcClient,
     /// grpc public client
-    pub grpc_public: Option<PublicServiceClient<tonic::transport::Channel>>,
+    pub grpc_public: Option<GrpcPublicClient>,
     /// grpc private client
-    pub grpc_private: Option<PrivateServiceClient<tonic::transport::Channel>>,
+    pub grpc_private: Option<GrpcPrivateClient>,
+    /// API V2 WebSocket client, used for the subscriptions
+    pub api: Option<RpcClientV2>,
     /// Chain id
     pub chain_id: u64,
 }
 
 impl Client {
     /// creates a new client
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         ip: IpAddr,
         public_port: u16,
         private_port: u16,
         grpc_public_port: u16,
         grpc_private_port: u16,
+        api_port: u16,
         chain_id: u64,
         http_config: &HttpConfig,
+        ws_config: &WsConfig,
+        grpc_config: &GrpcConfig,
     ) -> Result<Client, ClientError> {
         let public_socket_addr = SocketAddr::new(ip, public_port);
         let private_socket_addr = SocketAddr::new(ip, private_port);
@@ -97,31 +118,42 @@ impl Client {
         let grpc_private_socket_addr = SocketAddr::new(ip, grpc_private_port);
         let public_url = format!("http://{}", public_socket_addr);
         let private_url = format!("http://{}", private_socket_addr);
-        let grpc_public_url = format!("grpc://{}", grpc_public_socket_addr);
-        let grpc_private_url = format!("grpc://{}", grpc_private_socket_addr);
 
         // try to start grpc client (public api) and connect to the server
-        let grpc_pub_client = match tonic::transport::Channel::from_shared(grpc_public_url)?
-            .connect()
-            .await
+        let grpc_pub_client =
+            match GrpcPublicClient::connect(grpc_public_socket_addr, &grpc_config.public).await {
+                Ok(client) => Some(client),
+                Err(e) => {
+                    tracing::warn!("unable to connect to grpc server {}", e);
+                    None
+                }
+            };
+
+        // try to start grpc client (private api) and connect to the server
+        let grpc_priv_client = match GrpcPrivateClient::connect(
+            grpc_private_socket_addr,
+            &grpc_config.private,
+        )
+        .await
         {
-            Ok(channel) => Some(PublicServiceClient::new(channel)),
+            Ok(client) => Some(client),
             Err(e) => {
                 tracing::warn!("unable to connect to grpc server {}", e);
                 None
             }
         };
 
-        // try to start grpc client (private api) and connect to the server
-        let grpc_priv_client = match tonic::transport::Channel::from_shared(grpc_private_url)?
-            .connect()
-            .await
-        {
-            Ok(channel) => Some(PrivateServiceClient::new(channel)),
-            Err(e) => {
-                tracing::warn!("unable to connect to grpc server {}", e);
-                None
+        // try to start the WebSocket client (API V2) and connect to the server
+        let api_client = if ws_config.enabled {
+            match RpcClientV2::ws_from_url(SocketAddr::new(ip, api_port), ws_config).await {
+                Ok(api_client) => Some(api_client),
+                Err(e) => {
+                    tracing::warn!("unable to connect to WebSocket server {}", e);
+                    None
+                }
             }
+        } else {
+            None
         };
 
         Ok(Client {
@@ -129,6 +161,7 @@ impl Client {
             private: RpcClient::from_url(&private_url, http_config).await,
             grpc_public: grpc_pub_client,
             grpc_private: grpc_priv_client,
+            api: api_client,
             chain_id,
         })
     }
@@ -190,6 +223,30 @@ impl RpcClient {
             .map_err(|e| to_error_obj(e.to_string()))
     }
 
+    /// Return the blocks and endorsements missed by the given staking addresses, all of them if empty.
+    pub async fn get_missed_slots(&self, addresses: Vec<Address>) -> RpcResult<MissedSlotsInfo> {
+        self.http_client
+            .request("get_missed_slots", rpc_params![addresses])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Return the status of the MIPs known by the node, with the votes of the node operator.
+    pub async fn get_mip_status(&self) -> RpcResult<Vec<MipStatus>> {
+        self.http_client
+            .request("get_mip_status", rpc_params![])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Vote for or against a MIP, or remove the vote.
+    pub async fn node_set_mip_vote(&self, mip_vote: MipVoteInput) -> RpcResult<()> {
+        self.http_client
+            .request("node_set_mip_vote", rpc_params![mip_vote])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
     /// Bans given ip address(es)
     /// No confirmation to expect.
     pub async fn node_ban_by_ip(&self, ips: Vec<IpAddr>) -> RpcResult<()> {
@@ -387,6 +444,73 @@ impl RpcClient {
             .map_err(|e| to_error_obj(e.to_string()))
     }
 
+    /// Get the pending asynchronous messages matching a filter
+    pub async fn get_async_messages(
+        &self,
+        filter: AsyncMessageFilter,
+    ) -> RpcResult<Vec<AsyncMessageOutput>> {
+        self.http_client
+            .request("get_async_messages", rpc_params![filter])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Get a page of the final ledger changes of an address
+    pub async fn get_address_ledger_history(
+        &self,
+        request: AddressHistoryRequest,
+    ) -> RpcResult<AddressHistoryPage> {
+        self.http_client
+            .request("get_address_ledger_history", rpc_params![request])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Get a proof of the value (or absence) of a final ledger sub-entry, against the root computed by the node.
+    /// The root is not committed on chain: the proof is only as trustworthy as the node, or as the other nodes returning the same root
+    pub async fn get_ledger_entry_proof(
+        &self,
+        request: LedgerEntryProofRequest,
+    ) -> RpcResult<LedgerEntryProof> {
+        self.http_client
+            .request("get_ledger_entry_proof", rpc_params![request])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Get the upcoming block and endorsement draws of addresses
+    pub async fn get_addresses_draws(
+        &self,
+        request: AddressDrawsRequest,
+    ) -> RpcResult<AddressDrawsPage> {
+        self.http_client
+            .request("get_addresses_draws", rpc_params![request])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Get the recorded final rewards and slashing losses of an address
+    pub async fn get_address_rewards(
+        &self,
+        request: AddressRewardsRequest,
+    ) -> RpcResult<AddressRewardsPage> {
+        self.http_client
+            .request("get_address_rewards", rpc_params![request])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Get the recorded final operations and coin transfers sent or received by an address
+    pub async fn get_address_transactions(
+        &self,
+        request: AddressTransactionsRequest,
+    ) -> RpcResult<AddressTransactionsPage> {
+        self.http_client
+            .request("get_address_transactions", rpc_params![request])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
     /// Get the block graph within the specified time interval.
     /// Optional parameters: from `<time_start>` (included) and to `<time_end>` (excluded) millisecond timestamp
     pub(crate) async fn _get_graph_interval(
@@ -530,6 +654,18 @@ impl RpcClientV2 {
         }
     }
 
+    /// WebSocket only constructor, returning an error if the connection fails
+    pub async fn ws_from_url(
+        socket_addr: SocketAddr,
+        ws_config: &WsConfig,
+    ) -> Result<RpcClientV2, jsonrpsee::core::Error> {
+        let ws_url = format!("ws://{}", socket_addr);
+        Ok(RpcClientV2 {
+            http_client: None,
+            ws_client: Some(try_ws_client_from_url(&ws_url, ws_config).await?),
+        })
+    }
+
     ////////////////
     //   API V2   //
     ////////////////
@@ -661,6 +797,18 @@ fn http_client_from_url(url: &str, http_config: &HttpConfig) -> HttpClient<HttpB
 }
 
 async fn ws_client_from_url(url: &str, ws_config: &WsConfig) -> WsClient
+where
+    WsClient: SubscriptionClientT,
+{
+    try_ws_client_from_url(url, ws_config)
+        .await
+        .unwrap_or_else(|_| panic!("unable to create WebSocket client for {}", url))
+}
+
+async fn try_ws_client_from_url(
+    url: &str,
+    ws_config: &WsConfig,
+) -> Result<WsClient, jsonrpsee::core::Error>
 where
     WsClient: SubscriptionClientT,
 {
@@ -679,10 +827,7 @@ where
         _ => {}
     }
 
-    builder
-        .build(url)
-        .await
-        .unwrap_or_else(|_| panic!("unable to create WebSocket client for {}", url))
+    builder.build(url).await
 }
 
 fn get_id_kind(id_kind: &str) -> IdKind {
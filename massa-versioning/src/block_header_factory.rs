@@ -0,0 +1,149 @@
+use crate::{
+    versioning::{MipComponent, MipStore},
+    versioning_factory::{FactoryError, FactoryStrategy, VersioningFactory},
+};
+use massa_hash::Hash;
+use massa_models::{
+    block_header::{BlockHeader, BlockHeaderVersionAt},
+    block_id::BlockId,
+    denunciation::Denunciation,
+    endorsement::SecureShareEndorsement,
+    slot::Slot,
+    timeslots::get_block_slot_timestamp,
+};
+use massa_time::MassaTime;
+
+/// Factory for block headers: the block header version (i.e. the version of `MipComponent::Block`)
+/// selects the layout of the headers, and is the one active at their slot
+#[derive(Clone)]
+pub struct BlockHeaderFactory {
+    pub mip_store: MipStore,
+    pub thread_count: u8,
+    pub t0: MassaTime,
+    pub genesis_timestamp: MassaTime,
+}
+
+pub struct BlockHeaderArgs {
+    pub current_version: u32,
+    pub announced_version: Option<u32>,
+    pub slot: Slot,
+    pub parents: Vec<BlockId>,
+    pub operation_merkle_root: Hash,
+    pub endorsements: Vec<SecureShareEndorsement>,
+    pub denunciations: Vec<Denunciation>,
+}
+
+impl BlockHeaderFactory {
+    /// Get the strategy to create the block header of a slot, i.e. with the version active at the slot
+    pub fn strategy_at(&self, slot: Slot) -> Result<FactoryStrategy, FactoryError> {
+        let ts = get_block_slot_timestamp(self.thread_count, self.t0, self.genesis_timestamp, slot)
+            .map_err(|e| FactoryError::OnCreate("BlockHeader".to_string(), e.to_string()))?;
+        Ok(FactoryStrategy::At(ts))
+    }
+}
+
+impl VersioningFactory for BlockHeaderFactory {
+    type Output = BlockHeader;
+    type Error = FactoryError;
+    type Arguments = BlockHeaderArgs;
+
+    fn get_component() -> MipComponent {
+        MipComponent::Block
+    }
+
+    fn get_versioning_store(&self) -> MipStore {
+        self.mip_store.clone()
+    }
+
+    fn create(
+        &self,
+        args: &Self::Arguments,
+        strategy: FactoryStrategy,
+    ) -> Result<Self::Output, Self::Error> {
+        let version = self.get_component_version_with_strategy(strategy)?;
+
+        let output = match version {
+            0 => BlockHeader {
+                current_version: args.current_version,
+                announced_version: args.announced_version,
+                slot: args.slot,
+                parents: args.parents.clone(),
+                operation_merkle_root: args.operation_merkle_root,
+                endorsements: args.endorsements.clone(),
+                denunciations: args.denunciations.clone(),
+            },
+            v => return Err(FactoryError::UnimplementedVersion(v)),
+        };
+
+        Ok(output)
+    }
+}
+
+impl BlockHeaderVersionAt for BlockHeaderFactory {
+    fn block_header_version_at(&self, slot: Slot) -> u32 {
+        // a slot too far away to have a timestamp gets the latest version
+        let ts = get_block_slot_timestamp(self.thread_count, self.t0, self.genesis_timestamp, slot)
+            .unwrap_or_else(|_| MassaTime::max());
+        self.mip_store
+            .get_latest_component_version_at(&MipComponent::Block, ts)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use num::rational::Ratio;
+    use std::collections::BTreeMap;
+
+    use crate::test_helpers::versioning_helpers::advance_state_until;
+    use crate::versioning::{ComponentState, MipInfo, MipStatsConfig};
+
+    #[test]
+    fn test_block_header_factory() {
+        let mip_info = MipInfo {
+            name: "MIP-0002".to_string(),
+            version: 1,
+            components: BTreeMap::from([(MipComponent::Block, 1)]),
+            start: MassaTime::from_millis(12),
+            timeout: MassaTime::from_millis(15),
+            activation_delay: MassaTime::from_millis(2),
+        };
+        let mip_state = advance_state_until(ComponentState::active(MassaTime::now()), &mip_info);
+        let mip_stats_cfg = MipStatsConfig {
+            block_count_considered: 10,
+            warn_announced_version_ratio: Ratio::new_raw(30, 100),
+        };
+        let mip_store = MipStore::try_from(([(mip_info, mip_state)], mip_stats_cfg)).unwrap();
+
+        // slot (0, 0) is at 10ms, before the MIP start, and slot (1, 0) is at 26ms, once it is active
+        let factory = BlockHeaderFactory {
+            mip_store,
+            thread_count: 2,
+            t0: MassaTime::from_millis(16),
+            genesis_timestamp: MassaTime::from_millis(10),
+        };
+        let args = BlockHeaderArgs {
+            current_version: 0,
+            announced_version: None,
+            slot: Slot::new(0, 0),
+            parents: vec![],
+            operation_merkle_root: Hash::compute_from("mno".as_bytes()),
+            endorsements: vec![],
+            denunciations: vec![],
+        };
+
+        assert_eq!(factory.block_header_version_at(Slot::new(0, 0)), 0);
+        assert_eq!(factory.block_header_version_at(Slot::new(1, 0)), 1);
+
+        let strategy = factory.strategy_at(Slot::new(0, 0)).unwrap();
+        assert!(factory.create(&args, strategy).is_ok());
+
+        // the layout of version 1 is not implemented yet
+        let strategy = factory.strategy_at(Slot::new(1, 0)).unwrap();
+        assert!(matches!(
+            factory.create(&args, strategy),
+            Err(FactoryError::UnimplementedVersion(1))
+        ));
+    }
+}
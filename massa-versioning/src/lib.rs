@@ -73,8 +73,11 @@
 //! the versioning transition (e.g. User 1 has upgraded to network version 1 while User 2 has not yet upgraded)
 
 pub mod address_factory;
+pub mod block_header_factory;
 pub mod grpc_mapping;
 pub mod keypair_factory;
+pub mod mip_simulator;
+pub mod mip_votes;
 pub mod mips;
 pub mod versioning;
 pub mod versioning_factory;
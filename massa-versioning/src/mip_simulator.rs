@@ -0,0 +1,172 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Offline simulation of the deployment of MIPs, to check governance proposals before deployment.
+//!
+//! The MIP states only advance at the sample timestamps, as they only advance at the final slots on the network:
+//! the activation time is the timestamp of the first sample after the activation delay.
+
+use std::collections::BTreeMap;
+use std::fmt::Formatter;
+
+use massa_time::MassaTime;
+use num::rational::Ratio;
+use serde::Deserialize;
+
+use crate::versioning::{Active, Advance, ComponentState, ComponentStateTypeId, MipInfo, MipState};
+
+/// Ratios of the network versions announced by the latest blocks, at a given time
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnnouncementSample {
+    /// timestamp (in millis) of the sample
+    pub timestamp: MassaTime,
+    /// percentage of the latest blocks announcing each network version
+    pub announced: BTreeMap<u32, u64>,
+}
+
+/// Result of the simulation of a MIP deployment
+#[derive(Debug, Clone, PartialEq)]
+pub struct MipSimulation {
+    /// MIP name
+    pub name: String,
+    /// network version of the MIP
+    pub version: u32,
+    /// state changes, with the timestamp of the sample they happened at
+    pub transitions: Vec<(MassaTime, ComponentStateTypeId)>,
+    /// state after the last sample
+    pub state: ComponentStateTypeId,
+    /// timestamp at which the MIP became active, if it did
+    pub activation: Option<MassaTime>,
+}
+
+impl std::fmt::Display for MipSimulation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} (network version {}):", self.name, self.version)?;
+        for (timestamp, state) in &self.transitions {
+            writeln!(f, "\tat {}: {:?}", timestamp.as_millis(), state)?;
+        }
+        match self.activation {
+            Some(activation) => writeln!(f, "\tactive from {}", activation.as_millis()),
+            None => writeln!(f, "\tnot active, last state: {:?}", self.state),
+        }
+    }
+}
+
+/// Simulate the deployment of MIPs given the network versions announced over time
+///
+/// # Arguments
+/// * `mips`: MIPs with their initial state
+/// * `samples`: announced network version ratios, in any order
+pub fn simulate_mips(
+    mips: &[(MipInfo, MipState)],
+    samples: &[AnnouncementSample],
+) -> Vec<MipSimulation> {
+    let mut samples = samples.to_vec();
+    samples.sort_by_key(|sample| sample.timestamp);
+
+    mips.iter()
+        .map(|(mip_info, mip_state)| {
+            let mut mip_state = mip_state.clone();
+            let mut state = ComponentStateTypeId::from(&mip_state.state);
+            let mut transitions = Vec::new();
+            for sample in &samples {
+                if matches!(
+                    state,
+                    ComponentStateTypeId::Active
+                        | ComponentStateTypeId::Failed
+                        | ComponentStateTypeId::Error
+                ) {
+                    break;
+                }
+                let percentage = sample
+                    .announced
+                    .get(&mip_info.version)
+                    .copied()
+                    .unwrap_or(0)
+                    .min(100);
+                mip_state.on_advance(&Advance {
+                    start_timestamp: mip_info.start,
+                    timeout: mip_info.timeout,
+                    activation_delay: mip_info.activation_delay,
+                    threshold: Ratio::new(percentage, 100),
+                    now: sample.timestamp,
+                });
+                let new_state = ComponentStateTypeId::from(&mip_state.state);
+                if new_state != state {
+                    transitions.push((sample.timestamp, new_state.clone()));
+                    state = new_state;
+                }
+            }
+
+            let activation = match mip_state.state {
+                ComponentState::Active(Active { at }) => Some(at),
+                _ => None,
+            };
+            MipSimulation {
+                name: mip_info.name.clone(),
+                version: mip_info.version,
+                transitions,
+                state,
+                activation,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::versioning::MipComponent;
+
+    fn sample(timestamp: u64, percentage: u64) -> AnnouncementSample {
+        AnnouncementSample {
+            timestamp: MassaTime::from_millis(timestamp),
+            announced: BTreeMap::from([(1, percentage)]),
+        }
+    }
+
+    #[test]
+    fn test_simulate_mips() {
+        let mip_info = MipInfo {
+            name: "MIP-0002".to_string(),
+            version: 1,
+            components: BTreeMap::from([(MipComponent::Address, 1)]),
+            start: MassaTime::from_millis(10),
+            timeout: MassaTime::from_millis(100),
+            activation_delay: MassaTime::from_millis(20),
+        };
+        let mips = [(mip_info, MipState::new(MassaTime::from_millis(0)))];
+
+        // not enough announcements before the timeout
+        let simulations = simulate_mips(&mips, &[sample(5, 0), sample(20, 50), sample(100, 70)]);
+        assert_eq!(
+            simulations[0].transitions,
+            vec![
+                (MassaTime::from_millis(20), ComponentStateTypeId::Started),
+                (MassaTime::from_millis(100), ComponentStateTypeId::Failed),
+            ]
+        );
+        assert_eq!(simulations[0].activation, None);
+
+        // locked in at 30, active at the first sample after the activation delay
+        let simulations = simulate_mips(
+            &mips,
+            &[
+                sample(40, 80),
+                sample(20, 10),
+                sample(30, 80),
+                sample(60, 0),
+            ],
+        );
+        assert_eq!(
+            simulations[0].transitions,
+            vec![
+                (MassaTime::from_millis(20), ComponentStateTypeId::Started),
+                (MassaTime::from_millis(30), ComponentStateTypeId::LockedIn),
+                (MassaTime::from_millis(60), ComponentStateTypeId::Active),
+            ]
+        );
+        assert_eq!(simulations[0].activation, Some(MassaTime::from_millis(60)));
+        assert_eq!(simulations[0].state, ComponentStateTypeId::Active);
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Votes of the node operator on MIPs, choosing the network version announced by the node.
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+use tracing::warn;
+
+/// Vote of the node operator on a MIP
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MipVote {
+    /// announce this MIP in priority while it is started or locked in
+    For,
+    /// never announce this MIP
+    Against,
+}
+
+/// Error while reading or writing the MIP votes
+#[derive(Error, Debug)]
+pub enum MipVotesError {
+    #[error("IO error on MIP votes file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid MIP votes file: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Votes of the node operator on MIPs, by MIP name, persisted in a JSON file.
+/// Clones share the same votes.
+#[derive(Debug, Clone)]
+pub struct MipVotes {
+    path: PathBuf,
+    votes: Arc<RwLock<BTreeMap<String, MipVote>>>,
+}
+
+impl MipVotes {
+    /// Load the votes from a file.
+    /// The configured votes are only used while the file does not exist, i.e. until votes are changed at runtime:
+    /// a warning is emitted if they differ from the votes of the file.
+    ///
+    /// # Arguments
+    /// * `path`: file where the votes are persisted
+    /// * `configured_votes`: votes from the node configuration
+    pub fn load(
+        path: &Path,
+        configured_votes: BTreeMap<String, MipVote>,
+    ) -> Result<Self, MipVotesError> {
+        let votes = if path.exists() {
+            let votes: BTreeMap<String, MipVote> = serde_json::from_slice(&fs::read(path)?)?;
+            if !configured_votes.is_empty() && configured_votes != votes {
+                warn!(
+                    "the MIP votes of the configuration are ignored: the votes changed at runtime and persisted in {} are used instead. Remove this file to use the configured votes",
+                    path.display()
+                );
+            }
+            votes
+        } else {
+            configured_votes
+        };
+        Ok(MipVotes {
+            path: path.to_path_buf(),
+            votes: Arc::new(RwLock::new(votes)),
+        })
+    }
+
+    /// Get all the votes, by MIP name
+    pub fn get_votes(&self) -> BTreeMap<String, MipVote> {
+        self.votes.read().clone()
+    }
+
+    /// Get the vote on a MIP, if any
+    pub fn get_vote(&self, name: &str) -> Option<MipVote> {
+        self.votes.read().get(name).copied()
+    }
+
+    /// Set or remove (with `None`) the vote on a MIP, and persist the votes
+    pub fn set_vote(&self, name: String, vote: Option<MipVote>) -> Result<(), MipVotesError> {
+        let mut votes = self.votes.write();
+        let mut new_votes = votes.clone();
+        match vote {
+            Some(vote) => new_votes.insert(name, vote),
+            None => new_votes.remove(&name),
+        };
+
+        // write to a temporary file first so that a crash cannot leave a truncated file
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(&new_votes)?)?;
+        fs::rename(&tmp_path, &self.path)?;
+
+        *votes = new_votes;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_mip_votes_persistence() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("mip_votes.json");
+        let configured_votes = BTreeMap::from([("MIP-0001".to_string(), MipVote::Against)]);
+
+        // without file, the configured votes are used
+        let votes = MipVotes::load(&path, configured_votes.clone()).unwrap();
+        assert_eq!(votes.get_vote("MIP-0001"), Some(MipVote::Against));
+
+        votes
+            .set_vote("MIP-0002".to_string(), Some(MipVote::For))
+            .unwrap();
+        votes.set_vote("MIP-0001".to_string(), None).unwrap();
+
+        // once changed, the persisted votes take precedence
+        let votes = MipVotes::load(&path, configured_votes).unwrap();
+        assert_eq!(
+            votes.get_votes(),
+            BTreeMap::from([("MIP-0002".to_string(), MipVote::For)])
+        );
+    }
+}
@@ -1,11 +1,10 @@
-#[allow(unused_imports)]
 use std::collections::BTreeMap;
 
-#[allow(unused_imports)]
 use massa_time::MassaTime;
+use serde::Deserialize;
+use thiserror::Error;
 
-#[allow(unused_imports)]
-use crate::versioning::{MipComponent, MipInfo, MipState};
+use crate::versioning::{IsConsistentError, MipComponent, MipInfo, MipState};
 
 pub fn get_mip_list() -> [(MipInfo, MipState); 0] {
     // placeholder
@@ -30,3 +29,150 @@ pub fn get_mip_list() -> [(MipInfo, MipState); 0] {
     #[allow(clippy::let_and_return)]
     mip_list
 }
+
+/// MIP definition read from the node configuration, to rehearse network upgrades on private networks
+#[derive(Debug, Clone, Deserialize)]
+pub struct MipDefinition {
+    /// MIP name or descriptive name
+    pub name: String,
+    /// Network (or global) version
+    pub version: u32,
+    /// Components concerned by this MIP (case insensitive name: address, keypair, block, vm, finalstatehashkind),
+    /// and the associated component version
+    pub components: BTreeMap<String, u32>,
+    /// timestamp (in millis) at which the version gains its meaning
+    pub start: MassaTime,
+    /// timestamp (in millis) at which the deployment is considered failed
+    pub timeout: MassaTime,
+    /// once locked in, duration (in millis) to wait before the deployment is considered active
+    pub activation_delay: MassaTime,
+    /// timestamp (in millis) at which the MIP is defined, 0 if absent
+    pub defined: Option<MassaTime>,
+}
+
+/// Error while reading a MIP definition
+#[derive(Error, Debug)]
+pub enum MipDefinitionError {
+    #[error("unknown component {1} in MIP {0}")]
+    UnknownComponent(String, String),
+    #[error("MIP {0} has no component")]
+    NoComponent(String),
+    #[error("MIP {0} is not consistent: {1}")]
+    Inconsistent(String, IsConsistentError),
+}
+
+impl MipDefinition {
+    /// Build the MIP info and its initial state, checking that they are consistent
+    pub fn to_mip(&self) -> Result<(MipInfo, MipState), MipDefinitionError> {
+        if self.components.is_empty() {
+            return Err(MipDefinitionError::NoComponent(self.name.clone()));
+        }
+        let components = self
+            .components
+            .iter()
+            .map(|(name, version)| {
+                parse_mip_component(name)
+                    .map(|component| (component, *version))
+                    .ok_or_else(|| {
+                        MipDefinitionError::UnknownComponent(self.name.clone(), name.clone())
+                    })
+            })
+            .collect::<Result<BTreeMap<MipComponent, u32>, _>>()?;
+
+        let mip_info = MipInfo {
+            name: self.name.clone(),
+            version: self.version,
+            components,
+            start: self.start,
+            timeout: self.timeout,
+            activation_delay: self.activation_delay,
+        };
+        let mip_state = MipState::new(self.defined.unwrap_or(MassaTime::from_millis(0)));
+        mip_state
+            .is_consistent_with(&mip_info)
+            .map_err(|err| MipDefinitionError::Inconsistent(self.name.clone(), err))?;
+
+        Ok((mip_info, mip_state))
+    }
+}
+
+/// Get the MIP list supported by the node: the built-in MIPs followed by the MIPs defined in the configuration
+pub fn get_mip_list_with(
+    definitions: &[MipDefinition],
+) -> Result<Vec<(MipInfo, MipState)>, MipDefinitionError> {
+    let mut mip_list = Vec::from(get_mip_list());
+    for definition in definitions {
+        mip_list.push(definition.to_mip()?);
+    }
+    Ok(mip_list)
+}
+
+/// Parse a component name, case insensitive
+fn parse_mip_component(name: &str) -> Option<MipComponent> {
+    [
+        ("address", MipComponent::Address),
+        ("keypair", MipComponent::KeyPair),
+        ("block", MipComponent::Block),
+        ("vm", MipComponent::VM),
+        ("finalstatehashkind", MipComponent::FinalStateHashKind),
+    ]
+    .into_iter()
+    .find(|(component_name, _)| component_name.eq_ignore_ascii_case(name))
+    .map(|(_, component)| component)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn definition() -> MipDefinition {
+        MipDefinition {
+            name: "MIP-0001-DEVNET".to_string(),
+            version: 1,
+            components: BTreeMap::from([("Block".to_string(), 1)]),
+            start: MassaTime::from_millis(2),
+            timeout: MassaTime::from_millis(5),
+            activation_delay: MassaTime::from_millis(2),
+            defined: None,
+        }
+    }
+
+    #[test]
+    fn test_mip_definition() {
+        let (mip_info, mip_state) = definition().to_mip().unwrap();
+        assert_eq!(mip_info.name, "MIP-0001-DEVNET");
+        assert_eq!(
+            mip_info.components,
+            BTreeMap::from([(MipComponent::Block, 1)])
+        );
+        assert_eq!(mip_state, MipState::new(MassaTime::from_millis(0)));
+        assert_eq!(get_mip_list_with(&[definition()]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_mip_definition() {
+        let mut unknown_component = definition();
+        unknown_component.components = BTreeMap::from([("Ledger".to_string(), 1)]);
+        assert!(matches!(
+            unknown_component.to_mip(),
+            Err(MipDefinitionError::UnknownComponent(_, _))
+        ));
+
+        let mut timeout_before_start = definition();
+        timeout_before_start.timeout = MassaTime::from_millis(1);
+        assert!(matches!(
+            timeout_before_start.to_mip(),
+            Err(MipDefinitionError::Inconsistent(
+                _,
+                IsConsistentError::Invalid
+            ))
+        ));
+
+        let mut defined_after_start = definition();
+        defined_after_start.defined = Some(MassaTime::from_millis(3));
+        assert!(matches!(
+            defined_after_start.to_mip(),
+            Err(MipDefinitionError::Inconsistent(_, _))
+        ));
+    }
+}
@@ -27,6 +27,7 @@ use massa_serialization::{DeserializeError, Deserializer, SerializeError, Serial
 use massa_time::MassaTime;
 use variant_count::VariantCount;
 
+use crate::mip_votes::MipVote;
 use crate::versioning_ser_der::{
     MipInfoDeserializer, MipInfoSerializer, MipStateDeserializer, MipStateSerializer,
     MipStoreStatsDeserializer, MipStoreStatsSerializer,
@@ -600,18 +601,31 @@ impl MipStore {
     /// Retrieve the network version number to announce in block header
     /// return 0 is there is nothing to announce
     pub fn get_network_version_to_announce(&self) -> Option<u32> {
+        self.get_network_version_to_announce_with_votes(&BTreeMap::new())
+    }
+
+    /// Retrieve the network version number to announce in block header, following the votes of the node operator:
+    /// the latest MIP voted for is announced in priority, MIPs voted against are never announced
+    pub fn get_network_version_to_announce_with_votes(
+        &self,
+        votes: &BTreeMap<String, MipVote>,
+    ) -> Option<u32> {
         let lock = self.0.read();
         let store = lock.deref();
         // Announce the latest versioning info in Started / LockedIn state
         // Defined == Not yet ready to announce
         // Active == current version
-        store.store.iter().rev().find_map(|(k, v)| {
+        let mut announceable = store.store.iter().rev().filter(|(_k, v)| {
             matches!(
                 &v.state,
                 &ComponentState::Started(_) | &ComponentState::LockedIn(_)
             )
-            .then_some(k.version)
-        })
+        });
+        announceable
+            .clone()
+            .find(|(k, _v)| votes.get(&k.name) == Some(&MipVote::For))
+            .or_else(|| announceable.find(|(k, _v)| votes.get(&k.name) != Some(&MipVote::Against)))
+            .map(|(k, _v)| k.version)
     }
 
     pub fn update_network_version_stats(
@@ -770,6 +784,16 @@ impl<const N: usize> TryFrom<([(MipInfo, MipState); N], MipStatsConfig)> for Mip
     }
 }
 
+impl TryFrom<(Vec<(MipInfo, MipState)>, MipStatsConfig)> for MipStore {
+    type Error = UpdateWithError;
+
+    fn try_from(
+        (value, cfg): (Vec<(MipInfo, MipState)>, MipStatsConfig),
+    ) -> Result<Self, Self::Error> {
+        MipStoreRaw::try_from((value, cfg)).map(|store_raw| Self(Arc::new(RwLock::new(store_raw))))
+    }
+}
+
 /// Statistics in MipStoreRaw
 #[derive(Debug, Clone, PartialEq)]
 pub struct MipStatsConfig {
@@ -1556,6 +1580,16 @@ impl<const N: usize> TryFrom<([(MipInfo, MipState); N], MipStatsConfig)> for Mip
 
     fn try_from(
         (value, cfg): ([(MipInfo, MipState); N], MipStatsConfig),
+    ) -> Result<Self, Self::Error> {
+        Self::try_from((Vec::from(value), cfg))
+    }
+}
+
+impl TryFrom<(Vec<(MipInfo, MipState)>, MipStatsConfig)> for MipStoreRaw {
+    type Error = UpdateWithError;
+
+    fn try_from(
+        (value, cfg): (Vec<(MipInfo, MipState)>, MipStatsConfig),
     ) -> Result<Self, Self::Error> {
         // Build an empty store
         let mut store = Self {
@@ -1565,7 +1599,7 @@ impl<const N: usize> TryFrom<([(MipInfo, MipState); N], MipStatsConfig)> for Mip
 
         // Build another one with given value
         let other_store = Self {
-            store: BTreeMap::from(value),
+            store: BTreeMap::from_iter(value),
             stats: MipStoreStats::new(cfg),
         };
 
@@ -1878,6 +1912,15 @@ mod test {
         assert_eq!(vs.get_network_version_current(), mi.version);
         assert_eq!(vs.get_network_version_to_announce(), Some(mi_2.version));
 
+        // Votes of the node operator
+        let votes = BTreeMap::from([(mi_2.name.clone(), MipVote::Against)]);
+        assert_eq!(vs.get_network_version_to_announce_with_votes(&votes), None);
+        let votes = BTreeMap::from([(mi.name.clone(), MipVote::For)]);
+        assert_eq!(
+            vs.get_network_version_to_announce_with_votes(&votes),
+            Some(mi_2.version)
+        );
+
         // Test also an empty versioning store
         let vs_raw = MipStoreRaw {
             store: Default::default(),
@@ -2546,6 +2589,8 @@ mod test {
             max_versioning_elements_size: 100_000,
             thread_count: THREAD_COUNT,
             max_ledger_backups: 10,
+            state_proofs_enabled: false,
+            index_history_periods: 1000,
         };
         let db = Arc::new(RwLock::new(
             Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>
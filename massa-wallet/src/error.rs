@@ -26,4 +26,10 @@ pub enum WalletError {
     MassaCipherError(#[from] massa_cipher::CipherError),
     /// Version error: {0}
     VersionError(String),
+    /// Remote signer error: {0}
+    RemoteSignerError(String),
+    /// HD wallet error: {0}
+    HdError(String),
+    /// Watch-only entry error: {0}
+    WatchOnlyError(String),
 }
@@ -0,0 +1,219 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Hierarchical deterministic keys: SLIP-10 Ed25519 derivation from a BIP-39 mnemonic,
+//! so that every key of a wallet can be recovered from a single backup.
+
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+use massa_cipher::{decrypt, encrypt_with_version, CipherData, CipherVersion, Salt};
+use massa_signature::KeyPair;
+use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha512;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::WalletError;
+
+/// Name of the file holding the encrypted seed, in the wallet directory
+pub(crate) const HD_SEED_FILE_NAME: &str = "hd_seed.yaml";
+/// File written before replacing the seed file, left over if the node stopped in between
+pub(crate) const HD_SEED_TMP_FILE_NAME: &str = "hd_seed.yaml.tmp";
+
+/// Massa coin type, as registered in SLIP-44
+const MASSA_COIN_TYPE: u32 = 632;
+
+/// Only hardened derivation is defined for Ed25519
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// Size of the entropy of the generated mnemonics, giving 24 words
+const MNEMONIC_ENTROPY_SIZE: usize = 32;
+
+/// As in the wallet files, version 1 derives the cipher key with PBKDF2, version 2 with Argon2id.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct HdSeedFileFormat {
+    version: u64,
+    salt: Salt,
+    nonce: [u8; 12],
+    ciphered_data: Vec<u8>,
+    next_index: u32,
+}
+
+/// Generates a random 24-word BIP-39 mnemonic
+pub fn generate_mnemonic() -> String {
+    let mut entropy = [0u8; MNEMONIC_ENTROPY_SIZE];
+    thread_rng().fill_bytes(&mut entropy);
+    let mnemonic = Mnemonic::from_entropy(&entropy).expect("valid mnemonic entropy size");
+    entropy.zeroize();
+    mnemonic.to_string()
+}
+
+/// Seed of the hierarchical deterministic keys of a wallet
+#[derive(Clone, Debug, Zeroize, ZeroizeOnDrop)]
+pub(crate) struct HdSeed {
+    /// BIP-39 seed
+    seed: Vec<u8>,
+    /// index of the next key to derive
+    pub(crate) next_index: u32,
+}
+
+impl HdSeed {
+    /// Computes the seed of a BIP-39 mnemonic
+    pub(crate) fn from_mnemonic(mnemonic: &str, passphrase: &str) -> Result<Self, WalletError> {
+        let mnemonic = Mnemonic::parse_normalized(mnemonic)
+            .map_err(|err| WalletError::HdError(format!("invalid mnemonic: {}", err)))?;
+        let mut seed = mnemonic.to_seed_normalized(passphrase);
+        let hd_seed = HdSeed {
+            seed: seed.to_vec(),
+            next_index: 0,
+        };
+        seed.zeroize();
+        Ok(hd_seed)
+    }
+
+    /// Returns true if both seeds derive the same keys
+    pub(crate) fn has_same_seed(&self, other: &HdSeed) -> bool {
+        self.seed == other.seed
+    }
+
+    /// Reads and decrypts a seed file, returns the seed and the version of its encryption
+    pub(crate) fn load(path: &Path, password: &str) -> Result<(Self, CipherVersion), WalletError> {
+        let file = serde_yaml::from_slice::<HdSeedFileFormat>(&std::fs::read(path)?)?;
+        let cipher_version = CipherVersion::try_from(file.version).map_err(|_| {
+            WalletError::VersionError(format!("Unsupported seed file version {}", file.version))
+        })?;
+        let seed = decrypt(
+            password,
+            CipherData {
+                version: cipher_version,
+                salt: file.salt,
+                nonce: file.nonce,
+                encrypted_bytes: file.ciphered_data,
+            },
+        )?;
+        Ok((
+            HdSeed {
+                seed,
+                next_index: file.next_index,
+            },
+            cipher_version,
+        ))
+    }
+
+    /// Encrypts and writes the seed file
+    pub(crate) fn save(
+        &self,
+        path: &Path,
+        password: &str,
+        cipher_version: CipherVersion,
+    ) -> Result<(), WalletError> {
+        let encrypted_seed = encrypt_with_version(cipher_version, password, &self.seed)?;
+        let file = HdSeedFileFormat {
+            version: cipher_version.into(),
+            salt: encrypted_seed.salt,
+            nonce: encrypted_seed.nonce,
+            ciphered_data: encrypted_seed.encrypted_bytes,
+            next_index: self.next_index,
+        };
+        // write a new file then move it over the old one, so that a crash leaves either of them
+        let tmp_path = path.with_file_name(HD_SEED_TMP_FILE_NAME);
+        let mut tmp_file = File::create(&tmp_path)?;
+        tmp_file.write_all(serde_yaml::to_string(&file)?.as_bytes())?;
+        tmp_file.sync_all()?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Derives the key pair of an index, on the path m/44'/632'/0'/0'/index'
+    pub(crate) fn derive_keypair(&self, index: u32) -> Result<KeyPair, WalletError> {
+        if index >= HARDENED_OFFSET {
+            return Err(WalletError::HdError(format!(
+                "derivation index {} is too large",
+                index
+            )));
+        }
+        let mut secret_key =
+            derive_ed25519_secret_key(&self.seed, &[44, MASSA_COIN_TYPE, 0, 0, index]);
+        // version(1B) + secret key(32B)
+        let mut bytes = vec![0u8];
+        bytes.extend_from_slice(&secret_key);
+        secret_key.zeroize();
+        let keypair = KeyPair::from_bytes(&bytes);
+        bytes.zeroize();
+        Ok(keypair?)
+    }
+}
+
+/// SLIP-10 Ed25519 derivation of the secret key of a path, every index being hardened
+fn derive_ed25519_secret_key(seed: &[u8], path: &[u32]) -> [u8; 32] {
+    let mut output = hmac_sha512(b"ed25519 seed", &[seed]);
+    for index in path {
+        let (key, chain_code) = output.split_at(32);
+        let index_bytes = (index | HARDENED_OFFSET).to_be_bytes();
+        let next_output = hmac_sha512(chain_code, &[&[0u8][..], key, &index_bytes[..]]);
+        output.zeroize();
+        output = next_output;
+    }
+    let mut secret_key = [0u8; 32];
+    secret_key.copy_from_slice(&output[..32]);
+    output.zeroize();
+    secret_key
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    for chunk in data {
+        mac.update(chunk);
+    }
+    let mut output = [0u8; 64];
+    output.copy_from_slice(&mac.finalize().into_bytes());
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_slip10_ed25519_vectors() {
+        // test vector 1 of SLIP-10 for Ed25519
+        let seed = (0u8..16).collect::<Vec<u8>>();
+        assert_eq!(
+            derive_ed25519_secret_key(&seed, &[]).to_vec(),
+            hex_to_bytes("2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7")
+        );
+        assert_eq!(
+            derive_ed25519_secret_key(&seed, &[0]).to_vec(),
+            hex_to_bytes("68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3")
+        );
+    }
+
+    #[test]
+    fn test_mnemonic_derivation() {
+        let mnemonic = generate_mnemonic();
+        assert_eq!(mnemonic.split_whitespace().count(), 24);
+
+        let hd_seed = HdSeed::from_mnemonic(&mnemonic, "").unwrap();
+        let restored_seed = HdSeed::from_mnemonic(&mnemonic, "").unwrap();
+        assert_eq!(
+            hd_seed.derive_keypair(3).unwrap().to_bytes(),
+            restored_seed.derive_keypair(3).unwrap().to_bytes()
+        );
+        assert_ne!(
+            hd_seed.derive_keypair(0).unwrap().to_bytes(),
+            hd_seed.derive_keypair(1).unwrap().to_bytes()
+        );
+
+        assert!(HdSeed::from_mnemonic("not a mnemonic", "").is_err());
+    }
+
+    fn hex_to_bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+}
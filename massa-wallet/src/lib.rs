@@ -5,8 +5,15 @@
 #![warn(unused_crate_dependencies)]
 
 pub use error::WalletError;
+pub use hd::generate_mnemonic;
+pub use massa_cipher::CipherVersion;
+pub use remote_signer::{
+    serve_remote_signer, RemoteSigner, RemoteSignerEndpoint, RemoteSignerListener,
+    SignedItemsRecord,
+};
+pub use signer::{LocalSigner, Signer};
 
-use massa_cipher::{decrypt, encrypt, CipherData, Salt};
+use massa_cipher::{decrypt, encrypt_with_version, CipherData, CipherVersion, Salt};
 use massa_hash::Hash;
 use massa_models::address::Address;
 use massa_models::composite::PubkeySig;
@@ -17,15 +24,21 @@ use massa_signature::{KeyPair, PublicKey};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::Entry;
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+use crate::hd::{HdSeed, HD_SEED_FILE_NAME, HD_SEED_TMP_FILE_NAME};
+
 mod error;
+mod hd;
+mod remote_signer;
+mod signer;
 
-const WALLET_VERSION: u64 = 1;
+/// Prefix of the names of the files holding the watch-only entries, in the wallet directory
+const WATCH_ONLY_FILE_PREFIX: &str = "watch_only_";
 
-/// Contains the keypairs created in the wallet.
+/// Contains the keypairs created in the wallet, and the watch-only addresses.
 #[derive(Clone, Debug, Deserialize, Serialize, Zeroize, ZeroizeOnDrop)]
 pub struct Wallet {
     /// Keypairs and addresses
@@ -38,11 +51,25 @@ pub struct Wallet {
     password: String,
     /// chain id
     chain_id: u64,
+    /// Version of the encryption of the wallet files, identifying the key derivation function
+    #[serde(skip)]
+    #[zeroize(skip)]
+    cipher_version: CipherVersion,
+    /// Seed of the hierarchical deterministic keys, if any
+    #[serde(skip)]
+    hd_seed: Option<HdSeed>,
+    /// Watch-only addresses, with their public key if known
+    #[zeroize(skip)]
+    watch_only: PreHashMap<Address, Option<PublicKey>>,
+    /// User labels of the addresses of the wallet, watch-only or not
+    #[zeroize(skip)]
+    labels: PreHashMap<Address, String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 /// Follow the standard: https://github.com/massalabs/massa-standards/blob/main/wallet/file-format.md
+/// Version 1 derives the cipher key with PBKDF2, version 2 with Argon2id.
 struct WalletFileFormat {
     version: u64,
     nickname: String,
@@ -53,16 +80,54 @@ struct WalletFileFormat {
     public_key: Vec<u8>,
 }
 
+/// Watch-only entry: an address, and its public key if known, without secret key
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct WatchOnlyFileFormat {
+    nickname: String,
+    address: String,
+    public_key: Option<Vec<u8>>,
+}
+
 //TODO: Use exports and mock it
 impl Wallet {
     /// Generates a new wallet initialized with the provided file content
     pub fn new(path: PathBuf, password: String, chain_id: u64) -> Result<Wallet, WalletError> {
+        recover_password_change(&path)?;
         if path.is_dir() {
             let mut keys = PreHashMap::default();
+            let mut hd_seed = None;
+            let mut cipher_version = CipherVersion::default();
+            let mut watch_only = PreHashMap::default();
+            let mut labels = PreHashMap::default();
             for entry in std::fs::read_dir(&path)? {
                 let entry = entry?;
                 let path = entry.path();
-                if path.is_file() {
+                if path.is_file() && entry.file_name() == HD_SEED_FILE_NAME {
+                    let (seed, seed_cipher_version) = HdSeed::load(&path, &password)?;
+                    hd_seed = Some(seed);
+                    cipher_version = cipher_version.max(seed_cipher_version);
+                } else if entry.file_name() == HD_SEED_TMP_FILE_NAME {
+                    // incomplete seed file write, the previous seed file is still valid
+                    continue;
+                } else if path.is_file()
+                    && entry
+                        .file_name()
+                        .to_string_lossy()
+                        .starts_with(WATCH_ONLY_FILE_PREFIX)
+                {
+                    let content = &std::fs::read(&path)?[..];
+                    let entry = serde_yaml::from_slice::<WatchOnlyFileFormat>(content)?;
+                    let address = Address::from_str(&entry.address)?;
+                    let public_key = entry
+                        .public_key
+                        .map(|public_key| PublicKey::from_bytes(&public_key))
+                        .transpose()?;
+                    if entry.nickname != entry.address {
+                        labels.insert(address, entry.nickname);
+                    }
+                    watch_only.insert(address, public_key);
+                } else if path.is_file() {
                     let content = &std::fs::read(&path)?[..];
                     let mut wallet = serde_yaml::from_slice::<WalletFileFormat>(content)?;
                     if wallet.version == 0 {
@@ -70,15 +135,18 @@ impl Wallet {
                         wallet.version = 1;
                     }
                     // check version
-                    if wallet.version != WALLET_VERSION {
-                        return Err(WalletError::VersionError(format!(
-                            "Unsupported wallet version {}",
-                            wallet.version
-                        )));
-                    }
+                    let file_cipher_version =
+                        CipherVersion::try_from(wallet.version).map_err(|_| {
+                            WalletError::VersionError(format!(
+                                "Unsupported wallet version {}",
+                                wallet.version
+                            ))
+                        })?;
+                    cipher_version = cipher_version.max(file_cipher_version);
                     let mut secret_key = decrypt(
                         &password,
                         CipherData {
+                            version: file_cipher_version,
                             salt: wallet.salt,
                             nonce: wallet.nonce,
                             encrypted_bytes: wallet.ciphered_data,
@@ -101,10 +169,12 @@ impl Wallet {
                             return Err(WalletError::VersionError("Invalid wallet/version matching: your wallet does not follow its version's secret key encoding format.".to_string()))
                         }
                     }
-                    keys.insert(
-                        Address::from_str(&wallet.address)?,
-                        KeyPair::from_bytes(&secret_key)?,
-                    );
+                    let address = Address::from_str(&wallet.address)?;
+                    // the nickname defaults to the address
+                    if wallet.nickname != wallet.address {
+                        labels.insert(address, wallet.nickname);
+                    }
+                    keys.insert(address, KeyPair::from_bytes(&secret_key)?);
                 }
             }
             Ok(Wallet {
@@ -112,6 +182,10 @@ impl Wallet {
                 wallet_path: path,
                 password,
                 chain_id,
+                cipher_version,
+                hd_seed,
+                watch_only,
+                labels,
             })
         } else {
             let wallet = Wallet {
@@ -119,6 +193,10 @@ impl Wallet {
                 wallet_path: path,
                 password,
                 chain_id,
+                cipher_version: CipherVersion::default(),
+                hd_seed: None,
+                watch_only: PreHashMap::default(),
+                labels: PreHashMap::default(),
             };
             wallet.save()?;
             Ok(wallet)
@@ -152,6 +230,8 @@ impl Wallet {
             let addr = Address::from_public_key(&key.get_public_key());
             if let Entry::Vacant(e) = self.keys.entry(addr) {
                 e.insert(key);
+                // the secret key of a watch-only address was added
+                self.watch_only.remove(&addr);
                 changed = true;
             }
             addrs.push(addr);
@@ -162,12 +242,129 @@ impl Wallet {
         Ok(addrs)
     }
 
-    /// Removes wallet entries given a list of addresses. Missing entries are ignored.
+    /// Sets the seed of the hierarchical deterministic keys from a BIP-39 mnemonic.
+    /// Keys are then derived from index 0 by `generate_hd_keypair`.
+    /// Setting again the mnemonic of the wallet does nothing, setting another one fails.
+    /// The wallet file is updated.
+    pub fn set_mnemonic(&mut self, mnemonic: &str, passphrase: &str) -> Result<(), WalletError> {
+        let hd_seed = HdSeed::from_mnemonic(mnemonic, passphrase)?;
+        match &self.hd_seed {
+            Some(current_seed) if current_seed.has_same_seed(&hd_seed) => Ok(()),
+            Some(_) => Err(WalletError::HdError(
+                "the wallet already has another mnemonic".to_string(),
+            )),
+            None => {
+                self.hd_seed = Some(hd_seed);
+                self.save()
+            }
+        }
+    }
+
+    /// Returns true if the wallet has a seed to derive keys from
+    pub fn has_mnemonic(&self) -> bool {
+        self.hd_seed.is_some()
+    }
+
+    /// Derives the keypair of an index from the seed of the wallet, without adding it to the wallet
+    pub fn derive_hd_keypair(&self, index: u32) -> Result<KeyPair, WalletError> {
+        self.hd_seed
+            .as_ref()
+            .ok_or_else(|| WalletError::HdError("the wallet has no mnemonic".to_string()))?
+            .derive_keypair(index)
+    }
+
+    /// Derives the keypair of the next index from the seed of the wallet, adds it to the wallet and returns its address.
+    /// The wallet file is updated.
+    pub fn generate_hd_keypair(&mut self) -> Result<Address, WalletError> {
+        let index = self
+            .hd_seed
+            .as_ref()
+            .map(|hd_seed| hd_seed.next_index)
+            .ok_or_else(|| WalletError::HdError("the wallet has no mnemonic".to_string()))?;
+        let keypair = self.derive_hd_keypair(index)?;
+        let address = Address::from_public_key(&keypair.get_public_key());
+        self.keys.insert(address, keypair);
+        self.set_hd_next_index(index.saturating_add(1))?;
+        Ok(address)
+    }
+
+    /// Sets the index of the next keypair derived by `generate_hd_keypair`, if it is greater than the current one.
+    /// The wallet file is updated.
+    pub fn set_hd_next_index(&mut self, next_index: u32) -> Result<(), WalletError> {
+        let hd_seed = self
+            .hd_seed
+            .as_mut()
+            .ok_or_else(|| WalletError::HdError("the wallet has no mnemonic".to_string()))?;
+        hd_seed.next_index = hd_seed.next_index.max(next_index);
+        self.save()
+    }
+
+    /// Adds a watch-only address, with its public key if known, and an optional label.
+    /// Adding again a watch-only address updates its public key and label.
+    /// The wallet file is updated.
+    pub fn add_watch_only(
+        &mut self,
+        address: Address,
+        public_key: Option<PublicKey>,
+        label: Option<String>,
+    ) -> Result<(), WalletError> {
+        if self.keys.contains_key(&address) {
+            return Err(WalletError::WatchOnlyError(format!(
+                "the wallet holds the secret key of {}",
+                address
+            )));
+        }
+        if let Some(public_key) = &public_key {
+            if Address::from_public_key(public_key) != address {
+                return Err(WalletError::WatchOnlyError(format!(
+                    "public key {} does not match address {}",
+                    public_key, address
+                )));
+            }
+        }
+        self.watch_only.insert(address, public_key);
+        if let Some(label) = label {
+            self.labels.insert(address, label);
+        }
+        self.save()
+    }
+
+    /// Get the watch-only addresses, with their public key if known
+    pub fn get_watch_only_addresses(&self) -> &PreHashMap<Address, Option<PublicKey>> {
+        &self.watch_only
+    }
+
+    /// Sets the label of an address of the wallet, or removes it if `label` is `None`.
+    /// The wallet file is updated.
+    pub fn set_label(
+        &mut self,
+        address: Address,
+        label: Option<String>,
+    ) -> Result<(), WalletError> {
+        if !self.keys.contains_key(&address) && !self.watch_only.contains_key(&address) {
+            return Err(WalletError::MissingKeyError(address));
+        }
+        match label {
+            Some(label) => self.labels.insert(address, label),
+            None => self.labels.remove(&address),
+        };
+        self.save()
+    }
+
+    /// Get the label of an address of the wallet, if any
+    pub fn get_label(&self, address: &Address) -> Option<&String> {
+        self.labels.get(address)
+    }
+
+    /// Removes wallet entries given a list of addresses, watch-only or not. Missing entries are ignored.
     /// call save() to persist the changes on disk.
     pub fn remove_addresses(&mut self, addresses: &Vec<Address>) -> Result<bool, WalletError> {
         let mut changed = false;
         for address in addresses {
-            if self.keys.remove(address).is_some() {
+            let removed_key = self.keys.remove(address).is_some();
+            let removed_watch_only = self.watch_only.remove(address).is_some();
+            if removed_key || removed_watch_only {
+                self.labels.remove(address);
                 changed = true;
             }
         }
@@ -186,7 +383,7 @@ impl Wallet {
             .map(|keypair| keypair.get_public_key())
     }
 
-    /// Get all addresses in the wallet
+    /// Get all addresses of the keys in the wallet, without the watch-only ones
     pub fn get_wallet_address_list(&self) -> PreHashSet<Address> {
         self.keys.keys().copied().collect()
     }
@@ -205,10 +402,11 @@ impl Wallet {
         let mut persisted_keys: HashSet<PathBuf> = HashSet::new();
         // write the keys in the directory
         for (addr, keypair) in &self.keys {
-            let encrypted_secret = encrypt(&self.password, &keypair.to_bytes())?;
+            let encrypted_secret =
+                encrypt_with_version(self.cipher_version, &self.password, &keypair.to_bytes())?;
             let file_formatted = WalletFileFormat {
-                version: WALLET_VERSION,
-                nickname: addr.to_string(),
+                version: self.cipher_version.into(),
+                nickname: self.nickname(addr),
                 address: addr.to_string(),
                 salt: encrypted_secret.salt,
                 nonce: encrypted_secret.nonce,
@@ -222,6 +420,27 @@ impl Wallet {
             persisted_keys.insert(file_path);
         }
 
+        // write the watch-only entries
+        for (addr, public_key) in &self.watch_only {
+            let file_formatted = WatchOnlyFileFormat {
+                nickname: self.nickname(addr),
+                address: addr.to_string(),
+                public_key: public_key.as_ref().map(|public_key| public_key.to_bytes()),
+            };
+            let file_path = self
+                .wallet_path
+                .join(format!("{}{}.yaml", WATCH_ONLY_FILE_PREFIX, addr));
+            std::fs::write(&file_path, serde_yaml::to_string(&file_formatted)?)?;
+            persisted_keys.insert(file_path);
+        }
+
+        // write the seed of the hierarchical deterministic keys
+        if let Some(hd_seed) = &self.hd_seed {
+            let file_path = self.wallet_path.join(HD_SEED_FILE_NAME);
+            hd_seed.save(&file_path, &self.password, self.cipher_version)?;
+            persisted_keys.insert(file_path);
+        }
+
         let to_remove = existing_keys.difference(&persisted_keys);
         for path in to_remove {
             std::fs::remove_file(path)?;
@@ -230,6 +449,55 @@ impl Wallet {
         Ok(())
     }
 
+    /// Nickname of an address in the wallet files: its label, or the address itself
+    fn nickname(&self, address: &Address) -> String {
+        self.labels
+            .get(address)
+            .cloned()
+            .unwrap_or_else(|| address.to_string())
+    }
+
+    /// Changes the password of the wallet, re-encrypting every wallet file with a key derived
+    /// by the key derivation function of `cipher_version`.
+    ///
+    /// The re-encrypted files are written in a new directory that then replaces the wallet directory,
+    /// so that the wallet files are never encrypted with different passwords.
+    /// A change interrupted between the two renames is completed when the wallet is loaded again,
+    /// with the new password.
+    pub fn change_password(
+        &mut self,
+        new_password: String,
+        cipher_version: CipherVersion,
+    ) -> Result<(), WalletError> {
+        let (new_path, old_path) = password_change_paths(&self.wallet_path);
+        for path in [&new_path, &old_path] {
+            if path.exists() {
+                std::fs::remove_dir_all(path)?;
+            }
+        }
+
+        let mut new_wallet = self.clone();
+        new_wallet.wallet_path = new_path.clone();
+        new_wallet.password = new_password;
+        new_wallet.cipher_version = cipher_version;
+        if let Err(err) = new_wallet.save() {
+            let _ = std::fs::remove_dir_all(&new_path);
+            return Err(err);
+        }
+
+        std::fs::rename(&self.wallet_path, &old_path)?;
+        if let Err(err) = std::fs::rename(&new_path, &self.wallet_path) {
+            // put back the wallet encrypted with the current password
+            std::fs::rename(&old_path, &self.wallet_path)?;
+            return Err(err.into());
+        }
+        std::fs::remove_dir_all(&old_path)?;
+
+        new_wallet.wallet_path = self.wallet_path.clone();
+        *self = new_wallet;
+        Ok(())
+    }
+
     /// Export keys and addresses
     pub fn get_full_wallet(&self) -> &PreHashMap<Address, KeyPair> {
         &self.keys
@@ -254,6 +522,26 @@ impl Wallet {
     }
 }
 
+/// Directories of the re-encrypted wallet and of the replaced one during a password change
+fn password_change_paths(wallet_path: &Path) -> (PathBuf, PathBuf) {
+    let mut new_path = wallet_path.as_os_str().to_owned();
+    new_path.push(".new");
+    let mut old_path = wallet_path.as_os_str().to_owned();
+    old_path.push(".old");
+    (PathBuf::from(new_path), PathBuf::from(old_path))
+}
+
+/// Completes a password change interrupted after the wallet directory was moved away:
+/// the re-encrypted wallet was fully written before, so it replaces the wallet directory.
+fn recover_password_change(wallet_path: &Path) -> Result<(), WalletError> {
+    let (new_path, old_path) = password_change_paths(wallet_path);
+    if !wallet_path.exists() && old_path.is_dir() && new_path.is_dir() {
+        std::fs::rename(&new_path, wallet_path)?;
+        std::fs::remove_dir_all(&old_path)?;
+    }
+    Ok(())
+}
+
 impl std::fmt::Display for Wallet {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         writeln!(f)?;
@@ -261,6 +549,18 @@ impl std::fmt::Display for Wallet {
             writeln!(f, "Secret key: {}", keypair)?;
             writeln!(f, "Public key: {}", keypair.get_public_key())?;
             writeln!(f, "Address: {}", addr)?;
+            if let Some(label) = self.labels.get(addr) {
+                writeln!(f, "Label: {}", label)?;
+            }
+        }
+        for (addr, public_key) in &self.watch_only {
+            if let Some(public_key) = public_key {
+                writeln!(f, "Public key: {}", public_key)?;
+            }
+            writeln!(f, "Address: {} (watch-only)", addr)?;
+            if let Some(label) = self.labels.get(addr) {
+                writeln!(f, "Label: {}", label)?;
+            }
         }
         Ok(())
     }
@@ -269,3 +569,86 @@ impl std::fmt::Display for Wallet {
 /// Test utils
 #[cfg(feature = "test-exports")]
 pub mod test_exports;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_change_password() {
+        let dir = tempfile::tempdir().unwrap();
+        let wallet_path = dir.path().join("wallet");
+        let mut wallet = Wallet::new(wallet_path.clone(), "old".to_string(), 77).unwrap();
+        wallet.set_mnemonic(&generate_mnemonic(), "").unwrap();
+        let address = wallet.generate_hd_keypair().unwrap();
+        wallet
+            .add_keypairs(vec![KeyPair::generate(0).unwrap()])
+            .unwrap();
+
+        wallet
+            .change_password("new".to_string(), CipherVersion::Argon2id)
+            .unwrap();
+        assert!(Wallet::new(wallet_path.clone(), "old".to_string(), 77).is_err());
+        let reloaded = Wallet::new(wallet_path.clone(), "new".to_string(), 77).unwrap();
+        assert_eq!(reloaded.keys.len(), 2);
+        assert_eq!(reloaded.cipher_version, CipherVersion::Argon2id);
+        assert_eq!(
+            reloaded.derive_hd_keypair(0).unwrap().to_bytes(),
+            wallet.find_associated_keypair(&address).unwrap().to_bytes()
+        );
+
+        // a change interrupted after moving away the wallet directory is completed on load
+        let (new_path, old_path) = password_change_paths(&wallet_path);
+        reloaded
+            .clone()
+            .change_password("newer".to_string(), CipherVersion::Pbkdf2)
+            .unwrap();
+        std::fs::rename(&wallet_path, &new_path).unwrap();
+        std::fs::create_dir(&old_path).unwrap();
+        let recovered = Wallet::new(wallet_path, "newer".to_string(), 77).unwrap();
+        assert_eq!(recovered.keys.len(), 2);
+        assert!(!old_path.exists() && !new_path.exists());
+    }
+
+    #[test]
+    fn test_watch_only_and_labels() {
+        let dir = tempfile::tempdir().unwrap();
+        let wallet_path = dir.path().join("wallet");
+        let mut wallet = Wallet::new(wallet_path.clone(), "password".to_string(), 77).unwrap();
+        let address = wallet
+            .add_keypairs(vec![KeyPair::generate(0).unwrap()])
+            .unwrap()[0];
+        wallet
+            .set_label(address, Some("hot wallet".to_string()))
+            .unwrap();
+
+        let cold_keypair = KeyPair::generate(0).unwrap();
+        let cold_address = Address::from_public_key(&cold_keypair.get_public_key());
+        let other_address =
+            Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        assert!(wallet
+            .add_watch_only(cold_address, Some(cold_keypair.get_public_key()), None)
+            .is_ok());
+        assert!(wallet
+            .add_watch_only(other_address, Some(cold_keypair.get_public_key()), None)
+            .is_err());
+        assert!(wallet.add_watch_only(address, None, None).is_err());
+        wallet
+            .add_watch_only(other_address, None, Some("customer".to_string()))
+            .unwrap();
+
+        let reloaded = Wallet::new(wallet_path, "password".to_string(), 77).unwrap();
+        assert_eq!(reloaded.get_wallet_address_list().len(), 1);
+        assert_eq!(reloaded.get_label(&address).unwrap(), "hot wallet");
+        assert_eq!(reloaded.get_label(&other_address).unwrap(), "customer");
+        assert_eq!(reloaded.get_label(&cold_address), None);
+        assert_eq!(
+            reloaded.get_watch_only_addresses().get(&cold_address),
+            Some(&Some(cold_keypair.get_public_key()))
+        );
+        assert_eq!(
+            reloaded.get_watch_only_addresses().get(&other_address),
+            Some(&None)
+        );
+    }
+}
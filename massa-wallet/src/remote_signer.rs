@@ -0,0 +1,750 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Remote signer: the keys live on an isolated signing host, the node only asks for signatures.
+//!
+//! The protocol is line-delimited JSON over TCP or a Unix socket:
+//! * on connection, the server sends a `RemoteSignerHello` with a random challenge;
+//! * the client then sends `RemoteSignerEnvelope`s, each one authenticated by a MAC
+//!   over the shared secret, the challenge, a strictly increasing counter and the request;
+//! * the server answers each request with a `RemoteSignerResponse`.
+//!
+//! Responses are not authenticated: the client checks them against the public keys,
+//! which are themselves checked against the addresses they are returned for.
+//!
+//! The signing host only signs block headers and endorsements, given their serialized content:
+//! it computes the signed hash itself and records every signed item,
+//! so that it never signs two different items for the same address, slot and kind.
+
+use crate::{Signer, WalletError};
+use massa_hash::Hash;
+use massa_models::{
+    address::Address,
+    block_header::BlockHeader,
+    block_id::BlockId,
+    config::{CHAINID, ENDORSEMENT_COUNT, THREAD_COUNT},
+    endorsement::{Endorsement, EndorsementId},
+    prehash::PreHashMap,
+    slot::Slot,
+};
+use massa_signature::{PublicKey, Signature};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Maximum size of a message of the protocol
+const MAX_MESSAGE_SIZE: u64 = 1_000_000;
+
+/// The public keys of the signing host are refreshed at this interval
+const PUBLIC_KEYS_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Maximum number of connections served at the same time by a signing host
+const MAX_CONNECTIONS: usize = 16;
+
+/// A signing host closes the connections idle for this long
+const CONNECTION_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Where a remote signer listens: `tcp://host:port` or `unix:///path/to/socket`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteSignerEndpoint {
+    /// TCP socket address
+    Tcp(String),
+    /// Unix socket path
+    Unix(PathBuf),
+}
+
+impl FromStr for RemoteSignerEndpoint {
+    type Err = WalletError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(addr) = s.strip_prefix("tcp://") {
+            Ok(RemoteSignerEndpoint::Tcp(addr.to_string()))
+        } else if let Some(path) = s.strip_prefix("unix://") {
+            Ok(RemoteSignerEndpoint::Unix(PathBuf::from(path)))
+        } else {
+            Err(WalletError::RemoteSignerError(format!(
+                "invalid remote signer endpoint {}: expected tcp://host:port or unix:///path",
+                s
+            )))
+        }
+    }
+}
+
+impl Display for RemoteSignerEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RemoteSignerEndpoint::Tcp(addr) => write!(f, "tcp://{}", addr),
+            RemoteSignerEndpoint::Unix(path) => write!(f, "unix://{}", path.display()),
+        }
+    }
+}
+
+/// A connected byte stream
+trait Stream: Read + Write + Send {}
+impl<T: Read + Write + Send> Stream for T {}
+
+impl RemoteSignerEndpoint {
+    fn connect(&self, timeout: Duration) -> Result<Box<dyn Stream>, WalletError> {
+        match self {
+            RemoteSignerEndpoint::Tcp(addr) => {
+                let stream = TcpStream::connect(addr)?;
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                Ok(Box::new(stream))
+            }
+            #[cfg(unix)]
+            RemoteSignerEndpoint::Unix(path) => {
+                let stream = UnixStream::connect(path)?;
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                Ok(Box::new(stream))
+            }
+            #[cfg(not(unix))]
+            RemoteSignerEndpoint::Unix(_) => Err(WalletError::RemoteSignerError(
+                "unix sockets are not supported on this platform".to_string(),
+            )),
+        }
+    }
+}
+
+/// First message sent by the server on each connection
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoteSignerHello {
+    /// random challenge, part of the MAC of every request of the connection
+    pub challenge: Hash,
+}
+
+/// Request to the signing host
+#[derive(Debug, Serialize, Deserialize)]
+pub enum RemoteSignerRequest {
+    /// get the public keys of all the managed addresses
+    GetPublicKeys,
+    /// sign a block header with the key of an address
+    SignBlockHeader {
+        /// signing address
+        address: Address,
+        /// serialized header content
+        serialized_content: Vec<u8>,
+    },
+    /// sign an endorsement with the key of an address
+    SignEndorsement {
+        /// signing address
+        address: Address,
+        /// serialized endorsement content
+        serialized_content: Vec<u8>,
+    },
+}
+
+/// Authenticated request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoteSignerEnvelope {
+    /// strictly increasing on a connection
+    pub counter: u64,
+    /// JSON serialized `RemoteSignerRequest`
+    pub request: String,
+    /// MAC of the request, see `compute_mac`
+    pub mac: Hash,
+}
+
+/// Response of the signing host
+#[derive(Debug, Serialize, Deserialize)]
+pub enum RemoteSignerResponse {
+    /// public keys of all the managed addresses
+    PublicKeys(Vec<PublicKey>),
+    /// requested signature
+    Signature(Signature),
+    /// the request failed
+    Error(String),
+}
+
+/// MAC of a request: keyed hash of the challenge of the connection, the counter and the request
+fn compute_mac(secret: &[u8], challenge: &Hash, counter: u64, request: &str) -> Hash {
+    Hash::compute_from_tuple(&[
+        secret,
+        challenge.to_bytes(),
+        &counter.to_be_bytes(),
+        request.as_bytes(),
+    ])
+}
+
+/// Compares two MACs in constant time
+fn mac_eq(a: &Hash, b: &Hash) -> bool {
+    a.to_bytes()
+        .iter()
+        .zip(b.to_bytes().iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+/// Writes a message on a single line
+fn write_message<T: Serialize>(stream: &mut dyn Stream, message: &T) -> Result<(), WalletError> {
+    let mut line = serde_json::to_vec(message)
+        .map_err(|err| WalletError::RemoteSignerError(err.to_string()))?;
+    line.push(b'\n');
+    stream.write_all(&line)?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Reads a message written by `write_message`
+fn read_message<T: for<'de> Deserialize<'de>>(
+    reader: &mut BufReader<Box<dyn Stream>>,
+) -> Result<T, WalletError> {
+    let mut line = String::new();
+    if reader
+        .by_ref()
+        .take(MAX_MESSAGE_SIZE)
+        .read_line(&mut line)?
+        == 0
+    {
+        return Err(WalletError::RemoteSignerError(
+            "connection closed".to_string(),
+        ));
+    }
+    serde_json::from_str(&line).map_err(|err| WalletError::RemoteSignerError(err.to_string()))
+}
+
+/// An open connection to the signing host
+struct Connection {
+    reader: BufReader<Box<dyn Stream>>,
+    challenge: Hash,
+    counter: u64,
+}
+
+struct RemoteSignerClient {
+    endpoint: RemoteSignerEndpoint,
+    secret: Vec<u8>,
+    timeout: Duration,
+    connection: Option<Connection>,
+    public_keys: PreHashMap<Address, PublicKey>,
+    public_keys_refreshed: Option<Instant>,
+}
+
+impl RemoteSignerClient {
+    fn connect(&self) -> Result<Connection, WalletError> {
+        let mut reader = BufReader::new(self.endpoint.connect(self.timeout)?);
+        let hello: RemoteSignerHello = read_message(&mut reader)?;
+        Ok(Connection {
+            reader,
+            challenge: hello.challenge,
+            counter: 0,
+        })
+    }
+
+    fn send(&mut self, request: &RemoteSignerRequest) -> Result<RemoteSignerResponse, WalletError> {
+        let request = serde_json::to_string(request)
+            .map_err(|err| WalletError::RemoteSignerError(err.to_string()))?;
+        if self.connection.is_none() {
+            self.connection = Some(self.connect()?);
+        }
+        let connection = self.connection.as_mut().expect("connection just opened");
+        connection.counter += 1;
+        let envelope = RemoteSignerEnvelope {
+            counter: connection.counter,
+            mac: compute_mac(
+                &self.secret,
+                &connection.challenge,
+                connection.counter,
+                &request,
+            ),
+            request,
+        };
+        write_message(connection.reader.get_mut().as_mut(), &envelope)?;
+        read_message(&mut connection.reader)
+    }
+
+    /// Sends a request, reconnecting once if the connection was lost
+    fn request(
+        &mut self,
+        request: RemoteSignerRequest,
+    ) -> Result<RemoteSignerResponse, WalletError> {
+        let response = match self.send(&request) {
+            Err(_) if self.connection.is_some() => {
+                self.connection = None;
+                self.send(&request)
+            }
+            response => response,
+        };
+        match response {
+            Ok(RemoteSignerResponse::Error(err)) => Err(WalletError::RemoteSignerError(err)),
+            Ok(response) => Ok(response),
+            Err(err) => {
+                self.connection = None;
+                Err(err)
+            }
+        }
+    }
+
+    fn refresh_public_keys(&mut self) -> Result<(), WalletError> {
+        if matches!(self.public_keys_refreshed, Some(refreshed) if refreshed.elapsed() < PUBLIC_KEYS_REFRESH_INTERVAL)
+        {
+            return Ok(());
+        }
+        match self.request(RemoteSignerRequest::GetPublicKeys)? {
+            RemoteSignerResponse::PublicKeys(public_keys) => {
+                self.public_keys = public_keys
+                    .into_iter()
+                    .map(|public_key| (Address::from_public_key(&public_key), public_key))
+                    .collect();
+                self.public_keys_refreshed = Some(Instant::now());
+                Ok(())
+            }
+            response => Err(WalletError::RemoteSignerError(format!(
+                "unexpected response {:?}",
+                response
+            ))),
+        }
+    }
+}
+
+/// Signer delegating signatures to a remote signing host
+#[derive(Clone)]
+pub struct RemoteSigner {
+    client: Arc<Mutex<RemoteSignerClient>>,
+}
+
+impl RemoteSigner {
+    /// Creates a remote signer. The connection is opened on the first request.
+    ///
+    /// # Arguments
+    /// * `endpoint`: where the signing host listens
+    /// * `secret`: secret shared with the signing host, authenticating the requests
+    /// * `timeout`: timeout of the socket operations
+    pub fn new(endpoint: RemoteSignerEndpoint, secret: Vec<u8>, timeout: Duration) -> Self {
+        RemoteSigner {
+            client: Arc::new(Mutex::new(RemoteSignerClient {
+                endpoint,
+                secret,
+                timeout,
+                connection: None,
+                public_keys: PreHashMap::default(),
+                public_keys_refreshed: None,
+            })),
+        }
+    }
+
+    /// Gets the public key of an address, which must be managed by the signing host
+    fn get_managed_public_key(&self, address: &Address) -> Result<PublicKey, WalletError> {
+        self.get_public_key(address)?
+            .ok_or(WalletError::MissingKeyError(*address))
+    }
+
+    /// Sends a signature request and checks that the returned signature is the one of `hash`
+    fn request_signature(
+        &self,
+        request: RemoteSignerRequest,
+        public_key: &PublicKey,
+        hash: &Hash,
+    ) -> Result<Signature, WalletError> {
+        match self.client.lock().request(request)? {
+            RemoteSignerResponse::Signature(signature) => {
+                public_key.verify_signature(hash, &signature)?;
+                Ok(signature)
+            }
+            response => Err(WalletError::RemoteSignerError(format!(
+                "unexpected response {:?}",
+                response
+            ))),
+        }
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn get_public_key(&self, address: &Address) -> Result<Option<PublicKey>, WalletError> {
+        let mut client = self.client.lock();
+        client.refresh_public_keys()?;
+        Ok(client.public_keys.get(address).copied())
+    }
+
+    fn get_public_keys(&self) -> Result<Vec<PublicKey>, WalletError> {
+        let mut client = self.client.lock();
+        client.refresh_public_keys()?;
+        Ok(client.public_keys.values().copied().collect())
+    }
+
+    fn sign(&self, _address: &Address, _hash: &Hash) -> Result<Signature, WalletError> {
+        Err(WalletError::RemoteSignerError(
+            "the remote signer only signs block headers and endorsements".to_string(),
+        ))
+    }
+
+    fn sign_block_header(
+        &self,
+        address: &Address,
+        serialized_content: &[u8],
+    ) -> Result<Signature, WalletError> {
+        let public_key = self.get_managed_public_key(address)?;
+        let (_, _, hash) = BlockHeader::compute_signed_hash_from_serialized(
+            serialized_content,
+            &public_key,
+            THREAD_COUNT,
+        )?;
+        self.request_signature(
+            RemoteSignerRequest::SignBlockHeader {
+                address: *address,
+                serialized_content: serialized_content.to_vec(),
+            },
+            &public_key,
+            &hash,
+        )
+    }
+
+    fn sign_endorsement(
+        &self,
+        address: &Address,
+        serialized_content: &[u8],
+    ) -> Result<Signature, WalletError> {
+        let public_key = self.get_managed_public_key(address)?;
+        let (_, _, hash) = Endorsement::compute_signed_hash_from_serialized(
+            serialized_content,
+            &public_key,
+            *CHAINID,
+            THREAD_COUNT,
+            ENDORSEMENT_COUNT,
+        )?;
+        self.request_signature(
+            RemoteSignerRequest::SignEndorsement {
+                address: *address,
+                serialized_content: serialized_content.to_vec(),
+            },
+            &public_key,
+            &hash,
+        )
+    }
+
+    fn clone_box(&self) -> Box<dyn Signer> {
+        Box::new(self.clone())
+    }
+}
+
+/// Record of the items signed by a signing host, consulted before signing
+pub trait SignedItemsRecord: Send {
+    /// Records a block header about to be signed by `address`.
+    /// Fails if a different header was already signed by `address` at `slot`.
+    fn record_block_header(
+        &mut self,
+        address: Address,
+        slot: Slot,
+        id: BlockId,
+    ) -> Result<(), WalletError>;
+
+    /// Records an endorsement about to be signed by `address`.
+    /// Fails if a different endorsement was already signed by `address` at `slot` and `index`.
+    fn record_endorsement(
+        &mut self,
+        address: Address,
+        slot: Slot,
+        index: u32,
+        id: EndorsementId,
+    ) -> Result<(), WalletError>;
+}
+
+/// Listening socket of a signing host
+pub enum RemoteSignerListener {
+    /// TCP listener
+    Tcp(TcpListener),
+    /// Unix socket listener
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl RemoteSignerListener {
+    /// Listens on `endpoint`
+    pub fn bind(endpoint: &RemoteSignerEndpoint) -> Result<Self, WalletError> {
+        match endpoint {
+            RemoteSignerEndpoint::Tcp(addr) => {
+                Ok(RemoteSignerListener::Tcp(TcpListener::bind(addr)?))
+            }
+            #[cfg(unix)]
+            RemoteSignerEndpoint::Unix(path) => {
+                Ok(RemoteSignerListener::Unix(UnixListener::bind(path)?))
+            }
+            #[cfg(not(unix))]
+            RemoteSignerEndpoint::Unix(_) => Err(WalletError::RemoteSignerError(
+                "unix sockets are not supported on this platform".to_string(),
+            )),
+        }
+    }
+
+    /// Accepts a connection, closed by reads and writes after `CONNECTION_IDLE_TIMEOUT`
+    fn accept(&self) -> std::io::Result<Box<dyn Stream>> {
+        match self {
+            RemoteSignerListener::Tcp(listener) => {
+                let (stream, _) = listener.accept()?;
+                stream.set_read_timeout(Some(CONNECTION_IDLE_TIMEOUT))?;
+                stream.set_write_timeout(Some(CONNECTION_IDLE_TIMEOUT))?;
+                Ok(Box::new(stream))
+            }
+            #[cfg(unix)]
+            RemoteSignerListener::Unix(listener) => {
+                let (stream, _) = listener.accept()?;
+                stream.set_read_timeout(Some(CONNECTION_IDLE_TIMEOUT))?;
+                stream.set_write_timeout(Some(CONNECTION_IDLE_TIMEOUT))?;
+                Ok(Box::new(stream))
+            }
+        }
+    }
+}
+
+/// Decrements the count of open connections when dropped
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Serves signature requests on `listener` with the keys of `signer`, until an error occurs.
+/// Each connection is served by its own thread, up to `MAX_CONNECTIONS` at the same time.
+///
+/// Every signed item is recorded in `signed_items` before being signed.
+pub fn serve_remote_signer(
+    listener: RemoteSignerListener,
+    signer: Box<dyn Signer>,
+    secret: Vec<u8>,
+    signed_items: Arc<Mutex<dyn SignedItemsRecord>>,
+) -> Result<(), WalletError> {
+    let secret = Arc::new(secret);
+    let connection_count = Arc::new(AtomicUsize::new(0));
+    loop {
+        let stream = match listener.accept() {
+            Ok(stream) => stream,
+            // a connection that could not be set up does not stop the others
+            Err(_) => continue,
+        };
+        if connection_count.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            // too many connections: close this one
+            connection_count.fetch_sub(1, Ordering::SeqCst);
+            continue;
+        }
+        let slot = ConnectionSlot(connection_count.clone());
+        let signer = signer.clone();
+        let secret = secret.clone();
+        let signed_items = signed_items.clone();
+        std::thread::Builder::new()
+            .name("remote-signer-connection".into())
+            .spawn(move || {
+                let _slot = slot;
+                // the connection is dropped on the first error
+                let _ = serve_connection(stream, signer.as_ref(), &secret, &signed_items);
+            })?;
+    }
+}
+
+/// Serves the requests of a single connection
+fn serve_connection(
+    stream: Box<dyn Stream>,
+    signer: &dyn Signer,
+    secret: &[u8],
+    signed_items: &Mutex<dyn SignedItemsRecord>,
+) -> Result<(), WalletError> {
+    let mut reader = BufReader::new(stream);
+    let challenge = Hash::compute_from(&rand::random::<[u8; 32]>());
+    write_message(reader.get_mut().as_mut(), &RemoteSignerHello { challenge })?;
+    let mut last_counter = 0;
+    loop {
+        let envelope: RemoteSignerEnvelope = read_message(&mut reader)?;
+        if envelope.counter <= last_counter
+            || !mac_eq(
+                &envelope.mac,
+                &compute_mac(secret, &challenge, envelope.counter, &envelope.request),
+            )
+        {
+            write_message(
+                reader.get_mut().as_mut(),
+                &RemoteSignerResponse::Error("unauthenticated request".to_string()),
+            )?;
+            return Err(WalletError::RemoteSignerError(
+                "unauthenticated request".to_string(),
+            ));
+        }
+        last_counter = envelope.counter;
+        let response = match serde_json::from_str(&envelope.request) {
+            Ok(RemoteSignerRequest::GetPublicKeys) => match signer.get_public_keys() {
+                Ok(public_keys) => RemoteSignerResponse::PublicKeys(public_keys),
+                Err(err) => RemoteSignerResponse::Error(err.to_string()),
+            },
+            Ok(request) => match sign_request(signer, signed_items, request) {
+                Ok(signature) => RemoteSignerResponse::Signature(signature),
+                Err(err) => RemoteSignerResponse::Error(err.to_string()),
+            },
+            Err(err) => RemoteSignerResponse::Error(format!("invalid request: {}", err)),
+        };
+        write_message(reader.get_mut().as_mut(), &response)?;
+    }
+}
+
+/// Records then signs the block header or endorsement of a request
+fn sign_request(
+    signer: &dyn Signer,
+    signed_items: &Mutex<dyn SignedItemsRecord>,
+    request: RemoteSignerRequest,
+) -> Result<Signature, WalletError> {
+    let (address, hash) = match request {
+        RemoteSignerRequest::GetPublicKeys => {
+            return Err(WalletError::RemoteSignerError(
+                "not a signature request".to_string(),
+            ))
+        }
+        RemoteSignerRequest::SignBlockHeader {
+            address,
+            serialized_content,
+        } => {
+            let public_key = signer
+                .get_public_key(&address)?
+                .ok_or(WalletError::MissingKeyError(address))?;
+            let (slot, id, hash) = BlockHeader::compute_signed_hash_from_serialized(
+                &serialized_content,
+                &public_key,
+                THREAD_COUNT,
+            )?;
+            signed_items.lock().record_block_header(address, slot, id)?;
+            (address, hash)
+        }
+        RemoteSignerRequest::SignEndorsement {
+            address,
+            serialized_content,
+        } => {
+            let public_key = signer
+                .get_public_key(&address)?
+                .ok_or(WalletError::MissingKeyError(address))?;
+            let (endorsement, id, hash) = Endorsement::compute_signed_hash_from_serialized(
+                &serialized_content,
+                &public_key,
+                *CHAINID,
+                THREAD_COUNT,
+                ENDORSEMENT_COUNT,
+            )?;
+            signed_items.lock().record_endorsement(
+                address,
+                endorsement.slot,
+                endorsement.index,
+                id,
+            )?;
+            (address, hash)
+        }
+    };
+    signer.sign(&address, &hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use massa_models::endorsement::{EndorsementSerializer, SecureShareEndorsement};
+    use massa_models::error::ModelsError;
+    use massa_models::secure_share::{Id, SecureShareContent};
+    use massa_signature::KeyPair;
+    use std::collections::HashMap;
+
+    /// In-memory record of the signed items, by address, slot and endorsement index
+    #[derive(Default)]
+    struct TestSignedItems(HashMap<(Address, Slot, Option<u32>), Hash>);
+
+    impl TestSignedItems {
+        fn record(
+            &mut self,
+            key: (Address, Slot, Option<u32>),
+            id: Hash,
+        ) -> Result<(), WalletError> {
+            match self.0.insert(key, id) {
+                Some(existing) if existing != id => {
+                    Err(WalletError::RemoteSignerError("double signing".to_string()))
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    impl SignedItemsRecord for TestSignedItems {
+        fn record_block_header(
+            &mut self,
+            address: Address,
+            slot: Slot,
+            id: BlockId,
+        ) -> Result<(), WalletError> {
+            self.record((address, slot, None), *id.get_hash())
+        }
+
+        fn record_endorsement(
+            &mut self,
+            address: Address,
+            slot: Slot,
+            index: u32,
+            id: EndorsementId,
+        ) -> Result<(), WalletError> {
+            self.record((address, slot, Some(index)), *id.get_hash())
+        }
+    }
+
+    fn sign_endorsement(
+        signer: &RemoteSigner,
+        keypair: &KeyPair,
+        endorsed_block: BlockId,
+    ) -> Result<SecureShareEndorsement, ModelsError> {
+        let address = Address::from_public_key(&keypair.get_public_key());
+        Endorsement {
+            slot: Slot::new(1, 0),
+            index: 3,
+            endorsed_block,
+        }
+        .new_verifiable_with_signer(
+            EndorsementSerializer::new(),
+            keypair.get_public_key(),
+            *CHAINID,
+            |serialized_content, _| {
+                signer
+                    .sign_endorsement(&address, serialized_content)
+                    .map_err(|err| ModelsError::ErrorRaised(err.to_string()))
+            },
+        )
+    }
+
+    #[test]
+    fn test_remote_signer() {
+        let keypair = KeyPair::generate(0).unwrap();
+        let address = Address::from_public_key(&keypair.get_public_key());
+        // the listener is bound before the clients connect
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = RemoteSignerEndpoint::Tcp(listener.local_addr().unwrap().to_string());
+        let server_keypair = keypair.clone();
+        std::thread::spawn(move || {
+            serve_remote_signer(
+                RemoteSignerListener::Tcp(listener),
+                Box::new(server_keypair),
+                b"secret".to_vec(),
+                Arc::new(Mutex::new(TestSignedItems::default())),
+            )
+        });
+
+        let signer =
+            RemoteSigner::new(endpoint.clone(), b"secret".to_vec(), Duration::from_secs(5));
+        assert_eq!(
+            signer.get_public_key(&address).unwrap(),
+            Some(keypair.get_public_key())
+        );
+
+        // endorsements are signed, once per slot and index
+        let block_a = BlockId::generate_from_hash(Hash::compute_from(b"a"));
+        let block_b = BlockId::generate_from_hash(Hash::compute_from(b"b"));
+        let endorsement = sign_endorsement(&signer, &keypair, block_a).unwrap();
+        endorsement.verify_signature().unwrap();
+        sign_endorsement(&signer, &keypair, block_a).unwrap();
+        assert!(sign_endorsement(&signer, &keypair, block_b).is_err());
+
+        // arbitrary hashes are not signed
+        assert!(signer.sign(&address, &Hash::compute_from(b"data")).is_err());
+
+        // a wrong secret is refused
+        let signer = RemoteSigner::new(endpoint, b"wrong".to_vec(), Duration::from_secs(5));
+        assert!(signer.get_public_key(&address).is_err());
+    }
+}
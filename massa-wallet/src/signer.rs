@@ -0,0 +1,160 @@
+// Copyright (c) 2023 MASSA LABS <info@massa.net>
+
+//! Signers: abstraction over where the keys of the node addresses live.
+//!
+//! The local implementation signs with the keys of the node wallet,
+//! the remote one (see `remote_signer`) delegates signatures to an isolated signing host.
+
+use crate::{Wallet, WalletError};
+use massa_hash::Hash;
+use massa_models::{
+    address::Address,
+    block_header::BlockHeader,
+    composite::PubkeySig,
+    config::{CHAINID, ENDORSEMENT_COUNT, THREAD_COUNT},
+    endorsement::Endorsement,
+};
+use massa_signature::{KeyPair, PublicKey, Signature};
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// Signs hashes on behalf of a set of addresses
+pub trait Signer: Send + Sync {
+    /// Gets the public key of an address
+    ///
+    /// # Returns
+    /// The public key, or None if the address is not managed by this signer
+    fn get_public_key(&self, address: &Address) -> Result<Option<PublicKey>, WalletError>;
+
+    /// Gets the public keys of all the addresses managed by this signer
+    fn get_public_keys(&self) -> Result<Vec<PublicKey>, WalletError>;
+
+    /// Signs a hash with the key of `address`
+    fn sign(&self, address: &Address, hash: &Hash) -> Result<Signature, WalletError>;
+
+    /// Signs a block header with the key of `address`, given its serialized content
+    fn sign_block_header(
+        &self,
+        address: &Address,
+        serialized_content: &[u8],
+    ) -> Result<Signature, WalletError> {
+        let public_key = self
+            .get_public_key(address)?
+            .ok_or(WalletError::MissingKeyError(*address))?;
+        let (_, _, hash) = BlockHeader::compute_signed_hash_from_serialized(
+            serialized_content,
+            &public_key,
+            THREAD_COUNT,
+        )?;
+        self.sign(address, &hash)
+    }
+
+    /// Signs an endorsement with the key of `address`, given its serialized content
+    fn sign_endorsement(
+        &self,
+        address: &Address,
+        serialized_content: &[u8],
+    ) -> Result<Signature, WalletError> {
+        let public_key = self
+            .get_public_key(address)?
+            .ok_or(WalletError::MissingKeyError(*address))?;
+        let (_, _, hash) = Endorsement::compute_signed_hash_from_serialized(
+            serialized_content,
+            &public_key,
+            *CHAINID,
+            THREAD_COUNT,
+            ENDORSEMENT_COUNT,
+        )?;
+        self.sign(address, &hash)
+    }
+
+    /// Signs an arbitrary message with the key of `address`
+    ///
+    /// # Returns
+    /// The public key that signed the message and the signature
+    fn sign_message(&self, address: &Address, message: &[u8]) -> Result<PubkeySig, WalletError> {
+        let public_key = self
+            .get_public_key(address)?
+            .ok_or(WalletError::MissingKeyError(*address))?;
+        Ok(PubkeySig {
+            public_key,
+            signature: self.sign(address, &Hash::compute_from(message))?,
+        })
+    }
+
+    /// Returns a boxed clone of self.
+    /// Allows cloning `Box<dyn Signer>`,
+    fn clone_box(&self) -> Box<dyn Signer>;
+}
+
+/// Allow cloning `Box<dyn Signer>`
+/// Uses `Signer::clone_box` internally
+impl Clone for Box<dyn Signer> {
+    fn clone(&self) -> Box<dyn Signer> {
+        self.clone_box()
+    }
+}
+
+/// Signer using the keys of the node wallet
+#[derive(Clone)]
+pub struct LocalSigner {
+    wallet: Arc<RwLock<Wallet>>,
+}
+
+impl LocalSigner {
+    /// Creates a signer using the keys of `wallet`
+    pub fn new(wallet: Arc<RwLock<Wallet>>) -> Self {
+        LocalSigner { wallet }
+    }
+}
+
+impl Signer for LocalSigner {
+    fn get_public_key(&self, address: &Address) -> Result<Option<PublicKey>, WalletError> {
+        Ok(self.wallet.read().find_associated_public_key(address))
+    }
+
+    fn get_public_keys(&self) -> Result<Vec<PublicKey>, WalletError> {
+        Ok(self
+            .wallet
+            .read()
+            .keys
+            .values()
+            .map(|keypair| keypair.get_public_key())
+            .collect())
+    }
+
+    fn sign(&self, address: &Address, hash: &Hash) -> Result<Signature, WalletError> {
+        let wallet = self.wallet.read();
+        let keypair = wallet
+            .find_associated_keypair(address)
+            .ok_or(WalletError::MissingKeyError(*address))?;
+        Ok(keypair.sign(hash)?)
+    }
+
+    fn clone_box(&self) -> Box<dyn Signer> {
+        Box::new(self.clone())
+    }
+}
+
+/// A single key-pair is a signer for its own address
+impl Signer for KeyPair {
+    fn get_public_key(&self, address: &Address) -> Result<Option<PublicKey>, WalletError> {
+        let public_key = self.get_public_key();
+        Ok((Address::from_public_key(&public_key) == *address).then_some(public_key))
+    }
+
+    fn get_public_keys(&self) -> Result<Vec<PublicKey>, WalletError> {
+        Ok(vec![self.get_public_key()])
+    }
+
+    fn sign(&self, address: &Address, hash: &Hash) -> Result<Signature, WalletError> {
+        if Address::from_public_key(&self.get_public_key()) != *address {
+            return Err(WalletError::MissingKeyError(*address));
+        }
+        Ok(KeyPair::sign(self, hash)?)
+    }
+
+    fn clone_box(&self) -> Box<dyn Signer> {
+        Box::new(self.clone())
+    }
+}